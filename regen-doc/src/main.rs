@@ -7,24 +7,48 @@ use std::io::BufReader;
 use std::io::{self};
 use std::panic::panic_any;
 use std::path::Path;
-use std::path::PathBuf;
 use std::path::{self};
 use std::process::Command;
 use std::process::Stdio;
-use std::string::FromUtf8Error;
 use std::thread;
 
 use log::error;
 use log::info;
-use log::warn;
 use serde::ser::Serialize;
-use thiserror::Error;
+use serde::Deserialize;
 
 const CONFIG_FILE: &str = include_str!("../data/config.toml");
 
+/// The config of `regen-src`, which lists the targets the standard library is documented for.
+/// `regen-src` merges their output, so that items missing from some of them are generated behind
+/// the matching `cfg`.
+const TARGETS_FILE: &str = "regen-src/data/config.toml";
+
+/// The part of the `regen-src` config shared with `regen-doc`.
+#[derive(Deserialize)]
+struct SharedConfig {
+	targets: Targets,
+}
+
+#[derive(Deserialize)]
+struct Targets {
+	documented: Vec<Target>,
+}
+
+#[derive(Deserialize)]
+struct Target {
+	triple: String,
+}
+
 fn main() -> anyhow::Result<()> {
 	env_logger::init();
 
+	let targets_path = path::absolute(TARGETS_FILE)?;
+	info!("Reading targets from {}...", targets_path.display());
+	let config = toml::from_str::<SharedConfig>(&fs::read_to_string(targets_path)?)?;
+	let targets =
+		config.targets.documented.into_iter().map(|target| target.triple).collect::<Vec<_>>();
+
 	let rust_src_dir = std::env::var("RUST_SRC_DIR").unwrap_or("./rust".to_owned());
 	let rust_src_path = path::absolute(&rust_src_dir)?;
 	let should_skip_rustdoc = match std::env::var("REGEN_RUSTDOC_SKIP") {
@@ -91,23 +115,26 @@ fn main() -> anyhow::Result<()> {
 			std::process::exit(1);
 		}
 
-		regen_rustdoc(&rust_src_path)?;
+		regen_rustdoc(&rust_src_path, &targets)?;
 	}
 
-	let target_path_option = discover_target(rust_src_path);
-	let Some(target_path) = target_path_option else {
-		error!("Unable to find a build target. Was rustdoc built?");
-		std::process::exit(1)
-	};
+	fs::create_dir_all("./data/std")?;
+	for target in &targets {
+		let target_path = rust_src_path.join(rustdoc_build_path(target));
+		if !target_path.exists() {
+			error!("Unable to find rustdoc output for {}. Was rustdoc built?", target);
+			std::process::exit(1)
+		}
 
-	info!("Parsing and normalizing data...");
-	let data_raw = fs::read_to_string(target_path)?;
-	let mut data: rustdoc_types::Crate = serde_json::from_str(&data_raw)?;
-	let value = normalize_paths(&mut data)?;
+		info!("Parsing and normalizing data for {}...", target);
+		let data_raw = fs::read_to_string(target_path)?;
+		let mut data: rustdoc_types::Crate = serde_json::from_str(&data_raw)?;
+		let value = normalize_paths(&mut data)?;
 
-	info!("Outputting and formatting data to this project...");
-	let out = prettify_json(&value)?;
-	fs::write("./data/std.json", out)?;
+		info!("Outputting and formatting data to this project...");
+		let out = prettify_json(&value)?;
+		fs::write(format!("./data/std/{target}.json"), out)?;
+	}
 
 	info!("Done!");
 	Ok(())
@@ -152,85 +179,11 @@ fn normalize_paths(doc_crate: &mut rustdoc_types::Crate) -> anyhow::Result<serde
 	Ok(value)
 }
 
-fn discover_target(rust_src_path: impl AsRef<Path>) -> Option<PathBuf> {
-	let target_env = std::env::var("CARGO_BUILD_TARGET").or_else(|_| std::env::var("TARGET"));
-	target_env
-		.ok()
-		.and_then(|target| {
-			let std_rustdoc_path = rust_src_path.as_ref().join(rustdoc_build_path(&target));
-			if std_rustdoc_path.exists() {
-				info!("Using target: {}", target);
-				Some(std_rustdoc_path)
-			} else {
-				warn!("Invalid target: {}", target);
-				None
-			}
-		})
-		.or_else(|| {
-			info!("Looking up possible targets...");
-			// See also: https://github.com/rust-lang/cargo/issues/3946
-			let rustc_dir = std::env::var("CARGO_RUSTC_CURRENT_DIR").ok();
-			let targets = match targets(rustc_dir.as_deref()) {
-				Ok(targets) => targets,
-				Err(e) => {
-					error!("Unable to discover rustc targets (is rustc available?): {}", e);
-					std::process::exit(1)
-				}
-			};
-			targets
-				.iter()
-				.filter(|target| is_native_target(target))
-				.map(|target| (target, rust_src_path.as_ref().join(rustdoc_build_path(target))))
-				.find(|(_, std_rustdoc_path)| std_rustdoc_path.exists())
-				.map(|(target, std_rustdoc_path)| {
-					info!("Detected existing target: {}", target);
-					std_rustdoc_path
-				})
-		})
-}
-
-// Returns the list of targets that can be built for this OS and architecture.
-fn targets(rustc_dir: Option<&str>) -> Result<Vec<String>, CommandError> {
-	let mut command = Command::new("rustc");
-	command.args([
-		"--print",
-		"target-list",
-	]);
-	if let Some(dir) = rustc_dir {
-		command.current_dir(dir);
-	}
-	let output = command.output()?;
-	if !output.status.success() {
-		return Err(CommandError::ExitCode(output.status.code().unwrap_or(-1)));
-	}
-	let contents = String::from_utf8(output.stdout);
-	match contents {
-		Ok(targets) => Ok(targets.split('\n').map(str::to_owned).collect()),
-		Err(e) => Err(CommandError::ParseError(e)),
-	}
-}
-
-#[derive(Error, Debug)]
-pub enum CommandError {
-	#[error("unable to run command")]
-	Io(#[from] io::Error),
-	#[error("unsuccessful exit (code {0})")]
-	ExitCode(i32),
-	#[error("unable to parse output")]
-	ParseError(#[from] FromUtf8Error),
-}
-
-/// Returns true if the given target matches the current architecture and OS.
-fn is_native_target(target: &str) -> bool {
-	let arch = env::consts::ARCH;
-	target.starts_with(arch) && target[arch.len()..].contains(env::consts::OS)
-}
-
 fn rustdoc_build_path(target: &str) -> String {
 	format!("build/{target}/doc/std.json")
 }
 
-fn regen_rustdoc(rust_src_dir: impl AsRef<Path>) -> io::Result<()> {
+fn regen_rustdoc(rust_src_dir: impl AsRef<Path>, targets: &[String]) -> io::Result<()> {
 	let mut command = Command::new("python");
 	command.current_dir(rust_src_dir);
 	command.args([
@@ -240,6 +193,8 @@ fn regen_rustdoc(rust_src_dir: impl AsRef<Path>) -> io::Result<()> {
 		// Specify stage explicitly otherwise this fails on GitHub Actions.
 		"--stage",
 		"0",
+		"--target",
+		&targets.join(","),
 	]);
 	// Rust-lang refuses to download LLVM in GitHub Actions, so trick it.
 	command.env_remove("GITHUB_ACTIONS");
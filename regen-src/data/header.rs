@@ -1,2 +1,2 @@
 // This file is auto-generated. DO NOT edit by hand. See README.md for more details.
-#![allow(clippy::tabs_in_doc_comments)]
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
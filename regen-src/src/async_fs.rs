@@ -0,0 +1,331 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::Path;
+
+use log::info;
+use proc_macro2::Ident;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use rustdoc_types::Function;
+use rustdoc_types::Id;
+use rustdoc_types::Type;
+
+use crate::associated_context;
+use crate::associated_type;
+use crate::config::Module;
+use crate::import;
+use crate::print;
+use crate::rustdoc_util::NamedItem;
+use crate::visitor;
+use crate::write_source;
+use crate::SourceError;
+use crate::StructTrait;
+
+/// A blocking filesystem that the asynchronous implementations forward calls to.
+#[derive(Clone, Copy)]
+enum Backend {
+	/// The native backend of the module, calling the standard library on the global pool.
+	Native,
+	/// `Blocking<F>`, calling the wrapped `F` on its own pool.
+	Blocking,
+}
+
+impl Backend {
+	/// Generic parameters of the implementation, which are bound by a `where` clause.
+	fn generics(self) -> TokenStream {
+		match self {
+			Self::Native => TokenStream::new(),
+			Self::Blocking => quote!(<F>),
+		}
+	}
+
+	/// The implementing type.
+	fn self_type(self, module: &Module) -> TokenStream {
+		match self {
+			Self::Native => {
+				let native = format_ident!("{}", module.names.native);
+				quote!(#native)
+			}
+			Self::Blocking => quote!(Blocking<F>),
+		}
+	}
+
+	/// The type the backend uses for the struct.
+	fn struct_type(self, struct_trait: &StructTrait) -> TokenStream {
+		match self {
+			Self::Native => print::path(&struct_trait.path),
+			Self::Blocking => {
+				let name = format_ident!("{}", struct_trait.name);
+				quote!(F::#name)
+			}
+		}
+	}
+
+	/// Statements run before spawning, moving what the call needs into the closure.
+	fn setup(self) -> TokenStream {
+		match self {
+			Self::Native => TokenStream::new(),
+			Self::Blocking => quote!(let fs = Arc::clone(&self.fs);),
+		}
+	}
+
+	/// Expression of the pool the call is spawned on.
+	fn pool(self) -> TokenStream {
+		match self {
+			Self::Native => quote!(ThreadPool::global()),
+			Self::Blocking => quote!(self.pool),
+		}
+	}
+
+	/// The forwarded call of the function of `module` with the given arguments.
+	fn call(self, module: &Module, name: &str, args: impl Iterator<Item = Ident>) -> TokenStream {
+		match self {
+			Self::Native => {
+				let function = print::path(&module.item_path(name));
+				quote!(#function(#(#args),*))
+			}
+			Self::Blocking => {
+				let trait_name = format_ident!("{}", module.names.trait_name);
+				let name = format_ident!("{name}");
+				quote!(#trait_name::#name(&*fs, #(#args),*))
+			}
+		}
+	}
+}
+
+fn is_iterator(struct_trait: &StructTrait) -> bool {
+	struct_trait.supertraits.iter().any(|supertrait| supertrait.path == "Iterator")
+}
+
+/// Returns the structs referenced by the signatures of the functions, along with the structs
+/// those reference in turn, in the order of `struct_traits`.
+fn referenced_structs(
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Vec<Id> {
+	let referenced = RefCell::new(HashSet::new());
+	let reference_visitor = |id| {
+		referenced.borrow_mut().insert(id);
+		true
+	};
+	for item in function_list {
+		for (_, input_type) in &item.inner.sig.inputs {
+			visitor::visit_type(input_type, &reference_visitor);
+		}
+		if let Some(output_type) = &item.inner.sig.output {
+			visitor::visit_type(output_type, &reference_visitor);
+		}
+	}
+	let mut referenced = referenced.into_inner();
+	loop {
+		let count = referenced.len();
+		for struct_trait in struct_traits {
+			if referenced.contains(&struct_trait.item.base.id) {
+				referenced.extend(struct_trait.associated_types.iter().copied());
+			}
+		}
+		if referenced.len() == count {
+			break;
+		}
+	}
+	struct_traits
+		.iter()
+		.map(|struct_trait| struct_trait.item.base.id)
+		.filter(|id| referenced.contains(id))
+		.collect()
+}
+
+/// Returns the struct returned by the function if it is an iterator, which is drained on the
+/// pool so that iterating does not block.
+fn iterator_output<'a>(
+	struct_traits: &'a [StructTrait<'a>],
+	function: &Function,
+) -> Option<&'a StructTrait<'a>> {
+	let output_type = print::first_type_arg(function.sig.output.as_ref()?)?;
+	let Type::ResolvedPath(path) = output_type else {
+		return None;
+	};
+	struct_traits
+		.iter()
+		.find(|struct_trait| struct_trait.item.base.id == path.id && is_iterator(struct_trait))
+}
+
+/// Returns the type a backend uses for the struct, buffering iterators as `Blocking` types.
+fn backend_type(
+	backend: Backend,
+	struct_traits: &[StructTrait],
+	struct_trait: &StructTrait,
+) -> TokenStream {
+	if !is_iterator(struct_trait) {
+		return backend.struct_type(struct_trait);
+	}
+	let blocking_name = format_ident!("Blocking{}", struct_trait.name);
+	let args = struct_traits
+		.iter()
+		.filter(|other| struct_trait.associated_types.contains(&other.item.base.id))
+		.map(|other| backend.struct_type(other));
+	quote!(#blocking_name<#(#args),*>)
+}
+
+fn impl_items(
+	backend: Backend,
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+	referenced: &[Id],
+) -> Result<TokenStream, SourceError> {
+	let ctx = associated_context(struct_traits, referenced);
+	let referenced_traits =
+		struct_traits.iter().filter(|struct_trait| referenced.contains(&struct_trait.item.base.id));
+
+	let generics = backend.generics();
+	let self_type = backend.self_type(module);
+	let trait_name = format_ident!("{}", module.names.trait_name);
+	let async_name = format_ident!("{}", module.names.async_fs);
+	let where_clause = matches!(backend, Backend::Blocking).then(|| {
+		// Iterators are drained on the pool, so only the types leaving it need to be `Send`.
+		let sent = referenced_traits
+			.clone()
+			.filter(|struct_trait| !is_iterator(struct_trait))
+			.map(|struct_trait| format_ident!("{}", struct_trait.name));
+		quote!(where F: #trait_name + Send + Sync + 'static, #(F::#sent: Send,)*)
+	});
+	let types = referenced_traits.map(|struct_trait| {
+		let name = format_ident!("{}", struct_trait.name);
+		let backend_type = backend_type(backend, struct_traits, struct_trait);
+		quote!(type #name = #backend_type;)
+	});
+	let methods = function_list.iter().map(|item| {
+		let cfg = print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name))?;
+		let method = print::async_method(&ctx, item.name, item.inner)
+			.map_err(SourceError::printing(item.base.id, item.name))?;
+		let setup = backend.setup();
+		let owned = item
+			.inner
+			.sig
+			.inputs
+			.iter()
+			.filter(|(_, input_type)| {
+				matches!(input_type, Type::Generic(generic_name)
+					if print::as_ref_target(item.inner, generic_name).is_some())
+			})
+			.map(|(input_name, _)| {
+				let input_name = print::input_name(input_name)?;
+				Ok(quote!(let #input_name = #input_name.as_ref().to_owned();))
+			})
+			.collect::<Result<Vec<_>, print::Error>>()
+			.map_err(SourceError::printing(item.base.id, item.name))?;
+		let mut call = backend.call(module, item.name, print::input_names(item.inner));
+		if let Some(struct_trait) = iterator_output(struct_traits, item.inner) {
+			let blocking_name = format_ident!("Blocking{}", struct_trait.name);
+			call = quote!(#call.map(#blocking_name::new));
+		}
+		let pool = backend.pool();
+		Ok(quote! {
+			#cfg
+			#method {
+				#setup
+				#(#owned)*
+				#pool.spawn(move || #call)
+			}
+		})
+	});
+	let methods = methods.collect::<Result<Vec<_>, SourceError>>()?;
+	Ok(quote! {
+		impl #generics #async_name for #self_type #where_clause {
+			#(#types)*
+			#(#methods)*
+		}
+	})
+}
+
+fn async_fs_doc(module: &Module) -> String {
+	let trait_name = &module.names.trait_name;
+	let native = &module.names.native;
+	format!(
+		"Asynchronous counterpart of [`{trait_name}`].
+
+Methods return `Send` futures, so that callers can spawn them on multi-threaded executors.
+Implementations are free to use `async fn` instead. Values such as metadata are the blocking
+types of [`{trait_name}`], and directories are read ahead of time so that iterating them does not block.
+
+[`{native}`] runs every call on the [global](ThreadPool::global) [`ThreadPool`], and [`Blocking`]
+does the same for any other [`{trait_name}`]."
+	)
+}
+
+fn async_fs_trait_items(
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+	referenced: &[Id],
+) -> Result<TokenStream, SourceError> {
+	let ctx = associated_context(struct_traits, referenced);
+	let doc = print::doc_text(&async_fs_doc(module));
+	let async_name = format_ident!("{}", module.names.async_fs);
+	let declarations = struct_traits
+		.iter()
+		.filter(|struct_trait| referenced.contains(&struct_trait.item.base.id))
+		.map(|struct_trait| {
+			associated_type(struct_traits, struct_trait, referenced, &[quote!(Send)])
+		});
+	let methods = function_list.iter().map(|item| {
+		let cfg = print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name))?;
+		let doc = print::doc(item.base);
+		let method = print::async_method(&ctx, item.name, item.inner)
+			.map_err(SourceError::printing(item.base.id, item.name))?;
+		Ok(quote! {
+			#cfg
+			#doc
+			#method;
+		})
+	});
+	let methods = methods.collect::<Result<Vec<_>, SourceError>>()?;
+	Ok(quote! {
+		#doc
+		pub trait #async_name {
+			#(#declarations)*
+			#(#methods)*
+		}
+	})
+}
+
+pub fn generate_async_fs(
+	output_path: impl AsRef<Path>,
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	let referenced = referenced_structs(function_list, struct_traits);
+	let referenced_traits =
+		struct_traits.iter().filter(|struct_trait| referenced.contains(&struct_trait.item.base.id));
+	let trait_imports = [
+		&module.names.trait_name,
+		&module.names.native,
+	]
+	.map(|name| import(&module.files.functions, name));
+	let imports = referenced_traits
+		.clone()
+		.map(|struct_trait| import(&module.files.structs, struct_trait.name));
+	let blocking_imports = referenced_traits
+		.filter(|struct_trait| is_iterator(struct_trait))
+		.map(|struct_trait| format_ident!("Blocking{}", struct_trait.name));
+	let mut items = quote! {
+		use std::future::Future;
+		use std::io;
+		use std::path;
+		use std::sync::Arc;
+
+		#(#trait_imports)*
+		#(#imports)*
+		use crate::Blocking;
+		#(use crate::#blocking_imports;)*
+		use crate::ThreadPool;
+	};
+	items.extend(async_fs_trait_items(module, function_list, struct_traits, &referenced)?);
+	items.extend(impl_items(Backend::Native, module, function_list, struct_traits, &referenced)?);
+	items.extend(impl_items(Backend::Blocking, module, function_list, struct_traits, &referenced)?);
+	write_source(output_path, items)
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use rustdoc_types::Item;
+use serde::Deserialize;
+
+use crate::platform::Platform;
+use crate::print;
+use crate::rustdoc_util;
+use crate::targets::Targets;
+
+/// What `regen-src` generates, as read from `data/config.toml`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+	/// Modules of the standard library that are mirrored, each into its own files.
+	pub modules: Vec<Module>,
+	/// Directory the generated files are written to, relative to the workspace. Its `mod.rs`
+	/// declares the files of every module.
+	pub output_dir: String,
+	#[serde(default)]
+	pub items: Items,
+	/// Names of the traits generated for structs, by path, where they differ from the struct.
+	#[serde(default)]
+	pub renames: BTreeMap<String, String>,
+	/// Supertraits added to the traits generated for structs, by path, on top of the mirrored
+	/// implementations.
+	#[serde(default)]
+	pub bounds: BTreeMap<String, Vec<String>>,
+	#[serde(default)]
+	pub supertraits: Vec<Supertrait>,
+	pub targets: Targets,
+}
+
+impl Config {
+	/// Checks that the generated names, and the module names given by the generated files, are
+	/// identifiers.
+	pub fn check_names(&self) -> Result<(), print::Error> {
+		for module in &self.modules {
+			for name in module.names.all() {
+				print::ident("generated name", name)?;
+			}
+			for file in
+				module.files.all().chain(module.platforms.iter().map(|platform| &platform.file))
+			{
+				print::ident("file name", module_name(file))?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A module whose functions become methods of a trait, and whose structs become the traits of its
+/// handles, as listed in the `modules` of the [`Config`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Module {
+	/// Path of the module, such as `std::fs`.
+	pub path: String,
+	pub names: Names,
+	pub files: Files,
+	#[serde(default)]
+	pub platforms: Vec<Platform>,
+}
+
+impl Module {
+	/// Returns the path of an item of the module, such as `std::fs::File`.
+	pub fn item_path(&self, name: &str) -> String {
+		format!("{}::{name}", self.path)
+	}
+
+	/// Returns the last segment of the path, such as `fs`, which is also the name of the module
+	/// extending it in `std::os::{platform}`.
+	pub fn name(&self) -> &str {
+		self.path.rsplit("::").next().unwrap_or(&self.path)
+	}
+}
+
+/// Names of the traits and backends generated for a [`Module`].
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Names {
+	/// Trait whose methods mirror the functions of the module, such as `Fs`.
+	#[serde(rename = "trait")]
+	pub trait_name: String,
+	/// Backend implementing the trait by calling the module, such as `Native`.
+	pub native: String,
+	/// Dyn-compatible counterpart of the trait, such as `DynFs`.
+	pub dynamic: String,
+	/// Asynchronous counterpart of the trait, such as `AsyncFs`.
+	pub async_fs: String,
+	/// Implementation of the trait checking expected calls, such as `MockFs`.
+	pub mock: String,
+}
+
+impl Names {
+	fn all(&self) -> impl Iterator<Item = &String> {
+		[
+			&self.trait_name,
+			&self.native,
+			&self.dynamic,
+			&self.async_fs,
+			&self.mock,
+		]
+		.into_iter()
+	}
+}
+
+/// Names of the files generated from a [`Module`], within [`Config::output_dir`]. Each is declared
+/// as a module named after the file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Files {
+	pub structs: String,
+	pub functions: String,
+	pub dynamic: String,
+	pub async_fs: String,
+	pub mock: String,
+}
+
+impl Files {
+	pub fn all(&self) -> impl Iterator<Item = &String> {
+		[
+			&self.structs,
+			&self.functions,
+			&self.dynamic,
+			&self.async_fs,
+			&self.mock,
+		]
+		.into_iter()
+	}
+}
+
+/// Returns the name of the module declared for a generated file, such as `structs` for
+/// `structs.rs`.
+pub fn module_name(file: &str) -> &str {
+	file.strip_suffix(".rs").unwrap_or(file)
+}
+
+/// Rules selecting the items that are generated, by path such as `std::fs::File::lock`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(
+	default,
+	deny_unknown_fields
+)]
+pub struct Items {
+	/// Whether deprecated items are generated.
+	pub deprecated: bool,
+	/// Whether items only available on nightly Rust are generated.
+	pub unstable: bool,
+	/// Items that are generated regardless of the rules above.
+	pub include: Vec<String>,
+	/// Items that are never generated.
+	pub exclude: Vec<String>,
+}
+
+impl Items {
+	/// Returns whether the item at `path` is generated.
+	pub fn keeps(&self, path: &str, item: &Item) -> bool {
+		if self.exclude.iter().any(|excluded| excluded == path) {
+			return false;
+		}
+		self.include.iter().any(|included| included == path)
+			|| (self.deprecated || item.deprecation.is_none())
+				&& (self.unstable || !rustdoc_util::is_unstable(item))
+	}
+}
+
+/// A trait implemented by structs that is mirrored as a supertrait of the generated traits.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Supertrait {
+	/// Path of the trait in the documentation, such as `core::clone::Clone`.
+	pub std: String,
+	/// Path emitted in the generated sources, which may rely on their imports.
+	pub path: String,
+}
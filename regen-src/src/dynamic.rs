@@ -0,0 +1,538 @@
+use std::iter;
+use std::path::Path;
+
+use log::info;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use rustdoc_types::Function;
+use rustdoc_types::Id;
+use rustdoc_types::Type;
+
+use crate::associated_context;
+use crate::config::Module;
+use crate::import;
+use crate::print;
+use crate::rustdoc_util::NamedItem;
+use crate::to_snake_case;
+use crate::write_source;
+use crate::SourceError;
+use crate::StructTrait;
+
+/// How the receiver of a method is passed on when forwarding a call.
+#[derive(Clone, Copy)]
+enum Receiver {
+	Ref,
+	Mut,
+	Value,
+}
+
+impl Receiver {
+	fn of(function: &Function) -> Option<Self> {
+		let (input_name, input_type) = function.sig.inputs.first()?;
+		if input_name != "self" {
+			return None;
+		}
+		Some(match input_type {
+			Type::BorrowedRef {
+				is_mutable: true,
+				..
+			} => Self::Mut,
+			Type::BorrowedRef {
+				..
+			} => Self::Ref,
+			_ => Self::Value,
+		})
+	}
+
+	/// The receiver expression inside the blanket implementation, where `self` is the handle.
+	fn blanket(self) -> TokenStream {
+		match self {
+			Self::Ref | Self::Mut => quote!(self),
+			Self::Value => quote!(*self),
+		}
+	}
+
+	/// The receiver expression inside the implementation for boxed trait objects.
+	fn boxed(self) -> TokenStream {
+		match self {
+			Self::Ref => quote!(&**self),
+			Self::Mut => quote!(&mut **self),
+			Self::Value => quote!(self),
+		}
+	}
+}
+
+/// Returns whether the type is printed as a boxed trait object in dyn-compatible signatures.
+fn is_boxed(ctx: &print::Context, item_type: &Type) -> bool {
+	match item_type {
+		Type::Generic(_) => ctx.self_type.is_some() && print::is_self(item_type),
+		Type::ResolvedPath(path) => {
+			ctx.self_type.is_some_and(|(id, _)| id == path.id)
+				|| ctx.associated_types.contains_key(&path.id)
+		}
+		_ => false,
+	}
+}
+
+/// Returns an expression boxing the handles within `expr`, which has the given type, or `None` if
+/// it does not hold any.
+fn box_expr(
+	ctx: &print::Context,
+	expr: &TokenStream,
+	item_type: &Type,
+) -> Result<Option<TokenStream>, print::Error> {
+	if is_boxed(ctx, item_type) {
+		let boxed_type = print::ty(ctx, item_type)?;
+		return Ok(Some(quote!(Box::new(#expr) as #boxed_type)));
+	}
+	let Some(inner_type) = print::first_type_arg(item_type) else {
+		return Ok(None);
+	};
+	let inner = box_expr(ctx, &quote!(value), inner_type)?;
+	Ok(inner.map(|inner| quote!(#expr.map(|value| #inner))))
+}
+
+/// Returns a call forwarding the method from the blanket implementation to the generic trait.
+fn blanket_call(
+	ctx: &print::Context,
+	trait_name: &str,
+	name: &str,
+	function: &Function,
+) -> Result<TokenStream, print::Error> {
+	let receiver = Receiver::of(function).map_or_else(|| quote!(self), Receiver::blanket);
+	let args = function
+		.sig
+		.inputs
+		.iter()
+		.filter(|(input_name, _)| input_name != "self")
+		.map(|(input_name, input_type)| {
+			let input_name = print::input_name(input_name)?;
+			if is_boxed(ctx, input_type) {
+				Ok(quote!(downcast(#input_name)?))
+			} else {
+				Ok(quote!(#input_name))
+			}
+		})
+		.collect::<Result<Vec<_>, print::Error>>()?;
+	let args = iter::once(receiver).chain(args);
+	let trait_name = format_ident!("{trait_name}");
+	let name = format_ident!("{name}");
+	let call = quote!(#trait_name::#name(#(#args),*));
+	Ok(match &function.sig.output {
+		Some(output_type) if print::is_builder_output(Some(output_type)) => quote!(#call;),
+		Some(output_type) => box_expr(ctx, &call, output_type)?.unwrap_or(call),
+		None => call,
+	})
+}
+
+/// Returns a call forwarding the method from the implementation for boxed trait objects, which
+/// implement the dyn-compatible trait `dyn_name`.
+fn boxed_call(
+	dyn_name: &str,
+	name: &str,
+	function: &Function,
+) -> Result<TokenStream, print::Error> {
+	let receiver = Receiver::of(function).map_or_else(|| quote!(&**self), Receiver::boxed);
+	let args = function
+		.sig
+		.inputs
+		.iter()
+		.filter(|(input_name, _)| input_name != "self")
+		.map(|(input_name, input_type)| {
+			let is_as_ref = matches!(input_type, Type::Generic(generic_name)
+				if print::as_ref_target(function, generic_name).is_some());
+			let input_name = print::input_name(input_name)?;
+			if is_as_ref {
+				Ok(quote!(#input_name.as_ref()))
+			} else {
+				Ok(quote!(#input_name))
+			}
+		})
+		.collect::<Result<Vec<_>, print::Error>>()?;
+	let args = iter::once(receiver).chain(args);
+	let dyn_name = format_ident!("{dyn_name}");
+	let name = format_ident!("{name}");
+	let call = quote!(#dyn_name::#name(#(#args),*));
+	if print::is_builder_output(function.sig.output.as_ref()) {
+		Ok(quote! {
+			#call;
+			self
+		})
+	} else {
+		Ok(call)
+	}
+}
+
+/// Returns a function attaching a method of the struct to print errors.
+fn method_error(
+	struct_trait: &StructTrait,
+	method: &NamedItem<Function>,
+) -> impl FnOnce(print::Error) -> SourceError {
+	SourceError::printing(method.base.id, format!("{}::{}", struct_trait.item.name, method.name))
+}
+
+/// Returns the `where` clause requiring the associated types of `T` to be dyn-compatible as well.
+fn where_clause(
+	struct_traits: &[StructTrait],
+	trait_name: &str,
+	associated_types: &[Id],
+) -> TokenStream {
+	let trait_name = format_ident!("{trait_name}");
+	let bounds = struct_traits
+		.iter()
+		.filter(|other| associated_types.contains(&other.item.base.id))
+		.map(|other| {
+			let name = format_ident!("{}", other.name);
+			let dyn_name = format_ident!("Dyn{}", other.name);
+			quote!(T::#name: #dyn_name)
+		});
+	quote!(where T: #trait_name + Any, #(#bounds,)*)
+}
+
+fn struct_trait_items(
+	struct_traits: &[StructTrait],
+	struct_trait: &StructTrait,
+) -> Result<TokenStream, SourceError> {
+	let name = struct_trait.name;
+	let mut ctx = associated_context(struct_traits, &struct_trait.associated_types);
+	ctx.self_type = Some((struct_trait.item.base.id, name));
+	let mut boxed_ctx = associated_context(struct_traits, &struct_trait.associated_types);
+	boxed_ctx.self_type = ctx.self_type;
+	boxed_ctx.boxed = true;
+	let is_clone = struct_trait.supertraits.iter().any(|supertrait| supertrait.path == "Clone");
+	let item_binding = struct_trait
+		.supertraits
+		.iter()
+		.filter(|supertrait| supertrait.path == "Iterator")
+		.flat_map(|supertrait| &supertrait.bindings)
+		.find(|(binding_name, _)| *binding_name == "Item")
+		.map(|(_, binding_type)| binding_type);
+
+	let doc = print::doc_text(&format!(
+		"Dyn-compatible counterpart of [`{name}`], implemented for every `'static` [`{name}`]."
+	));
+	let dyn_name = format_ident!("Dyn{name}");
+	let boxed = print::boxed(name);
+	// These are not dyn-compatible, and are replaced by methods below.
+	let supertraits = struct_trait
+		.supertraits
+		.iter()
+		.filter(|supertrait| supertrait.path != "Clone" && supertrait.path != "Iterator")
+		.map(|supertrait| print::path(supertrait.path));
+	let clone_box =
+		is_clone.then(|| quote!(fn clone_box(&self) -> #boxed)).into_iter().collect::<Vec<_>>();
+	let next_entry = item_binding
+		.into_iter()
+		.map(|item_type| {
+			let item_type = print::ty(&boxed_ctx, item_type)?;
+			Ok(quote!(fn next_entry(&mut self) -> Option<#item_type>))
+		})
+		.collect::<Result<Vec<_>, print::Error>>()
+		.map_err(SourceError::printing(struct_trait.item.base.id, struct_trait.item.name))?;
+	let signatures = struct_trait
+		.methods
+		.iter()
+		.map(|method| {
+			let cfg = print::cfg(method.base).map_err(method_error(struct_trait, method))?;
+			let method_name = format_ident!("{}", method.name);
+			let args = print::dyn_function_args(&boxed_ctx, method.inner, None)
+				.map_err(method_error(struct_trait, method))?;
+			Ok(quote!(#cfg fn #method_name #args))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let mut items = quote! {
+		#doc
+		pub trait #dyn_name: Any #(+ #supertraits)* {
+			#(#clone_box;)*
+			#(#next_entry;)*
+			#(#signatures;)*
+		}
+	};
+
+	let where_clause = where_clause(struct_traits, name, &struct_trait.associated_types);
+	let next = item_binding
+		.map(|item_type| box_expr(&boxed_ctx, &quote!(value), item_type))
+		.transpose()
+		.map_err(SourceError::printing(struct_trait.item.base.id, struct_trait.item.name))?
+		.flatten()
+		.unwrap_or_else(|| quote!(value));
+	let next = next_entry.iter().map(|_| &next);
+	let calls = struct_trait
+		.methods
+		.iter()
+		.map(|method| {
+			blanket_call(&boxed_ctx, name, method.name, method.inner)
+				.map_err(method_error(struct_trait, method))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	items.extend(quote! {
+		impl<T> #dyn_name for T #where_clause {
+			#(#clone_box {
+				Box::new(Clone::clone(self))
+			})*
+			#(#next_entry {
+				Iterator::next(self).map(|value| #next)
+			})*
+			#(#signatures {
+				#calls
+			})*
+		}
+	});
+
+	items.extend(boxed_impls(
+		struct_traits,
+		struct_trait,
+		&ctx,
+		&boxed_ctx,
+		is_clone,
+		item_binding,
+	)?);
+	Ok(items)
+}
+
+/// Returns the implementations making boxed trait objects usable as the original trait, including
+/// the supertraits that are not dyn-compatible.
+fn boxed_impls(
+	struct_traits: &[StructTrait],
+	struct_trait: &StructTrait,
+	ctx: &print::Context,
+	boxed_ctx: &print::Context,
+	is_clone: bool,
+	item_binding: Option<&Type>,
+) -> Result<TokenStream, SourceError> {
+	let name = struct_trait.name;
+	let trait_name = format_ident!("{name}");
+	let dyn_name = format_ident!("Dyn{name}");
+	let boxed = print::boxed(name);
+	let associated_names = struct_traits
+		.iter()
+		.filter(|other| struct_trait.associated_types.contains(&other.item.base.id))
+		.map(|other| other.name);
+	let associated_types = associated_names.clone().map(|other| format_ident!("{other}"));
+	let associated_boxes = associated_names.map(print::boxed);
+	let methods = struct_trait
+		.methods
+		.iter()
+		.map(|method| {
+			let cfg = print::cfg(method.base).map_err(method_error(struct_trait, method))?;
+			let method_name = format_ident!("{}", method.name);
+			let args = print::function_args(ctx, method.inner, None)
+				.map_err(method_error(struct_trait, method))?;
+			let call = boxed_call(&format!("Dyn{name}"), method.name, method.inner)
+				.map_err(method_error(struct_trait, method))?;
+			Ok(quote! {
+				#cfg
+				fn #method_name #args {
+					#call
+				}
+			})
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let mut items = quote! {
+		impl #trait_name for #boxed {
+			#(type #associated_types = #associated_boxes;)*
+			#(#methods)*
+		}
+	};
+
+	if is_clone {
+		items.extend(quote! {
+			impl Clone for #boxed {
+				fn clone(&self) -> Self {
+					#dyn_name::clone_box(&**self)
+				}
+			}
+		});
+	}
+	if let Some(item_type) = item_binding {
+		let item_type = print::ty(boxed_ctx, item_type)
+			.map_err(SourceError::printing(struct_trait.item.base.id, struct_trait.item.name))?;
+		items.extend(quote! {
+			impl Iterator for #boxed {
+				type Item = #item_type;
+
+				fn next(&mut self) -> Option<Self::Item> {
+					#dyn_name::next_entry(&mut **self)
+				}
+			}
+		});
+	}
+	Ok(items)
+}
+
+fn fs_doc(module: &Module) -> String {
+	let trait_name = &module.names.trait_name;
+	let dyn_name = &module.names.dynamic;
+	format!(
+		"Dyn-compatible counterpart of [`{trait_name}`], implemented for every `'static` \
+		 [`{trait_name}`].
+
+Paths are taken as `&Path` and handles are returned as boxed trait objects. In turn, boxed
+`{dyn_name}` trait objects implement [`{trait_name}`], so a filesystem chosen at runtime can be stored without
+making its owner generic. Call methods through [`{trait_name}`], as method calls are ambiguous when both
+traits are in scope.
+
+# Examples
+
+```
+use io_fs::{trait_name};
+use io_fs::MemFs;
+
+struct Config {{
+\tfs: Box<dyn io_fs::{dyn_name}>,
+}}
+
+let config = Config {{
+\tfs: Box::new(MemFs::new()),
+}};
+config.fs.write(\"config.toml\", \"verbose = true\")?;
+assert!(config.fs.exists(\"config.toml\")?);
+# Ok::<(), std::io::Error>(())
+```"
+	)
+}
+
+fn fs_trait_items(
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<TokenStream, SourceError> {
+	let struct_ids =
+		struct_traits.iter().map(|struct_trait| struct_trait.item.base.id).collect::<Vec<_>>();
+	let ctx = associated_context(struct_traits, &struct_ids);
+	let mut boxed_ctx = associated_context(struct_traits, &struct_ids);
+	boxed_ctx.boxed = true;
+	let constructors = struct_traits
+		.iter()
+		.filter_map(|struct_trait| {
+			let constructor = struct_trait.constructor.as_ref()?;
+			let name = struct_trait.name;
+			let cfg = print::cfg(constructor.base).map_err(method_error(struct_trait, constructor));
+			Some(cfg.map(|cfg| {
+				let snake_name = format_ident!("{}", to_snake_case(name));
+				(cfg, format_ident!("{name}"), snake_name, print::boxed(name))
+			}))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let functions = function_list.iter();
+
+	let doc = print::doc_text(&fs_doc(module));
+	let names = &module.names;
+	let trait_name = format_ident!("{}", names.trait_name);
+	let dyn_name = format_ident!("{}", names.dynamic);
+	let constructor_signatures = constructors
+		.iter()
+		.map(|(cfg, _, snake_name, boxed)| quote!(#cfg fn #snake_name(&self) -> #boxed))
+		.collect::<Vec<_>>();
+	let signatures = functions
+		.clone()
+		.map(|item| {
+			let cfg =
+				print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name))?;
+			let name = format_ident!("{}", item.name);
+			let args = print::dyn_function_args(&boxed_ctx, item.inner, Some(quote!(&self)))
+				.map_err(SourceError::printing(item.base.id, item.name))?;
+			Ok(quote!(#cfg fn #name #args))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let mut items = quote! {
+		#doc
+		pub trait #dyn_name: Any {
+			#(#constructor_signatures;)*
+			#(#signatures;)*
+		}
+	};
+
+	let where_clause = where_clause(struct_traits, &names.trait_name, &struct_ids);
+	let constructor_names = constructors.iter().map(|(_, _, snake_name, _)| snake_name);
+	let calls = functions
+		.clone()
+		.map(|item| {
+			blanket_call(&boxed_ctx, &names.trait_name, item.name, item.inner)
+				.map_err(SourceError::printing(item.base.id, item.name))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	items.extend(quote! {
+		impl<T> #dyn_name for T #where_clause {
+			#(#constructor_signatures {
+				Box::new(#trait_name::#constructor_names(self))
+			})*
+			#(#signatures {
+				#calls
+			})*
+		}
+	});
+
+	let struct_names = struct_traits.iter().map(|struct_trait| struct_trait.name);
+	let struct_types = struct_names.clone().map(|name| format_ident!("{name}"));
+	let struct_boxes = struct_names.map(print::boxed);
+	let constructor_impls = constructors.iter().map(|(cfg, name, snake_name, _)| {
+		quote! {
+			#cfg
+			fn #snake_name(&self) -> Self::#name {
+				#dyn_name::#snake_name(&**self)
+			}
+		}
+	});
+	let methods = functions
+		.map(|item| {
+			let cfg =
+				print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name))?;
+			let method = print::method(&ctx, item.name, item.inner)
+				.map_err(SourceError::printing(item.base.id, item.name))?;
+			let call = boxed_call(&names.dynamic, item.name, item.inner)
+				.map_err(SourceError::printing(item.base.id, item.name))?;
+			Ok(quote! {
+				#cfg
+				#method {
+					#call
+				}
+			})
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	items.extend(quote! {
+		impl<D: #dyn_name + ?Sized> #trait_name for Box<D> {
+			#(type #struct_types = #struct_boxes;)*
+			#(#constructor_impls)*
+			#(#methods)*
+		}
+	});
+	Ok(items)
+}
+
+pub fn generate_dynamic(
+	output_path: impl AsRef<Path>,
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	let trait_import = import(&module.files.functions, &module.names.trait_name);
+	let imports =
+		struct_traits.iter().map(|struct_trait| import(&module.files.structs, struct_trait.name));
+	let mut items = quote! {
+		use std::any::Any;
+		use std::ffi::OsString;
+		use std::io;
+		use std::path;
+		use std::path::Path;
+		use std::path::PathBuf;
+		use std::time::SystemTime;
+
+		#trait_import
+		#(#imports)*
+
+		/// Helper used by the blanket implementations to recover concrete handles passed back in.
+		fn downcast<T: Any>(value: Box<dyn Any>) -> io::Result<T> {
+			value.downcast().map(|value| *value).map_err(|_| {
+				io::Error::new(io::ErrorKind::InvalidInput, "handle belongs to a different backend")
+			})
+		}
+	};
+	items.extend(fs_trait_items(module, function_list, struct_traits)?);
+	for struct_trait in struct_traits {
+		items.extend(struct_trait_items(struct_traits, struct_trait)?);
+	}
+	write_source(output_path, items)
+}
@@ -1,25 +1,30 @@
 #![warn(clippy::pedantic)]
+mod async_fs;
+mod config;
+mod dynamic;
+mod mock;
+mod platform;
 mod print;
 mod rustdoc_util;
+mod targets;
 mod visitor;
 
-use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fmt;
 use std::fs;
-use std::fs::OpenOptions;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::io::Write;
 use std::io::{self};
-use std::panic::panic_any;
 use std::path::Path;
 use std::path::{self};
-use std::process::Command;
-use std::process::Stdio;
-use std::thread;
 
 use log::info;
+use log::warn;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
 use rustdoc_types::Id;
 use rustdoc_types::ItemEnum;
+use rustdoc_types::ItemKind;
 use thiserror::Error;
 
 const HEADER: &str = include_str!("../data/header.rs");
@@ -27,73 +32,37 @@ const HEADER: &str = include_str!("../data/header.rs");
 fn main() -> anyhow::Result<()> {
 	env_logger::init();
 
-	let input_path = path::absolute("data/std.json")?;
-	info!("Parsing doc from {}...", input_path.display());
-	let input_data = fs::read_to_string(input_path)?;
-	let mut doc_crate = serde_json::from_str(&input_data)?;
-	remove_preludes(&mut doc_crate).map_err(SourceError::ParseError)?;
+	let config_path = path::absolute("regen-src/data/config.toml")?;
+	info!("Reading config from {}...", config_path.display());
+	let config = toml::from_str::<config::Config>(&fs::read_to_string(config_path)?)?;
+	config.check_names()?;
 
-	let output_dir = path::absolute("src/generated/")?;
-	info!("Regenerating source into {}...", output_dir.display());
-	json_to_rs(&doc_crate, &output_dir)?;
+	let targets = &config.targets;
+	anyhow::ensure!(
+		(1..=targets::Targets::MAX).contains(&targets.documented.len()),
+		"between 1 and {} targets must be documented",
+		targets::Targets::MAX,
+	);
+	let mut docs = Vec::new();
+	for target in &targets.documented {
+		let input_path = path::absolute(format!("data/std/{}.json", target.triple))?;
+		info!("Parsing doc from {}...", input_path.display());
+		let input_data = fs::read_to_string(input_path)?;
+		let mut doc_crate = serde_json::from_str(&input_data)?;
+		remove_preludes(&mut doc_crate).map_err(SourceError::ParseError)?;
+		docs.push(doc_crate);
+	}
+	info!("Merging targets...");
+	let doc_crate = targets::merge(targets, docs).map_err(SourceError::ParseError)?;
 
-	info!("Formatting generated files...");
-	let paths = fs::read_dir(&output_dir)?
-		.map(|entry| entry.map(|entry| entry.path().as_os_str().to_string_lossy().into_owned()))
-		.collect::<Result<Vec<_>, _>>()?;
-	rustfmt(&paths)?;
+	let output_dir = path::absolute(&config.output_dir)?;
+	info!("Regenerating source into {}...", output_dir.display());
+	json_to_rs(&doc_crate, &config, &output_dir)?;
 
 	info!("Done!");
 	Ok(())
 }
 
-fn rustfmt(paths: &[String]) -> io::Result<()> {
-	let cargo_path = env!("CARGO");
-	let manifest_path = env!("CARGO_MANIFEST_DIR");
-	let mut command = Command::new(cargo_path);
-	command.current_dir(manifest_path);
-	command.args([
-		"fmt",
-		"--",
-	]);
-	command.args(paths);
-	command_redirect_output(command)
-}
-
-fn command_redirect_output(mut command: Command) -> io::Result<()> {
-	command.stdout(Stdio::piped()).stderr(Stdio::piped());
-
-	let mut child = command.spawn()?;
-	thread::scope::<_, io::Result<()>>(|scope| {
-		let handle = scope.spawn::<_, io::Result<()>>(|| {
-			if let Some(stdout) = &mut child.stderr {
-				let lines = BufReader::new(stdout).lines();
-				for line in lines {
-					eprintln!("{}", line?);
-				}
-			}
-			Ok(())
-		});
-		if let Some(stdout) = &mut child.stdout {
-			let lines = BufReader::new(stdout).lines();
-			for line in lines {
-				println!("{}", line?);
-			}
-		}
-		propagate_panic(handle.join())?;
-		Ok(())
-	})?;
-	child.wait()?;
-	Ok(())
-}
-
-fn propagate_panic<T>(handle_result: Result<T, Box<dyn Any + Send>>) -> T {
-	match handle_result {
-		Ok(result) => result,
-		Err(a) => panic_any(a),
-	}
-}
-
 fn remove_preludes(doc: &mut rustdoc_types::Crate) -> Result<(), rustdoc_util::ItemError> {
 	let mut prelude_index = None;
 	{
@@ -110,7 +79,7 @@ fn remove_preludes(doc: &mut rustdoc_types::Crate) -> Result<(), rustdoc_util::I
 		}
 	}
 
-	let root_item = rustdoc_util::get_mut(doc, doc.root).unwrap();
+	let root_item = rustdoc_util::get_mut(doc, doc.root)?;
 	let rustdoc_types::ItemEnum::Module(root_module) = &mut root_item.inner else {
 		unreachable!("already checked type earlier");
 	};
@@ -127,224 +96,769 @@ pub enum SourceError {
 	Io(#[from] io::Error),
 	#[error("parse error: {0}")]
 	ParseError(rustdoc_util::ItemError),
+	#[error("generated invalid source")]
+	Syntax(#[from] syn::Error),
+	#[error("cannot find module `{0}`")]
+	MissingModule(String),
+	#[error("cannot print item {} (`{path}`)", id.0)]
+	Print {
+		id: Id,
+		path: String,
+		source: print::Error,
+	},
 }
 
-fn json_to_rs(doc: &rustdoc_types::Crate, output_dir: impl AsRef<Path>) -> Result<(), SourceError> {
-	let mut buf = Vec::new();
+impl SourceError {
+	/// Returns a function attaching the item being printed to print errors, with `path` relative to
+	/// the generated module such as `File::open`. It is made absolute by [`Self::within`].
+	fn printing(id: Id, path: impl fmt::Display) -> impl FnOnce(print::Error) -> Self {
+		move |source| {
+			Self::Print {
+				id,
+				path: path.to_string(),
+				source,
+			}
+		}
+	}
+
+	/// Prefixes the path of print errors with `module`.
+	fn within(self, module: &str) -> Self {
+		match self {
+			Self::Print {
+				id,
+				path,
+				source,
+			} => {
+				Self::Print {
+					id,
+					path: format!("{module}::{path}"),
+					source,
+				}
+			}
+			error => error,
+		}
+	}
+}
 
+fn json_to_rs(
+	doc: &rustdoc_types::Crate,
+	config: &config::Config,
+	output_dir: impl AsRef<Path>,
+) -> Result<(), SourceError> {
 	let path_resolver = rustdoc_util::PathResolver::from(doc).map_err(SourceError::ParseError)?;
-	let root_module = path_resolver.root();
-	for id in &root_module.inner.items {
-		if let Some(item) = doc.index.get(id) {
-			if let rustdoc_types::ItemEnum::Module(item_module) = &item.inner {
-				if item.name.as_ref().is_some_and(|name| name == "fs") {
-					let mut function_list = Vec::new();
-					let mut struct_list = Vec::new();
-
-					for id in &item_module.items {
-						let Some(item) = doc.index.get(id) else {
-							continue;
-						};
-						match &item.inner {
-							rustdoc_types::ItemEnum::Function(function) => {
-								if let Some(name) = &item.name {
-									function_list.push(rustdoc_util::NamedItem {
-										name,
-										base: item,
-										inner: function,
-									});
-								}
-							}
-							rustdoc_types::ItemEnum::Struct(doc_struct) => {
-								if let Some(name) = &item.name {
-									struct_list.push(rustdoc_util::NamedItem {
-										name,
-										base: item,
-										inner: doc_struct,
-									});
-								}
-							}
-							_ => {}
-						}
-					}
+	let output_dir = output_dir.as_ref();
+	for module in &config.modules {
+		generate_module(&path_resolver, config, module, output_dir)?;
+	}
+	generate_mod(output_dir.join("mod.rs"), &config.modules)
+}
+
+/// Generates the files of `module` and of its platforms into `output_dir`.
+fn generate_module(
+	path_resolver: &rustdoc_util::PathResolver,
+	config: &config::Config,
+	module: &config::Module,
+	output_dir: &Path,
+) -> Result<(), SourceError> {
+	let doc = path_resolver.doc();
+	let module_path = module.path.split("::").collect::<Vec<_>>();
+	let Some(ItemEnum::Module(doc_module)) =
+		rustdoc_util::find_item(doc, &module_path, ItemKind::Module)
+			.and_then(|id| doc.index.get(id))
+			.map(|item| &item.inner)
+	else {
+		return Err(SourceError::MissingModule(module.path.clone()));
+	};
 
-					function_list.sort();
-					struct_list.sort();
-
-					generate_structs(
-						output_dir.as_ref().join("structs.rs"),
-						&mut buf,
-						&path_resolver,
-						&struct_list,
-					)?;
-					buf.clear();
-
-					generate_functions(
-						output_dir.as_ref().join("functions.rs"),
-						&mut buf,
-						doc,
-						&function_list,
-					)?;
+	let mut function_list = Vec::new();
+	let mut struct_list = Vec::new();
+	for id in &doc_module.items {
+		let Some(item) = doc.index.get(id) else {
+			continue;
+		};
+		let Some(name) = &item.name else {
+			continue;
+		};
+		if !config.items.keeps(&module.item_path(name), item) {
+			continue;
+		}
+		match &item.inner {
+			ItemEnum::Function(function) => {
+				function_list.push(rustdoc_util::NamedItem {
+					name,
+					base: item,
+					inner: function,
+				});
+			}
+			ItemEnum::Struct(doc_struct) => {
+				// Handles are associated types of the module trait, which cannot depend on the
+				// target.
+				if item.attrs.iter().any(|attr| attr.starts_with("#[cfg(")) {
+					warn!("Skipping struct {name}, which is not on every target");
+					continue;
 				}
+				struct_list.push(rustdoc_util::NamedItem {
+					name,
+					base: item,
+					inner: doc_struct,
+				});
 			}
+			_ => {}
 		}
 	}
+	function_list.sort();
+	struct_list.sort();
+
+	let files = &module.files;
+	let struct_traits = collect_struct_traits(path_resolver, config, module, &struct_list);
+	// Print errors of these generators are relative to the module.
+	let generate_files = || -> Result<(), SourceError> {
+		generate_structs(output_dir.join(&files.structs), &struct_traits)?;
+		generate_functions(
+			output_dir.join(&files.functions),
+			module,
+			&function_list,
+			&struct_traits,
+		)?;
+		dynamic::generate_dynamic(
+			output_dir.join(&files.dynamic),
+			module,
+			&function_list,
+			&struct_traits,
+		)?;
+		async_fs::generate_async_fs(
+			output_dir.join(&files.async_fs),
+			module,
+			&function_list,
+			&struct_traits,
+		)?;
+		mock::generate_mock(output_dir.join(&files.mock), module, &function_list, &struct_traits)
+	};
+	generate_files().map_err(|error| error.within(&module.path))?;
+
+	for platform in &module.platforms {
+		platform::generate_platform(
+			output_dir.join(&platform.file),
+			module,
+			platform,
+			&config.targets,
+			&platform::collect_functions(path_resolver, module, platform, &config.items),
+			&struct_traits,
+		)?;
+	}
 	Ok(())
 }
 
-fn generate_structs(
+/// Generates the module declaring the files of every module, whose items are re-exported, and the
+/// files of their platforms, which stay public modules.
+fn generate_mod(
 	output_path: impl AsRef<Path>,
-	buf: &mut Vec<u8>,
-	path_resolver: &rustdoc_util::PathResolver,
-	struct_list: &Vec<rustdoc_util::NamedItem<rustdoc_types::Struct>>,
-) -> io::Result<()> {
-	info!("Generating structs.rs...");
+	modules: &[config::Module],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	write_source(output_path, mod_items(modules))
+}
+
+fn mod_items(modules: &[config::Module]) -> TokenStream {
+	let mut reexported = modules
+		.iter()
+		.flat_map(|module| module.files.all())
+		.map(|file| config::module_name(file))
+		.collect::<Vec<_>>();
+	reexported.sort_unstable();
+	let reexported = reexported.iter().map(|name| format_ident!("{name}")).collect::<Vec<_>>();
+	let mut platforms = modules
+		.iter()
+		.flat_map(|module| &module.platforms)
+		.map(|platform| config::module_name(&platform.file))
+		.collect::<Vec<_>>();
+	platforms.sort_unstable();
+	let platforms = platforms.iter().map(|name| format_ident!("{name}"));
+	quote! {
+		#(
+			mod #reexported;
+			pub use #reexported::*;
+		)*
+		#(pub mod #platforms;)*
+	}
+}
+
+/// Returns the declaration importing `name` from the generated `file` into a sibling file.
+fn import(file: &str, name: &str) -> TokenStream {
+	let module = format_ident!("{}", config::module_name(file));
+	let name = format_ident!("{name}");
+	quote!(use super::#module::#name;)
+}
+
+/// The shape of the trait generated for a struct of the module.
+struct StructTrait<'a> {
+	item: rustdoc_util::NamedItem<'a, rustdoc_types::Struct>,
+	/// Name of the trait, which is that of the struct unless renamed.
+	name: &'a str,
+	/// Path of the struct, such as `std::fs::File`.
+	path: String,
+	supertraits: Vec<Supertrait<'a>>,
+	/// Supertraits added by the config, after the mirrored ones.
+	bounds: &'a [String],
+	/// Associated function creating a default instance, which is exposed through the module trait
+	/// instead.
+	constructor: Option<rustdoc_util::NamedItem<'a, rustdoc_types::Function>>,
+	methods: Vec<rustdoc_util::NamedItem<'a, rustdoc_types::Function>>,
+	/// Other structs referenced by the trait, which become associated types.
+	associated_types: Vec<Id>,
+	/// Traits of `std::os` implemented by the struct, which are generated for their platform only.
+	extensions: Vec<platform::Extension<'a>>,
+}
+
+struct Supertrait<'a> {
+	path: &'a str,
+	/// Associated types of the implementation, whose names are qualified by
+	/// [`rustdoc_util::PathResolver::qualify`].
+	bindings: Vec<(&'a String, rustdoc_types::Type)>,
+}
+
+fn collect_struct_traits<'a>(
+	path_resolver: &rustdoc_util::PathResolver<'a>,
+	config: &'a config::Config,
+	module: &'a config::Module,
+	struct_list: &[rustdoc_util::NamedItem<'a, rustdoc_types::Struct>],
+) -> Vec<StructTrait<'a>> {
 	let doc_crate = path_resolver.doc();
-	let deny = rustdoc_util::find_item(
-		doc_crate,
-		&[
-			"core",
-			"marker",
-			"StructuralPartialEq",
-		],
-	)
-	.unwrap();
+	let supertrait_ids = supertrait_ids(doc_crate, config);
 
+	let mut struct_traits = Vec::new();
 	for item in struct_list {
-		writeln!(buf)?;
-		print::write_doc(buf, item.base)?;
-		writeln!(buf, "pub trait {} {{", item.name)?;
+		let path = module.item_path(item.name);
+		let mut struct_trait = StructTrait {
+			item: item.clone(),
+			name: config.renames.get(&path).unwrap_or(item.name),
+			bounds: config.bounds.get(&path).map_or(&[], Vec::as_slice),
+			path,
+			supertraits: Vec::new(),
+			constructor: None,
+			methods: Vec::new(),
+			associated_types: Vec::new(),
+			extensions: Vec::new(),
+		};
+		let referenced = RefCell::new(HashSet::new());
+		let reference_visitor = |id| {
+			if id != item.base.id {
+				referenced.borrow_mut().insert(id);
+			}
+			true
+		};
+
 		for impl_id in &item.inner.impls {
-			if let Some(impl_item) = doc_crate.index.get(impl_id) {
-				if let ItemEnum::Impl(doc_impl) = &impl_item.inner {
-					if let Some(impl_trait) = &doc_impl.trait_ {
-						if &impl_trait.id == deny {
-							continue;
-						}
-						if doc_impl.blanket_impl.is_some() || doc_impl.is_synthetic {
-							continue;
-						}
-
-						if !visitor::visit_item(impl_item, &|id| {
-							if has_module_with_name(path_resolver, id, "windows") {
-								return false;
-							}
-							if has_module_with_name(path_resolver, id, "unix") {
-								return false;
-							}
-							if has_module_with_name(path_resolver, id, "linux") {
-								return false;
-							}
-							if has_module_with_name(path_resolver, id, "wasi") {
-								return false;
-							}
-							true
-						}) {
-							continue;
-						}
-
-						write!(buf, "// impl ")?;
-						print::write_path(buf, doc_crate, impl_trait)?;
-						writeln!(buf)?;
-						continue;
-					}
-					for item_id in &doc_impl.items {
-						if let Some(impl_item) = doc_crate.index.get(item_id) {
-							if let ItemEnum::Function(impl_func) = &impl_item.inner {
-								write!(
-									buf,
-									"// fn {}",
-									impl_item.name.as_ref().unwrap_or(&"unknown".to_owned())
-								)?;
-								print::write_function_args(buf, doc_crate, impl_func)?;
-								writeln!(buf, ";")?;
-							}
-						}
+			let Some(impl_item) = doc_crate.index.get(impl_id) else {
+				continue;
+			};
+			let ItemEnum::Impl(doc_impl) = &impl_item.inner else {
+				continue;
+			};
+			if doc_impl.blanket_impl.is_some() || doc_impl.is_synthetic {
+				continue;
+			}
+			// Skip implementations for wrappers, such as `impl Read for &File`.
+			if !matches!(&doc_impl.for_, rustdoc_types::Type::ResolvedPath(path) if path.id == item.base.id)
+			{
+				continue;
+			}
+
+			if let Some(impl_trait) = &doc_impl.trait_ {
+				if let Some(extension) = platform::collect_extension(
+					path_resolver,
+					config,
+					module,
+					impl_item,
+					impl_trait,
+				) {
+					struct_trait.extensions.push(extension);
+					continue;
+				}
+				if let Some(supertrait) = collect_supertrait(
+					path_resolver,
+					&supertrait_ids,
+					impl_item,
+					impl_trait,
+					doc_impl,
+				) {
+					for (_, binding_type) in &supertrait.bindings {
+						visitor::visit_type(binding_type, &reference_visitor);
 					}
+					struct_trait.supertraits.push(supertrait);
 				}
+				continue;
 			}
+
+			collect_methods(
+				doc_crate,
+				&config.items,
+				&mut struct_trait,
+				doc_impl,
+				&reference_visitor,
+			);
 		}
-		writeln!(buf, "}}")?;
-		writeln!(buf)?;
-		writeln!(buf, "// impl {0} for std::fs::{0} {{}}", item.name)?;
+
+		struct_trait.supertraits.sort_by_key(|supertrait| {
+			supertrait_ids.iter().position(|(_, path)| *path == supertrait.path)
+		});
+		let referenced = referenced.into_inner();
+		struct_trait.associated_types = struct_list
+			.iter()
+			.map(|other| other.base.id)
+			.filter(|id| referenced.contains(id))
+			.collect();
+		struct_traits.push(struct_trait);
 	}
-	let mut out_file =
-		OpenOptions::new().write(true).create(true).truncate(true).open(&output_path)?;
-	write!(out_file, "{HEADER}")?;
-	out_file.write_all(buf)?;
-	Ok(())
+	struct_traits
 }
 
-fn generate_functions(
-	output_path: impl AsRef<Path>,
-	buf: &mut Vec<u8>,
-	doc_crate: &rustdoc_types::Crate,
-	function_list: &Vec<rustdoc_util::NamedItem<rustdoc_types::Function>>,
-) -> io::Result<()> {
-	info!("Generating functions.rs...");
-	writeln!(buf)?;
-	writeln!(buf, "pub trait Fs {{")?;
-	for item in function_list {
-		if item.base.deprecation.is_some() {
+/// Adds the methods of an inherent implementation of the struct selected by `items` to its trait,
+/// along with its constructor if any. Structs referenced by methods are passed to
+/// `reference_visitor`.
+fn collect_methods<'a, T>(
+	doc_crate: &'a rustdoc_types::Crate,
+	items: &config::Items,
+	struct_trait: &mut StructTrait<'a>,
+	doc_impl: &'a rustdoc_types::Impl,
+	reference_visitor: &T,
+) where
+	T: Fn(Id) -> bool,
+{
+	for item_id in &doc_impl.items {
+		let Some(impl_item) = doc_crate.index.get(item_id) else {
+			continue;
+		};
+		let ItemEnum::Function(impl_func) = &impl_item.inner else {
 			continue;
+		};
+		let Some(name) = &impl_item.name else {
+			continue;
+		};
+		if !items.keeps(&format!("{}::{name}", struct_trait.path), impl_item) {
+			continue;
+		}
+		let method = rustdoc_util::NamedItem {
+			name,
+			base: impl_item,
+			inner: impl_func,
+		};
+		// Constructors may name the struct instead of `Self`, such as `DirBuilder::new`.
+		let returns_self = match &impl_func.sig.output {
+			Some(rustdoc_types::Type::Generic(name)) => name == "Self",
+			Some(rustdoc_types::Type::ResolvedPath(path)) => path.id == struct_trait.item.base.id,
+			_ => false,
+		};
+		if impl_func.sig.inputs.first().is_some_and(|(input_name, _)| input_name == "self") {
+			visitor::visit_item(impl_item, reference_visitor);
+			struct_trait.methods.push(method);
+		} else if impl_func.sig.inputs.is_empty() && returns_self {
+			struct_trait.constructor = Some(method);
 		}
+	}
+}
 
-		writeln!(buf)?;
-		print::write_doc(buf, item.base)?;
-		print::write_function(buf, doc_crate, item.name, item.inner)?;
-		writeln!(buf, ";")?;
+/// Returns the ids of the traits mirrored as supertraits, in order, along with the path that is
+/// emitted for each. Traits that are not documented are left out.
+fn supertrait_ids<'a>(
+	doc: &rustdoc_types::Crate,
+	config: &'a config::Config,
+) -> Vec<(Id, &'a str)> {
+	config
+		.supertraits
+		.iter()
+		.filter_map(|supertrait| {
+			let std_path = supertrait.std.split("::").collect::<Vec<_>>();
+			let id = rustdoc_util::find_item(doc, &std_path, ItemKind::Trait)?;
+			Some((*id, supertrait.path.as_str()))
+		})
+		.collect()
+}
+
+fn collect_supertrait<'a>(
+	path_resolver: &rustdoc_util::PathResolver<'a>,
+	supertrait_ids: &[(Id, &'a str)],
+	impl_item: &'a rustdoc_types::Item,
+	impl_trait: &rustdoc_types::Path,
+	doc_impl: &'a rustdoc_types::Impl,
+) -> Option<Supertrait<'a>> {
+	let (_, path) = supertrait_ids.iter().find(|(id, _)| *id == impl_trait.id)?;
+	// Implementations involving `std::os` items only exist on some platforms.
+	if !visitor::visit_item(impl_item, &|id| {
+		!platform::is_platform_path(&path_resolver.canonical_path(id))
+	}) {
+		return None;
 	}
-	writeln!(buf, "}}")?;
-
-	writeln!(buf)?;
-	writeln!(buf, "pub struct Native {{}}")?;
-	writeln!(buf)?;
-	writeln!(buf, "impl Fs for Native {{")?;
-	for item in function_list {
-		if item.base.deprecation.is_some() {
-			continue;
+
+	let mut bindings = Vec::new();
+	for item_id in &doc_impl.items {
+		if let Some(assoc_item) = path_resolver.doc().index.get(item_id) {
+			if let ItemEnum::AssocType {
+				type_: Some(assoc_type),
+				..
+			} = &assoc_item.inner
+			{
+				if let Some(name) = &assoc_item.name {
+					bindings.push((name, path_resolver.qualify(assoc_type)));
+				}
+			}
 		}
+	}
+	Some(Supertrait {
+		path,
+		bindings,
+	})
+}
 
-		writeln!(buf)?;
-		print::write_function(buf, doc_crate, item.name, item.inner)?;
-		writeln!(buf, " {{")?;
-		write!(buf, "	std::fs::{}(", item.name)?;
-		for (input_name, _) in &item.inner.sig.inputs {
-			write!(buf, "{input_name}, ")?;
+/// Returns a print context where the given structs are printed as associated types.
+fn associated_context<'a>(
+	struct_traits: &'a [StructTrait],
+	associated_types: &[Id],
+) -> print::Context<'a> {
+	let mut ctx = print::Context::default();
+	for struct_trait in struct_traits {
+		if associated_types.contains(&struct_trait.item.base.id) {
+			ctx.associated_types.insert(struct_trait.item.base.id, struct_trait.name);
 		}
-		writeln!(buf, ")")?;
-		writeln!(buf, "}}")?;
 	}
-	writeln!(buf, "}}")?;
-
-	let mut out_file =
-		OpenOptions::new().write(true).create(true).truncate(true).open(&output_path)?;
-	write!(out_file, "{HEADER}")?;
-	write!(
-		out_file,
-		"use std::io;
-use std::fs::Metadata;
-use std::fs::ReadDir;
-use std::fs::Permissions;
-use std::path;
-"
-	)?;
-	out_file.write_all(buf)?;
+	ctx
+}
+
+/// Returns the declaration of an associated type bound by the trait generated for the same
+/// struct, along with `extra_bounds`. Associated types of that trait are constrained to the ones
+/// available in the enclosing trait.
+fn associated_type(
+	struct_traits: &[StructTrait],
+	struct_trait: &StructTrait,
+	available: &[Id],
+	extra_bounds: &[TokenStream],
+) -> TokenStream {
+	let doc = print::doc_text(&format!("The backend counterpart of [`{}`].", struct_trait.path));
+	let name = format_ident!("{}", struct_trait.name);
+	let constraints = struct_traits
+		.iter()
+		.filter(|other| {
+			struct_trait.associated_types.contains(&other.item.base.id)
+				&& available.contains(&other.item.base.id)
+		})
+		.map(|other| format_ident!("{}", other.name))
+		.collect::<Vec<_>>();
+	let constraints =
+		(!constraints.is_empty()).then(|| quote!(<#(#constraints = Self::#constraints),*>));
+	quote! {
+		#doc
+		type #name: #name #constraints #(+ #extra_bounds)*;
+	}
+}
+
+/// Formats the generated items and writes them to `output_path`.
+fn write_source(output_path: impl AsRef<Path>, items: TokenStream) -> Result<(), SourceError> {
+	fs::write(output_path, format_source(items)?)?;
 	Ok(())
 }
 
-// Hacky but works for now. Would like to check full path instead.
-fn has_module_with_name(path_resolver: &rustdoc_util::PathResolver, id: Id, name: &str) -> bool {
-	let mut id = id;
-	while let Some(parent) = path_resolver.canonical_parent(id) {
-		id = parent;
-		if let Some(item) = path_resolver.doc().index.get(&id) {
-			if let Some(item_name) = &item.name {
-				if item_name == name {
-					return true;
+/// Formats the generated items after [`HEADER`].
+fn format_source(items: TokenStream) -> Result<String, SourceError> {
+	let file = syn::parse2::<syn::File>(items)?;
+	let source = layout(&prettyplease::unparse(&file));
+	Ok(format!("{HEADER}{source}"))
+}
+
+/// Indents formatted source with tabs like the rest of the crate, and separates items and the
+/// members of traits and implementations with blank lines, which are not preserved by
+/// `prettyplease`.
+fn layout(source: &str) -> String {
+	const INDENT: usize = 4;
+	let mut out = String::new();
+	let mut previous = "";
+	// Whether the current top-level item is a trait or an implementation, as opposed to a
+	// function whose statements are not separated.
+	let mut has_members = false;
+	for line in source.lines() {
+		let code = line.trim_start_matches(' ');
+		let depth = (line.len() - code.len()) / INDENT;
+		if depth == 0
+			&& [
+				"pub trait ",
+				"trait ",
+				"impl",
+			]
+			.iter()
+			.any(|start| code.starts_with(start))
+		{
+			has_members = true;
+		} else if depth == 0 && (code.starts_with("pub fn ") || code.starts_with("fn ")) {
+			has_members = false;
+		}
+		let is_member = depth == 0 || depth == 1 && has_members;
+		// Module declarations are listed along with the re-exports of their items.
+		let is_declaration_list = [
+			&["use "][..],
+			&["type "],
+			&["fn "],
+			&[
+				"mod ",
+				"pub mod ",
+				"pub use ",
+			],
+		]
+		.iter()
+		.any(|starts| {
+			starts.iter().any(|start| previous.starts_with(start))
+				&& starts.iter().any(|start| code.starts_with(start))
+		});
+		if is_member
+			&& !previous.starts_with("//")
+			&& (previous.ends_with(';') || previous.ends_with('}'))
+			&& !code.starts_with('}')
+			&& !is_declaration_list
+		{
+			out.push('\n');
+		}
+		out.push_str(&"\t".repeat(depth));
+		out.push_str(&line[depth * INDENT..]);
+		out.push('\n');
+		previous = code;
+	}
+	out
+}
+
+fn generate_structs(
+	output_path: impl AsRef<Path>,
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	let mut items = quote! {
+		use std::ffi::OsString;
+		use std::io;
+		use std::path::Path;
+		use std::path::PathBuf;
+		use std::time::SystemTime;
+	};
+	for struct_trait in struct_traits {
+		let item = &struct_trait.item;
+		let mut ctx = associated_context(struct_traits, &struct_trait.associated_types);
+		ctx.self_type = Some((item.base.id, struct_trait.name));
+		let name = format_ident!("{}", struct_trait.name);
+		let std_struct = print::path(&struct_trait.path);
+
+		let supertraits = struct_trait
+			.supertraits
+			.iter()
+			.map(|supertrait| {
+				let path = print::path(supertrait.path);
+				if supertrait.bindings.is_empty() {
+					return Ok(path);
+				}
+				let bindings = supertrait
+					.bindings
+					.iter()
+					.map(|(binding_name, binding_type)| {
+						let binding_name = format_ident!("{binding_name}");
+						let binding_type = print::ty(&ctx, binding_type)
+							.map_err(SourceError::printing(item.base.id, item.name))?;
+						Ok(quote!(#binding_name = #binding_type))
+					})
+					.collect::<Result<Vec<_>, SourceError>>()?;
+				Ok(quote!(#path<#(#bindings),*>))
+			})
+			.chain(struct_trait.bounds.iter().map(|bound| {
+				let bound = syn::parse_str::<syn::TypeParamBound>(bound)?;
+				Ok(quote!(#bound))
+			}))
+			.collect::<Result<Vec<_>, SourceError>>()?;
+		let associated_types = struct_traits
+			.iter()
+			.filter(|other| struct_trait.associated_types.contains(&other.item.base.id))
+			.collect::<Vec<_>>();
+		let declarations = associated_types.iter().map(|other| {
+			associated_type(struct_traits, other, &struct_trait.associated_types, &[])
+		});
+		let signatures = struct_trait
+			.methods
+			.iter()
+			.map(|method| {
+				let cfg = print::cfg(method.base);
+				let args = print::function_args(&ctx, method.inner, None);
+				cfg.and_then(|cfg| Ok((cfg, args?))).map_err(SourceError::printing(
+					method.base.id,
+					format!("{}::{}", item.name, method.name),
+				))
+			})
+			.collect::<Result<Vec<_>, SourceError>>()?;
+		let methods = struct_trait.methods.iter().zip(&signatures).map(|(method, (cfg, args))| {
+			let doc = print::doc(method.base);
+			let method_name = format_ident!("{}", method.name);
+			quote! {
+				#cfg
+				#doc
+				fn #method_name #args;
+			}
+		});
+		let doc = print::doc(item.base);
+		// Handles mirror concrete types, which allows methods such as `File::try_clone` to return
+		// `io::Result<Self>`.
+		items.extend(quote! {
+			#doc
+			pub trait #name: Sized #(+ #supertraits)* {
+				#(#declarations)*
+				#(#methods)*
+			}
+		});
+
+		let associated_names = associated_types.iter().map(|other| format_ident!("{}", other.name));
+		let associated_structs = associated_types.iter().map(|other| print::path(&other.path));
+		let methods = struct_trait.methods.iter().zip(&signatures).map(|(method, (cfg, args))| {
+			let method_name = format_ident!("{}", method.name);
+			let input_names = print::input_names(method.inner);
+			quote! {
+				#cfg
+				fn #method_name #args {
+					#std_struct::#method_name(#(#input_names),*)
+				}
+			}
+		});
+		items.extend(quote! {
+			impl #name for #std_struct {
+				#(type #associated_names = #associated_structs;)*
+				#(#methods)*
+			}
+		});
+	}
+	write_source(output_path, items)
+}
+
+fn generate_functions(
+	output_path: impl AsRef<Path>,
+	module: &config::Module,
+	function_list: &[rustdoc_util::NamedItem<rustdoc_types::Function>],
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	let struct_ids =
+		struct_traits.iter().map(|struct_trait| struct_trait.item.base.id).collect::<Vec<_>>();
+	let ctx = associated_context(struct_traits, &struct_ids);
+	let struct_names =
+		struct_traits.iter().map(|struct_trait| format_ident!("{}", struct_trait.name));
+	let struct_paths = struct_traits.iter().map(|struct_trait| print::path(&struct_trait.path));
+	let constructors = struct_traits
+		.iter()
+		.filter_map(|struct_trait| {
+			let constructor = struct_trait.constructor.as_ref()?;
+			let cfg = print::cfg(constructor.base).map_err(SourceError::printing(
+				constructor.base.id,
+				format!("{}::{}", struct_trait.item.name, constructor.name),
+			));
+			let name = format_ident!("{}", struct_trait.name);
+			let snake_name = format_ident!("{}", to_snake_case(struct_trait.name));
+			let constructor_path =
+				print::path(&format!("{}::{}", struct_trait.path, constructor.name));
+			Some(cfg.map(|cfg| (constructor, cfg, name, snake_name, constructor_path)))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let functions = function_list.iter();
+
+	let declarations = struct_traits
+		.iter()
+		.map(|struct_trait| associated_type(struct_traits, struct_trait, &struct_ids, &[]));
+	let constructor_declarations =
+		constructors.iter().map(|(constructor, cfg, name, snake_name, _)| {
+			let doc = print::doc(constructor.base);
+			quote! {
+				#cfg
+				#doc
+				fn #snake_name(&self) -> Self::#name;
+			}
+		});
+	let methods = functions
+		.clone()
+		.map(|item| {
+			let cfg = print::cfg(item.base);
+			let method = print::method(&ctx, item.name, item.inner);
+			cfg.and_then(|cfg| Ok((cfg, method?)))
+				.map_err(SourceError::printing(item.base.id, item.name))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let function_declarations = functions.clone().zip(&methods).map(|(item, (cfg, method))| {
+		let doc = print::doc(item.base);
+		quote! {
+			#cfg
+			#doc
+			#method;
+		}
+	});
+	let constructor_impls =
+		constructors.iter().map(|(_, cfg, name, snake_name, constructor_path)| {
+			quote! {
+				#cfg
+				fn #snake_name(&self) -> Self::#name {
+					#constructor_path()
 				}
 			}
+		});
+	let function_impls = functions.zip(&methods).map(|(item, (cfg, method))| {
+		let function = print::path(&module.item_path(item.name));
+		let input_names = print::input_names(item.inner);
+		quote! {
+			#cfg
+			#method {
+				#function(#(#input_names),*)
+			}
+		}
+	});
+	let imports =
+		struct_traits.iter().map(|struct_trait| import(&module.files.structs, struct_trait.name));
+	let trait_name = format_ident!("{}", module.names.trait_name);
+	let native = format_ident!("{}", module.names.native);
+	let items = quote! {
+		use std::io;
+		use std::path;
+
+		#(#imports)*
+
+		pub trait #trait_name {
+			#(#declarations)*
+			#(#constructor_declarations)*
+			#(#function_declarations)*
 		}
+
+		#[derive(Clone, Copy, Debug, Default)]
+		pub struct #native {}
+
+		impl #trait_name for #native {
+			#(type #struct_names = #struct_paths;)*
+			#(#constructor_impls)*
+			#(#function_impls)*
+		}
+	};
+	write_source(output_path, items)
+}
+
+/// Converts a type name such as `OpenOptions` into `open_options`.
+fn to_snake_case(name: &str) -> String {
+	let mut snake_case = String::new();
+	for (index, c) in name.char_indices() {
+		if c.is_uppercase() {
+			if index != 0 {
+				snake_case.push('_');
+			}
+			snake_case.extend(c.to_lowercase());
+		} else {
+			snake_case.push(c);
+		}
+	}
+	snake_case
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn generated_mod_declares_the_configured_files() {
+		let config = toml::from_str::<config::Config>(include_str!("../data/config.toml")).unwrap();
+		config.check_names().unwrap();
+		let source = format_source(mod_items(&config.modules)).unwrap();
+		assert_eq!(source, include_str!("../../src/generated/mod.rs"));
 	}
-	false
 }
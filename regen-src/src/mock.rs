@@ -0,0 +1,268 @@
+use std::path::Path;
+
+use log::info;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use rustdoc_types::Function;
+use rustdoc_types::Type;
+
+use crate::associated_context;
+use crate::config::Module;
+use crate::import;
+use crate::print;
+use crate::rustdoc_util::NamedItem;
+use crate::to_snake_case;
+use crate::write_source;
+use crate::SourceError;
+use crate::StructTrait;
+
+fn mock_fs_doc(module: &Module) -> String {
+	let trait_name = &module.names.trait_name;
+	let mock = &module.names.mock;
+	format!(
+		"A scripted [`{trait_name}`] answering calls according to [`Expectation`]s set up in advance.
+
+Each method of [`{trait_name}`] has an `expect_` counterpart adding an expectation for it. A call is
+answered by the first expectation of its method that accepts its arguments and has calls left,
+and fails with [`io::ErrorKind::Other`] if there is none. [`verify`](Self::verify) then
+reports the expectations that were not met, along with the calls that matched none.
+
+Handles such as [`{trait_name}::OpenOptions`] are not scripted, and come from the backend the mock is
+created with.
+
+# Examples
+
+```
+use std::io;
+
+use io_fs::Expectation;
+use io_fs::{trait_name};
+use io_fs::MemFs;
+use io_fs::{mock};
+
+let fs = {mock}::new(MemFs::new());
+let config = Expectation::ok(\"verbose = true\".to_owned());
+fs.expect_read_to_string(config.with(\"app.toml\".into()));
+fs.expect_write(Expectation::err(io::ErrorKind::StorageFull).times(2));
+
+assert_eq!(fs.read_to_string(\"app.toml\")?, \"verbose = true\");
+assert!(fs.write(\"app.log\", \"started\").is_err());
+let report = fs.verify().unwrap_err();
+assert_eq!(report.unmet, [\"write(..) expected exactly 2 calls, got 1\"]);
+# Ok::<(), io::Error>(())
+```"
+	)
+}
+
+/// Returns the owned form of an input, in which expectations receive it. Generic `AsRef<T>` inputs
+/// are converted to the owned counterpart of `T`.
+fn owned_input(
+	ctx: &print::Context,
+	function: &Function,
+	input_type: &Type,
+) -> Result<TokenStream, print::Error> {
+	let Type::Generic(generic_name) = input_type else {
+		return print::ty(ctx, input_type);
+	};
+	match print::require_as_ref_target(function, generic_name)? {
+		Type::ResolvedPath(path) if path.name.ends_with("Path") => Ok(quote!(path::PathBuf)),
+		Type::Slice(slice_type) if matches!(&**slice_type, Type::Primitive(name) if name == "u8") => {
+			Ok(quote!(Vec<u8>))
+		}
+		target => {
+			let target = print::ty(ctx, target)?;
+			Ok(quote!(<#target as ToOwned>::Owned))
+		}
+	}
+}
+
+/// Returns `items` as a tuple if there are several, and as is otherwise.
+fn tuple_if_several(items: &[TokenStream]) -> TokenStream {
+	if let [item] = items {
+		item.clone()
+	} else {
+		quote!((#(#items),*))
+	}
+}
+
+/// Returns the `Expectation` type of the function, answering with the `T` of its `io::Result<T>`.
+/// Its arguments are received in a tuple if there are several.
+fn expectation_type(
+	ctx: &print::Context,
+	kind: &str,
+	function: &Function,
+) -> Result<TokenStream, print::Error> {
+	let kind = format_ident!("{kind}");
+	let args_type = tuple_if_several(
+		&function
+			.sig
+			.inputs
+			.iter()
+			.map(|(_, input_type)| owned_input(ctx, function, input_type))
+			.collect::<Result<Vec<_>, print::Error>>()?,
+	);
+	let output_type = function.sig.output.as_ref().and_then(print::first_type_arg);
+	let output_type = print::ty(ctx, output_type.ok_or(print::Error::NotIoResult)?)?;
+	Ok(quote!(#kind<#args_type, #output_type>))
+}
+
+/// Returns the implementation of the module trait for the mock, answering calls from the
+/// expectations of each method and forwarding factories to the backend.
+fn fs_impl(
+	module: &Module,
+	ctx: &print::Context,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<TokenStream, SourceError> {
+	let struct_names =
+		struct_traits.iter().map(|struct_trait| format_ident!("{}", struct_trait.name));
+	let constructors = struct_traits
+		.iter()
+		.filter(|struct_trait| struct_trait.constructor.is_some())
+		.map(|struct_trait| {
+			let name = format_ident!("{}", struct_trait.name);
+			let snake_name = format_ident!("{}", to_snake_case(struct_trait.name));
+			let cfg = struct_trait.constructor.as_ref().map(|constructor| {
+				print::cfg(constructor.base).map_err(SourceError::printing(
+					constructor.base.id,
+					format!("{}::{}", struct_trait.item.name, constructor.name),
+				))
+			});
+			let cfg = cfg.transpose()?;
+			Ok(quote! {
+				#cfg
+				fn #snake_name(&self) -> Self::#name {
+					self.backend.#snake_name()
+				}
+			})
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let methods = function_list.iter().map(|item| {
+		let cfg = print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name))?;
+		let method = print::method(ctx, item.name, item.inner)
+			.map_err(SourceError::printing(item.base.id, item.name))?;
+		let name = format_ident!("{}", item.name);
+		let args = tuple_if_several(
+			&item
+				.inner
+				.sig
+				.inputs
+				.iter()
+				.map(|(input_name, input_type)| {
+					let input_name = print::input_name(input_name)?;
+					if matches!(input_type, Type::Generic(_)) {
+						Ok(quote!(#input_name.as_ref().to_owned()))
+					} else {
+						Ok(quote!(#input_name))
+					}
+				})
+				.collect::<Result<Vec<_>, print::Error>>()
+				.map_err(SourceError::printing(item.base.id, item.name))?,
+		);
+		Ok(quote! {
+			#cfg
+			#method {
+				self.#name.call(#args)
+			}
+		})
+	});
+	let methods = methods.collect::<Result<Vec<_>, SourceError>>()?;
+	let trait_name = format_ident!("{}", module.names.trait_name);
+	let mock = format_ident!("{}", module.names.mock);
+	Ok(quote! {
+		impl<F: #trait_name> #trait_name for #mock<F> {
+			#(type #struct_names = F::#struct_names;)*
+			#(#constructors)*
+			#(#methods)*
+		}
+	})
+}
+
+pub fn generate_mock(
+	output_path: impl AsRef<Path>,
+	module: &Module,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", output_path.as_ref().display());
+	let struct_ids =
+		struct_traits.iter().map(|struct_trait| struct_trait.item.base.id).collect::<Vec<_>>();
+	let ctx = associated_context(struct_traits, &struct_ids);
+	let mut field_ctx = associated_context(struct_traits, &struct_ids);
+	field_ctx.owner = Some("F");
+	let functions = function_list.iter();
+	let names = functions.clone().map(|item| format_ident!("{}", item.name)).collect::<Vec<_>>();
+	let expect_names = functions.clone().map(|item| format_ident!("expect_{}", item.name));
+	let method_names = functions.clone().map(|item| item.name);
+	let trait_name = format_ident!("{}", module.names.trait_name);
+	let mock = format_ident!("{}", module.names.mock);
+	let expect_docs = functions
+		.clone()
+		.map(|item| print::doc_text(&format!("Expects calls to [`{trait_name}::{}`].", item.name)));
+	let expectation_types = |kind| {
+		functions
+			.clone()
+			.map(|item| {
+				expectation_type(&field_ctx, kind, item.inner)
+					.map_err(SourceError::printing(item.base.id, item.name))
+			})
+			.collect::<Result<Vec<_>, SourceError>>()
+	};
+	let cfgs = functions
+		.clone()
+		.map(|item| print::cfg(item.base).map_err(SourceError::printing(item.base.id, item.name)))
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let fields = expectation_types("Expectations")?;
+	let expectations = expectation_types("Expectation")?;
+
+	let doc = print::doc_text(&mock_fs_doc(module));
+	let trait_import = import(&module.files.functions, &module.names.trait_name);
+	let mut items = quote! {
+		use std::io;
+		use std::path;
+
+		#trait_import
+		use crate::Expectation;
+		use crate::Expectations;
+		use crate::MockReport;
+
+		#doc
+		#[derive(Debug)]
+		pub struct #mock<F: #trait_name> {
+			backend: F,
+			#(#cfgs #names: #fields,)*
+		}
+
+		impl<F: #trait_name> #mock<F> {
+			/// Creates a mock without expectations, taking handles from `backend`.
+			pub fn new(backend: F) -> Self {
+				Self {
+					backend,
+					#(#cfgs #names: Expectations::new(#method_names),)*
+				}
+			}
+
+			#(
+				#cfgs
+				#expect_docs
+				pub fn #expect_names(&self, expectation: #expectations) {
+					self.#names.push(expectation);
+				}
+			)*
+
+			/// Returns the expectations that were not met and the calls that matched none, if any.
+			pub fn verify(&self) -> Result<(), MockReport> {
+				let mut report = MockReport::default();
+				#(#cfgs self.#names.verify(&mut report);)*
+				report.into_result()
+			}
+
+			pub fn get_ref(&self) -> &F {
+				&self.backend
+			}
+		}
+	};
+	items.extend(fs_impl(module, &ctx, function_list, struct_traits)?);
+	write_source(output_path, items)
+}
@@ -0,0 +1,398 @@
+use std::path::Path;
+
+use log::info;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use rustdoc_types::Function;
+use rustdoc_types::Id;
+use rustdoc_types::Item;
+use rustdoc_types::ItemEnum;
+use rustdoc_types::ItemKind;
+use rustdoc_types::Trait;
+use serde::Deserialize;
+
+use crate::associated_context;
+use crate::config::Config;
+use crate::config::Items;
+use crate::config::Module;
+use crate::import;
+use crate::print;
+use crate::rustdoc_util;
+use crate::rustdoc_util::NamedItem;
+use crate::rustdoc_util::PathResolver;
+use crate::targets;
+use crate::targets::Targets;
+use crate::write_source;
+use crate::SourceError;
+use crate::StructTrait;
+
+/// A module of `std::os` whose extensions of a [`Module`] are mirrored into a module of the same
+/// name, as listed in the `platforms` of the module, such as `std::os::unix::fs` for `std::fs`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Platform {
+	module: String,
+	/// Name used in documentation, such as `Unix` in "Unix-specific".
+	title: String,
+	/// Name of the extension of the module trait whose methods mirror the functions of the module.
+	#[serde(rename = "trait")]
+	trait_name: String,
+	/// Name of the generated file, within the output directory.
+	pub file: String,
+	/// Imports covering the paths used by signatures in the mirrored module.
+	imports: Vec<String>,
+}
+
+impl Platform {
+	/// Returns the platform of `parent` whose module contains the item at `path`.
+	fn of<'a>(parent: &'a Module, path: &[&str]) -> Option<&'a Self> {
+		match path {
+			["std", "os", module, name, ..] if *name == parent.name() => {
+				parent.platforms.iter().find(|platform| platform.module == *module)
+			}
+			_ => None,
+		}
+	}
+
+	/// Returns the path of an item in the module extending `parent`, such as
+	/// `std::os::unix::fs::PermissionsExt`.
+	fn std_path(&self, parent: &Module, name: &str) -> String {
+		format!("{}::{name}", self.std_module(parent))
+	}
+
+	/// Returns the module extending `parent`, such as `std::os::unix::fs`.
+	fn std_module(&self, parent: &Module) -> String {
+		format!("std::os::{}::{}", self.module, parent.name())
+	}
+
+	/// Returns the `cfg` predicate of the targets the module is compiled for, if not all of them.
+	fn predicate(&self, targets: &Targets) -> Option<TokenStream> {
+		targets.predicate(targets.os_module(&self.module))
+	}
+
+	/// Returns a function attaching the item being printed to print errors, with `path` relative to
+	/// the module extending `parent`.
+	fn printing(
+		&self,
+		parent: &Module,
+		id: Id,
+		path: &str,
+	) -> impl FnOnce(print::Error) -> SourceError {
+		let path = self.std_path(parent, path);
+		move |source| {
+			SourceError::Print {
+				id,
+				path,
+				source,
+			}
+		}
+	}
+}
+
+/// Returns whether the item at `path` is part of `std::os`, and so only exists on some platforms.
+pub fn is_platform_path(path: &[&str]) -> bool {
+	path.starts_with(&[
+		"std",
+		"os",
+	])
+}
+
+/// A trait of a platform module implemented by a struct of its parent, such as
+/// `std::os::unix::fs::PermissionsExt` for `std::fs::Permissions`.
+pub struct Extension<'a> {
+	parent: &'a Module,
+	platform: &'a Platform,
+	/// The implementation for the struct, whose `cfg` applies to the whole extension.
+	impl_item: &'a Item,
+	item: NamedItem<'a, Trait>,
+	methods: Vec<NamedItem<'a, Function>>,
+}
+
+/// Returns the extension implemented by a trait implementation, if its trait belongs to one of the
+/// platforms of `parent`. Traits and methods are selected by the item rules of the config.
+pub fn collect_extension<'a>(
+	path_resolver: &PathResolver<'a>,
+	config: &'a Config,
+	parent: &'a Module,
+	impl_item: &'a Item,
+	impl_trait: &rustdoc_types::Path,
+) -> Option<Extension<'a>> {
+	let platform = Platform::of(parent, &path_resolver.canonical_path(impl_trait.id))?;
+	let items = &config.items;
+	let doc_crate = path_resolver.doc();
+	let trait_item = doc_crate.index.get(&impl_trait.id)?;
+	let (Some(name), ItemEnum::Trait(inner)) = (&trait_item.name, &trait_item.inner) else {
+		return None;
+	};
+	let trait_path = platform.std_path(parent, name);
+	if !items.keeps(&trait_path, trait_item) {
+		return None;
+	}
+	let methods = inner
+		.items
+		.iter()
+		.filter_map(|id| doc_crate.index.get(id))
+		.filter_map(|item| {
+			match (&item.name, &item.inner) {
+				(Some(name), ItemEnum::Function(function))
+					if items.keeps(&format!("{trait_path}::{name}"), item) =>
+				{
+					Some(NamedItem {
+						name,
+						base: item,
+						inner: function,
+					})
+				}
+				_ => None,
+			}
+		})
+		.collect();
+	Some(Extension {
+		parent,
+		platform,
+		impl_item,
+		item: NamedItem {
+			name,
+			base: trait_item,
+			inner,
+		},
+		methods,
+	})
+}
+
+/// Returns the functions of the module of `platform` extending `parent`, selected by `items` and
+/// sorted by name.
+pub fn collect_functions<'a>(
+	path_resolver: &PathResolver<'a>,
+	parent: &Module,
+	platform: &Platform,
+	items: &Items,
+) -> Vec<NamedItem<'a, Function>> {
+	let doc_crate = path_resolver.doc();
+	let Some(module) = rustdoc_util::find_item(
+		doc_crate,
+		&[
+			"std",
+			"os",
+			platform.module.as_str(),
+			parent.name(),
+		],
+		ItemKind::Module,
+	)
+	.and_then(|id| doc_crate.index.get(id)) else {
+		return Vec::new();
+	};
+	let ItemEnum::Module(module) = &module.inner else {
+		return Vec::new();
+	};
+	let mut functions = module
+		.items
+		.iter()
+		.filter_map(|id| doc_crate.index.get(id))
+		.filter_map(|item| {
+			match (&item.name, &item.inner) {
+				(Some(name), ItemEnum::Function(function))
+					if items.keeps(&platform.std_path(parent, name), item) =>
+				{
+					Some(NamedItem {
+						name,
+						base: item,
+						inner: function,
+					})
+				}
+				_ => None,
+			}
+		})
+		.collect::<Vec<_>>();
+	functions.sort();
+	functions
+}
+
+/// Returns the extension trait mirroring `extension` on top of the trait generated for the struct,
+/// along with its implementation for the standard library struct.
+fn extension_items(
+	struct_traits: &[StructTrait],
+	struct_trait: &StructTrait,
+	extension: &Extension,
+	implied: Option<&str>,
+) -> Result<TokenStream, SourceError> {
+	let parent = extension.parent;
+	let platform = extension.platform;
+	let struct_name = struct_trait.name;
+	let trait_name = extension.item.name;
+	let mut ctx = associated_context(struct_traits, &struct_trait.associated_types);
+	ctx.self_type = Some((struct_trait.item.base.id, struct_name));
+
+	let signatures = extension
+		.methods
+		.iter()
+		.map(|method| {
+			let cfg = print::cfg_within(method.base, implied);
+			let args = print::function_args(&ctx, method.inner, None);
+			cfg.and_then(|cfg| Ok((cfg, args?))).map_err(platform.printing(
+				parent,
+				method.base.id,
+				&format!("{trait_name}::{}", method.name),
+			))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	// The examples of `std` import its own extension traits, so methods link to them instead.
+	let methods = extension.methods.iter().zip(&signatures).map(|(method, (cfg, args))| {
+		let doc = print::doc_text(&format!(
+			"See [`{}::{}`].",
+			platform.std_path(parent, trait_name),
+			method.name
+		));
+		let method_name = format_ident!("{}", method.name);
+		quote! {
+			#cfg
+			#doc
+			fn #method_name #args;
+		}
+	});
+	let doc = print::doc_text(&format!(
+		"{}-specific extensions to [`{struct_name}`], mirroring [`{}`].",
+		platform.title,
+		platform.std_path(parent, trait_name)
+	));
+	let cfg = print::cfg_within(extension.impl_item, implied).map_err(platform.printing(
+		parent,
+		extension.item.base.id,
+		trait_name,
+	))?;
+	let name = format_ident!("{trait_name}");
+	let supertrait = format_ident!("{struct_name}");
+	let std_struct = print::path(&struct_trait.path);
+	let mut items = quote! {
+		#cfg
+		#doc
+		pub trait #name: #supertrait {
+			#(#methods)*
+		}
+	};
+
+	let std_trait = print::path(&platform.std_path(parent, trait_name));
+	let methods = extension.methods.iter().zip(&signatures).map(|(method, (cfg, args))| {
+		let method_name = format_ident!("{}", method.name);
+		let input_names = print::input_names(method.inner);
+		quote! {
+			#cfg
+			fn #method_name #args {
+				<#std_struct as #std_trait>::#method_name(#(#input_names),*)
+			}
+		}
+	});
+	items.extend(quote! {
+		#cfg
+		impl #name for #std_struct {
+			#(#methods)*
+		}
+	});
+	Ok(items)
+}
+
+/// Generates the module mirroring the module of `platform` extending `parent`, compiled for the
+/// targets that have it. Traits implemented by the mirrored structs become extensions of the
+/// generated struct traits, and functions become methods of an extension of the module trait. Both
+/// are implemented for the native backend.
+pub fn generate_platform(
+	output_path: impl AsRef<Path>,
+	parent: &Module,
+	platform: &Platform,
+	targets: &Targets,
+	function_list: &[NamedItem<Function>],
+	struct_traits: &[StructTrait],
+) -> Result<(), SourceError> {
+	info!("Generating {}...", platform.file);
+	let predicate = platform.predicate(targets);
+	let implied = predicate.as_ref().map(targets::attribute);
+	let implied = implied.as_deref();
+	let module_cfg = predicate.map(|predicate| quote!(#![cfg(#predicate)]));
+	let imports = platform.imports.iter().map(|import| print::path(import));
+	let extended = struct_traits
+		.iter()
+		.filter(|struct_trait| {
+			struct_trait
+				.extensions
+				.iter()
+				.any(|extension| extension.platform.module == platform.module)
+		})
+		.collect::<Vec<_>>();
+	let trait_imports = [
+		&parent.names.trait_name,
+		&parent.names.native,
+	]
+	.map(|name| import(&parent.files.functions, name));
+	let struct_imports =
+		extended.iter().map(|struct_trait| import(&parent.files.structs, struct_trait.name));
+	let mut items = quote! {
+		#module_cfg
+
+		#(use #imports;)*
+
+		#(#trait_imports)*
+		#(#struct_imports)*
+	};
+	for struct_trait in &extended {
+		for extension in &struct_trait.extensions {
+			if extension.platform.module == platform.module {
+				items.extend(extension_items(struct_traits, struct_trait, extension, implied)?);
+			}
+		}
+	}
+
+	let struct_ids =
+		struct_traits.iter().map(|struct_trait| struct_trait.item.base.id).collect::<Vec<_>>();
+	let ctx = associated_context(struct_traits, &struct_ids);
+	let methods = function_list
+		.iter()
+		.map(|item| {
+			let cfg = print::cfg_within(item.base, implied);
+			let method = print::method(&ctx, item.name, item.inner);
+			cfg.and_then(|cfg| Ok((cfg, method?))).map_err(platform.printing(
+				parent,
+				item.base.id,
+				item.name,
+			))
+		})
+		.collect::<Result<Vec<_>, SourceError>>()?;
+	let declarations = function_list.iter().zip(&methods).map(|(item, (cfg, method))| {
+		let doc = print::doc_text(&format!("See [`{}`].", platform.std_path(parent, item.name)));
+		quote! {
+			#cfg
+			#doc
+			#method;
+		}
+	});
+	let implementations = function_list.iter().zip(&methods).map(|(item, (cfg, method))| {
+		let function = print::path(&platform.std_path(parent, item.name));
+		let input_names = print::input_names(item.inner);
+		quote! {
+			#cfg
+			#method {
+				#function(#(#input_names),*)
+			}
+		}
+	});
+	let doc = print::doc_text(&format!(
+		"{}-specific functions of [`{}`], as methods of [`{}`].",
+		platform.title,
+		platform.std_module(parent),
+		parent.names.trait_name
+	));
+	let trait_name = format_ident!("{}", platform.trait_name);
+	let parent_trait = format_ident!("{}", parent.names.trait_name);
+	let native = format_ident!("{}", parent.names.native);
+	items.extend(quote! {
+		#doc
+		pub trait #trait_name: #parent_trait {
+			#(#declarations)*
+		}
+
+		impl #trait_name for #native {
+			#(#implementations)*
+		}
+	});
+	write_source(output_path, items)
+}
@@ -1,204 +1,1033 @@
-use std::io;
-use std::io::Write;
+use std::collections::HashMap;
 
-use rustdoc_types::Crate;
+use proc_macro2::Ident;
+use proc_macro2::LexError;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::ToTokens;
+use rustdoc_types::Abi;
+use rustdoc_types::AssocItemConstraint;
+use rustdoc_types::AssocItemConstraintKind;
+use rustdoc_types::Constant;
 use rustdoc_types::Function;
+use rustdoc_types::FunctionHeader;
+use rustdoc_types::FunctionPointer;
 use rustdoc_types::GenericArg;
 use rustdoc_types::GenericArgs;
 use rustdoc_types::GenericBound;
+use rustdoc_types::GenericParamDef;
 use rustdoc_types::GenericParamDefKind;
+use rustdoc_types::Generics;
+use rustdoc_types::Id;
 use rustdoc_types::Item;
 use rustdoc_types::Path;
+use rustdoc_types::Term;
 use rustdoc_types::TraitBoundModifier;
 use rustdoc_types::Type;
+use rustdoc_types::WherePredicate;
+use syn::Lifetime;
+use thiserror::Error;
 
-pub fn write_doc<W: Write>(out: &mut W, item: &Item) -> io::Result<()> {
-	if let Some(docs) = &item.docs {
-		for line in docs.lines() {
-			writeln!(out, "/// {line}")?;
+/// State shared while printing items from a single crate.
+#[derive(Default)]
+pub struct Context<'a> {
+	/// Items that are printed as associated types on `Self` instead of by path.
+	pub associated_types: HashMap<Id, &'a str>,
+	/// Item that is printed as `Self` instead of by path, along with its name.
+	pub self_type: Option<(Id, &'a str)>,
+	/// Whether `Self` and associated types are printed as boxed `Dyn` trait objects instead.
+	pub boxed: bool,
+	/// Type parameter that associated types are printed on, such as `F` in `F::Metadata`, for
+	/// positions where `Self` is not available. Defaults to `Self`.
+	pub owner: Option<&'a str>,
+}
+
+/// Error returned when part of a signature cannot be printed as Rust source. The generators attach
+/// the item being printed.
+#[derive(Error, Debug)]
+pub enum Error {
+	#[error("invalid {kind} `{text}`: {reason}")]
+	Tokens {
+		kind: &'static str,
+		text: String,
+		reason: String,
+	},
+	#[error("{0} cannot be written in stable Rust")]
+	Unstable(&'static str),
+	#[error("generic parameter `{0}` is not bound by `AsRef`")]
+	NotAsRef(String),
+	#[error("output is not an `io::Result`")]
+	NotIoResult,
+}
+
+/// Returns the documentation of the item as `#[doc]` attributes, which are printed as `///`
+/// comments.
+pub fn doc(item: &Item) -> TokenStream {
+	item.docs.as_deref().map(doc_text).unwrap_or_default()
+}
+
+/// Returns `text` as `#[doc]` attributes, one per line.
+pub fn doc_text(text: &str) -> TokenStream {
+	let lines = text.lines().map(|line| {
+		if line.is_empty() {
+			String::new()
+		} else {
+			format!(" {line}")
 		}
-	}
-	Ok(())
+	});
+	quote!(#(#[doc = #lines])*)
 }
 
-pub fn write_path<W: Write>(out: &mut W, root: &Crate, path: &Path) -> io::Result<()> {
-	if let Some(item_summary) = root.paths.get(&path.id) {
-		for i in 0..item_summary.path.len() {
-			let path = &item_summary.path[i];
-			write!(out, "{path}")?;
-			if i != item_summary.path.len() - 1 {
-				write!(out, "::")?;
-			}
+/// Returns the `#[cfg]` attributes of the item, which are added when merging the documentation of
+/// several targets.
+pub fn cfg(item: &Item) -> Result<TokenStream, Error> {
+	cfg_within(item, None)
+}
+
+/// Returns the `#[cfg]` attributes of the item like [`cfg`], leaving out `implied` if it already
+/// applies to the enclosing module.
+pub fn cfg_within(item: &Item, implied: Option<&str>) -> Result<TokenStream, Error> {
+	item.attrs
+		.iter()
+		.filter(|attr| attr.starts_with("#[cfg(") && Some(attr.as_str()) != implied)
+		.map(|attr| tokens("attribute", attr))
+		.collect()
+}
+
+/// Returns a path such as `io::Result` as tokens. Segments may be keywords such as `Self`.
+pub fn path(name: &str) -> TokenStream {
+	let segments = name.split("::").map(|segment| format_ident!("{segment}"));
+	quote!(#(#segments)::*)
+}
+
+/// Returns a lifetime, which rustdoc usually names along with its leading apostrophe.
+fn lifetime(name: &str) -> Result<Lifetime, Error> {
+	let text = if name.starts_with('\'') {
+		name.to_owned()
+	} else {
+		format!("'{name}")
+	};
+	syn::parse_str(&text).map_err(|error: syn::Error| {
+		Error::Tokens {
+			kind: "lifetime",
+			text,
+			reason: error.to_string(),
+		}
+	})
+}
+
+/// Returns the name of a function input, which rustdoc prints from its pattern. Inputs
+/// destructured by a pattern such as `(a, b)` have no name to print.
+pub fn input_name(name: &str) -> Result<Ident, Error> {
+	ident("input name", name)
+}
+
+/// Returns `name` as an identifier, or an error describing it as a `kind` otherwise.
+pub fn ident(kind: &'static str, name: &str) -> Result<Ident, Error> {
+	syn::parse_str(name).map_err(|error: syn::Error| {
+		Error::Tokens {
+			kind,
+			text: name.to_owned(),
+			reason: error.to_string(),
+		}
+	})
+}
+
+/// Returns source text that rustdoc keeps verbatim, such as array lengths, as tokens.
+fn tokens(kind: &'static str, text: &str) -> Result<TokenStream, Error> {
+	text.parse().map_err(|error: LexError| {
+		Error::Tokens {
+			kind,
+			text: text.to_owned(),
+			reason: error.to_string(),
 		}
+	})
+}
+
+/// Returns a constant used as a generic argument. Anything but literals and single identifiers
+/// must be written in braces.
+fn constant(constant: &Constant) -> Result<TokenStream, Error> {
+	let expr = tokens("constant", &constant.expr)?;
+	if expr.clone().into_iter().count() == 1 {
+		Ok(expr)
 	} else {
-		write!(out, "{}", path.name)?;
-	}
-	if let Some(args) = &path.args {
-		write_generic_args(out, root, args)?;
+		Ok(quote!({ #expr }))
 	}
-	Ok(())
 }
 
-pub fn write_resolved_path<W: Write>(out: &mut W, root: &Crate, path: &Path) -> io::Result<()> {
+/// Returns the boxed trait object standing for the struct with the given name, as printed in
+/// dyn-compatible signatures.
+pub fn boxed(name: &str) -> TokenStream {
+	let dyn_name = format_ident!("Dyn{name}");
+	quote!(Box<dyn #dyn_name>)
+}
+
+pub fn resolved_path(ctx: &Context, path: &Path) -> Result<TokenStream, Error> {
 	const CRATE_PATH: &str = "crate::";
-	let name = &path.name;
-	write!(out, "{}", name.strip_prefix(CRATE_PATH).unwrap_or(name))?;
-	if let Some(args) = &path.args {
-		write_generic_args(out, root, args)?;
+	if let Some((_, self_name)) = ctx.self_type.filter(|(id, _)| *id == path.id) {
+		return Ok(if ctx.boxed {
+			boxed(self_name)
+		} else {
+			quote!(Self)
+		});
+	}
+	if let Some(associated_name) = ctx.associated_types.get(&path.id) {
+		if ctx.boxed {
+			return Ok(boxed(associated_name));
+		}
+		let owner = format_ident!("{}", ctx.owner.unwrap_or("Self"));
+		let associated_name = format_ident!("{associated_name}");
+		return Ok(quote!(#owner::#associated_name));
 	}
-	Ok(())
+	let name = self::path(path.name.strip_prefix(CRATE_PATH).unwrap_or(&path.name));
+	let args = path.args.as_deref().map(|args| generic_args(ctx, args)).transpose()?;
+	Ok(quote!(#name #args))
 }
 
-pub fn write_type<W: Write>(out: &mut W, root: &Crate, item_type: &Type) -> io::Result<()> {
-	match item_type {
-		Type::ResolvedPath(path) => {
-			write_resolved_path(out, root, path)?;
+pub fn ty(ctx: &Context, item_type: &Type) -> Result<TokenStream, Error> {
+	let tokens = match item_type {
+		Type::ResolvedPath(path) => resolved_path(ctx, path)?,
+		Type::DynTrait(dyn_trait) => {
+			let traits = dyn_trait
+				.traits
+				.iter()
+				.map(|poly_trait| {
+					let generic_params = higher_ranked(ctx, &poly_trait.generic_params)?;
+					let trait_ = resolved_path(ctx, &poly_trait.trait_)?;
+					Ok(quote!(#generic_params #trait_))
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+			let lifetime = dyn_trait.lifetime.as_deref().map(lifetime).transpose()?.into_iter();
+			quote!(dyn #(#traits)+* #(+ #lifetime)*)
 		}
 		Type::Generic(generic_name) => {
-			write!(out, "{generic_name}")?;
-		}
-		Type::Primitive(primitive_name) => {
-			write!(out, "{primitive_name}")?;
+			match ctx.self_type {
+				Some((_, self_name)) if ctx.boxed && is_self(item_type) => boxed(self_name),
+				_ => path(generic_name),
+			}
 		}
+		Type::Primitive(primitive_name) => path(primitive_name),
+		Type::FunctionPointer(function_pointer) => self::function_pointer(ctx, function_pointer)?,
 		Type::Tuple(tuple_list) => {
-			write!(out, "(")?;
-			for doc_tuple in tuple_list {
-				write_type(out, root, doc_tuple)?;
-			}
-			write!(out, ")")?;
+			// A trailing comma keeps single-element tuples from being read as parentheses.
+			let tuple_list = tuple_list
+				.iter()
+				.map(|tuple_type| ty(ctx, tuple_type))
+				.collect::<Result<Vec<_>, Error>>()?;
+			quote!((#(#tuple_list,)*))
 		}
 		Type::Slice(slice_type) => {
-			write!(out, "[")?;
-			write_type(out, root, slice_type)?;
-			write!(out, "]")?;
+			let slice_type = ty(ctx, slice_type)?;
+			quote!([#slice_type])
+		}
+		Type::Array {
+			type_,
+			len,
+		} => {
+			let type_ = ty(ctx, type_)?;
+			let len = tokens("array length", len)?;
+			quote!([#type_; #len])
+		}
+		Type::Pat {
+			type_,
+			__pat_unstable_do_not_use: pattern,
+		} => {
+			let type_ = ty(ctx, type_)?;
+			let pattern = tokens("pattern", pattern)?;
+			quote!(std::pat::pattern_type!(#type_ is #pattern))
+		}
+		Type::ImplTrait(bounds) => {
+			let bounds = generic_bounds(ctx, bounds)?;
+			quote!(impl #(#bounds)+*)
+		}
+		Type::Infer => quote!(_),
+		Type::RawPointer {
+			is_mutable,
+			type_,
+		} => {
+			let mutability = if *is_mutable {
+				quote!(mut)
+			} else {
+				quote!(const)
+			};
+			let type_ = pointee(ctx, type_)?;
+			quote!(*#mutability #type_)
 		}
 		Type::BorrowedRef {
 			lifetime,
 			is_mutable,
 			type_,
 		} => {
-			write!(out, "&")?;
-			if let Some(lifetime_name) = lifetime {
-				write!(out, "`{lifetime_name} ")?;
-			}
-			if *is_mutable {
-				write!(out, "mut ")?;
+			let lifetime = lifetime.as_deref().map(self::lifetime).transpose()?;
+			let mutability = is_mutable.then(|| quote!(mut));
+			let type_ = pointee(ctx, type_)?;
+			quote!(&#lifetime #mutability #type_)
+		}
+		Type::QualifiedPath {
+			name,
+			args,
+			self_type,
+			trait_,
+		} => {
+			let name = format_ident!("{name}");
+			let args = generic_args(ctx, args)?;
+			let self_type = ty(ctx, self_type)?;
+			if let Some(trait_) = trait_ {
+				let trait_ = resolved_path(ctx, trait_)?;
+				quote!(<#self_type as #trait_>::#name #args)
+			} else {
+				quote!(<#self_type>::#name #args)
 			}
-			write_type(out, root, type_)?;
 		}
-		_ => unimplemented!("{item_type:?}"),
+	};
+	Ok(tokens)
+}
+
+/// Returns the type behind a reference or pointer. Trait objects and `impl Trait` types with
+/// several bounds are parenthesized, as `&dyn Read + Send` does not parse.
+fn pointee(ctx: &Context, item_type: &Type) -> Result<TokenStream, Error> {
+	let type_ = ty(ctx, item_type)?;
+	let has_several_bounds = match item_type {
+		Type::DynTrait(dyn_trait) => {
+			dyn_trait.traits.len() + usize::from(dyn_trait.lifetime.is_some()) > 1
+		}
+		Type::ImplTrait(bounds) => bounds.len() > 1,
+		_ => false,
+	};
+	if has_several_bounds {
+		Ok(quote!((#type_)))
+	} else {
+		Ok(type_)
 	}
-	Ok(())
 }
 
-pub fn write_generic_args<W: Write>(
-	out: &mut W,
-	root: &Crate,
-	args: &GenericArgs,
-) -> io::Result<()> {
-	if let GenericArgs::AngleBracketed {
-		args,
-		constraints,
-	} = args
-	{
-		if !args.is_empty() {
-			write!(out, "<")?;
-			for arg in args {
-				match arg {
-					GenericArg::Lifetime(lifetime_name) => {
-						write!(out, "`{lifetime_name}")?;
-					}
-					GenericArg::Type(generic_type) => {
-						write_type(out, root, generic_type)?;
-					}
-					GenericArg::Infer => {
-						write!(out, "_")?;
+fn function_pointer(
+	ctx: &Context,
+	function_pointer: &FunctionPointer,
+) -> Result<TokenStream, Error> {
+	let generic_params = higher_ranked(ctx, &function_pointer.generic_params)?;
+	let header = header(&function_pointer.header);
+	let inputs = function_pointer
+		.sig
+		.inputs
+		.iter()
+		.map(|(input_name, input_type)| {
+			let input_type = ty(ctx, input_type)?;
+			if input_name.is_empty() || input_name == "_" {
+				return Ok(input_type);
+			}
+			let input_name = self::input_name(input_name)?;
+			Ok(quote!(#input_name: #input_type))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	let variadic = function_pointer.sig.is_c_variadic.then(|| quote!(...));
+	let output = output(ctx, function_pointer.sig.output.as_ref())?;
+	Ok(quote!(#generic_params #header fn(#(#inputs,)* #variadic) #output))
+}
+
+/// Returns the qualifiers of a function, such as `unsafe extern "C"`.
+fn header(header: &FunctionHeader) -> TokenStream {
+	let constness = header.is_const.then(|| quote!(const));
+	let asyncness = header.is_async.then(|| quote!(async));
+	let unsafety = header.is_unsafe.then(|| quote!(unsafe));
+	let (name, unwind) = match &header.abi {
+		Abi::Rust => return quote!(#constness #asyncness #unsafety),
+		Abi::C {
+			unwind,
+		} => ("C", *unwind),
+		Abi::Cdecl {
+			unwind,
+		} => ("cdecl", *unwind),
+		Abi::Stdcall {
+			unwind,
+		} => ("stdcall", *unwind),
+		Abi::Fastcall {
+			unwind,
+		} => ("fastcall", *unwind),
+		Abi::Aapcs {
+			unwind,
+		} => ("aapcs", *unwind),
+		Abi::Win64 {
+			unwind,
+		} => ("win64", *unwind),
+		Abi::SysV64 {
+			unwind,
+		} => ("sysv64", *unwind),
+		Abi::System {
+			unwind,
+		} => ("system", *unwind),
+		Abi::Other(name) => (name.trim_matches('"'), false),
+	};
+	let abi = if unwind {
+		format!("{name}-unwind")
+	} else {
+		name.to_owned()
+	};
+	quote!(#constness #asyncness #unsafety extern #abi)
+}
+
+fn output(ctx: &Context, output_type: Option<&Type>) -> Result<Option<TokenStream>, Error> {
+	output_type
+		.map(|output_type| {
+			let output_type = ty(ctx, output_type)?;
+			Ok(quote!(-> #output_type))
+		})
+		.transpose()
+}
+
+pub fn generic_args(ctx: &Context, args: &GenericArgs) -> Result<TokenStream, Error> {
+	match args {
+		GenericArgs::AngleBracketed {
+			args,
+			constraints,
+		} => {
+			if args.is_empty() && constraints.is_empty() {
+				return Ok(TokenStream::new());
+			}
+			let args = args
+				.iter()
+				.map(|arg| {
+					match arg {
+						GenericArg::Lifetime(lifetime_name) => {
+							Ok(lifetime(lifetime_name)?.into_token_stream())
+						}
+						GenericArg::Type(generic_type) => ty(ctx, generic_type),
+						GenericArg::Const(generic_constant) => constant(generic_constant),
+						GenericArg::Infer => Ok(quote!(_)),
 					}
-					GenericArg::Const(_) => {
-						unimplemented!();
+				})
+				.chain(constraints.iter().map(|constraint| self::constraint(ctx, constraint)))
+				.collect::<Result<Vec<_>, Error>>()?;
+			Ok(quote!(<#(#args),*>))
+		}
+		GenericArgs::Parenthesized {
+			inputs,
+			output,
+		} => {
+			let inputs =
+				inputs.iter().map(|input| ty(ctx, input)).collect::<Result<Vec<_>, Error>>()?;
+			let output = self::output(ctx, output.as_ref())?;
+			Ok(quote!((#(#inputs),*) #output))
+		}
+	}
+}
+
+/// Returns an associated item constraint such as `Item = u8` or `Item: Copy`.
+fn constraint(ctx: &Context, constraint: &AssocItemConstraint) -> Result<TokenStream, Error> {
+	let name = format_ident!("{}", constraint.name);
+	let args = generic_args(ctx, &constraint.args)?;
+	match &constraint.binding {
+		AssocItemConstraintKind::Equality(term) => {
+			let term = self::term(ctx, term)?;
+			Ok(quote!(#name #args = #term))
+		}
+		AssocItemConstraintKind::Constraint(bounds) => {
+			let bounds = generic_bounds(ctx, bounds)?;
+			Ok(quote!(#name #args: #(#bounds)+*))
+		}
+	}
+}
+
+fn term(ctx: &Context, term: &Term) -> Result<TokenStream, Error> {
+	match term {
+		Term::Type(term_type) => ty(ctx, term_type),
+		Term::Constant(term_constant) => constant(term_constant),
+	}
+}
+
+fn generic_bounds(ctx: &Context, bounds: &[GenericBound]) -> Result<Vec<TokenStream>, Error> {
+	bounds.iter().map(|bound| generic_bound(ctx, bound)).collect()
+}
+
+fn generic_bound(ctx: &Context, bound: &GenericBound) -> Result<TokenStream, Error> {
+	match bound {
+		GenericBound::TraitBound {
+			trait_,
+			generic_params,
+			modifier,
+		} => {
+			let generic_params = higher_ranked(ctx, generic_params)?;
+			let modifier = match modifier {
+				TraitBoundModifier::None => None,
+				TraitBoundModifier::Maybe => Some(quote!(?)),
+				TraitBoundModifier::MaybeConst => return Err(Error::Unstable("`~const` bound")),
+			};
+			let trait_ = resolved_path(ctx, trait_)?;
+			Ok(quote!(#generic_params #modifier #trait_))
+		}
+		GenericBound::Outlives(lifetime_name) => Ok(lifetime(lifetime_name)?.into_token_stream()),
+		GenericBound::Use(names) => {
+			let names = names
+				.iter()
+				.map(|name| {
+					if name.starts_with('\'') {
+						Ok(lifetime(name)?.into_token_stream())
+					} else {
+						Ok(format_ident!("{name}").into_token_stream())
 					}
-				}
-				write!(out, ",")?;
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+			Ok(quote!(use<#(#names),*>))
+		}
+	}
+}
+
+/// Returns the `for<...>` binder of higher-ranked bounds and types, if any.
+fn higher_ranked(
+	ctx: &Context,
+	generic_params: &[GenericParamDef],
+) -> Result<Option<TokenStream>, Error> {
+	if generic_params.is_empty() {
+		return Ok(None);
+	}
+	let generic_params = generic_params
+		.iter()
+		.map(|generic_param| generic_param_def(ctx, generic_param, &[]))
+		.collect::<Result<Vec<_>, Error>>()?;
+	Ok(Some(quote!(for<#(#generic_params),*>)))
+}
+
+/// Returns a generic parameter with its bounds and default, adding `extra_bounds` to type
+/// parameters.
+fn generic_param_def(
+	ctx: &Context,
+	generic_param: &GenericParamDef,
+	extra_bounds: &[TokenStream],
+) -> Result<TokenStream, Error> {
+	match &generic_param.kind {
+		GenericParamDefKind::Lifetime {
+			outlives,
+		} => {
+			let name = lifetime(&generic_param.name)?;
+			if outlives.is_empty() {
+				return Ok(name.into_token_stream());
 			}
-			write!(out, ">")?;
+			let outlives = outlives
+				.iter()
+				.map(|outlived| lifetime(outlived))
+				.collect::<Result<Vec<_>, Error>>()?;
+			Ok(quote!(#name: #(#outlives)+*))
 		}
-		if !constraints.is_empty() {
-			unimplemented!();
+		GenericParamDefKind::Type {
+			bounds,
+			default,
+			..
+		} => {
+			let name = format_ident!("{}", generic_param.name);
+			let mut bounds = generic_bounds(ctx, bounds)?;
+			bounds.extend(extra_bounds.iter().cloned());
+			let colon = (!bounds.is_empty()).then(|| quote!(:));
+			let default = default.as_ref().map(|default_type| ty(ctx, default_type)).transpose()?;
+			let default = default.map(|default_type| quote!(= #default_type));
+			Ok(quote!(#name #colon #(#bounds)+* #default))
+		}
+		GenericParamDefKind::Const {
+			type_,
+			default,
+		} => {
+			let name = format_ident!("{}", generic_param.name);
+			let type_ = ty(ctx, type_)?;
+			let default =
+				default.as_deref().map(|default| tokens("constant", default)).transpose()?;
+			let default = default.map(|default| quote!(= #default));
+			Ok(quote!(const #name: #type_ #default))
 		}
-	} else {
-		unimplemented!()
 	}
-	Ok(())
 }
 
-pub fn write_function_args<W: Write>(
-	out: &mut W,
-	root: &Crate,
+/// Returns the signature of the function after its name, with `receiver` as its first input.
+pub fn function_args(
+	ctx: &Context,
 	function: &Function,
-) -> io::Result<()> {
-	if !function.generics.params.is_empty() {
-		write!(out, "<")?;
-		for generic_param in &function.generics.params {
-			write!(out, "{}: ", generic_param.name)?;
-			match &generic_param.kind {
+	receiver: Option<TokenStream>,
+) -> Result<TokenStream, Error> {
+	let generics = generic_params(ctx, &function.generics, &[])?;
+	let inputs = inputs(ctx, function, receiver)?;
+	let output = output(ctx, function.sig.output.as_ref())?;
+	let where_clause = where_clause(ctx, &function.generics)?;
+	Ok(quote!(#generics #inputs #output #where_clause))
+}
+
+/// Returns the generic parameters, adding `extra_bounds` to type parameters. Synthetic parameters
+/// standing for `impl Trait` inputs are left out, as the inputs are printed as such.
+fn generic_params(
+	ctx: &Context,
+	generics: &Generics,
+	extra_bounds: &[TokenStream],
+) -> Result<TokenStream, Error> {
+	let params = generics
+		.params
+		.iter()
+		.filter(|generic_param| {
+			!matches!(
+				generic_param.kind,
 				GenericParamDefKind::Type {
+					is_synthetic: true,
+					..
+				}
+			)
+		})
+		.map(|generic_param| generic_param_def(ctx, generic_param, extra_bounds))
+		.collect::<Result<Vec<_>, Error>>()?;
+	if params.is_empty() {
+		return Ok(TokenStream::new());
+	}
+	Ok(quote!(<#(#params),*>))
+}
+
+fn where_clause(ctx: &Context, generics: &Generics) -> Result<TokenStream, Error> {
+	if generics.where_predicates.is_empty() {
+		return Ok(TokenStream::new());
+	}
+	let predicates = generics
+		.where_predicates
+		.iter()
+		.map(|predicate| {
+			match predicate {
+				WherePredicate::BoundPredicate {
+					type_,
 					bounds,
-					default,
-					is_synthetic,
+					generic_params,
 				} => {
-					for bound in bounds {
-						match bound {
-							GenericBound::TraitBound {
-								trait_,
-								generic_params,
-								modifier,
-							} => {
-								if !generic_params.is_empty()
-									|| *modifier != TraitBoundModifier::None
-								{
-									unimplemented!();
-								}
-								write!(out, "{}", trait_.name)?;
-								if let Some(args) = &trait_.args {
-									write_generic_args(out, root, args)?;
-								}
-								write!(out, " + ")?;
-							}
-							_ => unimplemented!(),
+					let generic_params = higher_ranked(ctx, generic_params)?;
+					let type_ = ty(ctx, type_)?;
+					let bounds = generic_bounds(ctx, bounds)?;
+					Ok(quote!(#generic_params #type_: #(#bounds)+*))
+				}
+				WherePredicate::LifetimePredicate {
+					lifetime,
+					outlives,
+				} => {
+					let lifetime = self::lifetime(lifetime)?;
+					let outlives = outlives
+						.iter()
+						.map(|outlived| self::lifetime(outlived))
+						.collect::<Result<Vec<_>, Error>>()?;
+					Ok(quote!(#lifetime: #(#outlives)+*))
+				}
+				WherePredicate::EqPredicate {
+					..
+				} => Err(Error::Unstable("equality predicate")),
+			}
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	Ok(quote!(where #(#predicates),*))
+}
+
+fn inputs(
+	ctx: &Context,
+	function: &Function,
+	receiver: Option<TokenStream>,
+) -> Result<TokenStream, Error> {
+	let inputs = function
+		.sig
+		.inputs
+		.iter()
+		.map(|(input_name, input_type)| {
+			if input_name == "self" {
+				self::receiver(ctx, input_type)
+			} else {
+				let input_name = self::input_name(input_name)?;
+				let input_type = ty(ctx, input_type)?;
+				Ok(quote!(#input_name: #input_type))
+			}
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	let inputs = receiver.into_iter().chain(inputs);
+	let variadic = function.sig.is_c_variadic.then(|| quote!(, ...));
+	Ok(quote!((#(#inputs),* #variadic)))
+}
+
+/// Returns the names of the inputs of the function, including `self`, as passed on when
+/// forwarding a call. The names are checked by [`input_name`] when the signature is printed.
+pub fn input_names(function: &Function) -> impl Iterator<Item = Ident> + '_ {
+	function.sig.inputs.iter().map(|(input_name, _)| format_ident!("{input_name}"))
+}
+
+/// Returns whether the type is `Self`.
+pub fn is_self(item_type: &Type) -> bool {
+	matches!(item_type, Type::Generic(name) if name == "Self")
+}
+
+/// Returns the first type argument of a path such as `io::Result<T>` or `Option<T>`.
+pub fn first_type_arg(item_type: &Type) -> Option<&Type> {
+	let Type::ResolvedPath(path) = item_type else {
+		return None;
+	};
+	let Some(GenericArgs::AngleBracketed {
+		args,
+		..
+	}) = path.args.as_deref()
+	else {
+		return None;
+	};
+	match args.first() {
+		Some(GenericArg::Type(arg_type)) => Some(arg_type),
+		_ => None,
+	}
+}
+
+/// Returns whether the type is `&mut Self`, as returned by builder methods.
+pub fn is_builder_output(output_type: Option<&Type>) -> bool {
+	matches!(output_type, Some(Type::BorrowedRef { is_mutable: true, type_, .. }) if is_self(type_))
+}
+
+/// Returns `T` if the named generic parameter of the function is bound by `AsRef<T>`.
+pub fn as_ref_target<'a>(function: &'a Function, generic_name: &str) -> Option<&'a Type> {
+	let generic_param = function.generics.params.iter().find(|param| param.name == generic_name)?;
+	let GenericParamDefKind::Type {
+		bounds,
+		..
+	} = &generic_param.kind
+	else {
+		return None;
+	};
+	bounds.iter().find_map(|bound| {
+		match bound {
+			GenericBound::TraitBound {
+				trait_,
+				..
+			} if trait_.name == "AsRef" => {
+				match trait_.args.as_deref() {
+					Some(GenericArgs::AngleBracketed {
+						args,
+						..
+					}) => {
+						match args.as_slice() {
+							[GenericArg::Type(target)] => Some(target),
+							_ => None,
 						}
 					}
-					if *is_synthetic || default.is_some() {
-						unimplemented!();
-					}
+					_ => None,
 				}
-				_ => unimplemented!(),
 			}
-			write!(out, ", ")?;
+			_ => None,
+		}
+	})
+}
+
+/// Returns `T` if the named generic parameter of the function is bound by `AsRef<T>`, and an error
+/// otherwise.
+pub fn require_as_ref_target<'a>(
+	function: &'a Function,
+	generic_name: &str,
+) -> Result<&'a Type, Error> {
+	as_ref_target(function, generic_name).ok_or_else(|| Error::NotAsRef(generic_name.to_owned()))
+}
+
+/// Returns the function signature in a dyn-compatible form. Generic `AsRef<T>` inputs are taken
+/// as `&T`, methods taking `self` by value take `self: Box<Self>`, and builder methods return
+/// nothing.
+pub fn dyn_function_args(
+	ctx: &Context,
+	function: &Function,
+	receiver: Option<TokenStream>,
+) -> Result<TokenStream, Error> {
+	let inputs = function
+		.sig
+		.inputs
+		.iter()
+		.map(|(input_name, input_type)| {
+			if input_name == "self" {
+				return if is_self(input_type) {
+					Ok(quote!(self: Box<Self>))
+				} else {
+					self::receiver(ctx, input_type)
+				};
+			}
+			let input_name = self::input_name(input_name)?;
+			let input_type = match input_type {
+				Type::Generic(generic_name) => {
+					let target = ty(ctx, require_as_ref_target(function, generic_name)?)?;
+					quote!(&#target)
+				}
+				_ => ty(ctx, input_type)?,
+			};
+			Ok(quote!(#input_name: #input_type))
+		})
+		.collect::<Result<Vec<_>, Error>>()?;
+	let inputs = receiver.into_iter().chain(inputs);
+	let output = match &function.sig.output {
+		Some(output_type) if !is_builder_output(Some(output_type)) => {
+			output(ctx, Some(output_type))?
+		}
+		_ => None,
+	};
+	Ok(quote!((#(#inputs),*) #output))
+}
+
+fn receiver(ctx: &Context, receiver_type: &Type) -> Result<TokenStream, Error> {
+	match receiver_type {
+		Type::Generic(_) if is_self(receiver_type) => Ok(quote!(self)),
+		Type::BorrowedRef {
+			lifetime: None,
+			is_mutable,
+			type_,
+		} if is_self(type_) => {
+			if *is_mutable {
+				Ok(quote!(&mut self))
+			} else {
+				Ok(quote!(&self))
+			}
+		}
+		_ => {
+			let receiver_type = ty(ctx, receiver_type)?;
+			Ok(quote!(self: #receiver_type))
 		}
-		write!(out, ">")?;
 	}
+}
+
+/// Returns the function signature as a method taking `&self`, in addition to its own inputs.
+pub fn method(ctx: &Context, name: &str, function: &Function) -> Result<TokenStream, Error> {
+	let name = format_ident!("{name}");
+	let args = function_args(ctx, function, Some(quote!(&self)))?;
+	Ok(quote!(fn #name #args))
+}
+
+/// Returns the function signature as a method taking `&self` and returning a `Send` future of its
+/// output. Generic parameters are required to be `Send` as well, so that implementations written
+/// as `async fn` can hold on to them across await points.
+pub fn async_method(ctx: &Context, name: &str, function: &Function) -> Result<TokenStream, Error> {
+	let name = format_ident!("{name}");
+	let generics = generic_params(ctx, &function.generics, &[quote!(Send)])?;
+	let inputs = inputs(ctx, function, Some(quote!(&self)))?;
+	let output =
+		function.sig.output.as_ref().map(|output_type| ty(ctx, output_type)).transpose()?;
+	let output = output.unwrap_or_else(|| quote!(()));
+	let where_clause = where_clause(ctx, &function.generics)?;
+	Ok(quote!(fn #name #generics #inputs -> impl Future<Output = #output> + Send #where_clause))
+}
 
-	write!(out, "(")?;
-	for (input_name, input_type) in &function.sig.inputs {
-		write!(out, "{input_name}: ")?;
-		write_type(out, root, input_type)?;
-		write!(out, ", ")?;
+#[cfg(test)]
+mod tests {
+	use rustdoc_types::FunctionSignature;
+
+	use super::*;
+
+	fn rust_header() -> FunctionHeader {
+		FunctionHeader {
+			is_const: false,
+			is_unsafe: false,
+			is_async: false,
+			abi: Abi::Rust,
+		}
 	}
-	write!(out, ")")?;
 
-	if let Some(output_type) = &function.sig.output {
-		write!(out, " -> ")?;
-		write_type(out, root, output_type)?;
+	fn signature(inputs: &[(&str, Type)]) -> FunctionSignature {
+		FunctionSignature {
+			inputs: inputs
+				.iter()
+				.map(|(name, input_type)| (name.to_string(), input_type.clone()))
+				.collect(),
+			output: None,
+			is_c_variadic: false,
+		}
 	}
-	Ok(())
-}
 
-pub fn write_function<W: Write>(
-	out: &mut W,
-	root: &Crate,
-	name: &str,
-	function: &Function,
-) -> io::Result<()> {
-	write!(out, "fn {name}")?;
-	write_function_args(out, root, function)
+	fn function(inputs: &[(&str, Type)]) -> Function {
+		Function {
+			sig: signature(inputs),
+			generics: Generics {
+				params: Vec::new(),
+				where_predicates: Vec::new(),
+			},
+			header: rust_header(),
+			has_body: false,
+		}
+	}
+
+	fn function_pointer(inputs: &[(&str, Type)]) -> Type {
+		Type::FunctionPointer(Box::new(FunctionPointer {
+			sig: signature(inputs),
+			generic_params: Vec::new(),
+			header: rust_header(),
+		}))
+	}
+
+	fn u8() -> Type {
+		Type::Primitive("u8".to_owned())
+	}
+
+	fn assert_invalid<T>(result: Result<T, Error>, expected_kind: &str) {
+		match result {
+			Err(Error::Tokens {
+				kind,
+				..
+			}) => assert_eq!(kind, expected_kind),
+			Err(error) => panic!("expected an invalid {expected_kind}, got: {error}"),
+			Ok(_) => panic!("expected an invalid {expected_kind}"),
+		}
+	}
+
+	#[test]
+	fn pattern_inputs_are_rejected() {
+		let ctx = Context::default();
+		let tuple = Type::Tuple(vec![
+			u8(),
+			u8(),
+		]);
+		assert_invalid(
+			function_args(&ctx, &function(&[("(a, b)", tuple.clone())]), None),
+			"input name",
+		);
+		assert_invalid(
+			dyn_function_args(&ctx, &function(&[("(a, b)", tuple.clone())]), None),
+			"input name",
+		);
+		assert_invalid(ty(&ctx, &function_pointer(&[("(a, b)", tuple)])), "input name");
+		assert_invalid(input_name("type"), "input name");
+	}
+
+	#[test]
+	fn unnamed_function_pointer_inputs_print_their_type() {
+		let ctx = Context::default();
+		let printed = ty(
+			&ctx,
+			&function_pointer(&[
+				("_", u8()),
+				("", u8()),
+			]),
+		)
+		.unwrap();
+		let parsed: syn::TypeBareFn = syn::parse2(printed).unwrap();
+		assert_eq!(parsed.inputs.len(), 2);
+		assert!(parsed.inputs.iter().all(|input| input.name.is_none()));
+	}
+
+	#[test]
+	fn malformed_lifetimes_are_rejected() {
+		let ctx = Context::default();
+		let reference = Type::BorrowedRef {
+			lifetime: Some("'a b".to_owned()),
+			is_mutable: false,
+			type_: Box::new(u8()),
+		};
+		assert_invalid(ty(&ctx, &reference), "lifetime");
+		assert_invalid(lifetime("'1"), "lifetime");
+		assert_eq!(lifetime("a").unwrap().to_string(), "'a");
+	}
+
+	fn resolved(name: &str, args: Option<GenericArgs>) -> Type {
+		Type::ResolvedPath(Path {
+			name: name.to_owned(),
+			id: Id(0),
+			args: args.map(Box::new),
+		})
+	}
+
+	fn trait_bound(name: &str, args: Option<GenericArgs>) -> GenericBound {
+		let Type::ResolvedPath(trait_) = resolved(name, args) else {
+			unreachable!("resolved paths are printed as such");
+		};
+		GenericBound::TraitBound {
+			trait_,
+			generic_params: Vec::new(),
+			modifier: TraitBoundModifier::None,
+		}
+	}
+
+	fn angle_bracketed(args: Vec<GenericArg>) -> GenericArgs {
+		GenericArgs::AngleBracketed {
+			args,
+			constraints: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn lifetimes_keep_their_apostrophe() {
+		let ctx = Context::default();
+		let cow = resolved(
+			"Cow",
+			Some(angle_bracketed(vec![
+				GenericArg::Lifetime("'a".to_owned()),
+				GenericArg::Type(Type::Primitive("str".to_owned())),
+			])),
+		);
+		let reference = Type::BorrowedRef {
+			lifetime: Some("a".to_owned()),
+			is_mutable: false,
+			type_: Box::new(cow),
+		};
+		let printed = ty(&ctx, &reference).unwrap();
+		let syn::Type::Reference(parsed) = syn::parse2(printed).unwrap() else {
+			panic!("expected a reference");
+		};
+		assert_eq!(parsed.lifetime.unwrap().ident, "a");
+		let syn::Type::Path(cow) = *parsed.elem else {
+			panic!("expected a path");
+		};
+		let syn::PathArguments::AngleBracketed(args) = &cow.path.segments[0].arguments else {
+			panic!("expected generic arguments");
+		};
+		assert!(
+			matches!(&args.args[0], syn::GenericArgument::Lifetime(lifetime) if lifetime.ident == "a")
+		);
+	}
+
+	#[test]
+	fn single_element_tuples_keep_their_comma() {
+		let ctx = Context::default();
+		let printed = ty(&ctx, &Type::Tuple(vec![u8()])).unwrap();
+		let syn::Type::Tuple(parsed) = syn::parse2(printed).unwrap() else {
+			panic!("expected a tuple");
+		};
+		assert_eq!(parsed.elems.len(), 1);
+
+		let printed = ty(&ctx, &Type::Tuple(Vec::new())).unwrap();
+		let syn::Type::Tuple(parsed) = syn::parse2(printed).unwrap() else {
+			panic!("expected the unit type");
+		};
+		assert!(parsed.elems.is_empty());
+	}
+
+	#[test]
+	fn generic_parameters_with_several_bounds_parse() {
+		let ctx = Context::default();
+		let as_ref_path = trait_bound(
+			"AsRef",
+			Some(angle_bracketed(vec![GenericArg::Type(resolved("path::Path", None))])),
+		);
+		let mut function = function(&[("path", Type::Generic("P".to_owned()))]);
+		function.generics.params.push(GenericParamDef {
+			name: "P".to_owned(),
+			kind: GenericParamDefKind::Type {
+				bounds: vec![
+					as_ref_path,
+					trait_bound("Send", None),
+				],
+				default: None,
+				is_synthetic: false,
+			},
+		});
+		let args = function_args(&ctx, &function, Some(quote!(&self))).unwrap();
+		let parsed: syn::Signature = syn::parse2(quote!(fn exists #args)).unwrap();
+		let syn::GenericParam::Type(param) = &parsed.generics.params[0] else {
+			panic!("expected a type parameter");
+		};
+		assert_eq!(param.ident, "P");
+		assert_eq!(param.bounds.len(), 2);
+		assert_eq!(parsed.inputs.len(), 2);
+
+		let impl_trait = Type::ImplTrait(vec![
+			trait_bound("Read", None),
+			trait_bound("Send", None),
+		]);
+		let reference = Type::BorrowedRef {
+			lifetime: None,
+			is_mutable: true,
+			type_: Box::new(impl_trait),
+		};
+		let printed = ty(&ctx, &reference).unwrap();
+		let syn::Type::Reference(parsed) = syn::parse2(printed).unwrap() else {
+			panic!("expected a reference");
+		};
+		let syn::Type::Paren(inner) = *parsed.elem else {
+			panic!("expected parentheses around the bounds");
+		};
+		let syn::Type::ImplTrait(bounds) = *inner.elem else {
+			panic!("expected an impl Trait type");
+		};
+		assert_eq!(bounds.bounds.len(), 2);
+	}
 }
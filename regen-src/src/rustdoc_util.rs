@@ -5,11 +5,14 @@ use std::fmt::Debug;
 use std::hash::Hash;
 
 use rustdoc_types::Crate;
+use rustdoc_types::GenericArg;
+use rustdoc_types::GenericArgs;
 use rustdoc_types::Id;
 use rustdoc_types::Item;
 use rustdoc_types::ItemEnum;
 use rustdoc_types::ItemKind;
 use rustdoc_types::Module;
+use rustdoc_types::Type;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -119,7 +122,7 @@ pub fn get_mut(doc: &mut Crate, id: Id) -> Result<&mut Item, ItemError> {
 	Ok(item)
 }
 
-pub fn root_module(doc: &Crate) -> Result<NamedItem<Module>, ItemError> {
+pub fn root_module(doc: &Crate) -> Result<NamedItem<'_, Module>, ItemError> {
 	let root_module = get(doc, &doc.root)?;
 	match &root_module.inner {
 		ItemEnum::Module(module) => {
@@ -146,12 +149,10 @@ pub fn root_module(doc: &Crate) -> Result<NamedItem<Module>, ItemError> {
 
 struct Parent<'a> {
 	id: &'a Id,
-	alias: Option<&'a String>,
 }
 
 pub struct PathResolver<'a> {
 	doc: &'a Crate,
-	root_module: NamedItem<'a, Module>,
 	child_parent_map: HashMap<Id, Id>,
 	import_map: HashMap<Id, Vec<Parent<'a>>>,
 }
@@ -185,7 +186,6 @@ impl<'a> PathResolver<'a> {
 						if let Some(import_id) = &use_item.id {
 							import_map.entry(*import_id).or_default().push(Parent {
 								id: module_id,
-								alias: Some(&use_item.name),
 							});
 						}
 					}
@@ -195,7 +195,6 @@ impl<'a> PathResolver<'a> {
 		}
 		Ok(Self {
 			doc,
-			root_module,
 			child_parent_map,
 			import_map,
 		})
@@ -244,21 +243,97 @@ impl<'a> PathResolver<'a> {
 		(shortest_parent, shortest_len)
 	}
 
-	pub fn root(&self) -> &NamedItem<'a, Module> {
-		&self.root_module
+	/// Returns the path under which the item is documented, such as
+	/// `std::os::unix::fs::PermissionsExt`. Items of other crates fall back to their path in the
+	/// crate summary, and are empty if it is missing.
+	pub fn canonical_path(&self, item_id: Id) -> Vec<&'a str> {
+		if !self.doc.index.contains_key(&item_id) {
+			return self
+				.doc
+				.paths
+				.get(&item_id)
+				.map(|summary| summary.path.iter().map(String::as_str).collect())
+				.unwrap_or_default();
+		}
+		let mut path = Vec::new();
+		let mut id = Some(item_id);
+		while let Some(current) = id {
+			if let Some(name) = self.doc.index.get(&current).and_then(|item| item.name.as_ref()) {
+				path.push(name.as_str());
+			}
+			id = self.canonical_parent(current);
+		}
+		path.reverse();
+		path
 	}
 
-	pub fn doc(&self) -> &Crate {
+	pub fn doc(&self) -> &'a Crate {
 		self.doc
 	}
+
+	/// Returns the type with bare names of documented items replaced by their canonical path.
+	/// Names are kept as written in the source, except in type aliases expanded by rustdoc, such
+	/// as the `Error` of `io::Result<T>` in associated types.
+	pub fn qualify(&self, item_type: &Type) -> Type {
+		let mut item_type = item_type.clone();
+		self.qualify_in_place(&mut item_type);
+		item_type
+	}
+
+	fn qualify_in_place(&self, item_type: &mut Type) {
+		match item_type {
+			Type::ResolvedPath(path) => {
+				if !path.name.contains("::") && self.doc.index.contains_key(&path.id) {
+					path.name = self.canonical_path(path.id).join("::");
+				}
+				if let Some(GenericArgs::AngleBracketed {
+					args,
+					..
+				}) = path.args.as_deref_mut()
+				{
+					for arg in args {
+						if let GenericArg::Type(arg_type) = arg {
+							self.qualify_in_place(arg_type);
+						}
+					}
+				}
+			}
+			Type::Tuple(types) => {
+				for element_type in types {
+					self.qualify_in_place(element_type);
+				}
+			}
+			Type::Slice(element_type)
+			| Type::Array {
+				type_: element_type,
+				..
+			}
+			| Type::RawPointer {
+				type_: element_type,
+				..
+			}
+			| Type::BorrowedRef {
+				type_: element_type,
+				..
+			} => self.qualify_in_place(element_type),
+			_ => {}
+		}
+	}
+}
+
+/// Returns true if the item is only available on nightly Rust.
+pub fn is_unstable(item: &Item) -> bool {
+	item.attrs.iter().any(|attr| attr.starts_with("#[unstable"))
 }
 
-pub fn find_item<'a>(doc: &'a Crate, name: &[&str]) -> Option<&'a Id> {
+/// Returns the item of the given kind at the path `name`. Derive macros share the path of their
+/// trait, such as `core::clone::Clone`.
+pub fn find_item<'a>(doc: &'a Crate, name: &[&str], kind: ItemKind) -> Option<&'a Id> {
 	if name.is_empty() {
 		return None;
 	}
 	for (id, item_summary) in &doc.paths {
-		if item_summary.path == name {
+		if item_summary.path == name && item_summary.kind == kind {
 			return Some(id);
 		}
 	}
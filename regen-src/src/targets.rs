@@ -0,0 +1,407 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use rustdoc_types::Crate;
+use rustdoc_types::Id;
+use rustdoc_types::ItemEnum;
+use rustdoc_types::Type;
+use serde::Deserialize;
+
+use crate::rustdoc_util::ItemError;
+use crate::rustdoc_util::PathResolver;
+
+/// A target the standard library is documented for by `regen-doc`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Target {
+	pub triple: String,
+	os: String,
+	/// Modules of `std::os` that are compiled for the target. Others such as `std::os::windows` on
+	/// Linux are documented all the same, but do not exist outside of documentation.
+	os_modules: Vec<String>,
+}
+
+/// The targets shared by `regen-doc` and `regen-src`, as listed in the `targets` of the
+/// [config](crate::config::Config).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Targets {
+	/// Targets whose documentation is merged, starting with the one items are taken from.
+	pub documented: Vec<Target>,
+	/// Documented operating systems of each target family, by bare `cfg` name such as `unix`.
+	#[serde(default)]
+	pub families: BTreeMap<String, Vec<String>>,
+}
+
+impl Targets {
+	/// Most targets a [`TargetSet`] can hold.
+	pub const MAX: usize = u32::BITS as usize;
+
+	pub fn all(&self) -> TargetSet {
+		TargetSet(u32::MAX >> (Self::MAX - self.documented.len()))
+	}
+
+	/// Returns the targets for which `predicate` holds.
+	fn matching(&self, predicate: impl Fn(&Target) -> bool) -> TargetSet {
+		let mut set = TargetSet::default();
+		for (index, target) in self.documented.iter().enumerate() {
+			if predicate(target) {
+				set.0 |= 1 << index;
+			}
+		}
+		set
+	}
+
+	/// Returns the targets on which the `std::os` module is compiled.
+	pub fn os_module(&self, module: &str) -> TargetSet {
+		self.matching(|target| target.os_modules.iter().any(|os_module| os_module == module))
+	}
+
+	/// Returns the `cfg` predicate selecting `targets`, or `None` if they are all selected.
+	///
+	/// Families are used where every documented target in them is selected, and operating systems
+	/// otherwise, so that items present on both Linux and macOS are available on every Unix.
+	pub fn predicate(&self, targets: TargetSet) -> Option<TokenStream> {
+		if targets == self.all() {
+			return None;
+		}
+		let mut remaining = targets;
+		let mut atoms = Vec::new();
+		for (family, systems) in &self.families {
+			let members = self.matching(|target| systems.contains(&target.os));
+			if members != TargetSet::default() && members.is_subset(targets) {
+				let family = format_ident!("{family}");
+				atoms.push(quote!(#family));
+				remaining.0 &= !members.0;
+			}
+		}
+		// Targets of the same operating system share an atom.
+		let mut selected_systems = Vec::new();
+		for (index, target) in self.documented.iter().enumerate() {
+			if remaining.contains(index) && !selected_systems.contains(&&target.os) {
+				selected_systems.push(&target.os);
+				let os = &target.os;
+				atoms.push(quote!(target_os = #os));
+			}
+		}
+		if let [atom] = atoms.as_slice() {
+			Some(atom.clone())
+		} else {
+			Some(quote!(any(#(#atoms),*)))
+		}
+	}
+}
+
+/// A subset of the [documented](Targets::documented) targets, by index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TargetSet(u32);
+
+impl TargetSet {
+	fn contains(self, index: usize) -> bool {
+		self.0 & (1 << index) != 0
+	}
+
+	fn is_subset(self, other: Self) -> bool {
+		self.0 & !other.0 == 0
+	}
+}
+
+/// Merges the documentation of every documented target, in order, into that of the first one.
+/// Items that are not available on every target get a `#[cfg]` attribute selecting the ones they
+/// are available on, which generators carry over with [`print::cfg`](crate::print::cfg).
+///
+/// Items are matched across targets by path, as ids differ between builds. Items only documented
+/// for other targets are left out.
+pub fn merge(targets: &Targets, mut docs: Vec<Crate>) -> Result<Crate, ItemError> {
+	let keys = docs.iter().map(item_keys).collect::<Result<Vec<_>, ItemError>>()?;
+	let target_keys =
+		keys.iter().map(|keys| keys.values().collect::<HashSet<_>>()).collect::<Vec<_>>();
+	let mut primary = docs.remove(0);
+	for (id, key) in &keys[0] {
+		let available = targets.matching(|target| is_compiled(target, key));
+		let mut documented = TargetSet::default();
+		for (index, keys) in target_keys.iter().enumerate() {
+			if keys.contains(key) && available.contains(index) {
+				documented.0 |= 1 << index;
+			}
+		}
+		if let Some(predicate) = targets.predicate(documented) {
+			if let Some(item) = primary.index.get_mut(id) {
+				item.attrs.push(attribute(&predicate));
+			}
+		}
+	}
+	Ok(primary)
+}
+
+/// Returns the `#[cfg]` attribute added to items by [`merge`], as it appears in their attributes.
+pub fn attribute(predicate: &TokenStream) -> String {
+	format!("#[cfg({predicate})]")
+}
+
+/// Returns whether every `std::os` module named in `key` is compiled for the target.
+fn is_compiled(target: &Target, key: &str) -> bool {
+	key.split("std::os::").skip(1).all(|rest| {
+		let module = rest.split("::").next().unwrap_or(rest);
+		target.os_modules.iter().any(|os_module| os_module == module)
+	})
+}
+
+/// Returns a key identifying each item across targets. Items in modules are keyed by path,
+/// implementations by their trait and type, and associated items by their parent and name.
+fn item_keys(doc: &Crate) -> Result<HashMap<Id, String>, ItemError> {
+	let path_resolver = PathResolver::from(doc)?;
+	let path = |id| path_resolver.canonical_path(id).join("::");
+	let mut keys = HashMap::new();
+	let mut children = Vec::new();
+	for (id, item) in &doc.index {
+		match &item.inner {
+			ItemEnum::Impl(doc_impl) => {
+				let Type::ResolvedPath(for_path) = &doc_impl.for_ else {
+					continue;
+				};
+				if doc_impl.blanket_impl.is_some() || doc_impl.is_synthetic {
+					continue;
+				}
+				let trait_path = doc_impl.trait_.as_ref().map(|trait_| path(trait_.id));
+				let key =
+					format!("impl {} for {}", trait_path.unwrap_or_default(), path(for_path.id));
+				children.push((key.clone(), &doc_impl.items));
+				keys.insert(*id, key);
+			}
+			ItemEnum::Trait(doc_trait) => {
+				let key = path(*id);
+				children.push((key.clone(), &doc_trait.items));
+				keys.insert(*id, key);
+			}
+			// Associated functions have no module, and are keyed along with their parent.
+			ItemEnum::Module(_) | ItemEnum::Struct(_) | ItemEnum::Function(_)
+				if path_resolver.canonical_parent(*id).is_some() =>
+			{
+				keys.insert(*id, path(*id));
+			}
+			_ => {}
+		}
+	}
+	for (parent_key, item_ids) in children {
+		for item_id in item_ids {
+			if let Some(name) = doc.index.get(item_id).and_then(|item| item.name.as_ref()) {
+				keys.insert(*item_id, format!("{parent_key}::{name}"));
+			}
+		}
+	}
+	Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+	use rustdoc_types::Item;
+	use rustdoc_types::Module;
+	use rustdoc_types::Visibility;
+
+	use super::*;
+	use crate::config::Config;
+
+	/// Returns the targets of the config, documented for Linux, Windows, macOS and WASI.
+	fn configured() -> Targets {
+		toml::from_str::<Config>(include_str!("../data/config.toml")).unwrap().targets
+	}
+
+	fn target(triple: &str, os: &str) -> Target {
+		Target {
+			triple: triple.to_owned(),
+			os: os.to_owned(),
+			os_modules: Vec::new(),
+		}
+	}
+
+	fn predicate(targets: &Targets, systems: &[&str]) -> Option<String> {
+		let selected = targets.matching(|target| systems.contains(&target.os.as_str()));
+		targets.predicate(selected).map(|predicate| predicate.to_string())
+	}
+
+	#[test]
+	fn families_are_named_when_every_documented_member_is_selected() {
+		let targets = configured();
+		assert_eq!(
+			predicate(
+				&targets,
+				&[
+					"linux",
+					"windows",
+					"macos",
+					"wasi"
+				]
+			),
+			None
+		);
+		assert_eq!(
+			predicate(
+				&targets,
+				&[
+					"linux",
+					"macos"
+				]
+			),
+			Some(quote!(unix).to_string())
+		);
+		assert_eq!(predicate(&targets, &["windows"]), Some(quote!(windows).to_string()));
+		assert_eq!(predicate(&targets, &["macos"]), Some(quote!(target_os = "macos").to_string()));
+		assert_eq!(
+			predicate(
+				&targets,
+				&[
+					"linux",
+					"windows"
+				]
+			),
+			Some(quote!(any(windows, target_os = "linux")).to_string())
+		);
+
+		// Members of a family that are not documented do not keep it from being named.
+		let targets = Targets {
+			documented: vec![
+				target("x86_64-unknown-linux-gnu", "linux"),
+				target("wasm32-wasip1", "wasi"),
+			],
+			families: BTreeMap::from([
+				(
+					"unix".to_owned(),
+					vec![
+						"linux".to_owned(),
+						"macos".to_owned(),
+					],
+				),
+			]),
+		};
+		assert_eq!(predicate(&targets, &["linux"]), Some(quote!(unix).to_string()));
+	}
+
+	#[test]
+	fn targets_of_one_system_share_an_atom() {
+		let targets = Targets {
+			documented: vec![
+				target("x86_64-unknown-linux-gnu", "linux"),
+				target("aarch64-unknown-linux-gnu", "linux"),
+				target("x86_64-pc-windows-msvc", "windows"),
+			],
+			families: BTreeMap::new(),
+		};
+		assert_eq!(predicate(&targets, &["linux"]), Some(quote!(target_os = "linux").to_string()));
+	}
+
+	/// Returns the documentation of a crate named `std` holding nested modules at `paths`, such as
+	/// `os::unix`.
+	fn doc(paths: &[&str]) -> Crate {
+		let mut index = HashMap::new();
+		let mut module = |id: u32, name: &str, items: Vec<Id>| {
+			index.insert(
+				Id(id),
+				Item {
+					id: Id(id),
+					crate_id: 0,
+					name: Some(name.to_owned()),
+					span: None,
+					visibility: Visibility::Public,
+					docs: None,
+					links: HashMap::new(),
+					attrs: Vec::new(),
+					deprecation: None,
+					inner: ItemEnum::Module(Module {
+						is_crate: id == 0,
+						items,
+						is_stripped: false,
+					}),
+				},
+			);
+		};
+		// Modules are numbered by their position in the sorted list of paths and their ancestors.
+		let mut all_paths = paths
+			.iter()
+			.flat_map(|path| {
+				let segments = path.split("::").collect::<Vec<_>>();
+				(1..=segments.len()).map(move |len| segments[..len].join("::"))
+			})
+			.collect::<Vec<_>>();
+		all_paths.sort();
+		all_paths.dedup();
+		let id = |path: &str| {
+			let position = all_paths.iter().position(|other| other == path).unwrap();
+			Id(u32::try_from(position).unwrap() + 1)
+		};
+		let children = |parent: Option<&str>| {
+			all_paths
+				.iter()
+				.filter(|path| {
+					match (parent, path.rsplit_once("::")) {
+						(None, None) => true,
+						(Some(parent), Some((path_parent, _))) => parent == path_parent,
+						_ => false,
+					}
+				})
+				.map(|path| id(path))
+				.collect::<Vec<_>>()
+		};
+		module(0, "std", children(None));
+		for path in &all_paths {
+			let name = path.rsplit("::").next().unwrap();
+			module(id(path).0, name, children(Some(path)));
+		}
+		Crate {
+			root: Id(0),
+			crate_version: None,
+			includes_private: false,
+			index,
+			paths: HashMap::new(),
+			external_crates: HashMap::new(),
+			format_version: rustdoc_types::FORMAT_VERSION,
+		}
+	}
+
+	fn attrs<'a>(doc: &'a Crate, name: &str) -> &'a [String] {
+		let item = doc.index.values().find(|item| item.name.as_deref() == Some(name)).unwrap();
+		&item.attrs
+	}
+
+	#[test]
+	fn merged_items_are_limited_to_their_targets() {
+		let merged = merge(
+			&configured(),
+			vec![
+				doc(&[
+					"fs::everywhere",
+					"fs::not_wasi",
+					"os::unix",
+					"os::windows",
+				]),
+				doc(&[
+					"fs::everywhere",
+					"fs::not_wasi",
+					"os::windows",
+				]),
+				doc(&[
+					"fs::everywhere",
+					"fs::not_wasi",
+					"os::unix",
+					"os::windows",
+				]),
+				doc(&[
+					"fs::everywhere",
+					"os::wasi",
+				]),
+			],
+		)
+		.unwrap();
+		assert!(attrs(&merged, "everywhere").is_empty());
+		assert_eq!(attrs(&merged, "not_wasi"), [attribute(&quote!(any(unix, windows)))]);
+		// `std::os` modules are documented for every target, but only compiled for some.
+		assert_eq!(attrs(&merged, "unix"), [attribute(&quote!(unix))]);
+		assert_eq!(attrs(&merged, "windows"), [attribute(&quote!(windows))]);
+		// Items only documented for other targets are left out.
+		assert!(!merged.index.values().any(|item| item.name.as_deref() == Some("wasi")));
+	}
+}
@@ -1,12 +1,25 @@
+use rustdoc_types::AssocItemConstraintKind;
+use rustdoc_types::GenericArg;
 use rustdoc_types::GenericArgs;
 use rustdoc_types::GenericBound;
 use rustdoc_types::GenericParamDef;
+use rustdoc_types::GenericParamDefKind;
+use rustdoc_types::Generics;
 use rustdoc_types::Id;
 use rustdoc_types::Item;
 use rustdoc_types::ItemEnum;
 use rustdoc_types::Path;
+use rustdoc_types::StructKind;
+use rustdoc_types::Term;
 use rustdoc_types::Type;
+use rustdoc_types::VariantKind;
+use rustdoc_types::WherePredicate;
 
+/// Calls `visitor` with the id of the item and of every item it refers to, stopping as soon as the
+/// visitor returns `false`. Returns whether all the ids were accepted.
+///
+/// Child items such as fields, variants and implementations are visited by id only, and are not
+/// descended into.
 pub fn visit_item<T>(item: &Item, visitor: &T) -> bool
 where
 	T: Fn(Id) -> bool,
@@ -15,66 +28,76 @@ where
 		return false;
 	}
 	match &item.inner {
-		ItemEnum::Module(module) => {
-			for id in &module.items {
-				if !visitor(*id) {
-					return false;
-				}
-			}
-		}
+		ItemEnum::Module(module) => visit_ids(&module.items, visitor),
 		ItemEnum::ExternCrate {
 			..
 		}
-		| ItemEnum::Macro(_) => return true,
-		ItemEnum::Use(use_item) => {
-			if let Some(id) = &use_item.id {
-				if !visitor(*id) {
-					return false;
-				}
-			}
-		}
+		| ItemEnum::Macro(_)
+		| ItemEnum::ProcMacro(_)
+		| ItemEnum::ExternType => true,
+		ItemEnum::Use(use_item) => use_item.id.is_none_or(visitor),
 		ItemEnum::Union(union) => {
-			for id in &union.fields {
-				if !visitor(*id) {
-					return false;
-				}
-			}
-			for id in &union.impls {
-				if !visitor(*id) {
-					return false;
-				}
+			visit_generics(&union.generics, visitor)
+				&& visit_ids(&union.fields, visitor)
+				&& visit_ids(&union.impls, visitor)
+		}
+		ItemEnum::Struct(struct_item) => {
+			let fields_accepted = match &struct_item.kind {
+				StructKind::Unit => true,
+				StructKind::Tuple(fields) => visit_ids(fields.iter().flatten(), visitor),
+				StructKind::Plain {
+					fields,
+					..
+				} => visit_ids(fields, visitor),
+			};
+			fields_accepted
+				&& visit_generics(&struct_item.generics, visitor)
+				&& visit_ids(&struct_item.impls, visitor)
+		}
+		ItemEnum::StructField(field_type) => visit_type(field_type, visitor),
+		ItemEnum::Enum(enum_item) => {
+			visit_generics(&enum_item.generics, visitor)
+				&& visit_ids(&enum_item.variants, visitor)
+				&& visit_ids(&enum_item.impls, visitor)
+		}
+		ItemEnum::Variant(variant) => {
+			match &variant.kind {
+				VariantKind::Plain => true,
+				VariantKind::Tuple(fields) => visit_ids(fields.iter().flatten(), visitor),
+				VariantKind::Struct {
+					fields,
+					..
+				} => visit_ids(fields, visitor),
 			}
-			unimplemented!();
 		}
-		ItemEnum::Struct(_) => unimplemented!(),
-		ItemEnum::StructField(_) => unimplemented!(),
-		ItemEnum::Enum(_) => unimplemented!(),
-		ItemEnum::Variant(_) => unimplemented!(),
-		ItemEnum::Function(_) => unimplemented!(),
-		ItemEnum::Trait(_) => unimplemented!(),
-		ItemEnum::TraitAlias(_) => unimplemented!(),
+		ItemEnum::Function(function) => {
+			visit_generics(&function.generics, visitor)
+				&& function.sig.inputs.iter().all(|(_, input_type)| visit_type(input_type, visitor))
+				&& function.sig.output.as_ref().is_none_or(|output| visit_type(output, visitor))
+		}
+		ItemEnum::Trait(trait_item) => {
+			visit_generics(&trait_item.generics, visitor)
+				&& visit_generic_bounds(&trait_item.bounds, visitor)
+				&& visit_ids(&trait_item.items, visitor)
+				&& visit_ids(&trait_item.implementations, visitor)
+		}
+		ItemEnum::TraitAlias(trait_alias) => {
+			visit_generics(&trait_alias.generics, visitor)
+				&& visit_generic_bounds(&trait_alias.params, visitor)
+		}
 		ItemEnum::Impl(impl_item) => {
-			if !visit_type(&impl_item.for_, visitor) {
-				return false;
-			}
-			if let Some(item_type) = &impl_item.blanket_impl {
-				if !visit_type(item_type, visitor) {
-					return false;
-				}
-			}
-			if let Some(path) = &impl_item.trait_ {
-				if !visit_path(path, visitor) {
-					return false;
-				}
-			}
-			for id in &impl_item.items {
-				if !visitor(*id) {
-					return false;
-				}
-			}
-			return visit_generic_params(&impl_item.generics.params, visitor);
+			visit_type(&impl_item.for_, visitor)
+				&& impl_item
+					.blanket_impl
+					.as_ref()
+					.is_none_or(|item_type| visit_type(item_type, visitor))
+				&& impl_item.trait_.as_ref().is_none_or(|path| visit_path(path, visitor))
+				&& visit_ids(&impl_item.items, visitor)
+				&& visit_generics(&impl_item.generics, visitor)
+		}
+		ItemEnum::TypeAlias(type_alias) => {
+			visit_generics(&type_alias.generics, visitor) && visit_type(&type_alias.type_, visitor)
 		}
-		ItemEnum::TypeAlias(_) => unimplemented!(),
 		ItemEnum::Constant {
 			type_,
 			..
@@ -82,16 +105,26 @@ where
 		| ItemEnum::AssocConst {
 			type_,
 			..
-		} => return visit_type(type_, visitor),
-		ItemEnum::Static(_) => unimplemented!(),
-		ItemEnum::ExternType => unimplemented!(),
-		ItemEnum::ProcMacro(_) => unimplemented!(),
-		ItemEnum::Primitive(_) => unimplemented!(),
+		} => visit_type(type_, visitor),
+		ItemEnum::Static(static_item) => visit_type(&static_item.type_, visitor),
+		ItemEnum::Primitive(primitive) => visit_ids(&primitive.impls, visitor),
 		ItemEnum::AssocType {
-			..
-		} => unimplemented!(),
+			generics,
+			bounds,
+			type_,
+		} => {
+			visit_generics(generics, visitor)
+				&& visit_generic_bounds(bounds, visitor)
+				&& type_.as_ref().is_none_or(|item_type| visit_type(item_type, visitor))
+		}
 	}
-	true
+}
+
+fn visit_ids<'a, T>(ids: impl IntoIterator<Item = &'a Id>, visitor: &T) -> bool
+where
+	T: Fn(Id) -> bool,
+{
+	ids.into_iter().all(|id| visitor(*id))
 }
 
 fn visit_path<T>(path: &Path, visitor: &T) -> bool
@@ -109,7 +142,7 @@ where
 	true
 }
 
-fn visit_type<T>(item_type: &Type, visitor: &T) -> bool
+pub fn visit_type<T>(item_type: &Type, visitor: &T) -> bool
 where
 	T: Fn(Id) -> bool,
 {
@@ -126,8 +159,13 @@ where
 			}
 			true
 		}
-		Type::Generic(_) | Type::Primitive(_) => true,
-		Type::FunctionPointer(_) => unimplemented!("visit function pointer"),
+		Type::Generic(_) | Type::Primitive(_) | Type::Infer => true,
+		Type::FunctionPointer(function_pointer) => {
+			let sig = &function_pointer.sig;
+			visit_generic_params(&function_pointer.generic_params, visitor)
+				&& sig.inputs.iter().all(|(_, input_type)| visit_type(input_type, visitor))
+				&& sig.output.as_ref().is_none_or(|output| visit_type(output, visitor))
+		}
 		Type::Tuple(vec) => {
 			for tuple_type in vec {
 				if !visit_type(tuple_type, visitor) {
@@ -136,8 +174,8 @@ where
 			}
 			true
 		}
-		Type::Slice(_) => unimplemented!(),
-		Type::Array {
+		Type::Slice(type_)
+		| Type::Array {
 			type_,
 			..
 		}
@@ -154,7 +192,6 @@ where
 			..
 		} => visit_type(type_, visitor),
 		Type::ImplTrait(vec) => visit_generic_bounds(vec, visitor),
-		Type::Infer => unimplemented!(),
 		Type::QualifiedPath {
 			args,
 			self_type,
@@ -179,20 +216,15 @@ where
 	T: Fn(Id) -> bool,
 {
 	match args {
-		rustdoc_types::GenericArgs::AngleBracketed {
+		GenericArgs::AngleBracketed {
 			args,
 			constraints,
 		} => {
 			for arg in args {
-				match arg {
-					rustdoc_types::GenericArg::Type(arg_type) => {
-						if !visit_type(arg_type, visitor) {
-							return false;
-						}
+				if let GenericArg::Type(arg_type) = arg {
+					if !visit_type(arg_type, visitor) {
+						return false;
 					}
-					rustdoc_types::GenericArg::Lifetime(_)
-					| rustdoc_types::GenericArg::Const(_)
-					| rustdoc_types::GenericArg::Infer => return true,
 				}
 			}
 			for constraint in constraints {
@@ -200,17 +232,12 @@ where
 					return false;
 				}
 				match &constraint.binding {
-					rustdoc_types::AssocItemConstraintKind::Equality(term) => {
-						match term {
-							rustdoc_types::Term::Type(term_type) => {
-								if !visit_type(term_type, visitor) {
-									return false;
-								}
-							}
-							rustdoc_types::Term::Constant(_) => return true,
+					AssocItemConstraintKind::Equality(term) => {
+						if !visit_term(term, visitor) {
+							return false;
 						}
 					}
-					rustdoc_types::AssocItemConstraintKind::Constraint(vec) => {
+					AssocItemConstraintKind::Constraint(vec) => {
 						if !visit_generic_bounds(vec, visitor) {
 							return false;
 						}
@@ -218,7 +245,7 @@ where
 				}
 			}
 		}
-		rustdoc_types::GenericArgs::Parenthesized {
+		GenericArgs::Parenthesized {
 			inputs,
 			output,
 		} => {
@@ -237,16 +264,55 @@ where
 	true
 }
 
+fn visit_term<T>(term: &Term, visitor: &T) -> bool
+where
+	T: Fn(Id) -> bool,
+{
+	match term {
+		Term::Type(term_type) => visit_type(term_type, visitor),
+		Term::Constant(_) => true,
+	}
+}
+
+fn visit_generics<T>(generics: &Generics, visitor: &T) -> bool
+where
+	T: Fn(Id) -> bool,
+{
+	if !visit_generic_params(&generics.params, visitor) {
+		return false;
+	}
+	generics.where_predicates.iter().all(|predicate| {
+		match predicate {
+			WherePredicate::BoundPredicate {
+				type_,
+				bounds,
+				generic_params,
+			} => {
+				visit_type(type_, visitor)
+					&& visit_generic_bounds(bounds, visitor)
+					&& visit_generic_params(generic_params, visitor)
+			}
+			WherePredicate::LifetimePredicate {
+				..
+			} => true,
+			WherePredicate::EqPredicate {
+				lhs,
+				rhs,
+			} => visit_type(lhs, visitor) && visit_term(rhs, visitor),
+		}
+	})
+}
+
 fn visit_generic_params<T>(generic_params: &[GenericParamDef], visitor: &T) -> bool
 where
 	T: Fn(Id) -> bool,
 {
 	for generic in generic_params {
 		match &generic.kind {
-			rustdoc_types::GenericParamDefKind::Lifetime {
+			GenericParamDefKind::Lifetime {
 				..
-			} => continue,
-			rustdoc_types::GenericParamDefKind::Type {
+			} => {}
+			GenericParamDefKind::Type {
 				bounds,
 				default,
 				..
@@ -260,7 +326,7 @@ where
 					return false;
 				}
 			}
-			rustdoc_types::GenericParamDefKind::Const {
+			GenericParamDefKind::Const {
 				type_,
 				..
 			} => {
@@ -278,21 +344,17 @@ where
 	T: Fn(Id) -> bool,
 {
 	for bound in bounds {
-		match bound {
-			rustdoc_types::GenericBound::TraitBound {
-				trait_,
-				generic_params,
-				..
-			} => {
-				if !visitor(trait_.id) {
-					return false;
-				}
-				if !visit_generic_params(generic_params, visitor) {
-					return false;
-				}
+		if let GenericBound::TraitBound {
+			trait_,
+			generic_params,
+			..
+		} = bound
+		{
+			if !visit_path(trait_, visitor) {
+				return false;
 			}
-			rustdoc_types::GenericBound::Outlives(_) | rustdoc_types::GenericBound::Use(_) => {
-				continue;
+			if !visit_generic_params(generic_params, visitor) {
+				return false;
 			}
 		}
 	}
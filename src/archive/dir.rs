@@ -0,0 +1,98 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::vec;
+
+use super::denied;
+use super::ArchiveFileType;
+use super::Node;
+use super::Tree;
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::Fs;
+use crate::ReadDir;
+
+/// A builder for directories in an archive, which always fails.
+#[derive(Debug, Default)]
+pub struct ArchiveDirBuilder {
+	_private: (),
+}
+
+impl DirBuilder for ArchiveDirBuilder {
+	fn recursive(&mut self, _: bool) -> &mut Self {
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+}
+
+/// Iterator over the entries of a directory in the archive backing `F`.
+#[derive(Debug)]
+pub struct ArchiveReadDir<F> {
+	entries: vec::IntoIter<ArchiveDirEntry<F>>,
+}
+
+impl<F: Clone> ArchiveReadDir<F> {
+	/// Lists the directory at `path` in `tree`, whose entries are read back through `fs`.
+	pub(crate) fn new<N: Node>(fs: &F, tree: &Tree<N>, path: &Path) -> io::Result<Self> {
+		let entries = tree
+			.read_dir(path)?
+			.iter()
+			.map(|(name, &index)| {
+				ArchiveDirEntry {
+					fs: fs.clone(),
+					path: path.join(name),
+					file_type: ArchiveFileType::from(tree.nodes[index].kind()),
+				}
+			})
+			.collect::<Vec<_>>();
+		Ok(Self {
+			entries: entries.into_iter(),
+		})
+	}
+}
+
+impl<F> Iterator for ArchiveReadDir<F> {
+	type Item = io::Result<ArchiveDirEntry<F>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.entries.next().map(Ok)
+	}
+}
+
+impl<F: Fs<FileType = ArchiveFileType> + fmt::Debug> ReadDir for ArchiveReadDir<F> {
+	type DirEntry = ArchiveDirEntry<F>;
+}
+
+/// An entry returned by [`ArchiveReadDir`].
+#[derive(Debug)]
+pub struct ArchiveDirEntry<F> {
+	fs: F,
+	path: PathBuf,
+	file_type: ArchiveFileType,
+}
+
+impl<F: Fs<FileType = ArchiveFileType> + fmt::Debug> DirEntry for ArchiveDirEntry<F> {
+	type FileType = ArchiveFileType;
+	type Metadata = F::Metadata;
+
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.fs.symlink_metadata(&self.path)
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		Ok(self.file_type)
+	}
+
+	fn file_name(&self) -> OsString {
+		self.path.file_name().map(OsString::from).unwrap_or_default()
+	}
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::SystemTime;
+
+use super::denied;
+use super::ArchiveFileTimes;
+use crate::File;
+use crate::Metadata;
+
+/// An open entry of an archive, with metadata of type `M`.
+///
+/// Handles created through [`File::try_clone`] share their cursor.
+pub struct ArchiveFile<M> {
+	bytes: Arc<[u8]>,
+	/// The range of `bytes` holding the contents, or `None` for a directory.
+	range: Option<Range<usize>>,
+	position: Arc<Mutex<u64>>,
+	metadata: M,
+}
+
+impl<M> ArchiveFile<M> {
+	pub(crate) fn new(bytes: Arc<[u8]>, range: Option<Range<usize>>, metadata: M) -> Self {
+		Self {
+			bytes,
+			range,
+			position: Arc::new(Mutex::new(0)),
+			metadata,
+		}
+	}
+
+	fn contents(&self) -> io::Result<&[u8]> {
+		let range = self.range.clone().ok_or(io::ErrorKind::IsADirectory)?;
+		Ok(&self.bytes[range])
+	}
+
+	fn position(&self) -> MutexGuard<'_, u64> {
+		self.position.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+impl<M: fmt::Debug> fmt::Debug for ArchiveFile<M> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ArchiveFile")
+			.field("range", &self.range)
+			.field("position", &self.position)
+			.field("metadata", &self.metadata)
+			.finish()
+	}
+}
+
+impl<M> io::Read for ArchiveFile<M> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let contents = self.contents()?;
+		let mut position = self.position();
+		let start = usize::try_from(*position).unwrap_or(usize::MAX).min(contents.len());
+		let len = buf.len().min(contents.len() - start);
+		buf[..len].copy_from_slice(&contents[start..start + len]);
+		*position += len as u64;
+		Ok(len)
+	}
+}
+
+impl<M> io::Write for ArchiveFile<M> {
+	fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+		Err(denied())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<M> io::Seek for ArchiveFile<M> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		let mut position = self.position();
+		let (base, offset) = match pos {
+			io::SeekFrom::Start(offset) => {
+				*position = offset;
+				return Ok(offset);
+			}
+			io::SeekFrom::End(offset) => (self.range.as_ref().map_or(0, Range::len) as u64, offset),
+			io::SeekFrom::Current(offset) => (*position, offset),
+		};
+		*position = base.checked_add_signed(offset).ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"invalid seek to a negative or overflowing position",
+			)
+		})?;
+		Ok(*position)
+	}
+}
+
+impl<M: Metadata> File for ArchiveFile<M> {
+	type FileTimes = ArchiveFileTimes;
+	type Metadata = M;
+	type Permissions = M::Permissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		Ok(())
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		Ok(())
+	}
+
+	fn set_len(&self, _: u64) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		Ok(self.metadata.clone())
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		Ok(Self {
+			bytes: Arc::clone(&self.bytes),
+			range: self.range.clone(),
+			position: Arc::clone(&self.position),
+			metadata: self.metadata.clone(),
+		})
+	}
+
+	fn set_permissions(&self, _: Self::Permissions) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_times(&self, _: Self::FileTimes) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_modified(&self, _: SystemTime) -> io::Result<()> {
+		Err(denied())
+	}
+}
+
+/// The options set on the [`OpenOptions`](crate::OpenOptions) of an archive.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Flags {
+	pub read: bool,
+	pub write: bool,
+	pub append: bool,
+	pub truncate: bool,
+	pub create: bool,
+	pub create_new: bool,
+}
+
+impl Flags {
+	/// Fails with [`io::ErrorKind::ReadOnlyFilesystem`] if any option could modify the archive,
+	/// and with [`io::ErrorKind::InvalidInput`] if the entry would not be read.
+	pub fn check(&self) -> io::Result<()> {
+		if self.write || self.append || self.truncate || self.create || self.create_new {
+			return Err(denied());
+		}
+		if !self.read {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,74 @@
+use std::time::SystemTime;
+
+use super::Kind;
+use crate::FileTimes;
+use crate::FileType;
+use crate::Permissions;
+
+/// The type of an entry in an archive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ArchiveFileType(Type);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Type {
+	Dir,
+	File,
+	Symlink,
+}
+
+impl FileType for ArchiveFileType {
+	fn is_dir(&self) -> bool {
+		self.0 == Type::Dir
+	}
+
+	fn is_file(&self) -> bool {
+		self.0 == Type::File
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.0 == Type::Symlink
+	}
+}
+
+impl<D> From<&Kind<D>> for ArchiveFileType {
+	fn from(kind: &Kind<D>) -> Self {
+		Self(match kind {
+			Kind::Dir(_) => Type::Dir,
+			Kind::File(_) => Type::File,
+			Kind::Symlink(_) => Type::Symlink,
+		})
+	}
+}
+
+/// Permissions of an entry in an archive, which only tell whether it is read-only.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ArchivePermissions {
+	pub(crate) readonly: bool,
+}
+
+impl Permissions for ArchivePermissions {
+	fn readonly(&self) -> bool {
+		self.readonly
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		self.readonly = readonly;
+	}
+}
+
+/// Timestamps for an [`ArchiveFile`](super::ArchiveFile). Archives cannot be modified, so they
+/// are never applied.
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveFileTimes {
+	_private: (),
+}
+
+impl FileTimes for ArchiveFileTimes {
+	fn set_accessed(self, _: SystemTime) -> Self {
+		self
+	}
+
+	fn set_modified(self, _: SystemTime) -> Self {
+		self
+	}
+}
@@ -0,0 +1,17 @@
+//! The parts of the archive backends that do not depend on the format: the tree of entries,
+//! resolving paths in it, and read-only handles to its entries.
+
+use std::io;
+
+mod dir;
+pub use dir::*;
+mod file;
+pub use file::*;
+mod metadata;
+pub use metadata::*;
+mod tree;
+pub(crate) use tree::*;
+
+pub(crate) fn denied() -> io::Error {
+	io::ErrorKind::ReadOnlyFilesystem.into()
+}
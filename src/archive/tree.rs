@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Maximum number of symbolic links followed while resolving a single path, matching Linux.
+const MAX_SYMLINKS: usize = 40;
+
+/// The index of the root directory in a [`Tree`].
+pub(crate) const ROOT: usize = 0;
+
+/// What an entry of an archive is, with `D` locating the contents of files.
+#[derive(Debug)]
+pub(crate) enum Kind<D> {
+	Dir(BTreeMap<OsString, usize>),
+	File(D),
+	Symlink(PathBuf),
+}
+
+/// An entry of a [`Tree`], along with whatever metadata its format records.
+pub(crate) trait Node {
+	type Data;
+
+	fn kind(&self) -> &Kind<Self::Data>;
+
+	fn kind_mut(&mut self) -> &mut Kind<Self::Data>;
+}
+
+/// The entries of an archive, rooted at [`ROOT`]. Directories refer to their entries by index.
+#[derive(Debug)]
+pub(crate) struct Tree<N> {
+	pub nodes: Vec<N>,
+}
+
+impl<N: Node> Tree<N> {
+	/// Creates a tree containing only the root directory.
+	pub fn new(root: N) -> Self {
+		Self {
+			nodes: vec![root],
+		}
+	}
+
+	pub fn entries(&self, index: usize) -> io::Result<&BTreeMap<OsString, usize>> {
+		match self.nodes[index].kind() {
+			Kind::Dir(entries) => Ok(entries),
+			_ => Err(io::ErrorKind::NotADirectory.into()),
+		}
+	}
+
+	/// Returns the entries of the node at `index`, or `None` if it is not a directory.
+	pub fn entries_mut(&mut self, index: usize) -> Option<&mut BTreeMap<OsString, usize>> {
+		match self.nodes[index].kind_mut() {
+			Kind::Dir(entries) => Some(entries),
+			_ => None,
+		}
+	}
+
+	/// Resolves `path` against the root, following symbolic links in every component except
+	/// possibly the last. Returns the node and its canonical path.
+	pub fn resolve(&self, path: &Path, follow: bool) -> io::Result<(usize, PathBuf)> {
+		let mut components: VecDeque<Component> = path.components().collect();
+		let mut ancestors = vec![(OsString::new(), ROOT)];
+		let mut symlinks = 0;
+		while let Some(component) = components.pop_front() {
+			let name = match component {
+				Component::Prefix(_) | Component::RootDir => {
+					ancestors.truncate(1);
+					continue;
+				}
+				Component::CurDir => continue,
+				Component::ParentDir => {
+					if ancestors.len() > 1 {
+						ancestors.pop();
+					}
+					continue;
+				}
+				Component::Normal(name) => name,
+			};
+			let dir = ancestors.last().expect("root is never popped").1;
+			let &index = self.entries(dir)?.get(name).ok_or(io::ErrorKind::NotFound)?;
+			let is_last = components.is_empty();
+			match self.nodes[index].kind() {
+				Kind::Symlink(target) if follow || !is_last => {
+					symlinks += 1;
+					if symlinks > MAX_SYMLINKS {
+						return Err(io::Error::other("too many levels of symbolic links"));
+					}
+					for component in target.components().rev() {
+						components.push_front(component);
+					}
+				}
+				Kind::Dir(_) => ancestors.push((name.to_owned(), index)),
+				_ if !is_last => return Err(io::ErrorKind::NotADirectory.into()),
+				_ => ancestors.push((name.to_owned(), index)),
+			}
+		}
+		let index = ancestors.last().expect("root is never popped").1;
+		let mut path = PathBuf::from("/");
+		path.extend(ancestors.iter().skip(1).map(|(name, _)| name));
+		Ok((index, path))
+	}
+
+	pub fn lookup(&self, path: &Path, follow: bool) -> io::Result<usize> {
+		Ok(self.resolve(path, follow)?.0)
+	}
+
+	pub fn exists(&self, path: &Path) -> io::Result<bool> {
+		match self.lookup(path, true) {
+			Ok(_) => Ok(true),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+			Err(error) => Err(error),
+		}
+	}
+
+	pub fn read_dir(&self, path: &Path) -> io::Result<&BTreeMap<OsString, usize>> {
+		self.entries(self.lookup(path, true)?)
+	}
+
+	pub fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+		match self.nodes[self.lookup(path, false)?].kind() {
+			Kind::Symlink(target) => Ok(target.clone()),
+			_ => Err(io::ErrorKind::InvalidInput.into()),
+		}
+	}
+}
+
+/// Splits a path stored in an archive into its components, or returns `None` if it escapes the
+/// archive.
+pub(crate) fn components(path: &str) -> Option<Vec<OsString>> {
+	let mut components = Vec::new();
+	for component in path.split('/') {
+		match component {
+			"" | "." => {}
+			".." => return None,
+			component => components.push(OsString::from(component)),
+		}
+	}
+	Some(components)
+}
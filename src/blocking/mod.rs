@@ -0,0 +1,78 @@
+use std::io;
+use std::sync::Arc;
+use std::vec;
+
+mod pool;
+pub use pool::*;
+
+use crate::DirEntry;
+use crate::ReadDir;
+
+/// Adapter implementing [`AsyncFs`](crate::AsyncFs) for a blocking [`Fs`](crate::Fs), by running
+/// every call on a [`ThreadPool`].
+///
+/// Clones share the same filesystem and pool.
+#[derive(Debug)]
+pub struct Blocking<F> {
+	pub(crate) fs: Arc<F>,
+	pub(crate) pool: ThreadPool,
+}
+
+impl<F> Blocking<F> {
+	/// Wraps `fs`, running its calls on the [global](ThreadPool::global) pool.
+	pub fn new(fs: F) -> Self {
+		Self::with_pool(fs, ThreadPool::global().clone())
+	}
+
+	/// Wraps `fs`, running its calls on `pool`.
+	pub fn with_pool(fs: F, pool: ThreadPool) -> Self {
+		Self {
+			fs: Arc::new(fs),
+			pool,
+		}
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.fs
+	}
+
+	pub fn pool(&self) -> &ThreadPool {
+		&self.pool
+	}
+}
+
+impl<F> Clone for Blocking<F> {
+	fn clone(&self) -> Self {
+		Self {
+			fs: Arc::clone(&self.fs),
+			pool: self.pool.clone(),
+		}
+	}
+}
+
+/// Entries of a directory that were read ahead of time, so that iterating them does not block.
+#[derive(Debug)]
+pub struct BlockingReadDir<E> {
+	entries: vec::IntoIter<io::Result<E>>,
+}
+
+impl<E> BlockingReadDir<E> {
+	/// Reads all remaining entries of `read_dir`.
+	pub fn new(read_dir: impl Iterator<Item = io::Result<E>>) -> Self {
+		Self {
+			entries: read_dir.collect::<Vec<_>>().into_iter(),
+		}
+	}
+}
+
+impl<E> Iterator for BlockingReadDir<E> {
+	type Item = io::Result<E>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.entries.next()
+	}
+}
+
+impl<E: DirEntry> ReadDir for BlockingReadDir<E> {
+	type DirEntry = E;
+}
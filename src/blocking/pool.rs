@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::OnceLock;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+use std::thread;
+use std::time::Duration;
+
+/// Maximum number of threads of the [global](ThreadPool::global) pool.
+const GLOBAL_MAX_THREADS: usize = 64;
+
+/// How long a worker waits for a new job before exiting.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A pool of threads for running blocking jobs, started on demand and stopped when idle.
+///
+/// Clones share the same threads.
+#[derive(Clone)]
+pub struct ThreadPool {
+	shared: Arc<Shared>,
+}
+
+struct Shared {
+	state: Mutex<State>,
+	job_queued: Condvar,
+	max_threads: usize,
+}
+
+#[derive(Default)]
+struct State {
+	jobs: VecDeque<Job>,
+	threads: usize,
+	idle: usize,
+}
+
+impl ThreadPool {
+	/// Creates a pool running at most `max_threads` jobs at once.
+	///
+	/// # Panics
+	///
+	/// Panics if `max_threads` is zero.
+	pub fn new(max_threads: usize) -> Self {
+		assert!(max_threads > 0, "a thread pool needs at least one thread");
+		Self {
+			shared: Arc::new(Shared {
+				state: Mutex::default(),
+				job_queued: Condvar::new(),
+				max_threads,
+			}),
+		}
+	}
+
+	/// Returns the pool shared by [`Native`](crate::Native) and
+	/// [`Blocking::new`](crate::Blocking::new).
+	pub fn global() -> &'static Self {
+		static GLOBAL: OnceLock<ThreadPool> = OnceLock::new();
+		GLOBAL.get_or_init(|| Self::new(GLOBAL_MAX_THREADS))
+	}
+
+	/// Runs `job` on the pool, returning a future that resolves to its result.
+	///
+	/// The job runs even if the future is dropped. If the job panics, the panic is resumed when
+	/// polling the future.
+	pub fn spawn<T, J>(&self, job: J) -> Task<T>
+	where
+		T: Send + 'static,
+		J: FnOnce() -> T + Send + 'static,
+	{
+		let slot = Arc::new(Mutex::new(Slot::Pending(None)));
+		let task = Task {
+			slot: Arc::clone(&slot),
+		};
+		let job = Box::new(move || {
+			let result = panic::catch_unwind(AssertUnwindSafe(job));
+			let previous = mem::replace(&mut *lock(&slot), Slot::Ready(result));
+			if let Slot::Pending(Some(waker)) = previous {
+				waker.wake();
+			}
+		});
+
+		let mut state = lock(&self.shared.state);
+		state.jobs.push_back(job);
+		if state.jobs.len() > state.idle && state.threads < self.shared.max_threads {
+			let shared = Arc::clone(&self.shared);
+			thread::Builder::new()
+				.name("io-fs-blocking".to_owned())
+				.spawn(move || shared.work())
+				.expect("failed to spawn thread pool worker");
+			state.threads += 1;
+		} else {
+			self.shared.job_queued.notify_one();
+		}
+		task
+	}
+}
+
+impl fmt::Debug for ThreadPool {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ThreadPool").field("max_threads", &self.shared.max_threads).finish()
+	}
+}
+
+impl Shared {
+	fn work(&self) {
+		let mut state = lock(&self.state);
+		loop {
+			if let Some(job) = state.jobs.pop_front() {
+				drop(state);
+				job();
+				state = lock(&self.state);
+				continue;
+			}
+			state.idle += 1;
+			let (next_state, timeout) = self
+				.job_queued
+				.wait_timeout(state, IDLE_TIMEOUT)
+				.unwrap_or_else(|error| error.into_inner());
+			state = next_state;
+			state.idle -= 1;
+			if timeout.timed_out() && state.jobs.is_empty() {
+				state.threads -= 1;
+				return;
+			}
+		}
+	}
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+	mutex.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+enum Slot<T> {
+	Pending(Option<Waker>),
+	Ready(thread::Result<T>),
+	Taken,
+}
+
+/// A future resolving to the result of a job spawned on a [`ThreadPool`].
+pub struct Task<T> {
+	slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> Future for Task<T> {
+	type Output = T;
+
+	fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+		let mut slot = lock(&self.slot);
+		match mem::replace(&mut *slot, Slot::Taken) {
+			Slot::Pending(_) => {
+				*slot = Slot::Pending(Some(cx.waker().clone()));
+				Poll::Pending
+			}
+			Slot::Ready(Ok(value)) => Poll::Ready(value),
+			Slot::Ready(Err(payload)) => {
+				drop(slot);
+				panic::resume_unwind(payload)
+			}
+			Slot::Taken => panic!("task polled after completion"),
+		}
+	}
+}
+
+impl<T> fmt::Debug for Task<T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Task").finish_non_exhaustive()
+	}
+}
@@ -0,0 +1,526 @@
+use std::io;
+use std::path;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::SystemTime;
+
+use super::json::Json;
+use super::value;
+use super::value::Codec;
+use super::value::Failure;
+use super::value::OpenFlags;
+use super::Cassette;
+use super::CassetteDirEntry;
+use super::CassetteFileTimes;
+use super::CassetteFileType;
+use super::CassetteMetadata;
+use super::CassettePermissions;
+use super::CassetteReadDir;
+use super::Interaction;
+use crate::DirBuilder;
+use crate::File;
+use crate::FileTimes;
+use crate::Fs;
+use crate::Metadata;
+use crate::OpenOptions;
+use crate::Operation;
+use crate::Permissions;
+
+/// A decorator recording every call made to another [`Fs`] and to its handles into a
+/// [`Cassette`], to be played back later by a [`ReplayFs`](crate::ReplayFs).
+///
+/// Metadata and directory listings are converted to the cassette's own types when they are
+/// returned, so code running against a `CaptureFs` sees the same types as it will during replay.
+/// Directories are listed in full as soon as they are read, along with the type and metadata of
+/// each entry.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::CaptureFs;
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::ReplayFs;
+///
+/// let fs = CaptureFs::new(MemFs::new());
+/// fs.write("config", "verbose = true")?;
+/// assert!(fs.read_to_string("missing").is_err());
+/// let cassette = fs.cassette();
+///
+/// let fs = ReplayFs::new(cassette);
+/// fs.write("config", "verbose = true")?;
+/// assert!(fs.read_to_string("missing").is_err());
+/// fs.assert_finished();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct CaptureFs<F> {
+	inner: F,
+	tape: Tape,
+}
+
+impl<F> CaptureFs<F> {
+	/// Wraps `inner` with an empty cassette.
+	pub fn new(inner: F) -> Self {
+		Self {
+			inner,
+			tape: Tape::default(),
+		}
+	}
+
+	/// Returns the calls recorded so far.
+	pub fn cassette(&self) -> Cassette {
+		self.tape.lock().cassette.clone()
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.inner
+	}
+
+	pub fn into_inner(self) -> F {
+		self.inner
+	}
+}
+
+impl<F: Fs> Fs for CaptureFs<F> {
+	type DirBuilder = CaptureDirBuilder<F::DirBuilder>;
+	type DirEntry = CassetteDirEntry;
+	type File = CaptureFile<F::File>;
+	type FileTimes = CassetteFileTimes;
+	type FileType = CassetteFileType;
+	type Metadata = CassetteMetadata;
+	type OpenOptions = CaptureOpenOptions<F::OpenOptions>;
+	type Permissions = CassettePermissions;
+	type ReadDir = CassetteReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		CaptureDirBuilder {
+			inner: self.inner.dir_builder(),
+			recursive: false,
+			tape: self.tape.clone(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		CassetteFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		CaptureOpenOptions {
+			inner: self.inner.open_options(),
+			flags: OpenFlags::default(),
+			times: self.inner.file_times(),
+			tape: self.tape.clone(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = path.as_ref();
+		self.tape.record(
+			Operation::Canonicalize,
+			vec![value::path(path)],
+			self.inner.canonicalize(path),
+		)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let (from, to) = (from.as_ref(), to.as_ref());
+		let args = vec![
+			value::path(from),
+			value::path(to),
+		];
+		self.tape.record(Operation::Copy, args, self.inner.copy(from, to))
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.tape.record(Operation::CreateDir, vec![value::path(path)], self.inner.create_dir(path))
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		let result = self.inner.create_dir_all(path);
+		self.tape.record(Operation::CreateDirAll, vec![value::path(path)], result)
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		let path = path.as_ref();
+		self.tape.record(Operation::Exists, vec![value::path(path)], self.inner.exists(path))
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let (original, link) = (original.as_ref(), link.as_ref());
+		let args = vec![
+			value::path(original),
+			value::path(link),
+		];
+		self.tape.record(Operation::HardLink, args, self.inner.hard_link(original, link))
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let path = path.as_ref();
+		let result = self.inner.metadata(path).map(|metadata| CassetteMetadata::new(&metadata));
+		self.tape.record(Operation::Metadata, vec![value::path(path)], result)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let path = path.as_ref();
+		self.tape.record(Operation::Read, vec![value::path(path)], self.inner.read(path))
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		let path = path.as_ref();
+		let result = self.inner.read_dir(path).map(CassetteReadDir::new);
+		self.tape.record(Operation::ReadDir, vec![value::path(path)], result)
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = path.as_ref();
+		self.tape.record(Operation::ReadLink, vec![value::path(path)], self.inner.read_link(path))
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		let path = path.as_ref();
+		let result = self.inner.read_to_string(path);
+		self.tape.record(Operation::ReadToString, vec![value::path(path)], result)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.tape.record(Operation::RemoveDir, vec![value::path(path)], self.inner.remove_dir(path))
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		let result = self.inner.remove_dir_all(path);
+		self.tape.record(Operation::RemoveDirAll, vec![value::path(path)], result)
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		let result = self.inner.remove_file(path);
+		self.tape.record(Operation::RemoveFile, vec![value::path(path)], result)
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		let (from, to) = (from.as_ref(), to.as_ref());
+		let args = vec![
+			value::path(from),
+			value::path(to),
+		];
+		self.tape.record(Operation::Rename, args, self.inner.rename(from, to))
+	}
+
+	/// Applies the read-only flag of `perm` to the current permissions of the file.
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let path = path.as_ref();
+		let result = self.inner.metadata(path).and_then(|metadata| {
+			let mut permissions = metadata.permissions();
+			permissions.set_readonly(perm.readonly());
+			self.inner.set_permissions(path, permissions)
+		});
+		self.tape.record(
+			Operation::SetPermissions,
+			vec![
+				value::path(path),
+				perm.encode(),
+			],
+			result,
+		)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let path = path.as_ref();
+		let result =
+			self.inner.symlink_metadata(path).map(|metadata| CassetteMetadata::new(&metadata));
+		self.tape.record(Operation::SymlinkMetadata, vec![value::path(path)], result)
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let (path, contents) = (path.as_ref(), contents.as_ref());
+		let args = vec![
+			value::path(path),
+			value::bytes(contents),
+		];
+		self.tape.record(Operation::Write, args, self.inner.write(path, contents))
+	}
+}
+
+/// The cassette of a [`CaptureFs`], shared with its handles.
+#[derive(Clone, Debug, Default)]
+struct Tape {
+	recording: Arc<Mutex<Recording>>,
+}
+
+#[derive(Debug, Default)]
+struct Recording {
+	cassette: Cassette,
+	/// The number of files opened so far, which is also the number of the last one.
+	files: u64,
+}
+
+impl Tape {
+	fn record<T: Codec>(
+		&self,
+		operation: Operation,
+		args: Vec<Json>,
+		result: io::Result<T>,
+	) -> io::Result<T> {
+		self.record_with(operation, args, result, T::encode)
+	}
+
+	fn record_with<T>(
+		&self,
+		operation: Operation,
+		args: Vec<Json>,
+		result: io::Result<T>,
+		encode: impl FnOnce(&T) -> Json,
+	) -> io::Result<T> {
+		let interaction = Interaction {
+			operation,
+			args,
+			result: result.as_ref().map(encode).map_err(Failure::new),
+		};
+		self.lock().cassette.interactions.push(interaction);
+		result
+	}
+
+	/// Numbers a newly opened file.
+	fn file(&self) -> u64 {
+		let mut recording = self.lock();
+		recording.files += 1;
+		recording.files
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Recording> {
+		self.recording.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+/// A [`DirBuilder`] of a [`CaptureFs`].
+#[derive(Debug)]
+pub struct CaptureDirBuilder<B> {
+	inner: B,
+	recursive: bool,
+	tape: Tape,
+}
+
+impl<B: DirBuilder> DirBuilder for CaptureDirBuilder<B> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.inner.recursive(recursive);
+		self.recursive = recursive;
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		let args = vec![
+			value::path(path),
+			self.recursive.into(),
+		];
+		self.tape.record(Operation::DirBuilderCreate, args, self.inner.create(path))
+	}
+}
+
+/// A [`File`] of a [`CaptureFs`].
+#[derive(Debug)]
+pub struct CaptureFile<H: File> {
+	inner: H,
+	id: u64,
+	/// Empty timestamps of the inner filesystem, to convert [`CassetteFileTimes`] to.
+	times: H::FileTimes,
+	tape: Tape,
+}
+
+impl<H: File> CaptureFile<H> {
+	fn record<T: Codec>(
+		&self,
+		operation: Operation,
+		args: Vec<Json>,
+		result: io::Result<T>,
+	) -> io::Result<T> {
+		let args = [
+			vec![self.id.into()],
+			args,
+		]
+		.concat();
+		self.tape.record(operation, args, result)
+	}
+}
+
+impl<H: File> io::Read for CaptureFile<H> {
+	/// Records the bytes read, to be returned again during replay.
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let result = self.inner.read(buf);
+		let args = vec![self.id.into()];
+		self.tape.record_with(Operation::FileRead, args, result, |len| value::bytes(&buf[..*len]))
+	}
+}
+
+impl<H: File> io::Write for CaptureFile<H> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let result = self.inner.write(buf);
+		self.record(Operation::FileWrite, vec![value::bytes(buf)], result)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		let result = self.inner.flush();
+		self.record(Operation::FileFlush, Vec::new(), result)
+	}
+}
+
+impl<H: File> io::Seek for CaptureFile<H> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		let result = self.inner.seek(pos);
+		self.record(Operation::FileSeek, vec![value::seek(pos)], result)
+	}
+}
+
+impl<H: File> File for CaptureFile<H> {
+	type FileTimes = CassetteFileTimes;
+	type Metadata = CassetteMetadata;
+	type Permissions = CassettePermissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		self.record(Operation::FileSyncAll, Vec::new(), self.inner.sync_all())
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		self.record(Operation::FileSyncData, Vec::new(), self.inner.sync_data())
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		self.record(Operation::FileSetLen, vec![size.into()], self.inner.set_len(size))
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		let result = self.inner.metadata().map(|metadata| CassetteMetadata::new(&metadata));
+		self.record(Operation::FileMetadata, Vec::new(), result)
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		let result = self.inner.try_clone().map(|inner| (inner, self.tape.file()));
+		let args = vec![self.id.into()];
+		let (inner, id) =
+			self.tape.record_with(Operation::FileTryClone, args, result, |(_, id)| (*id).into())?;
+		Ok(Self {
+			inner,
+			id,
+			times: self.times.clone(),
+			tape: self.tape.clone(),
+		})
+	}
+
+	/// Applies the read-only flag of `perm` to the current permissions of the file.
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		let result = self.inner.metadata().and_then(|metadata| {
+			let mut permissions = metadata.permissions();
+			permissions.set_readonly(perm.readonly());
+			self.inner.set_permissions(permissions)
+		});
+		self.record(Operation::FileSetPermissions, vec![perm.encode()], result)
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		let mut inner = self.times.clone();
+		if let Some(accessed) = times.accessed {
+			inner = inner.set_accessed(accessed);
+		}
+		if let Some(modified) = times.modified {
+			inner = inner.set_modified(modified);
+		}
+		self.record(Operation::FileSetTimes, vec![times.encode()], self.inner.set_times(inner))
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		self.record(Operation::FileSetModified, vec![time.encode()], self.inner.set_modified(time))
+	}
+}
+
+/// [`OpenOptions`] of a [`CaptureFs`].
+#[derive(Debug)]
+pub struct CaptureOpenOptions<O: OpenOptions> {
+	inner: O,
+	flags: OpenFlags,
+	times: <O::File as File>::FileTimes,
+	tape: Tape,
+}
+
+// Derived `Clone` would require the file to be `Clone` as well.
+impl<O: OpenOptions> Clone for CaptureOpenOptions<O> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			flags: self.flags.clone(),
+			times: self.times.clone(),
+			tape: self.tape.clone(),
+		}
+	}
+}
+
+impl<O: OpenOptions> OpenOptions for CaptureOpenOptions<O> {
+	type File = CaptureFile<O::File>;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.inner.read(read);
+		self.flags.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.inner.write(write);
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.inner.append(append);
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.inner.truncate(truncate);
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.inner.create(create);
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.inner.create_new(create_new);
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let path = path.as_ref();
+		let result = self.inner.open(path).map(|inner| (inner, self.tape.file()));
+		let args = vec![
+			value::path(path),
+			self.flags.encode(),
+		];
+		let (inner, id) =
+			self.tape.record_with(Operation::Open, args, result, |(_, id)| (*id).into())?;
+		Ok(CaptureFile {
+			inner,
+			id,
+			times: self.times.clone(),
+			tape: self.tape.clone(),
+		})
+	}
+}
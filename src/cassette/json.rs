@@ -0,0 +1,262 @@
+use std::fmt;
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// The subset of JSON used by cassettes: numbers are integers only.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Json {
+	Null,
+	Bool(bool),
+	Int(i128),
+	String(String),
+	Array(Vec<Json>),
+	Object(Vec<(String, Json)>),
+}
+
+impl Json {
+	pub fn get(&self, key: &str) -> Option<&Json> {
+		match self {
+			Self::Object(fields) => {
+				fields.iter().find(|(name, _)| name == key).map(|(_, value)| value)
+			}
+			_ => None,
+		}
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			Self::Bool(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	pub fn as_int(&self) -> Option<i128> {
+		match self {
+			Self::Int(value) => Some(*value),
+			_ => None,
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			Self::String(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[Json]> {
+		match self {
+			Self::Array(values) => Some(values),
+			_ => None,
+		}
+	}
+
+	pub fn parse(text: &str) -> Result<Self, String> {
+		let mut chars = text.chars().peekable();
+		let value = parse_value(&mut chars)?;
+		skip_whitespace(&mut chars);
+		match chars.next() {
+			None => Ok(value),
+			Some(c) => Err(format!("unexpected `{c}` after value")),
+		}
+	}
+}
+
+impl From<bool> for Json {
+	fn from(value: bool) -> Self {
+		Self::Bool(value)
+	}
+}
+
+impl From<u64> for Json {
+	fn from(value: u64) -> Self {
+		Self::Int(value.into())
+	}
+}
+
+impl From<&str> for Json {
+	fn from(value: &str) -> Self {
+		Self::String(value.to_owned())
+	}
+}
+
+impl fmt::Display for Json {
+	/// Formats the value on a single line, without spaces.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Null => f.write_str("null"),
+			Self::Bool(value) => write!(f, "{value}"),
+			Self::Int(value) => write!(f, "{value}"),
+			Self::String(value) => write_string(f, value),
+			Self::Array(values) => {
+				f.write_char('[')?;
+				for (index, value) in values.iter().enumerate() {
+					if index > 0 {
+						f.write_char(',')?;
+					}
+					write!(f, "{value}")?;
+				}
+				f.write_char(']')
+			}
+			Self::Object(fields) => {
+				f.write_char('{')?;
+				for (index, (name, value)) in fields.iter().enumerate() {
+					if index > 0 {
+						f.write_char(',')?;
+					}
+					write_string(f, name)?;
+					write!(f, ":{value}")?;
+				}
+				f.write_char('}')
+			}
+		}
+	}
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+	f.write_char('"')?;
+	for c in value.chars() {
+		match c {
+			'"' => f.write_str("\\\"")?,
+			'\\' => f.write_str("\\\\")?,
+			'\n' => f.write_str("\\n")?,
+			'\r' => f.write_str("\\r")?,
+			'\t' => f.write_str("\\t")?,
+			c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+			c => f.write_char(c)?,
+		}
+	}
+	f.write_char('"')
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+	while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+}
+
+fn expect(chars: &mut Peekable<Chars<'_>>, expected: char) -> Result<(), String> {
+	skip_whitespace(chars);
+	match chars.next() {
+		Some(c) if c == expected => Ok(()),
+		Some(c) => Err(format!("expected `{expected}`, found `{c}`")),
+		None => Err(format!("expected `{expected}`, found end of line")),
+	}
+}
+
+fn parse_value(chars: &mut Peekable<Chars<'_>>) -> Result<Json, String> {
+	skip_whitespace(chars);
+	match chars.peek() {
+		Some('n') => parse_keyword(chars, "null", Json::Null),
+		Some('t') => parse_keyword(chars, "true", Json::Bool(true)),
+		Some('f') => parse_keyword(chars, "false", Json::Bool(false)),
+		Some('"') => parse_string(chars).map(Json::String),
+		Some('[') => {
+			chars.next();
+			let mut values = Vec::new();
+			skip_whitespace(chars);
+			if chars.next_if_eq(&']').is_none() {
+				loop {
+					values.push(parse_value(chars)?);
+					skip_whitespace(chars);
+					match chars.next() {
+						Some(',') => continue,
+						Some(']') => break,
+						_ => return Err("expected `,` or `]` in array".to_owned()),
+					}
+				}
+			}
+			Ok(Json::Array(values))
+		}
+		Some('{') => {
+			chars.next();
+			let mut fields = Vec::new();
+			skip_whitespace(chars);
+			if chars.next_if_eq(&'}').is_none() {
+				loop {
+					skip_whitespace(chars);
+					let name = parse_string(chars)?;
+					expect(chars, ':')?;
+					fields.push((name, parse_value(chars)?));
+					skip_whitespace(chars);
+					match chars.next() {
+						Some(',') => continue,
+						Some('}') => break,
+						_ => return Err("expected `,` or `}` in object".to_owned()),
+					}
+				}
+			}
+			Ok(Json::Object(fields))
+		}
+		Some(c) if *c == '-' || c.is_ascii_digit() => {
+			let mut digits = String::new();
+			while let Some(c) = chars.next_if(|c| *c == '-' || c.is_ascii_digit()) {
+				digits.push(c);
+			}
+			digits.parse().map(Json::Int).map_err(|_| format!("invalid integer `{digits}`"))
+		}
+		Some(c) => Err(format!("unexpected `{c}`")),
+		None => Err("unexpected end of line".to_owned()),
+	}
+}
+
+fn parse_keyword(
+	chars: &mut Peekable<Chars<'_>>,
+	keyword: &str,
+	value: Json,
+) -> Result<Json, String> {
+	for expected in keyword.chars() {
+		if chars.next() != Some(expected) {
+			return Err(format!("expected `{keyword}`"));
+		}
+	}
+	Ok(value)
+}
+
+fn parse_string(chars: &mut Peekable<Chars<'_>>) -> Result<String, String> {
+	if chars.next() != Some('"') {
+		return Err("expected a string".to_owned());
+	}
+	let mut value = String::new();
+	loop {
+		match chars.next() {
+			Some('"') => return Ok(value),
+			Some('\\') => {
+				let c = match chars.next() {
+					Some('"') => '"',
+					Some('\\') => '\\',
+					Some('/') => '/',
+					Some('b') => '\u{8}',
+					Some('f') => '\u{c}',
+					Some('n') => '\n',
+					Some('r') => '\r',
+					Some('t') => '\t',
+					Some('u') => parse_escape(chars)?,
+					_ => return Err("invalid escape in string".to_owned()),
+				};
+				value.push(c);
+			}
+			Some(c) => value.push(c),
+			None => return Err("unterminated string".to_owned()),
+		}
+	}
+}
+
+/// Parses the hex digits of a `\u` escape, combining surrogate pairs.
+fn parse_escape(chars: &mut Peekable<Chars<'_>>) -> Result<char, String> {
+	let high = parse_unit(chars)?;
+	let code = if (0xd800..0xdc00).contains(&high) {
+		if chars.next() != Some('\\') || chars.next() != Some('u') {
+			return Err("unpaired surrogate in string".to_owned());
+		}
+		let low = parse_unit(chars)?;
+		0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff)
+	} else {
+		high
+	};
+	char::from_u32(code).ok_or_else(|| format!("invalid character U+{code:04X}"))
+}
+
+fn parse_unit(chars: &mut Peekable<Chars<'_>>) -> Result<u32, String> {
+	let hex = chars.by_ref().take(4).collect::<String>();
+	u32::from_str_radix(&hex, 16).map_err(|_| format!("invalid escape `\\u{hex}`"))
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+use std::io;
+use std::path;
+use std::str::FromStr;
+
+mod capture;
+pub use capture::*;
+mod json;
+use json::Json;
+mod replay;
+pub use replay::*;
+mod types;
+pub use types::*;
+mod value;
+use value::Codec;
+use value::Failure;
+
+use crate::Fs;
+use crate::Operation;
+
+/// A recorded trace of the calls made to a filesystem, with their arguments and results.
+///
+/// Cassettes are recorded by a [`CaptureFs`] and played back by a [`ReplayFs`]. They are stored as
+/// JSON lines, one call per line, such as:
+///
+/// ```text
+/// {"op":"write","args":["/data/a","hello"],"ok":null}
+/// {"op":"OpenOptions::open","args":["/data/b",{"read":true,...}],"err":{"kind":"NotFound","os":2}}
+/// {"op":"File::read","args":[1],"ok":"hello"}
+/// ```
+///
+/// Calls on a file refer to it by the number its `OpenOptions::open` or `File::try_clone` call
+/// returned.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cassette {
+	interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+	/// Creates an empty cassette.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of recorded calls.
+	pub fn len(&self) -> usize {
+		self.interactions.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.interactions.is_empty()
+	}
+
+	/// Returns the operations of the recorded calls, in order.
+	pub fn operations(&self) -> impl Iterator<Item = Operation> + '_ {
+		self.interactions.iter().map(|interaction| interaction.operation)
+	}
+
+	/// Parses a cassette from JSON lines, skipping blank lines.
+	pub fn parse(text: &str) -> io::Result<Self> {
+		let interactions = text
+			.lines()
+			.enumerate()
+			.filter(|(_, line)| !line.trim().is_empty())
+			.map(|(index, line)| {
+				Interaction::parse(line).map_err(|message| {
+					io::Error::new(
+						io::ErrorKind::InvalidData,
+						format!("line {}: {message}", index + 1),
+					)
+				})
+			})
+			.collect::<io::Result<_>>()?;
+		Ok(Self {
+			interactions,
+		})
+	}
+
+	/// Reads a cassette from the file at `path` in `fs`.
+	pub fn load<F: Fs, P: AsRef<path::Path>>(fs: &F, path: P) -> io::Result<Self> {
+		Self::parse(&fs.read_to_string(path)?)
+	}
+
+	/// Writes the cassette to the file at `path` in `fs`, replacing its contents.
+	pub fn save<F: Fs, P: AsRef<path::Path>>(&self, fs: &F, path: P) -> io::Result<()> {
+		fs.write(path, self.to_string())
+	}
+}
+
+impl fmt::Display for Cassette {
+	/// Formats the cassette as JSON lines.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		for interaction in &self.interactions {
+			writeln!(f, "{interaction}")?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for Cassette {
+	type Err = io::Error;
+
+	fn from_str(text: &str) -> io::Result<Self> {
+		Self::parse(text)
+	}
+}
+
+/// A recorded call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Interaction {
+	operation: Operation,
+	args: Vec<Json>,
+	result: Result<Json, Failure>,
+}
+
+impl Interaction {
+	fn parse(line: &str) -> Result<Self, String> {
+		let json = Json::parse(line)?;
+		let operation = json
+			.get("op")
+			.and_then(Json::as_str)
+			.ok_or("missing operation")?
+			.parse()
+			.map_err(|error: io::Error| error.to_string())?;
+		let args = json.get("args").and_then(Json::as_array).ok_or("missing arguments")?.to_vec();
+		let result = match (json.get("ok"), json.get("err")) {
+			(Some(value), None) => Ok(value.clone()),
+			(None, Some(failure)) => Err(Failure::decode(failure).ok_or("invalid error")?),
+			_ => return Err("expected either `ok` or `err`".to_owned()),
+		};
+		Ok(Self {
+			operation,
+			args,
+			result,
+		})
+	}
+}
+
+impl fmt::Display for Interaction {
+	/// Formats the call as a line of its cassette.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let result = match &self.result {
+			Ok(value) => ("ok".to_owned(), value.clone()),
+			Err(failure) => ("err".to_owned(), failure.encode()),
+		};
+		let json = Json::Object(vec![
+			("op".to_owned(), Json::from(self.operation.name())),
+			("args".to_owned(), Json::Array(self.args.clone())),
+			result,
+		]);
+		write!(f, "{json}")
+	}
+}
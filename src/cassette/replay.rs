@@ -0,0 +1,430 @@
+use std::io;
+use std::path;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::SystemTime;
+
+use super::json::Json;
+use super::value;
+use super::value::Codec;
+use super::value::OpenFlags;
+use super::Cassette;
+use super::CassetteDirEntry;
+use super::CassetteFileTimes;
+use super::CassetteFileType;
+use super::CassetteMetadata;
+use super::CassettePermissions;
+use super::CassetteReadDir;
+use crate::DirBuilder;
+use crate::File;
+use crate::Fs;
+use crate::OpenOptions;
+use crate::Operation;
+
+/// An [`Fs`] answering calls with the results recorded in a [`Cassette`].
+///
+/// Calls must be made in the order they were recorded, with the same arguments. Any other call
+/// panics, naming the call and the line of the cassette it diverged from. Clones and handles
+/// share the same position in the cassette.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::Cassette;
+/// use io_fs::Fs;
+/// use io_fs::ReplayFs;
+///
+/// let exists = r#"{"op":"exists","args":["/srv/lock"],"ok":true}"#;
+/// let remove = r#"{"op":"remove_file","args":["/srv/lock"],"err":{"kind":"PermissionDenied"}}"#;
+/// let cassette = Cassette::parse(&format!("{exists}\n{remove}"))?;
+/// let fs = ReplayFs::new(cassette);
+/// assert!(fs.exists("/srv/lock")?);
+/// let error = fs.remove_file("/srv/lock").unwrap_err();
+/// assert_eq!(error.kind(), std::io::ErrorKind::PermissionDenied);
+/// fs.assert_finished();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReplayFs {
+	player: Player,
+}
+
+impl ReplayFs {
+	/// Plays `cassette` back from its first call.
+	pub fn new(cassette: Cassette) -> Self {
+		Self {
+			player: Player {
+				playback: Arc::new(Mutex::new(Playback {
+					cassette,
+					position: 0,
+				})),
+			},
+		}
+	}
+
+	/// Returns the number of recorded calls not replayed yet.
+	pub fn remaining(&self) -> usize {
+		let playback = self.player.lock();
+		playback.cassette.len() - playback.position
+	}
+
+	/// Panics, naming the next recorded call, unless every recorded call was replayed.
+	#[track_caller]
+	pub fn assert_finished(&self) {
+		let playback = self.player.lock();
+		let Some(next) = playback.cassette.interactions.get(playback.position) else {
+			return;
+		};
+		let message = format!(
+			"{} recorded calls were never replayed, starting at line {}: {next}",
+			playback.cassette.len() - playback.position,
+			playback.position + 1,
+		);
+		drop(playback);
+		panic!("{message}");
+	}
+}
+
+impl Fs for ReplayFs {
+	type DirBuilder = ReplayDirBuilder;
+	type DirEntry = CassetteDirEntry;
+	type File = ReplayFile;
+	type FileTimes = CassetteFileTimes;
+	type FileType = CassetteFileType;
+	type Metadata = CassetteMetadata;
+	type OpenOptions = ReplayOpenOptions;
+	type Permissions = CassettePermissions;
+	type ReadDir = CassetteReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		ReplayDirBuilder {
+			recursive: false,
+			player: self.player.clone(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		CassetteFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		ReplayOpenOptions {
+			flags: OpenFlags::default(),
+			player: self.player.clone(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.player.play(Operation::Canonicalize, vec![value::path(path.as_ref())])
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let args = vec![
+			value::path(from.as_ref()),
+			value::path(to.as_ref()),
+		];
+		self.player.play(Operation::Copy, args)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.player.play(Operation::CreateDir, vec![value::path(path.as_ref())])
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.player.play(Operation::CreateDirAll, vec![value::path(path.as_ref())])
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.player.play(Operation::Exists, vec![value::path(path.as_ref())])
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let args = vec![
+			value::path(original.as_ref()),
+			value::path(link.as_ref()),
+		];
+		self.player.play(Operation::HardLink, args)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.player.play(Operation::Metadata, vec![value::path(path.as_ref())])
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		self.player.play(Operation::Read, vec![value::path(path.as_ref())])
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		self.player.play(Operation::ReadDir, vec![value::path(path.as_ref())])
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.player.play(Operation::ReadLink, vec![value::path(path.as_ref())])
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		self.player.play(Operation::ReadToString, vec![value::path(path.as_ref())])
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.player.play(Operation::RemoveDir, vec![value::path(path.as_ref())])
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.player.play(Operation::RemoveDirAll, vec![value::path(path.as_ref())])
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.player.play(Operation::RemoveFile, vec![value::path(path.as_ref())])
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		let args = vec![
+			value::path(from.as_ref()),
+			value::path(to.as_ref()),
+		];
+		self.player.play(Operation::Rename, args)
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let args = vec![
+			value::path(path.as_ref()),
+			perm.encode(),
+		];
+		self.player.play(Operation::SetPermissions, args)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.player.play(Operation::SymlinkMetadata, vec![value::path(path.as_ref())])
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let args = vec![
+			value::path(path.as_ref()),
+			value::bytes(contents.as_ref()),
+		];
+		self.player.play(Operation::Write, args)
+	}
+}
+
+/// The cassette of a [`ReplayFs`] and how far it was played, shared with its handles.
+#[derive(Clone, Debug)]
+struct Player {
+	playback: Arc<Mutex<Playback>>,
+}
+
+#[derive(Debug)]
+struct Playback {
+	cassette: Cassette,
+	position: usize,
+}
+
+impl Player {
+	/// Answers the call with the next recorded result, panicking if the call is not the next one
+	/// recorded.
+	fn play<T: Codec>(&self, operation: Operation, args: Vec<Json>) -> io::Result<T> {
+		let mut playback = self.lock();
+		let line = playback.position + 1;
+		let call = || {
+			let args = args.iter().map(Json::to_string).collect::<Vec<_>>();
+			format!("{operation}({})", args.join(", "))
+		};
+		let Some(next) = playback.cassette.interactions.get(playback.position) else {
+			drop(playback);
+			panic!("unexpected call {} after the end of the cassette", call());
+		};
+		if next.operation != operation || next.args != args {
+			let next = next.to_string();
+			drop(playback);
+			panic!("unexpected call {}, line {line} of the cassette expected {next}", call());
+		}
+		let result = match &next.result {
+			Ok(json) => T::decode(json).map(Ok),
+			Err(failure) => Some(Err(failure.to_error())),
+		};
+		let Some(result) = result else {
+			let next = next.to_string();
+			drop(playback);
+			panic!("malformed result for {} at line {line} of the cassette: {next}", call());
+		};
+		playback.position += 1;
+		result
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Playback> {
+		self.playback.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+/// A [`DirBuilder`] of a [`ReplayFs`].
+#[derive(Debug)]
+pub struct ReplayDirBuilder {
+	recursive: bool,
+	player: Player,
+}
+
+impl DirBuilder for ReplayDirBuilder {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.recursive = recursive;
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let args = vec![
+			value::path(path.as_ref()),
+			self.recursive.into(),
+		];
+		self.player.play(Operation::DirBuilderCreate, args)
+	}
+}
+
+/// A [`File`] of a [`ReplayFs`], known by the number the cassette gave it.
+#[derive(Debug)]
+pub struct ReplayFile {
+	id: u64,
+	player: Player,
+}
+
+impl ReplayFile {
+	fn play<T: Codec>(&self, operation: Operation, args: Vec<Json>) -> io::Result<T> {
+		self.player.play(
+			operation,
+			[
+				vec![self.id.into()],
+				args,
+			]
+			.concat(),
+		)
+	}
+}
+
+impl io::Read for ReplayFile {
+	/// Copies the recorded bytes into `buf`, panicking if they do not fit.
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let bytes: Vec<u8> = self.play(Operation::FileRead, Vec::new())?;
+		let Some(buf) = buf.get_mut(..bytes.len()) else {
+			panic!("File::read of {} bytes into a buffer of {} bytes", bytes.len(), buf.len());
+		};
+		buf.copy_from_slice(&bytes);
+		Ok(bytes.len())
+	}
+}
+
+impl io::Write for ReplayFile {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.play(Operation::FileWrite, vec![value::bytes(buf)])
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.play(Operation::FileFlush, Vec::new())
+	}
+}
+
+impl io::Seek for ReplayFile {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		self.play(Operation::FileSeek, vec![value::seek(pos)])
+	}
+}
+
+impl File for ReplayFile {
+	type FileTimes = CassetteFileTimes;
+	type Metadata = CassetteMetadata;
+	type Permissions = CassettePermissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		self.play(Operation::FileSyncAll, Vec::new())
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		self.play(Operation::FileSyncData, Vec::new())
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		self.play(Operation::FileSetLen, vec![size.into()])
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.play(Operation::FileMetadata, Vec::new())
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		Ok(Self {
+			id: self.play(Operation::FileTryClone, Vec::new())?,
+			player: self.player.clone(),
+		})
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		self.play(Operation::FileSetPermissions, vec![perm.encode()])
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		self.play(Operation::FileSetTimes, vec![times.encode()])
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		self.play(Operation::FileSetModified, vec![time.encode()])
+	}
+}
+
+/// [`OpenOptions`] of a [`ReplayFs`].
+#[derive(Clone, Debug)]
+pub struct ReplayOpenOptions {
+	flags: OpenFlags,
+	player: Player,
+}
+
+impl OpenOptions for ReplayOpenOptions {
+	type File = ReplayFile;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.flags.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let args = vec![
+			value::path(path.as_ref()),
+			self.flags.encode(),
+		];
+		Ok(ReplayFile {
+			id: self.player.play(Operation::Open, args)?,
+			player: self.player.clone(),
+		})
+	}
+}
@@ -0,0 +1,350 @@
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::json::Json;
+use super::value::Codec;
+use super::value::Failure;
+use crate::DirEntry;
+use crate::FileTimes;
+use crate::FileType;
+use crate::Metadata;
+use crate::Permissions;
+use crate::ReadDir;
+
+/// The type of a file recorded in a cassette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CassetteFileType(Type);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Type {
+	Dir,
+	File,
+	Symlink,
+	Other,
+}
+
+impl CassetteFileType {
+	pub(super) fn new<T: FileType>(file_type: &T) -> Self {
+		Self(if file_type.is_dir() {
+			Type::Dir
+		} else if file_type.is_file() {
+			Type::File
+		} else if file_type.is_symlink() {
+			Type::Symlink
+		} else {
+			Type::Other
+		})
+	}
+}
+
+impl FileType for CassetteFileType {
+	fn is_dir(&self) -> bool {
+		self.0 == Type::Dir
+	}
+
+	fn is_file(&self) -> bool {
+		self.0 == Type::File
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.0 == Type::Symlink
+	}
+}
+
+impl Codec for CassetteFileType {
+	fn encode(&self) -> Json {
+		Json::from(match self.0 {
+			Type::Dir => "dir",
+			Type::File => "file",
+			Type::Symlink => "symlink",
+			Type::Other => "other",
+		})
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self(match json.as_str()? {
+			"dir" => Type::Dir,
+			"file" => Type::File,
+			"symlink" => Type::Symlink,
+			"other" => Type::Other,
+			_ => return None,
+		}))
+	}
+}
+
+/// Permissions recorded in a cassette. Only the read-only flag is recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CassettePermissions {
+	readonly: bool,
+}
+
+impl Permissions for CassettePermissions {
+	fn readonly(&self) -> bool {
+		self.readonly
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		self.readonly = readonly;
+	}
+}
+
+impl Codec for CassettePermissions {
+	fn encode(&self) -> Json {
+		Json::Object(vec![("readonly".to_owned(), self.readonly.into())])
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self {
+			readonly: json.get("readonly")?.as_bool()?,
+		})
+	}
+}
+
+/// Timestamps to apply to a file, as recorded in a cassette.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CassetteFileTimes {
+	pub(super) accessed: Option<SystemTime>,
+	pub(super) modified: Option<SystemTime>,
+}
+
+impl FileTimes for CassetteFileTimes {
+	fn set_accessed(mut self, t: SystemTime) -> Self {
+		self.accessed = Some(t);
+		self
+	}
+
+	fn set_modified(mut self, t: SystemTime) -> Self {
+		self.modified = Some(t);
+		self
+	}
+}
+
+impl Codec for CassetteFileTimes {
+	fn encode(&self) -> Json {
+		Json::Object(vec![
+			("accessed".to_owned(), self.accessed.encode()),
+			("modified".to_owned(), self.modified.encode()),
+		])
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self {
+			accessed: Codec::decode(json.get("accessed")?)?,
+			modified: Codec::decode(json.get("modified")?)?,
+		})
+	}
+}
+
+/// Metadata recorded in a cassette.
+///
+/// Timestamps the recorded filesystem could not provide fail with
+/// [`io::ErrorKind::Unsupported`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CassetteMetadata {
+	file_type: CassetteFileType,
+	len: u64,
+	permissions: CassettePermissions,
+	modified: Option<SystemTime>,
+	accessed: Option<SystemTime>,
+	created: Option<SystemTime>,
+}
+
+impl CassetteMetadata {
+	pub(super) fn new<M: Metadata>(metadata: &M) -> Self {
+		Self {
+			file_type: CassetteFileType::new(&metadata.file_type()),
+			len: metadata.len(),
+			permissions: CassettePermissions {
+				readonly: metadata.permissions().readonly(),
+			},
+			modified: metadata.modified().ok(),
+			accessed: metadata.accessed().ok(),
+			created: metadata.created().ok(),
+		}
+	}
+}
+
+fn recorded(time: Option<SystemTime>) -> io::Result<SystemTime> {
+	time.ok_or_else(|| io::Error::from(io::ErrorKind::Unsupported))
+}
+
+impl Metadata for CassetteMetadata {
+	type FileType = CassetteFileType;
+	type Permissions = CassettePermissions;
+
+	fn file_type(&self) -> Self::FileType {
+		self.file_type
+	}
+
+	fn is_dir(&self) -> bool {
+		self.file_type.is_dir()
+	}
+
+	fn is_file(&self) -> bool {
+		self.file_type.is_file()
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.file_type.is_symlink()
+	}
+
+	fn len(&self) -> u64 {
+		self.len
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		self.permissions.clone()
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		recorded(self.modified)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		recorded(self.accessed)
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		recorded(self.created)
+	}
+}
+
+impl Codec for CassetteMetadata {
+	fn encode(&self) -> Json {
+		Json::Object(vec![
+			("type".to_owned(), self.file_type.encode()),
+			("len".to_owned(), self.len.encode()),
+			("readonly".to_owned(), self.permissions.readonly.into()),
+			("modified".to_owned(), self.modified.encode()),
+			("accessed".to_owned(), self.accessed.encode()),
+			("created".to_owned(), self.created.encode()),
+		])
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self {
+			file_type: Codec::decode(json.get("type")?)?,
+			len: Codec::decode(json.get("len")?)?,
+			permissions: CassettePermissions {
+				readonly: json.get("readonly")?.as_bool()?,
+			},
+			modified: Codec::decode(json.get("modified")?)?,
+			accessed: Codec::decode(json.get("accessed")?)?,
+			created: Codec::decode(json.get("created")?)?,
+		})
+	}
+}
+
+/// An entry of a [`CassetteReadDir`].
+///
+/// Its type and metadata are recorded along with the directory listing, so asking for them makes
+/// no further call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CassetteDirEntry {
+	path: PathBuf,
+	file_type: Result<CassetteFileType, Failure>,
+	metadata: Result<CassetteMetadata, Failure>,
+}
+
+impl CassetteDirEntry {
+	pub(super) fn new<E: DirEntry>(entry: &E) -> Self {
+		Self {
+			path: entry.path(),
+			file_type: entry
+				.file_type()
+				.map(|file_type| CassetteFileType::new(&file_type))
+				.map_err(|error| Failure::new(&error)),
+			metadata: entry
+				.metadata()
+				.map(|metadata| CassetteMetadata::new(&metadata))
+				.map_err(|error| Failure::new(&error)),
+		}
+	}
+}
+
+impl DirEntry for CassetteDirEntry {
+	type FileType = CassetteFileType;
+	type Metadata = CassetteMetadata;
+
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.metadata.clone().map_err(|failure| failure.to_error())
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		self.file_type.clone().map_err(|failure| failure.to_error())
+	}
+
+	fn file_name(&self) -> OsString {
+		self.path.file_name().map(OsString::from).unwrap_or_default()
+	}
+}
+
+impl Codec for CassetteDirEntry {
+	fn encode(&self) -> Json {
+		Json::Object(vec![
+			("path".to_owned(), self.path.encode()),
+			("type".to_owned(), self.file_type.encode()),
+			("metadata".to_owned(), self.metadata.encode()),
+		])
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self {
+			path: Codec::decode(json.get("path")?)?,
+			file_type: Codec::decode(json.get("type")?)?,
+			metadata: Codec::decode(json.get("metadata")?)?,
+		})
+	}
+}
+
+/// Iterator over the entries of a directory, as recorded in a cassette.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CassetteReadDir {
+	entries: VecDeque<Result<CassetteDirEntry, Failure>>,
+}
+
+impl CassetteReadDir {
+	/// Reads all of `read_dir` up front.
+	pub(super) fn new<R: ReadDir>(read_dir: R) -> Self {
+		Self {
+			entries: read_dir
+				.map(|entry| {
+					entry
+						.map(|entry| CassetteDirEntry::new(&entry))
+						.map_err(|error| Failure::new(&error))
+				})
+				.collect(),
+		}
+	}
+}
+
+impl Iterator for CassetteReadDir {
+	type Item = io::Result<CassetteDirEntry>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.entries.pop_front().map(|entry| entry.map_err(|failure| failure.to_error()))
+	}
+}
+
+impl ReadDir for CassetteReadDir {
+	type DirEntry = CassetteDirEntry;
+}
+
+impl Codec for CassetteReadDir {
+	fn encode(&self) -> Json {
+		Json::Array(self.entries.iter().map(Codec::encode).collect())
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		Some(Self {
+			entries: json.as_array()?.iter().map(Codec::decode).collect::<Option<_>>()?,
+		})
+	}
+}
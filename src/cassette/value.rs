@@ -0,0 +1,316 @@
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use super::json::Json;
+
+/// Converts the arguments and results of calls to and from their [`Json`] form in a cassette.
+pub(super) trait Codec: Sized {
+	fn encode(&self) -> Json;
+
+	fn decode(json: &Json) -> Option<Self>;
+}
+
+impl Codec for () {
+	fn encode(&self) -> Json {
+		Json::Null
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		(*json == Json::Null).then_some(())
+	}
+}
+
+impl Codec for bool {
+	fn encode(&self) -> Json {
+		Json::Bool(*self)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		json.as_bool()
+	}
+}
+
+impl Codec for u64 {
+	fn encode(&self) -> Json {
+		Json::from(*self)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		json.as_int()?.try_into().ok()
+	}
+}
+
+impl Codec for usize {
+	fn encode(&self) -> Json {
+		Json::Int(*self as i128)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		json.as_int()?.try_into().ok()
+	}
+}
+
+/// Bytes are stored as a string when they are valid UTF-8, and as `{"hex":"..."}` otherwise.
+impl Codec for Vec<u8> {
+	fn encode(&self) -> Json {
+		bytes(self)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		if let Some(text) = json.as_str() {
+			return Some(text.as_bytes().to_vec());
+		}
+		let hex = json.get("hex")?.as_str()?;
+		if hex.len() % 2 != 0 {
+			return None;
+		}
+		(0..hex.len())
+			.step_by(2)
+			.map(|index| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok())
+			.collect()
+	}
+}
+
+impl Codec for String {
+	fn encode(&self) -> Json {
+		Json::from(self.as_str())
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		json.as_str().map(str::to_owned)
+	}
+}
+
+/// Paths that are not valid Unicode are stored lossily.
+impl Codec for PathBuf {
+	fn encode(&self) -> Json {
+		path(self)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		json.as_str().map(PathBuf::from)
+	}
+}
+
+/// Times are stored as nanoseconds since the Unix epoch, negative for earlier times.
+impl Codec for SystemTime {
+	fn encode(&self) -> Json {
+		match self.duration_since(SystemTime::UNIX_EPOCH) {
+			Ok(after) => Json::Int(after.as_nanos() as i128),
+			Err(before) => Json::Int(-(before.duration().as_nanos() as i128)),
+		}
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		let nanos = json.as_int()?;
+		let duration = |nanos: u128| {
+			let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+			Some(Duration::new(secs, (nanos % 1_000_000_000) as u32))
+		};
+		if nanos < 0 {
+			SystemTime::UNIX_EPOCH.checked_sub(duration(nanos.unsigned_abs())?)
+		} else {
+			SystemTime::UNIX_EPOCH.checked_add(duration(nanos.unsigned_abs())?)
+		}
+	}
+}
+
+/// A missing value is stored as `null`.
+impl<T: Codec> Codec for Option<T> {
+	fn encode(&self) -> Json {
+		self.as_ref().map_or(Json::Null, T::encode)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		match json {
+			Json::Null => Some(None),
+			json => T::decode(json).map(Some),
+		}
+	}
+}
+
+/// Results are stored as `{"ok":...}` or `{"err":...}`.
+impl<T: Codec> Codec for Result<T, Failure> {
+	fn encode(&self) -> Json {
+		match self {
+			Ok(value) => Json::Object(vec![("ok".to_owned(), value.encode())]),
+			Err(failure) => Json::Object(vec![("err".to_owned(), failure.encode())]),
+		}
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		match (json.get("ok"), json.get("err")) {
+			(Some(value), None) => T::decode(value).map(Ok),
+			(None, Some(failure)) => Failure::decode(failure).map(Err),
+			_ => None,
+		}
+	}
+}
+
+pub(super) fn bytes(bytes: &[u8]) -> Json {
+	match std::str::from_utf8(bytes) {
+		Ok(text) => Json::from(text),
+		Err(_) => {
+			let mut hex = String::with_capacity(bytes.len() * 2);
+			for byte in bytes {
+				let _ = write!(hex, "{byte:02x}");
+			}
+			Json::Object(vec![("hex".to_owned(), Json::String(hex))])
+		}
+	}
+}
+
+pub(super) fn path(path: &Path) -> Json {
+	Json::String(path.to_string_lossy().into_owned())
+}
+
+/// An error returned by a recorded call.
+///
+/// OS errors keep their code, and are replayed as the same OS error when the current platform maps
+/// it to the same kind. Other errors keep their message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct Failure {
+	kind: io::ErrorKind,
+	os: Option<i32>,
+	message: Option<String>,
+}
+
+impl Failure {
+	pub fn new(error: &io::Error) -> Self {
+		let os = error.raw_os_error();
+		Self {
+			kind: error.kind(),
+			os,
+			message: os.is_none().then(|| error.to_string()),
+		}
+	}
+
+	pub fn to_error(&self) -> io::Error {
+		if let Some(os) = self.os {
+			let error = io::Error::from_raw_os_error(os);
+			if error.kind() == self.kind {
+				return error;
+			}
+		}
+		match &self.message {
+			Some(message) => io::Error::new(self.kind, message.clone()),
+			None => io::Error::from(self.kind),
+		}
+	}
+}
+
+impl Codec for Failure {
+	fn encode(&self) -> Json {
+		let mut fields = vec![("kind".to_owned(), Json::String(format!("{:?}", self.kind)))];
+		if let Some(os) = self.os {
+			fields.push(("os".to_owned(), Json::Int(os.into())));
+		}
+		if let Some(message) = &self.message {
+			fields.push(("message".to_owned(), Json::from(message.as_str())));
+		}
+		Json::Object(fields)
+	}
+
+	fn decode(json: &Json) -> Option<Self> {
+		let name = json.get("kind")?.as_str()?;
+		let kind = ERROR_KINDS
+			.into_iter()
+			.find(|kind| format!("{kind:?}") == name)
+			.unwrap_or(io::ErrorKind::Other);
+		let os = match json.get("os") {
+			Some(os) => Some(os.as_int()?.try_into().ok()?),
+			None => None,
+		};
+		let message = match json.get("message") {
+			Some(message) => Some(message.as_str()?.to_owned()),
+			None => None,
+		};
+		Some(Self {
+			kind,
+			os,
+			message,
+		})
+	}
+}
+
+/// The stable error kinds, which can be parsed back from their names. Other kinds are replayed as
+/// [`io::ErrorKind::Other`].
+const ERROR_KINDS: [io::ErrorKind; 39] = [
+	io::ErrorKind::NotFound,
+	io::ErrorKind::PermissionDenied,
+	io::ErrorKind::ConnectionRefused,
+	io::ErrorKind::ConnectionReset,
+	io::ErrorKind::HostUnreachable,
+	io::ErrorKind::NetworkUnreachable,
+	io::ErrorKind::ConnectionAborted,
+	io::ErrorKind::NotConnected,
+	io::ErrorKind::AddrInUse,
+	io::ErrorKind::AddrNotAvailable,
+	io::ErrorKind::NetworkDown,
+	io::ErrorKind::BrokenPipe,
+	io::ErrorKind::AlreadyExists,
+	io::ErrorKind::WouldBlock,
+	io::ErrorKind::NotADirectory,
+	io::ErrorKind::IsADirectory,
+	io::ErrorKind::DirectoryNotEmpty,
+	io::ErrorKind::ReadOnlyFilesystem,
+	io::ErrorKind::StaleNetworkFileHandle,
+	io::ErrorKind::InvalidInput,
+	io::ErrorKind::InvalidData,
+	io::ErrorKind::TimedOut,
+	io::ErrorKind::WriteZero,
+	io::ErrorKind::StorageFull,
+	io::ErrorKind::NotSeekable,
+	io::ErrorKind::QuotaExceeded,
+	io::ErrorKind::FileTooLarge,
+	io::ErrorKind::ResourceBusy,
+	io::ErrorKind::ExecutableFileBusy,
+	io::ErrorKind::Deadlock,
+	io::ErrorKind::CrossesDevices,
+	io::ErrorKind::TooManyLinks,
+	io::ErrorKind::InvalidFilename,
+	io::ErrorKind::ArgumentListTooLong,
+	io::ErrorKind::Interrupted,
+	io::ErrorKind::Unsupported,
+	io::ErrorKind::UnexpectedEof,
+	io::ErrorKind::OutOfMemory,
+	io::ErrorKind::Other,
+];
+
+/// The options a file is opened with, as passed to [`OpenOptions`](crate::OpenOptions).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(super) struct OpenFlags {
+	pub read: bool,
+	pub write: bool,
+	pub append: bool,
+	pub truncate: bool,
+	pub create: bool,
+	pub create_new: bool,
+}
+
+impl OpenFlags {
+	pub fn encode(&self) -> Json {
+		let flags = [
+			("read", self.read),
+			("write", self.write),
+			("append", self.append),
+			("truncate", self.truncate),
+			("create", self.create),
+			("create_new", self.create_new),
+		];
+		Json::Object(flags.into_iter().map(|(name, flag)| (name.to_owned(), flag.into())).collect())
+	}
+}
+
+pub(super) fn seek(pos: io::SeekFrom) -> Json {
+	let (name, offset) = match pos {
+		io::SeekFrom::Start(offset) => ("start", offset.into()),
+		io::SeekFrom::End(offset) => ("end", offset.into()),
+		io::SeekFrom::Current(offset) => ("current", offset.into()),
+	};
+	Json::Object(vec![(name.to_owned(), Json::Int(offset))])
+}
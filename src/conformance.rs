@@ -0,0 +1,465 @@
+//! Checks that an [`Fs`] implementation behaves like [`std::fs`].
+//!
+//! Every check takes a filesystem and an empty scratch directory inside it, and panics if the
+//! backend deviates from `std::fs`. Use [`fs_conformance_tests!`](crate::fs_conformance_tests) to
+//! run all of them as tests.
+//!
+//! Error kinds that differ between platforms are only checked on Unix.
+//!
+//! [`Scratch`], [`entry_names`] and [`expect_error`] are also meant for tests of the behavior
+//! specific to a backend.
+
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::File;
+use crate::FileType;
+use crate::Fs;
+use crate::Metadata;
+use crate::OpenOptions;
+use crate::Permissions;
+
+/// Generates a `#[test]` for every check in [`conformance`](crate::conformance), each running
+/// against a filesystem returned by `make_fs`.
+///
+/// The tests are named after the checks, so invoke the macro inside a dedicated module.
+///
+/// # Examples
+///
+/// ```
+/// io_fs::fs_conformance_tests!(io_fs::MemFs::new);
+/// ```
+#[macro_export]
+macro_rules! fs_conformance_tests {
+	(@tests $make_fs:expr; $($check:ident,)*) => {
+		$(
+			#[test]
+			fn $check() {
+				$crate::conformance::run($make_fs, $crate::conformance::$check);
+			}
+		)*
+	};
+	($make_fs:expr) => {
+		$crate::fs_conformance_tests!(
+			@tests $make_fs;
+			canonicalize,
+			copy,
+			create_dir,
+			create_dir_all,
+			dir_builder,
+			exists,
+			file_handle,
+			hard_link,
+			metadata,
+			open_options,
+			read,
+			read_dir,
+			read_link,
+			read_to_string,
+			remove_dir,
+			remove_dir_all,
+			remove_file,
+			rename,
+			set_permissions,
+			symlink_metadata,
+			write,
+		);
+	};
+}
+
+/// An empty directory that is removed, along with its contents, when dropped.
+#[derive(Debug)]
+pub struct Scratch<'a, F: Fs> {
+	fs: &'a F,
+	path: PathBuf,
+}
+
+impl<'a, F: Fs> Scratch<'a, F> {
+	/// Creates a uniquely named directory inside [`std::env::temp_dir`].
+	pub fn new(fs: &'a F) -> io::Result<Self> {
+		static COUNTER: AtomicUsize = AtomicUsize::new(0);
+		let name = format!(
+			"io-fs-conformance-{}-{}",
+			process::id(),
+			COUNTER.fetch_add(1, Ordering::Relaxed)
+		);
+		let path = std::env::temp_dir().join(name);
+		fs.create_dir_all(&path)?;
+		Ok(Self {
+			fs,
+			path,
+		})
+	}
+
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+
+impl<F: Fs> Drop for Scratch<'_, F> {
+	fn drop(&mut self) {
+		let _ = self.fs.remove_dir_all(&self.path);
+	}
+}
+
+/// Runs a check against a new filesystem, inside a new scratch directory.
+pub fn run<F: Fs>(make_fs: impl FnOnce() -> F, check: fn(&F, &Path)) {
+	let fs = make_fs();
+	let scratch = Scratch::new(&fs).expect("failed to create scratch directory");
+	check(&fs, scratch.path());
+}
+
+/// Returns the sorted names of the entries of `dir`, panicking if it cannot be listed.
+pub fn entry_names<F: Fs>(fs: &F, dir: impl AsRef<Path>) -> Vec<String> {
+	let mut names = fs
+		.read_dir(dir)
+		.unwrap()
+		.map(|entry| entry.unwrap().file_name().into_string().unwrap())
+		.collect::<Vec<_>>();
+	names.sort();
+	names
+}
+
+/// Panics unless `result` is an error of the given kind.
+#[track_caller]
+pub fn expect_error<T: fmt::Debug>(result: io::Result<T>, kind: io::ErrorKind) {
+	match result {
+		Ok(value) => panic!("expected error of kind {kind:?}, got Ok({value:?})"),
+		Err(error) => assert_eq!(error.kind(), kind, "unexpected error: {error}"),
+	}
+}
+
+/// Like [`expect_error`], but only checks the kind on Unix as it varies between platforms.
+#[track_caller]
+fn expect_unix_error<T: fmt::Debug>(result: io::Result<T>, kind: io::ErrorKind) {
+	if cfg!(unix) {
+		expect_error(result, kind);
+	} else {
+		assert!(result.is_err(), "expected error, got {result:?}");
+	}
+}
+
+pub fn canonicalize<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir(root.join("dir")).unwrap();
+	fs.write(root.join("file"), "").unwrap();
+	let file = fs.canonicalize(root.join("file")).unwrap();
+	assert!(file.is_absolute());
+	assert_eq!(fs.canonicalize(root.join("dir/../file")).unwrap(), file);
+	assert_eq!(fs.canonicalize(root.join("./dir/.")).unwrap(), file.with_file_name("dir"));
+	expect_error(fs.canonicalize(root.join("missing")), io::ErrorKind::NotFound);
+	expect_unix_error(fs.canonicalize(root.join("file/child")), io::ErrorKind::NotADirectory);
+}
+
+pub fn copy<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("from"), "contents").unwrap();
+	assert_eq!(fs.copy(root.join("from"), root.join("to")).unwrap(), 8);
+	assert_eq!(fs.read(root.join("to")).unwrap(), b"contents");
+	fs.write(root.join("from"), "new").unwrap();
+	assert_eq!(fs.copy(root.join("from"), root.join("to")).unwrap(), 3);
+	assert_eq!(fs.read(root.join("to")).unwrap(), b"new");
+	assert_eq!(fs.read(root.join("from")).unwrap(), b"new");
+	expect_error(fs.copy(root.join("missing"), root.join("to")), io::ErrorKind::NotFound);
+	fs.create_dir(root.join("dir")).unwrap();
+	expect_unix_error(fs.copy(root.join("dir"), root.join("to")), io::ErrorKind::InvalidInput);
+}
+
+pub fn create_dir<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir(root.join("dir")).unwrap();
+	assert!(fs.metadata(root.join("dir")).unwrap().is_dir());
+	expect_error(fs.create_dir(root.join("dir")), io::ErrorKind::AlreadyExists);
+	expect_error(fs.create_dir(root.join("missing/dir")), io::ErrorKind::NotFound);
+	fs.write(root.join("file"), "").unwrap();
+	expect_error(fs.create_dir(root.join("file")), io::ErrorKind::AlreadyExists);
+	expect_unix_error(fs.create_dir(root.join("file/dir")), io::ErrorKind::NotADirectory);
+}
+
+pub fn create_dir_all<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir_all(root.join("a/b/c")).unwrap();
+	assert!(fs.metadata(root.join("a/b/c")).unwrap().is_dir());
+	fs.create_dir_all(root.join("a/b")).unwrap();
+	fs.create_dir_all(root.join("a/b/c/")).unwrap();
+	fs.write(root.join("file"), "").unwrap();
+	assert!(fs.create_dir_all(root.join("file")).is_err());
+	assert!(fs.create_dir_all(root.join("file/dir")).is_err());
+}
+
+pub fn dir_builder<F: Fs>(fs: &F, root: &Path) {
+	let mut builder = fs.dir_builder();
+	expect_error(builder.create(root.join("a/b")), io::ErrorKind::NotFound);
+	builder.recursive(true);
+	builder.create(root.join("a/b")).unwrap();
+	builder.create(root.join("a/b")).unwrap();
+	assert!(fs.metadata(root.join("a/b")).unwrap().is_dir());
+	builder.recursive(false);
+	expect_error(builder.create(root.join("a/b")), io::ErrorKind::AlreadyExists);
+}
+
+pub fn exists<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "").unwrap();
+	assert!(fs.exists(root).unwrap());
+	assert!(fs.exists(root.join("file")).unwrap());
+	assert!(!fs.exists(root.join("missing")).unwrap());
+}
+
+pub fn file_handle<F: Fs>(fs: &F, root: &Path) {
+	let path = root.join("file");
+	let mut file = fs.open_options().read(true).write(true).create(true).open(&path).unwrap();
+	file.write_all(b"hello world").unwrap();
+	assert_eq!(file.seek(io::SeekFrom::Start(6)).unwrap(), 6);
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "world");
+	assert_eq!(file.seek(io::SeekFrom::End(-5)).unwrap(), 6);
+	assert_eq!(file.seek(io::SeekFrom::Current(-1)).unwrap(), 5);
+	expect_error(file.seek(io::SeekFrom::Current(-6)), io::ErrorKind::InvalidInput);
+
+	file.set_len(5).unwrap();
+	assert_eq!(File::metadata(&file).unwrap().len(), 5);
+	file.set_len(7).unwrap();
+	file.sync_all().unwrap();
+	assert_eq!(fs.read(&path).unwrap(), b"hello\0\0");
+
+	let mut clone = file.try_clone().unwrap();
+	clone.seek(io::SeekFrom::Start(0)).unwrap();
+	clone.write_all(b"J").unwrap();
+	assert_eq!(file.stream_position().unwrap(), 1, "clones share the cursor");
+	drop((file, clone));
+	assert_eq!(fs.read(&path).unwrap(), b"Jello\0\0");
+
+	let mut file = fs.open_options().append(true).open(&path).unwrap();
+	file.seek(io::SeekFrom::Start(0)).unwrap();
+	file.write_all(b"!").unwrap();
+	assert_eq!(fs.read(&path).unwrap(), b"Jello\0\0!");
+}
+
+pub fn hard_link<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("original"), "shared").unwrap();
+	fs.hard_link(root.join("original"), root.join("link")).unwrap();
+	assert_eq!(fs.read(root.join("link")).unwrap(), b"shared");
+	let mut file = fs.open_options().write(true).open(root.join("link")).unwrap();
+	file.write_all(b"S").unwrap();
+	assert_eq!(fs.read(root.join("original")).unwrap(), b"Shared");
+	fs.remove_file(root.join("original")).unwrap();
+	assert_eq!(fs.read(root.join("link")).unwrap(), b"Shared");
+	expect_error(fs.hard_link(root.join("missing"), root.join("other")), io::ErrorKind::NotFound);
+	fs.write(root.join("original"), "").unwrap();
+	expect_error(
+		fs.hard_link(root.join("original"), root.join("link")),
+		io::ErrorKind::AlreadyExists,
+	);
+	fs.create_dir(root.join("dir")).unwrap();
+	assert!(fs.hard_link(root.join("dir"), root.join("dir_link")).is_err());
+}
+
+pub fn metadata<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "12345").unwrap();
+	let metadata = fs.metadata(root.join("file")).unwrap();
+	assert!(metadata.is_file());
+	assert!(!metadata.is_dir());
+	assert!(!metadata.is_symlink());
+	assert!(metadata.file_type().is_file());
+	assert_eq!(metadata.len(), 5);
+	assert!(!metadata.permissions().readonly());
+	metadata.modified().unwrap();
+	let metadata = fs.metadata(root).unwrap();
+	assert!(metadata.is_dir());
+	assert!(metadata.file_type().is_dir());
+	expect_error(fs.metadata(root.join("missing")), io::ErrorKind::NotFound);
+}
+
+pub fn open_options<F: Fs>(fs: &F, root: &Path) {
+	let path = root.join("file");
+	expect_error(fs.open_options().open(&path), io::ErrorKind::InvalidInput);
+	expect_error(fs.open_options().read(true).open(&path), io::ErrorKind::NotFound);
+	expect_error(
+		fs.open_options().read(true).create(true).open(&path),
+		io::ErrorKind::InvalidInput,
+	);
+	expect_error(
+		fs.open_options().read(true).truncate(true).open(&path),
+		io::ErrorKind::InvalidInput,
+	);
+	expect_error(
+		fs.open_options().append(true).truncate(true).open(&path),
+		io::ErrorKind::InvalidInput,
+	);
+
+	fs.open_options().write(true).create_new(true).open(&path).unwrap();
+	expect_error(
+		fs.open_options().write(true).create_new(true).open(&path),
+		io::ErrorKind::AlreadyExists,
+	);
+	fs.write(&path, "contents").unwrap();
+	fs.open_options().write(true).create(true).open(&path).unwrap();
+	assert_eq!(fs.read(&path).unwrap(), b"contents");
+	fs.open_options().write(true).truncate(true).open(&path).unwrap();
+	assert_eq!(fs.read(&path).unwrap(), b"");
+	expect_error(
+		fs.open_options().write(true).create(true).open(root.join("missing/file")),
+		io::ErrorKind::NotFound,
+	);
+	expect_unix_error(fs.open_options().write(true).open(root), io::ErrorKind::IsADirectory);
+}
+
+pub fn read<F: Fs>(fs: &F, root: &Path) {
+	fs.write(
+		root.join("file"),
+		[
+			0,
+			159,
+			146,
+			150,
+		],
+	)
+	.unwrap();
+	assert_eq!(
+		fs.read(root.join("file")).unwrap(),
+		[
+			0,
+			159,
+			146,
+			150
+		]
+	);
+	expect_error(fs.read(root.join("missing")), io::ErrorKind::NotFound);
+	expect_unix_error(fs.read(root), io::ErrorKind::IsADirectory);
+}
+
+pub fn read_dir<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir(root.join("dir")).unwrap();
+	fs.write(root.join("file"), "").unwrap();
+	let mut entries = fs
+		.read_dir(root)
+		.unwrap()
+		.map(|entry| {
+			let entry = entry.unwrap();
+			assert_eq!(entry.path(), root.join(entry.file_name()));
+			let file_type = entry.file_type().unwrap();
+			assert_eq!(file_type.is_dir(), entry.metadata().unwrap().is_dir());
+			(entry.file_name(), file_type.is_dir())
+		})
+		.collect::<Vec<_>>();
+	entries.sort();
+	assert_eq!(
+		entries,
+		[
+			("dir".into(), true),
+			("file".into(), false),
+		]
+	);
+	assert_eq!(fs.read_dir(root.join("dir")).unwrap().count(), 0);
+	expect_error(fs.read_dir(root.join("missing")), io::ErrorKind::NotFound);
+	expect_unix_error(fs.read_dir(root.join("file")), io::ErrorKind::NotADirectory);
+}
+
+pub fn read_link<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "").unwrap();
+	expect_unix_error(fs.read_link(root.join("file")), io::ErrorKind::InvalidInput);
+	expect_error(fs.read_link(root.join("missing")), io::ErrorKind::NotFound);
+}
+
+pub fn read_to_string<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "text").unwrap();
+	assert_eq!(fs.read_to_string(root.join("file")).unwrap(), "text");
+	fs.write(
+		root.join("binary"),
+		[
+			0xff,
+			0xfe,
+		],
+	)
+	.unwrap();
+	expect_error(fs.read_to_string(root.join("binary")), io::ErrorKind::InvalidData);
+	expect_error(fs.read_to_string(root.join("missing")), io::ErrorKind::NotFound);
+}
+
+pub fn remove_dir<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir_all(root.join("full/child")).unwrap();
+	fs.create_dir(root.join("empty")).unwrap();
+	fs.remove_dir(root.join("empty")).unwrap();
+	assert!(!fs.exists(root.join("empty")).unwrap());
+	expect_unix_error(fs.remove_dir(root.join("full")), io::ErrorKind::DirectoryNotEmpty);
+	fs.write(root.join("file"), "").unwrap();
+	expect_unix_error(fs.remove_dir(root.join("file")), io::ErrorKind::NotADirectory);
+	expect_error(fs.remove_dir(root.join("missing")), io::ErrorKind::NotFound);
+}
+
+pub fn remove_dir_all<F: Fs>(fs: &F, root: &Path) {
+	fs.create_dir_all(root.join("dir/a/b")).unwrap();
+	fs.write(root.join("dir/a/file"), "").unwrap();
+	fs.remove_dir_all(root.join("dir")).unwrap();
+	assert!(!fs.exists(root.join("dir")).unwrap());
+	expect_error(fs.remove_dir_all(root.join("dir")), io::ErrorKind::NotFound);
+	fs.write(root.join("file"), "").unwrap();
+	assert!(fs.remove_dir_all(root.join("file")).is_err());
+	assert!(fs.exists(root.join("file")).unwrap());
+}
+
+pub fn remove_file<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "").unwrap();
+	fs.remove_file(root.join("file")).unwrap();
+	assert!(!fs.exists(root.join("file")).unwrap());
+	expect_error(fs.remove_file(root.join("file")), io::ErrorKind::NotFound);
+	fs.create_dir(root.join("dir")).unwrap();
+	assert!(fs.remove_file(root.join("dir")).is_err());
+	assert!(fs.exists(root.join("dir")).unwrap());
+}
+
+pub fn rename<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("from"), "moved").unwrap();
+	fs.rename(root.join("from"), root.join("to")).unwrap();
+	assert!(!fs.exists(root.join("from")).unwrap());
+	assert_eq!(fs.read(root.join("to")).unwrap(), b"moved");
+	fs.write(root.join("other"), "replaced").unwrap();
+	fs.rename(root.join("other"), root.join("to")).unwrap();
+	assert_eq!(fs.read(root.join("to")).unwrap(), b"replaced");
+
+	fs.create_dir_all(root.join("dir/child")).unwrap();
+	fs.rename(root.join("dir"), root.join("moved")).unwrap();
+	assert!(fs.metadata(root.join("moved/child")).unwrap().is_dir());
+	expect_error(fs.rename(root.join("missing"), root.join("to")), io::ErrorKind::NotFound);
+	expect_unix_error(fs.rename(root.join("to"), root.join("moved")), io::ErrorKind::IsADirectory);
+	expect_unix_error(fs.rename(root.join("moved"), root.join("to")), io::ErrorKind::NotADirectory);
+	assert!(fs.rename(root.join("moved"), root.join("moved/child/inside")).is_err());
+}
+
+pub fn set_permissions<F: Fs>(fs: &F, root: &Path) {
+	let path = root.join("file");
+	fs.write(&path, "").unwrap();
+	let mut permissions = fs.metadata(&path).unwrap().permissions();
+	permissions.set_readonly(true);
+	fs.set_permissions(&path, permissions.clone()).unwrap();
+	assert!(fs.metadata(&path).unwrap().permissions().readonly());
+	permissions.set_readonly(false);
+	fs.set_permissions(&path, permissions.clone()).unwrap();
+	assert!(!fs.metadata(&path).unwrap().permissions().readonly());
+	expect_error(fs.set_permissions(root.join("missing"), permissions), io::ErrorKind::NotFound);
+}
+
+pub fn symlink_metadata<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "123").unwrap();
+	let metadata = fs.symlink_metadata(root.join("file")).unwrap();
+	assert!(metadata.is_file());
+	assert!(!metadata.file_type().is_symlink());
+	assert_eq!(metadata.len(), 3);
+	assert!(fs.symlink_metadata(root).unwrap().is_dir());
+	expect_error(fs.symlink_metadata(root.join("missing")), io::ErrorKind::NotFound);
+}
+
+pub fn write<F: Fs>(fs: &F, root: &Path) {
+	fs.write(root.join("file"), "long contents").unwrap();
+	fs.write(root.join("file"), "short").unwrap();
+	assert_eq!(fs.read(root.join("file")).unwrap(), b"short");
+	expect_error(fs.write(root.join("missing/file"), ""), io::ErrorKind::NotFound);
+	expect_unix_error(fs.write(root, ""), io::ErrorKind::IsADirectory);
+}
@@ -0,0 +1,23 @@
+/// Computes the CRC-32 checksum used by zip and gzip, with the reflected 0xEDB88320 polynomial.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+	const TABLE: [u32; 256] = {
+		let mut table = [0; 256];
+		let mut i = 0;
+		while i < 256 {
+			let mut crc = i as u32;
+			let mut bit = 0;
+			while bit < 8 {
+				crc = if crc & 1 == 0 {
+					crc >> 1
+				} else {
+					(crc >> 1) ^ 0xedb8_8320
+				};
+				bit += 1;
+			}
+			table[i] = crc;
+			i += 1;
+		}
+		table
+	};
+	!data.iter().fold(!0, |crc, &byte| TABLE[usize::from(crc as u8 ^ byte)] ^ (crc >> 8))
+}
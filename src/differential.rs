@@ -0,0 +1,303 @@
+//! Compares an [`Fs`] implementation against [`Native`] on random sequences of operations.
+//!
+//! A script of [`Op`]s is generated from a seed, applied to [`Native`] inside a scratch directory
+//! and to the backend under test, and the results of every step as well as the final trees are
+//! compared. When they differ, the script is shrunk to a minimal reproduction before reporting.
+//!
+//! Errors are compared by [`io::ErrorKind`] only, and backends are expected to match the kinds
+//! reported on the platform running the comparison.
+//!
+//! # Examples
+//!
+//! ```
+//! io_fs::differential::check(io_fs::MemFs::new, 0, 32);
+//! ```
+
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::conformance::Scratch;
+use crate::rng::Rng;
+use crate::DirEntry;
+use crate::FileType;
+use crate::Fs;
+use crate::Metadata;
+use crate::Native;
+
+/// Names used for path components, kept small so that operations collide often.
+const NAMES: [&str; 3] = [
+	"a",
+	"b",
+	"c",
+];
+
+/// A single filesystem operation, with paths relative to the scratch directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op {
+	CreateDir(PathBuf),
+	CreateDirAll(PathBuf),
+	Write(PathBuf, Vec<u8>),
+	Read(PathBuf),
+	Rename(PathBuf, PathBuf),
+	HardLink(PathBuf, PathBuf),
+	RemoveFile(PathBuf),
+	RemoveDir(PathBuf),
+	RemoveDirAll(PathBuf),
+	ReadDir(PathBuf),
+}
+
+impl Op {
+	/// Applies the operation to `fs`, resolving paths against `root`.
+	pub fn apply<F: Fs>(&self, fs: &F, root: &Path) -> Outcome {
+		let result = match self {
+			Self::CreateDir(path) => fs.create_dir(root.join(path)).map(|()| Outcome::Done),
+			Self::CreateDirAll(path) => fs.create_dir_all(root.join(path)).map(|()| Outcome::Done),
+			Self::Write(path, contents) => {
+				fs.write(root.join(path), contents).map(|()| Outcome::Done)
+			}
+			Self::Read(path) => fs.read(root.join(path)).map(Outcome::Contents),
+			Self::Rename(from, to) => {
+				fs.rename(root.join(from), root.join(to)).map(|()| Outcome::Done)
+			}
+			Self::HardLink(original, link) => {
+				fs.hard_link(root.join(original), root.join(link)).map(|()| Outcome::Done)
+			}
+			Self::RemoveFile(path) => fs.remove_file(root.join(path)).map(|()| Outcome::Done),
+			Self::RemoveDir(path) => fs.remove_dir(root.join(path)).map(|()| Outcome::Done),
+			Self::RemoveDirAll(path) => fs.remove_dir_all(root.join(path)).map(|()| Outcome::Done),
+			Self::ReadDir(path) => read_dir_names(fs, &root.join(path)).map(Outcome::Entries),
+		};
+		result.unwrap_or_else(|error| Outcome::Failed(error.kind()))
+	}
+
+	/// Returns simpler variants of the operation, used while shrinking.
+	fn simplifications(&self) -> Vec<Self> {
+		match self {
+			Self::Write(path, contents) if !contents.is_empty() => {
+				vec![
+					Self::Write(path.clone(), Vec::new()),
+					Self::Write(path.clone(), contents[..contents.len() / 2].to_vec()),
+				]
+			}
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl fmt::Display for Op {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::CreateDir(path) => write!(f, "create_dir({path:?})"),
+			Self::CreateDirAll(path) => write!(f, "create_dir_all({path:?})"),
+			Self::Write(path, contents) => write!(f, "write({path:?}, {contents:?})"),
+			Self::Read(path) => write!(f, "read({path:?})"),
+			Self::Rename(from, to) => write!(f, "rename({from:?}, {to:?})"),
+			Self::HardLink(original, link) => write!(f, "hard_link({original:?}, {link:?})"),
+			Self::RemoveFile(path) => write!(f, "remove_file({path:?})"),
+			Self::RemoveDir(path) => write!(f, "remove_dir({path:?})"),
+			Self::RemoveDirAll(path) => write!(f, "remove_dir_all({path:?})"),
+			Self::ReadDir(path) => write!(f, "read_dir({path:?})"),
+		}
+	}
+}
+
+/// The result of applying an [`Op`], with errors reduced to their kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+	Done,
+	Contents(Vec<u8>),
+	/// Sorted names of the directory entries.
+	Entries(Vec<OsString>),
+	Failed(io::ErrorKind),
+}
+
+/// A node of a tree captured by [`snapshot`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Node {
+	Dir,
+	File(Vec<u8>),
+	Symlink(PathBuf),
+}
+
+/// Captures every node below `root`, keyed by its path relative to `root`.
+pub fn snapshot<F: Fs>(fs: &F, root: &Path) -> io::Result<BTreeMap<PathBuf, Node>> {
+	let mut nodes = BTreeMap::new();
+	let mut pending = vec![PathBuf::new()];
+	while let Some(dir) = pending.pop() {
+		for name in read_dir_names(fs, &root.join(&dir))? {
+			let path = dir.join(name);
+			let file_type = fs.symlink_metadata(root.join(&path))?.file_type();
+			let node = if file_type.is_dir() {
+				pending.push(path.clone());
+				Node::Dir
+			} else if file_type.is_symlink() {
+				Node::Symlink(fs.read_link(root.join(&path))?)
+			} else {
+				Node::File(fs.read(root.join(&path))?)
+			};
+			nodes.insert(path, node);
+		}
+	}
+	Ok(nodes)
+}
+
+fn read_dir_names<F: Fs>(fs: &F, path: &Path) -> io::Result<Vec<OsString>> {
+	let mut names = fs
+		.read_dir(path)?
+		.map(|entry| entry.map(|entry| entry.file_name()))
+		.collect::<io::Result<Vec<_>>>()?;
+	names.sort();
+	Ok(names)
+}
+
+/// Helpers generating the arguments of operations.
+impl Rng {
+	fn path(&mut self) -> PathBuf {
+		(0..=self.below(3)).map(|_| NAMES[self.below(NAMES.len())]).collect()
+	}
+
+	fn contents(&mut self) -> Vec<u8> {
+		(0..self.below(8)).map(|_| self.next() as u8).collect()
+	}
+}
+
+/// Generates a script of `len` operations from `seed`.
+pub fn generate(seed: u64, len: usize) -> Vec<Op> {
+	let mut rng = Rng::new(seed);
+	(0..len)
+		.map(|_| {
+			match rng.below(10) {
+				0 => Op::CreateDir(rng.path()),
+				1 => Op::CreateDirAll(rng.path()),
+				2 => Op::Write(rng.path(), rng.contents()),
+				3 => Op::Read(rng.path()),
+				4 => Op::Rename(rng.path(), rng.path()),
+				5 => Op::HardLink(rng.path(), rng.path()),
+				6 => Op::RemoveFile(rng.path()),
+				7 => Op::RemoveDir(rng.path()),
+				8 => Op::RemoveDirAll(rng.path()),
+				_ => Op::ReadDir(rng.path()),
+			}
+		})
+		.collect()
+}
+
+/// A difference between [`Native`] and the backend under test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+	/// The operation at `step` returned different outcomes.
+	Outcome {
+		step: usize,
+		native: Outcome,
+		backend: Outcome,
+	},
+	/// Every operation agreed, but the resulting trees differ.
+	Tree {
+		native: Result<BTreeMap<PathBuf, Node>, io::ErrorKind>,
+		backend: Result<BTreeMap<PathBuf, Node>, io::ErrorKind>,
+	},
+}
+
+impl fmt::Display for Mismatch {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Outcome {
+				step,
+				native,
+				backend,
+			} => write!(f, "step {step} returned {backend:?}, native returned {native:?}"),
+			Self::Tree {
+				native,
+				backend,
+			} => write!(f, "final tree is {backend:?}, native tree is {native:?}"),
+		}
+	}
+}
+
+/// Applies `ops` to [`Native`] and to a filesystem returned by `make_fs`, each inside a new
+/// scratch directory, and returns the first difference between them.
+pub fn compare<F: Fs>(make_fs: impl Fn() -> F, ops: &[Op]) -> Result<(), Mismatch> {
+	let native = Native {};
+	let native_root = Scratch::new(&native).expect("failed to create native scratch directory");
+	let fs = make_fs();
+	let root = Scratch::new(&fs).expect("failed to create scratch directory");
+	for (step, op) in ops.iter().enumerate() {
+		let native = op.apply(&native, native_root.path());
+		let backend = op.apply(&fs, root.path());
+		if native != backend {
+			return Err(Mismatch::Outcome {
+				step,
+				native,
+				backend,
+			});
+		}
+	}
+	let native = snapshot(&native, native_root.path()).map_err(|error| error.kind());
+	let backend = snapshot(&fs, root.path()).map_err(|error| error.kind());
+	if native != backend {
+		return Err(Mismatch::Tree {
+			native,
+			backend,
+		});
+	}
+	Ok(())
+}
+
+/// Returns a minimal subsequence of `ops`, with simplified operations, that still fails
+/// [`compare`].
+///
+/// Chunks of operations are removed while the script keeps failing, halving the chunk size
+/// down to single operations, and the remaining operations are then simplified one by one.
+pub fn shrink<F: Fs>(make_fs: impl Fn() -> F, mut ops: Vec<Op>) -> Vec<Op> {
+	let fails = |ops: &[Op]| compare(&make_fs, ops).is_err();
+	let mut chunk = ops.len() / 2;
+	while chunk > 0 {
+		let mut start = 0;
+		let mut removed = false;
+		while start < ops.len() {
+			let end = (start + chunk).min(ops.len());
+			let candidate = [
+				&ops[..start],
+				&ops[end..],
+			]
+			.concat();
+			if fails(&candidate) {
+				ops = candidate;
+				removed = true;
+			} else {
+				start = end;
+			}
+		}
+		if !removed {
+			chunk /= 2;
+		}
+	}
+	for index in 0..ops.len() {
+		while let Some(simpler) = ops[index].simplifications().into_iter().find(|simpler| {
+			let mut candidate = ops.clone();
+			candidate[index] = simpler.clone();
+			fails(&candidate)
+		}) {
+			ops[index] = simpler;
+		}
+	}
+	ops
+}
+
+/// Generates a script of `len` operations from `seed` and compares a filesystem returned by
+/// `make_fs` against [`Native`], panicking with a shrunk reproduction on the first difference.
+#[track_caller]
+pub fn check<F: Fs>(make_fs: impl Fn() -> F, seed: u64, len: usize) {
+	let ops = generate(seed, len);
+	if compare(&make_fs, &ops).is_ok() {
+		return;
+	}
+	let ops = shrink(&make_fs, ops);
+	let mismatch = compare(&make_fs, &ops).expect_err("shrunk script no longer fails");
+	let script: String = ops.iter().map(|op| format!("\n    {op}")).collect();
+	panic!("backend differs from native with seed {seed}: {mismatch}{script}");
+}
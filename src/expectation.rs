@@ -0,0 +1,241 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+/// An expected call to a method of a [`MockFs`](crate::MockFs), along with its answer.
+///
+/// `A` is the type of the arguments, owned and in a tuple for methods taking several, and `T` the
+/// type the method returns in an [`io::Result`]. A new expectation matches any arguments and must
+/// be called exactly once.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use std::path::PathBuf;
+///
+/// use io_fs::Expectation;
+///
+/// // Copying `a` to `b` twice, reporting 3 bytes each time.
+/// let copy = Expectation::ok(3).with((PathBuf::from("a"), PathBuf::from("b"))).times(2);
+/// // Reading any file under `/etc` at least once, failing.
+/// let read = Expectation::<PathBuf, Vec<u8>>::err(io::ErrorKind::PermissionDenied);
+/// let read = read.matching(|path| path.starts_with("/etc")).at_least(1);
+/// ```
+pub struct Expectation<A, T> {
+	/// Describes the arguments that are matched, if not all of them are.
+	description: Option<String>,
+	matcher: Box<dyn Fn(&A) -> bool + Send>,
+	answer: Answer<A, T>,
+	min: usize,
+	max: Option<usize>,
+	calls: usize,
+}
+
+/// Computes the result of a call from its arguments.
+type Answer<A, T> = Box<dyn FnMut(&A) -> io::Result<T> + Send>;
+
+impl<A, T> Expectation<A, T> {
+	/// Answers calls with the result of `answer`, which is given their arguments.
+	pub fn returning(answer: impl FnMut(&A) -> io::Result<T> + Send + 'static) -> Self {
+		Self {
+			description: None,
+			matcher: Box::new(|_| true),
+			answer: Box::new(answer),
+			min: 1,
+			max: Some(1),
+			calls: 0,
+		}
+	}
+
+	/// Answers calls with a clone of `value`.
+	pub fn ok(value: T) -> Self
+	where
+		T: Clone + Send + 'static,
+	{
+		Self::returning(move |_| Ok(value.clone()))
+	}
+
+	/// Fails calls with an error of this kind.
+	pub fn err(kind: io::ErrorKind) -> Self {
+		Self::returning(move |_| Err(kind.into()))
+	}
+
+	/// Only matches calls with these arguments.
+	pub fn with(mut self, args: A) -> Self
+	where
+		A: PartialEq + fmt::Debug + Send + 'static,
+	{
+		self.description = Some(format!("{args:?}"));
+		self.matcher = Box::new(move |actual| *actual == args);
+		self
+	}
+
+	/// Only matches calls whose arguments satisfy `predicate`.
+	pub fn matching(mut self, predicate: impl Fn(&A) -> bool + Send + 'static) -> Self {
+		self.description = Some("matching arguments".to_owned());
+		self.matcher = Box::new(predicate);
+		self
+	}
+
+	/// Expects exactly `count` calls. Further calls are left to the next matching expectation.
+	pub fn times(mut self, count: usize) -> Self {
+		self.min = count;
+		self.max = Some(count);
+		self
+	}
+
+	/// Expects `count` calls or more.
+	pub fn at_least(mut self, count: usize) -> Self {
+		self.min = count;
+		self.max = None;
+		self
+	}
+
+	/// Allows any number of calls, including none.
+	pub fn any_times(self) -> Self {
+		self.at_least(0)
+	}
+
+	fn accepts(&self, args: &A) -> bool {
+		self.max.is_none_or(|max| self.calls < max) && (self.matcher)(args)
+	}
+
+	fn expected(&self) -> String {
+		match self.max {
+			Some(max) if max == self.min => format!("exactly {max}"),
+			_ => format!("at least {}", self.min),
+		}
+	}
+}
+
+impl<A, T> fmt::Debug for Expectation<A, T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("Expectation")
+			.field("args", &self.description.as_deref().unwrap_or("any"))
+			.field("expected", &self.expected())
+			.field("calls", &self.calls)
+			.finish_non_exhaustive()
+	}
+}
+
+/// The expectations set on a method of a [`MockFs`](crate::MockFs), and the calls they did not
+/// match.
+pub(crate) struct Expectations<A, T> {
+	name: &'static str,
+	state: Mutex<State<A, T>>,
+}
+
+struct State<A, T> {
+	expectations: Vec<Expectation<A, T>>,
+	unexpected: Vec<String>,
+}
+
+impl<A: fmt::Debug, T> Expectations<A, T> {
+	pub fn new(name: &'static str) -> Self {
+		Self {
+			name,
+			state: Mutex::new(State {
+				expectations: Vec::new(),
+				unexpected: Vec::new(),
+			}),
+		}
+	}
+
+	pub fn push(&self, expectation: Expectation<A, T>) {
+		self.lock().expectations.push(expectation);
+	}
+
+	/// Answers the call with the first expectation accepting it, or fails it as unexpected.
+	pub fn call(&self, args: A) -> io::Result<T> {
+		let mut state = self.lock();
+		match state.expectations.iter_mut().find(|expectation| expectation.accepts(&args)) {
+			Some(expectation) => {
+				expectation.calls += 1;
+				(expectation.answer)(&args)
+			}
+			None => {
+				let call = self.render(&format!("{args:?}"));
+				state.unexpected.push(call.clone());
+				Err(io::Error::other(format!("unexpected call {call}")))
+			}
+		}
+	}
+
+	/// Adds the unmet expectations and the unexpected calls to `report`.
+	pub fn verify(&self, report: &mut MockReport) {
+		let state = self.lock();
+		for expectation in &state.expectations {
+			if expectation.calls < expectation.min {
+				report.unmet.push(format!(
+					"{} expected {} calls, got {}",
+					self.render(expectation.description.as_deref().unwrap_or("..")),
+					expectation.expected(),
+					expectation.calls,
+				));
+			}
+		}
+		report.unexpected.extend(state.unexpected.iter().cloned());
+	}
+
+	/// Renders a call, where `args` is the `Debug` form of its arguments.
+	fn render(&self, args: &str) -> String {
+		if args.starts_with('(') {
+			format!("{}{args}", self.name)
+		} else {
+			format!("{}({args})", self.name)
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<'_, State<A, T>> {
+		self.state.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+impl<A, T> fmt::Debug for Expectations<A, T> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+		f.debug_struct("Expectations")
+			.field("name", &self.name)
+			.field("expectations", &state.expectations)
+			.field("unexpected", &state.unexpected)
+			.finish()
+	}
+}
+
+/// The problems found when verifying a [`MockFs`](crate::MockFs).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MockReport {
+	/// Expectations that were called fewer times than expected, such as
+	/// `remove_file("a") expected exactly 1 calls, got 0`.
+	pub unmet: Vec<String>,
+	/// Calls matched by no expectation, such as `rename("a", "b")`.
+	pub unexpected: Vec<String>,
+}
+
+impl MockReport {
+	pub(crate) fn into_result(self) -> Result<(), Self> {
+		if self.unmet.is_empty() && self.unexpected.is_empty() {
+			Ok(())
+		} else {
+			Err(self)
+		}
+	}
+}
+
+impl fmt::Display for MockReport {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "mock expectations failed")?;
+		for unmet in &self.unmet {
+			write!(f, "\n  unmet: {unmet}")?;
+		}
+		for unexpected in &self.unexpected {
+			write!(f, "\n  unexpected: {unexpected}")?;
+		}
+		Ok(())
+	}
+}
+
+impl error::Error for MockReport {}
@@ -0,0 +1,172 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::rule::Injector;
+use crate::DirBuilder;
+use crate::File;
+use crate::OpenOptions;
+use crate::Operation;
+
+/// A [`DirBuilder`] of a [`FaultFs`](crate::FaultFs).
+#[derive(Debug)]
+pub struct FaultDirBuilder<B> {
+	pub(super) inner: B,
+	pub(super) injector: Injector,
+}
+
+impl<B: DirBuilder> DirBuilder for FaultDirBuilder<B> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.inner.recursive(recursive);
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::DirBuilderCreate, &[path.as_ref()])?;
+		self.inner.create(path)
+	}
+}
+
+/// A [`File`] of a [`FaultFs`](crate::FaultFs), remembering the path it was opened with.
+#[derive(Debug)]
+pub struct FaultFile<H> {
+	inner: H,
+	path: PathBuf,
+	injector: Injector,
+}
+
+impl<H> FaultFile<H> {
+	fn check(&self, operation: Operation) -> io::Result<()> {
+		self.injector.check(operation, &[&self.path])
+	}
+}
+
+impl<H: io::Read> io::Read for FaultFile<H> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.check(Operation::FileRead)?;
+		self.inner.read(buf)
+	}
+}
+
+impl<H: io::Write> io::Write for FaultFile<H> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		match self.injector.limit(Operation::FileWrite, &[&self.path])? {
+			Some(len) => self.inner.write(&buf[..len.min(buf.len())]),
+			None => self.inner.write(buf),
+		}
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.check(Operation::FileFlush)?;
+		self.inner.flush()
+	}
+}
+
+impl<H: io::Seek> io::Seek for FaultFile<H> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		self.check(Operation::FileSeek)?;
+		self.inner.seek(pos)
+	}
+}
+
+impl<H: File> File for FaultFile<H> {
+	type FileTimes = H::FileTimes;
+	type Metadata = H::Metadata;
+	type Permissions = H::Permissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		self.check(Operation::FileSyncAll)?;
+		self.inner.sync_all()
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		self.check(Operation::FileSyncData)?;
+		self.inner.sync_data()
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		self.check(Operation::FileSetLen)?;
+		self.inner.set_len(size)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.check(Operation::FileMetadata)?;
+		self.inner.metadata()
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		self.check(Operation::FileTryClone)?;
+		Ok(Self {
+			inner: self.inner.try_clone()?,
+			path: self.path.clone(),
+			injector: self.injector.clone(),
+		})
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		self.check(Operation::FileSetPermissions)?;
+		self.inner.set_permissions(perm)
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		self.check(Operation::FileSetTimes)?;
+		self.inner.set_times(times)
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		self.check(Operation::FileSetModified)?;
+		self.inner.set_modified(time)
+	}
+}
+
+/// [`OpenOptions`] of a [`FaultFs`](crate::FaultFs).
+#[derive(Clone, Debug)]
+pub struct FaultOpenOptions<O> {
+	pub(super) inner: O,
+	pub(super) injector: Injector,
+}
+
+impl<O: OpenOptions> OpenOptions for FaultOpenOptions<O> {
+	type File = FaultFile<O::File>;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.inner.read(read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.inner.write(write);
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.inner.append(append);
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.inner.truncate(truncate);
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.inner.create(create);
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.inner.create_new(create_new);
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let path = path.as_ref();
+		self.injector.check(Operation::Open, &[path])?;
+		Ok(FaultFile {
+			inner: self.inner.open(path)?,
+			path: path.to_owned(),
+			injector: self.injector.clone(),
+		})
+	}
+}
@@ -0,0 +1,228 @@
+use std::io;
+use std::path;
+
+mod file;
+pub use file::*;
+mod rule;
+pub use rule::Fault;
+pub use rule::FaultRule;
+use rule::Injector;
+
+use crate::Fs;
+use crate::Operation;
+
+/// A decorator injecting faults into the calls of another [`Fs`] and of its handles, according to
+/// [`FaultRule`]s.
+///
+/// Calls matched by no rule are forwarded unchanged. Clones and handles share the same rules, so
+/// rules can be added or cleared while the filesystem is in use.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Fault;
+/// use io_fs::FaultFs;
+/// use io_fs::FaultRule;
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::Operation;
+///
+/// let fs = FaultFs::new(MemFs::new());
+/// fs.inject(FaultRule::new(Fault::Error(io::ErrorKind::StorageFull)).operation(Operation::Write));
+/// assert_eq!(fs.write("file", "contents").unwrap_err().kind(), io::ErrorKind::StorageFull);
+/// assert!(!fs.exists("file")?);
+///
+/// fs.clear();
+/// fs.inject(FaultRule::new(Fault::ShortWrite(3)).nth(1));
+/// assert_eq!(fs.write("file", "contents").unwrap_err().kind(), io::ErrorKind::WriteZero);
+/// assert_eq!(fs.read("file")?, b"con");
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct FaultFs<F> {
+	inner: F,
+	injector: Injector,
+}
+
+impl<F> FaultFs<F> {
+	/// Wraps `inner` without any rules.
+	pub fn new(inner: F) -> Self {
+		Self {
+			inner,
+			injector: Injector::default(),
+		}
+	}
+
+	/// Adds a rule. Rules are checked in the order they were added, and the first one firing on a
+	/// call decides its fault.
+	pub fn inject(&self, rule: FaultRule) {
+		self.injector.push(rule);
+	}
+
+	/// Removes all rules.
+	pub fn clear(&self) {
+		self.injector.clear();
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.inner
+	}
+
+	pub fn into_inner(self) -> F {
+		self.inner
+	}
+}
+
+impl<F: Fs> Fs for FaultFs<F> {
+	type DirBuilder = FaultDirBuilder<F::DirBuilder>;
+	type DirEntry = F::DirEntry;
+	type File = FaultFile<F::File>;
+	type FileTimes = F::FileTimes;
+	type FileType = F::FileType;
+	type Metadata = F::Metadata;
+	type OpenOptions = FaultOpenOptions<F::OpenOptions>;
+	type Permissions = F::Permissions;
+	type ReadDir = F::ReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		FaultDirBuilder {
+			inner: self.inner.dir_builder(),
+			injector: self.injector.clone(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.inner.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		FaultOpenOptions {
+			inner: self.inner.open_options(),
+			injector: self.injector.clone(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.injector.check(Operation::Canonicalize, &[path.as_ref()])?;
+		self.inner.canonicalize(path)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		self.injector.check(
+			Operation::Copy,
+			&[
+				from.as_ref(),
+				to.as_ref(),
+			],
+		)?;
+		self.inner.copy(from, to)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::CreateDir, &[path.as_ref()])?;
+		self.inner.create_dir(path)
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::CreateDirAll, &[path.as_ref()])?;
+		self.inner.create_dir_all(path)
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.injector.check(Operation::Exists, &[path.as_ref()])?;
+		self.inner.exists(path)
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		self.injector.check(
+			Operation::HardLink,
+			&[
+				original.as_ref(),
+				link.as_ref(),
+			],
+		)?;
+		self.inner.hard_link(original, link)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.injector.check(Operation::Metadata, &[path.as_ref()])?;
+		self.inner.metadata(path)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		self.injector.check(Operation::Read, &[path.as_ref()])?;
+		self.inner.read(path)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		self.injector.check(Operation::ReadDir, &[path.as_ref()])?;
+		self.inner.read_dir(path)
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.injector.check(Operation::ReadLink, &[path.as_ref()])?;
+		self.inner.read_link(path)
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		self.injector.check(Operation::ReadToString, &[path.as_ref()])?;
+		self.inner.read_to_string(path)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::RemoveDir, &[path.as_ref()])?;
+		self.inner.remove_dir(path)
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::RemoveDirAll, &[path.as_ref()])?;
+		self.inner.remove_dir_all(path)
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.injector.check(Operation::RemoveFile, &[path.as_ref()])?;
+		self.inner.remove_file(path)
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		self.injector.check(
+			Operation::Rename,
+			&[
+				from.as_ref(),
+				to.as_ref(),
+			],
+		)?;
+		self.inner.rename(from, to)
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		self.injector.check(Operation::SetPermissions, &[path.as_ref()])?;
+		self.inner.set_permissions(path, perm)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.injector.check(Operation::SymlinkMetadata, &[path.as_ref()])?;
+		self.inner.symlink_metadata(path)
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let contents = contents.as_ref();
+		match self.injector.limit(Operation::Write, &[path.as_ref()])? {
+			Some(len) if len < contents.len() => {
+				self.inner.write(path, &contents[..len])?;
+				Err(io::ErrorKind::WriteZero.into())
+			}
+			_ => self.inner.write(path, contents),
+		}
+	}
+}
@@ -0,0 +1,201 @@
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use crate::rng::Rng;
+use crate::Operation;
+
+/// What happens to a call matched by a [`FaultRule`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+	/// Fails with an error of this kind, without calling the inner filesystem.
+	Error(io::ErrorKind),
+	/// Fails with this raw OS error, such as `28` for `ENOSPC` on Linux, without calling the inner
+	/// filesystem.
+	Os(i32),
+	/// Writes at most this many bytes. Writes through a file report the shortened length, while
+	/// [`Fs::write`](crate::Fs::write) fails with [`io::ErrorKind::WriteZero`] after writing them.
+	/// Rules with this fault only match [write](Operation::is_write) operations.
+	ShortWrite(usize),
+}
+
+/// A rule deciding which calls of a [`FaultFs`](crate::FaultFs) fail, and how.
+///
+/// A new rule matches every call. Each condition added narrows it down further.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Fault;
+/// use io_fs::FaultRule;
+/// use io_fs::Operation;
+///
+/// // Fail the third read from any log file.
+/// let rule = FaultRule::new(Fault::Error(io::ErrorKind::Other));
+/// let rule = rule.operation(Operation::FileRead).path("logs/*.log").nth(3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FaultRule {
+	fault: Fault,
+	operations: Vec<Operation>,
+	glob: Option<String>,
+	nth: Option<u64>,
+	probability: Option<(f64, Rng)>,
+}
+
+impl FaultRule {
+	pub fn new(fault: Fault) -> Self {
+		Self {
+			fault,
+			operations: Vec::new(),
+			glob: None,
+			nth: None,
+			probability: None,
+		}
+	}
+
+	/// Only matches calls of `operation`. Can be repeated to match several operations.
+	pub fn operation(mut self, operation: Operation) -> Self {
+		self.operations.push(operation);
+		self
+	}
+
+	/// Only matches calls with a path matching `glob`, such as `/tmp/**/*.txt`.
+	///
+	/// Paths are matched as they were passed, component by component. `*` and `?` match any
+	/// characters or a single character within a component, and `**` matches any number of
+	/// components. Calls on a file match the path it was opened with, and calls taking two paths
+	/// match if either does.
+	pub fn path(mut self, glob: impl Into<String>) -> Self {
+		self.glob = Some(glob.into());
+		self
+	}
+
+	/// Only fires on the `nth` matching call, counting from 1.
+	pub fn nth(mut self, nth: u64) -> Self {
+		self.nth = Some(nth);
+		self
+	}
+
+	/// Only fires with the given probability, drawn from a generator seeded with `seed`.
+	pub fn probability(mut self, probability: f64, seed: u64) -> Self {
+		self.probability = Some((probability, Rng::new(seed)));
+		self
+	}
+
+	fn matches(&self, operation: Operation, paths: &[&Path]) -> bool {
+		if matches!(self.fault, Fault::ShortWrite(_)) && !operation.is_write() {
+			return false;
+		}
+		if !self.operations.is_empty() && !self.operations.contains(&operation) {
+			return false;
+		}
+		match &self.glob {
+			Some(glob) => paths.iter().any(|path| glob_matches(glob, path)),
+			None => true,
+		}
+	}
+}
+
+/// The rules of a [`FaultFs`](crate::FaultFs), shared with its handles.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Injector {
+	rules: Arc<Mutex<Vec<(FaultRule, u64)>>>,
+}
+
+impl Injector {
+	pub fn push(&self, rule: FaultRule) {
+		self.lock().push((rule, 0));
+	}
+
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	/// Returns the fault of the first rule firing on the call. Every matching rule counts the call,
+	/// even if an earlier one fires.
+	fn fire(&self, operation: Operation, paths: &[&Path]) -> Option<Fault> {
+		let mut fired = None;
+		for (rule, calls) in self.lock().iter_mut() {
+			if !rule.matches(operation, paths) {
+				continue;
+			}
+			*calls += 1;
+			let nth = rule.nth.is_none_or(|nth| nth == *calls);
+			let chance = match &mut rule.probability {
+				Some((probability, rng)) => rng.chance(*probability),
+				None => true,
+			};
+			if nth && chance && fired.is_none() {
+				fired = Some(rule.fault.clone());
+			}
+		}
+		fired
+	}
+
+	/// Fails the call if a rule fires on it.
+	pub fn check(&self, operation: Operation, paths: &[&Path]) -> io::Result<()> {
+		self.limit(operation, paths).map(|_| ())
+	}
+
+	/// Fails the call if a rule fires on it, or returns the number of bytes it may write.
+	pub fn limit(&self, operation: Operation, paths: &[&Path]) -> io::Result<Option<usize>> {
+		match self.fire(operation, paths) {
+			Some(Fault::Error(kind)) => {
+				Err(io::Error::new(kind, format!("injected {operation} fault")))
+			}
+			Some(Fault::Os(code)) => Err(io::Error::from_raw_os_error(code)),
+			Some(Fault::ShortWrite(len)) => Ok(Some(len)),
+			None => Ok(None),
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Vec<(FaultRule, u64)>> {
+		self.rules.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+fn glob_matches(glob: &str, path: &Path) -> bool {
+	let patterns = glob.split('/').collect::<Vec<_>>();
+	let names = path
+		.components()
+		.map(|component| {
+			match component {
+				Component::RootDir => String::new(),
+				_ => component.as_os_str().to_string_lossy().into_owned(),
+			}
+		})
+		.collect::<Vec<_>>();
+	components_match(&patterns, &names)
+}
+
+fn components_match(patterns: &[&str], names: &[String]) -> bool {
+	match patterns.split_first() {
+		None => names.is_empty(),
+		Some((&"**", rest)) => (0..=names.len()).any(|skip| components_match(rest, &names[skip..])),
+		Some((pattern, rest)) => {
+			match names.split_first() {
+				Some((name, names)) => {
+					let pattern = pattern.chars().collect::<Vec<_>>();
+					let name = name.chars().collect::<Vec<_>>();
+					name_matches(&pattern, &name) && components_match(rest, names)
+				}
+				None => false,
+			}
+		}
+	}
+}
+
+fn name_matches(pattern: &[char], name: &[char]) -> bool {
+	match pattern.split_first() {
+		None => name.is_empty(),
+		Some(('*', rest)) => (0..=name.len()).any(|skip| name_matches(rest, &name[skip..])),
+		Some(('?', rest)) => !name.is_empty() && name_matches(rest, &name[1..]),
+		Some((c, rest)) => name.first() == Some(c) && name_matches(rest, &name[1..]),
+	}
+}
@@ -0,0 +1,838 @@
+// This file is auto-generated. DO NOT edit by hand. See README.md for more details.
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+use std::any::Any;
+use std::ffi::OsString;
+use std::io;
+use std::path;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use super::functions::Fs;
+use super::structs::DirBuilder;
+use super::structs::DirEntry;
+use super::structs::File;
+use super::structs::FileTimes;
+use super::structs::FileType;
+use super::structs::Metadata;
+use super::structs::OpenOptions;
+use super::structs::Permissions;
+use super::structs::ReadDir;
+
+/// Helper used by the blanket implementations to recover concrete handles passed back in.
+fn downcast<T: Any>(value: Box<dyn Any>) -> io::Result<T> {
+	value
+		.downcast()
+		.map(|value| *value)
+		.map_err(|_| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"handle belongs to a different backend",
+			)
+		})
+}
+
+/// Dyn-compatible counterpart of [`Fs`], implemented for every `'static` [`Fs`].
+///
+/// Paths are taken as `&Path` and handles are returned as boxed trait objects. In turn, boxed
+/// `DynFs` trait objects implement [`Fs`], so a filesystem chosen at runtime can be stored without
+/// making its owner generic. Call methods through [`Fs`], as method calls are ambiguous when both
+/// traits are in scope.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+///
+/// struct Config {
+/// 	fs: Box<dyn io_fs::DynFs>,
+/// }
+///
+/// let config = Config {
+/// 	fs: Box::new(MemFs::new()),
+/// };
+/// config.fs.write("config.toml", "verbose = true")?;
+/// assert!(config.fs.exists("config.toml")?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub trait DynFs: Any {
+	fn dir_builder(&self) -> Box<dyn DynDirBuilder>;
+	fn file_times(&self) -> Box<dyn DynFileTimes>;
+	fn open_options(&self) -> Box<dyn DynOpenOptions>;
+	fn canonicalize(&self, path: &path::Path) -> io::Result<path::PathBuf>;
+	fn copy(&self, from: &path::Path, to: &path::Path) -> io::Result<u64>;
+	fn create_dir(&self, path: &path::Path) -> io::Result<()>;
+	fn create_dir_all(&self, path: &path::Path) -> io::Result<()>;
+	fn exists(&self, path: &path::Path) -> io::Result<bool>;
+	fn hard_link(&self, original: &path::Path, link: &path::Path) -> io::Result<()>;
+	fn metadata(&self, path: &path::Path) -> io::Result<Box<dyn DynMetadata>>;
+	fn read(&self, path: &path::Path) -> io::Result<Vec<u8>>;
+	fn read_dir(&self, path: &path::Path) -> io::Result<Box<dyn DynReadDir>>;
+	fn read_link(&self, path: &path::Path) -> io::Result<path::PathBuf>;
+	fn read_to_string(&self, path: &path::Path) -> io::Result<String>;
+	fn remove_dir(&self, path: &path::Path) -> io::Result<()>;
+	fn remove_dir_all(&self, path: &path::Path) -> io::Result<()>;
+	fn remove_file(&self, path: &path::Path) -> io::Result<()>;
+	fn rename(&self, from: &path::Path, to: &path::Path) -> io::Result<()>;
+	fn set_permissions(
+		&self,
+		path: &path::Path,
+		perm: Box<dyn DynPermissions>,
+	) -> io::Result<()>;
+
+	fn symlink_metadata(&self, path: &path::Path) -> io::Result<Box<dyn DynMetadata>>;
+	fn write(&self, path: &path::Path, contents: &[u8]) -> io::Result<()>;
+}
+
+impl<T> DynFs for T
+where
+	T: Fs + Any,
+	T::DirBuilder: DynDirBuilder,
+	T::DirEntry: DynDirEntry,
+	T::File: DynFile,
+	T::FileTimes: DynFileTimes,
+	T::FileType: DynFileType,
+	T::Metadata: DynMetadata,
+	T::OpenOptions: DynOpenOptions,
+	T::Permissions: DynPermissions,
+	T::ReadDir: DynReadDir,
+{
+	fn dir_builder(&self) -> Box<dyn DynDirBuilder> {
+		Box::new(Fs::dir_builder(self))
+	}
+
+	fn file_times(&self) -> Box<dyn DynFileTimes> {
+		Box::new(Fs::file_times(self))
+	}
+
+	fn open_options(&self) -> Box<dyn DynOpenOptions> {
+		Box::new(Fs::open_options(self))
+	}
+
+	fn canonicalize(&self, path: &path::Path) -> io::Result<path::PathBuf> {
+		Fs::canonicalize(self, path)
+	}
+
+	fn copy(&self, from: &path::Path, to: &path::Path) -> io::Result<u64> {
+		Fs::copy(self, from, to)
+	}
+
+	fn create_dir(&self, path: &path::Path) -> io::Result<()> {
+		Fs::create_dir(self, path)
+	}
+
+	fn create_dir_all(&self, path: &path::Path) -> io::Result<()> {
+		Fs::create_dir_all(self, path)
+	}
+
+	fn exists(&self, path: &path::Path) -> io::Result<bool> {
+		Fs::exists(self, path)
+	}
+
+	fn hard_link(&self, original: &path::Path, link: &path::Path) -> io::Result<()> {
+		Fs::hard_link(self, original, link)
+	}
+
+	fn metadata(&self, path: &path::Path) -> io::Result<Box<dyn DynMetadata>> {
+		Fs::metadata(self, path).map(|value| Box::new(value) as Box<dyn DynMetadata>)
+	}
+
+	fn read(&self, path: &path::Path) -> io::Result<Vec<u8>> {
+		Fs::read(self, path)
+	}
+
+	fn read_dir(&self, path: &path::Path) -> io::Result<Box<dyn DynReadDir>> {
+		Fs::read_dir(self, path).map(|value| Box::new(value) as Box<dyn DynReadDir>)
+	}
+
+	fn read_link(&self, path: &path::Path) -> io::Result<path::PathBuf> {
+		Fs::read_link(self, path)
+	}
+
+	fn read_to_string(&self, path: &path::Path) -> io::Result<String> {
+		Fs::read_to_string(self, path)
+	}
+
+	fn remove_dir(&self, path: &path::Path) -> io::Result<()> {
+		Fs::remove_dir(self, path)
+	}
+
+	fn remove_dir_all(&self, path: &path::Path) -> io::Result<()> {
+		Fs::remove_dir_all(self, path)
+	}
+
+	fn remove_file(&self, path: &path::Path) -> io::Result<()> {
+		Fs::remove_file(self, path)
+	}
+
+	fn rename(&self, from: &path::Path, to: &path::Path) -> io::Result<()> {
+		Fs::rename(self, from, to)
+	}
+
+	fn set_permissions(
+		&self,
+		path: &path::Path,
+		perm: Box<dyn DynPermissions>,
+	) -> io::Result<()> {
+		Fs::set_permissions(self, path, downcast(perm)?)
+	}
+
+	fn symlink_metadata(&self, path: &path::Path) -> io::Result<Box<dyn DynMetadata>> {
+		Fs::symlink_metadata(self, path)
+			.map(|value| Box::new(value) as Box<dyn DynMetadata>)
+	}
+
+	fn write(&self, path: &path::Path, contents: &[u8]) -> io::Result<()> {
+		Fs::write(self, path, contents)
+	}
+}
+
+impl<D: DynFs + ?Sized> Fs for Box<D> {
+	type DirBuilder = Box<dyn DynDirBuilder>;
+	type DirEntry = Box<dyn DynDirEntry>;
+	type File = Box<dyn DynFile>;
+	type FileTimes = Box<dyn DynFileTimes>;
+	type FileType = Box<dyn DynFileType>;
+	type Metadata = Box<dyn DynMetadata>;
+	type OpenOptions = Box<dyn DynOpenOptions>;
+	type Permissions = Box<dyn DynPermissions>;
+	type ReadDir = Box<dyn DynReadDir>;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		DynFs::dir_builder(&**self)
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		DynFs::file_times(&**self)
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		DynFs::open_options(&**self)
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		DynFs::canonicalize(&**self, path.as_ref())
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<u64> {
+		DynFs::copy(&**self, from.as_ref(), to.as_ref())
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		DynFs::create_dir(&**self, path.as_ref())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		DynFs::create_dir_all(&**self, path.as_ref())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		DynFs::exists(&**self, path.as_ref())
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		DynFs::hard_link(&**self, original.as_ref(), link.as_ref())
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		DynFs::metadata(&**self, path.as_ref())
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		DynFs::read(&**self, path.as_ref())
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		DynFs::read_dir(&**self, path.as_ref())
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		DynFs::read_link(&**self, path.as_ref())
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		DynFs::read_to_string(&**self, path.as_ref())
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		DynFs::remove_dir(&**self, path.as_ref())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		DynFs::remove_dir_all(&**self, path.as_ref())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		DynFs::remove_file(&**self, path.as_ref())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<()> {
+		DynFs::rename(&**self, from.as_ref(), to.as_ref())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		DynFs::set_permissions(&**self, path.as_ref(), perm)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+	) -> io::Result<Self::Metadata> {
+		DynFs::symlink_metadata(&**self, path.as_ref())
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(
+		&self,
+		path: P,
+		contents: C,
+	) -> io::Result<()> {
+		DynFs::write(&**self, path.as_ref(), contents.as_ref())
+	}
+}
+
+/// Dyn-compatible counterpart of [`DirBuilder`], implemented for every `'static` [`DirBuilder`].
+pub trait DynDirBuilder: Any + std::fmt::Debug {
+	fn recursive(&mut self, recursive: bool);
+	fn create(&self, path: &Path) -> io::Result<()>;
+}
+
+impl<T> DynDirBuilder for T
+where
+	T: DirBuilder + Any,
+{
+	fn recursive(&mut self, recursive: bool) {
+		DirBuilder::recursive(self, recursive);
+	}
+
+	fn create(&self, path: &Path) -> io::Result<()> {
+		DirBuilder::create(self, path)
+	}
+}
+
+impl DirBuilder for Box<dyn DynDirBuilder> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		DynDirBuilder::recursive(&mut **self, recursive);
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		DynDirBuilder::create(&**self, path.as_ref())
+	}
+}
+
+/// Dyn-compatible counterpart of [`DirEntry`], implemented for every `'static` [`DirEntry`].
+pub trait DynDirEntry: Any + std::fmt::Debug {
+	fn path(&self) -> PathBuf;
+	fn metadata(&self) -> io::Result<Box<dyn DynMetadata>>;
+	fn file_type(&self) -> io::Result<Box<dyn DynFileType>>;
+	fn file_name(&self) -> OsString;
+}
+
+impl<T> DynDirEntry for T
+where
+	T: DirEntry + Any,
+	T::FileType: DynFileType,
+	T::Metadata: DynMetadata,
+{
+	fn path(&self) -> PathBuf {
+		DirEntry::path(self)
+	}
+
+	fn metadata(&self) -> io::Result<Box<dyn DynMetadata>> {
+		DirEntry::metadata(self).map(|value| Box::new(value) as Box<dyn DynMetadata>)
+	}
+
+	fn file_type(&self) -> io::Result<Box<dyn DynFileType>> {
+		DirEntry::file_type(self).map(|value| Box::new(value) as Box<dyn DynFileType>)
+	}
+
+	fn file_name(&self) -> OsString {
+		DirEntry::file_name(self)
+	}
+}
+
+impl DirEntry for Box<dyn DynDirEntry> {
+	type FileType = Box<dyn DynFileType>;
+	type Metadata = Box<dyn DynMetadata>;
+
+	fn path(&self) -> PathBuf {
+		DynDirEntry::path(&**self)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		DynDirEntry::metadata(&**self)
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		DynDirEntry::file_type(&**self)
+	}
+
+	fn file_name(&self) -> OsString {
+		DynDirEntry::file_name(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`File`], implemented for every `'static` [`File`].
+pub trait DynFile: Any + std::fmt::Debug + io::Read + io::Seek + io::Write {
+	fn sync_all(&self) -> io::Result<()>;
+	fn sync_data(&self) -> io::Result<()>;
+	fn set_len(&self, size: u64) -> io::Result<()>;
+	fn metadata(&self) -> io::Result<Box<dyn DynMetadata>>;
+	fn try_clone(&self) -> io::Result<Box<dyn DynFile>>;
+	fn set_permissions(&self, perm: Box<dyn DynPermissions>) -> io::Result<()>;
+	fn set_times(&self, times: Box<dyn DynFileTimes>) -> io::Result<()>;
+	fn set_modified(&self, time: SystemTime) -> io::Result<()>;
+}
+
+impl<T> DynFile for T
+where
+	T: File + Any,
+	T::FileTimes: DynFileTimes,
+	T::Metadata: DynMetadata,
+	T::Permissions: DynPermissions,
+{
+	fn sync_all(&self) -> io::Result<()> {
+		File::sync_all(self)
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		File::sync_data(self)
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		File::set_len(self, size)
+	}
+
+	fn metadata(&self) -> io::Result<Box<dyn DynMetadata>> {
+		File::metadata(self).map(|value| Box::new(value) as Box<dyn DynMetadata>)
+	}
+
+	fn try_clone(&self) -> io::Result<Box<dyn DynFile>> {
+		File::try_clone(self).map(|value| Box::new(value) as Box<dyn DynFile>)
+	}
+
+	fn set_permissions(&self, perm: Box<dyn DynPermissions>) -> io::Result<()> {
+		File::set_permissions(self, downcast(perm)?)
+	}
+
+	fn set_times(&self, times: Box<dyn DynFileTimes>) -> io::Result<()> {
+		File::set_times(self, downcast(times)?)
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		File::set_modified(self, time)
+	}
+}
+
+impl File for Box<dyn DynFile> {
+	type FileTimes = Box<dyn DynFileTimes>;
+	type Metadata = Box<dyn DynMetadata>;
+	type Permissions = Box<dyn DynPermissions>;
+
+	fn sync_all(&self) -> io::Result<()> {
+		DynFile::sync_all(&**self)
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		DynFile::sync_data(&**self)
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		DynFile::set_len(&**self, size)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		DynFile::metadata(&**self)
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		DynFile::try_clone(&**self)
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		DynFile::set_permissions(&**self, perm)
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		DynFile::set_times(&**self, times)
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		DynFile::set_modified(&**self, time)
+	}
+}
+
+/// Dyn-compatible counterpart of [`FileTimes`], implemented for every `'static` [`FileTimes`].
+pub trait DynFileTimes: Any + std::fmt::Debug {
+	fn clone_box(&self) -> Box<dyn DynFileTimes>;
+	fn set_accessed(self: Box<Self>, t: SystemTime) -> Box<dyn DynFileTimes>;
+	fn set_modified(self: Box<Self>, t: SystemTime) -> Box<dyn DynFileTimes>;
+}
+
+impl<T> DynFileTimes for T
+where
+	T: FileTimes + Any,
+{
+	fn clone_box(&self) -> Box<dyn DynFileTimes> {
+		Box::new(Clone::clone(self))
+	}
+
+	fn set_accessed(self: Box<Self>, t: SystemTime) -> Box<dyn DynFileTimes> {
+		Box::new(FileTimes::set_accessed(*self, t)) as Box<dyn DynFileTimes>
+	}
+
+	fn set_modified(self: Box<Self>, t: SystemTime) -> Box<dyn DynFileTimes> {
+		Box::new(FileTimes::set_modified(*self, t)) as Box<dyn DynFileTimes>
+	}
+}
+
+impl FileTimes for Box<dyn DynFileTimes> {
+	fn set_accessed(self, t: SystemTime) -> Self {
+		DynFileTimes::set_accessed(self, t)
+	}
+
+	fn set_modified(self, t: SystemTime) -> Self {
+		DynFileTimes::set_modified(self, t)
+	}
+}
+
+impl Clone for Box<dyn DynFileTimes> {
+	fn clone(&self) -> Self {
+		DynFileTimes::clone_box(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`FileType`], implemented for every `'static` [`FileType`].
+pub trait DynFileType: Any + std::fmt::Debug {
+	fn clone_box(&self) -> Box<dyn DynFileType>;
+	fn is_dir(&self) -> bool;
+	fn is_file(&self) -> bool;
+	fn is_symlink(&self) -> bool;
+}
+
+impl<T> DynFileType for T
+where
+	T: FileType + Any,
+{
+	fn clone_box(&self) -> Box<dyn DynFileType> {
+		Box::new(Clone::clone(self))
+	}
+
+	fn is_dir(&self) -> bool {
+		FileType::is_dir(self)
+	}
+
+	fn is_file(&self) -> bool {
+		FileType::is_file(self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		FileType::is_symlink(self)
+	}
+}
+
+impl FileType for Box<dyn DynFileType> {
+	fn is_dir(&self) -> bool {
+		DynFileType::is_dir(&**self)
+	}
+
+	fn is_file(&self) -> bool {
+		DynFileType::is_file(&**self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		DynFileType::is_symlink(&**self)
+	}
+}
+
+impl Clone for Box<dyn DynFileType> {
+	fn clone(&self) -> Self {
+		DynFileType::clone_box(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`Metadata`], implemented for every `'static` [`Metadata`].
+pub trait DynMetadata: Any + std::fmt::Debug {
+	fn clone_box(&self) -> Box<dyn DynMetadata>;
+	fn file_type(&self) -> Box<dyn DynFileType>;
+	fn is_dir(&self) -> bool;
+	fn is_file(&self) -> bool;
+	fn is_symlink(&self) -> bool;
+	fn len(&self) -> u64;
+	fn permissions(&self) -> Box<dyn DynPermissions>;
+	fn modified(&self) -> io::Result<SystemTime>;
+	fn accessed(&self) -> io::Result<SystemTime>;
+	fn created(&self) -> io::Result<SystemTime>;
+}
+
+impl<T> DynMetadata for T
+where
+	T: Metadata + Any,
+	T::FileType: DynFileType,
+	T::Permissions: DynPermissions,
+{
+	fn clone_box(&self) -> Box<dyn DynMetadata> {
+		Box::new(Clone::clone(self))
+	}
+
+	fn file_type(&self) -> Box<dyn DynFileType> {
+		Box::new(Metadata::file_type(self)) as Box<dyn DynFileType>
+	}
+
+	fn is_dir(&self) -> bool {
+		Metadata::is_dir(self)
+	}
+
+	fn is_file(&self) -> bool {
+		Metadata::is_file(self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		Metadata::is_symlink(self)
+	}
+
+	fn len(&self) -> u64 {
+		Metadata::len(self)
+	}
+
+	fn permissions(&self) -> Box<dyn DynPermissions> {
+		Box::new(Metadata::permissions(self)) as Box<dyn DynPermissions>
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		Metadata::modified(self)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		Metadata::accessed(self)
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		Metadata::created(self)
+	}
+}
+
+impl Metadata for Box<dyn DynMetadata> {
+	type FileType = Box<dyn DynFileType>;
+	type Permissions = Box<dyn DynPermissions>;
+
+	fn file_type(&self) -> Self::FileType {
+		DynMetadata::file_type(&**self)
+	}
+
+	fn is_dir(&self) -> bool {
+		DynMetadata::is_dir(&**self)
+	}
+
+	fn is_file(&self) -> bool {
+		DynMetadata::is_file(&**self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		DynMetadata::is_symlink(&**self)
+	}
+
+	fn len(&self) -> u64 {
+		DynMetadata::len(&**self)
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		DynMetadata::permissions(&**self)
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		DynMetadata::modified(&**self)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		DynMetadata::accessed(&**self)
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		DynMetadata::created(&**self)
+	}
+}
+
+impl Clone for Box<dyn DynMetadata> {
+	fn clone(&self) -> Self {
+		DynMetadata::clone_box(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`OpenOptions`], implemented for every `'static` [`OpenOptions`].
+pub trait DynOpenOptions: Any + std::fmt::Debug {
+	fn clone_box(&self) -> Box<dyn DynOpenOptions>;
+	fn read(&mut self, read: bool);
+	fn write(&mut self, write: bool);
+	fn append(&mut self, append: bool);
+	fn truncate(&mut self, truncate: bool);
+	fn create(&mut self, create: bool);
+	fn create_new(&mut self, create_new: bool);
+	fn open(&self, path: &Path) -> io::Result<Box<dyn DynFile>>;
+}
+
+impl<T> DynOpenOptions for T
+where
+	T: OpenOptions + Any,
+	T::File: DynFile,
+{
+	fn clone_box(&self) -> Box<dyn DynOpenOptions> {
+		Box::new(Clone::clone(self))
+	}
+
+	fn read(&mut self, read: bool) {
+		OpenOptions::read(self, read);
+	}
+
+	fn write(&mut self, write: bool) {
+		OpenOptions::write(self, write);
+	}
+
+	fn append(&mut self, append: bool) {
+		OpenOptions::append(self, append);
+	}
+
+	fn truncate(&mut self, truncate: bool) {
+		OpenOptions::truncate(self, truncate);
+	}
+
+	fn create(&mut self, create: bool) {
+		OpenOptions::create(self, create);
+	}
+
+	fn create_new(&mut self, create_new: bool) {
+		OpenOptions::create_new(self, create_new);
+	}
+
+	fn open(&self, path: &Path) -> io::Result<Box<dyn DynFile>> {
+		OpenOptions::open(self, path).map(|value| Box::new(value) as Box<dyn DynFile>)
+	}
+}
+
+impl OpenOptions for Box<dyn DynOpenOptions> {
+	type File = Box<dyn DynFile>;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		DynOpenOptions::read(&mut **self, read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		DynOpenOptions::write(&mut **self, write);
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		DynOpenOptions::append(&mut **self, append);
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		DynOpenOptions::truncate(&mut **self, truncate);
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		DynOpenOptions::create(&mut **self, create);
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		DynOpenOptions::create_new(&mut **self, create_new);
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		DynOpenOptions::open(&**self, path.as_ref())
+	}
+}
+
+impl Clone for Box<dyn DynOpenOptions> {
+	fn clone(&self) -> Self {
+		DynOpenOptions::clone_box(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`Permissions`], implemented for every `'static` [`Permissions`].
+pub trait DynPermissions: Any + std::fmt::Debug {
+	fn clone_box(&self) -> Box<dyn DynPermissions>;
+	fn readonly(&self) -> bool;
+	fn set_readonly(&mut self, readonly: bool);
+}
+
+impl<T> DynPermissions for T
+where
+	T: Permissions + Any,
+{
+	fn clone_box(&self) -> Box<dyn DynPermissions> {
+		Box::new(Clone::clone(self))
+	}
+
+	fn readonly(&self) -> bool {
+		Permissions::readonly(self)
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		Permissions::set_readonly(self, readonly)
+	}
+}
+
+impl Permissions for Box<dyn DynPermissions> {
+	fn readonly(&self) -> bool {
+		DynPermissions::readonly(&**self)
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		DynPermissions::set_readonly(&mut **self, readonly)
+	}
+}
+
+impl Clone for Box<dyn DynPermissions> {
+	fn clone(&self) -> Self {
+		DynPermissions::clone_box(&**self)
+	}
+}
+
+/// Dyn-compatible counterpart of [`ReadDir`], implemented for every `'static` [`ReadDir`].
+pub trait DynReadDir: Any + std::fmt::Debug {
+	fn next_entry(&mut self) -> Option<Result<Box<dyn DynDirEntry>, std::io::Error>>;
+}
+
+impl<T> DynReadDir for T
+where
+	T: ReadDir + Any,
+	T::DirEntry: DynDirEntry,
+{
+	fn next_entry(&mut self) -> Option<Result<Box<dyn DynDirEntry>, std::io::Error>> {
+		Iterator::next(self)
+			.map(|value| value.map(|value| Box::new(value) as Box<dyn DynDirEntry>))
+	}
+}
+
+impl ReadDir for Box<dyn DynReadDir> {
+	type DirEntry = Box<dyn DynDirEntry>;
+}
+
+impl Iterator for Box<dyn DynReadDir> {
+	type Item = Result<Box<dyn DynDirEntry>, std::io::Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		DynReadDir::next_entry(&mut **self)
+	}
+}
@@ -1,12 +1,80 @@
 // This file is auto-generated. DO NOT edit by hand. See README.md for more details.
-#![allow(clippy::tabs_in_doc_comments)]
-use std::fs::Metadata;
-use std::fs::Permissions;
-use std::fs::ReadDir;
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
 use std::io;
 use std::path;
+use super::structs::DirBuilder;
+use super::structs::DirEntry;
+use super::structs::File;
+use super::structs::FileTimes;
+use super::structs::FileType;
+use super::structs::Metadata;
+use super::structs::OpenOptions;
+use super::structs::Permissions;
+use super::structs::ReadDir;
 
 pub trait Fs {
+	/// The backend counterpart of [`std::fs::DirBuilder`].
+	type DirBuilder: DirBuilder;
+
+	/// The backend counterpart of [`std::fs::DirEntry`].
+	type DirEntry: DirEntry<FileType = Self::FileType, Metadata = Self::Metadata>;
+
+	/// The backend counterpart of [`std::fs::File`].
+	type File: File<
+			FileTimes = Self::FileTimes,
+			Metadata = Self::Metadata,
+			Permissions = Self::Permissions,
+		>;
+
+	/// The backend counterpart of [`std::fs::FileTimes`].
+	type FileTimes: FileTimes;
+
+	/// The backend counterpart of [`std::fs::FileType`].
+	type FileType: FileType;
+
+	/// The backend counterpart of [`std::fs::Metadata`].
+	type Metadata: Metadata<FileType = Self::FileType, Permissions = Self::Permissions>;
+
+	/// The backend counterpart of [`std::fs::OpenOptions`].
+	type OpenOptions: OpenOptions<File = Self::File>;
+
+	/// The backend counterpart of [`std::fs::Permissions`].
+	type Permissions: Permissions;
+
+	/// The backend counterpart of [`std::fs::ReadDir`].
+	type ReadDir: ReadDir<DirEntry = Self::DirEntry>;
+
+	/// Creates a new set of options with default mode/security settings for all
+	/// platforms and also non-recursive.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::fs::DirBuilder;
+	///
+	/// let builder = DirBuilder::new();
+	/// ```
+	fn dir_builder(&self) -> Self::DirBuilder;
+
+	/// Creates a new `FileTimes` with no times set.
+	///
+	/// Using the resulting `FileTimes` in [`File::set_times`] will not modify any timestamps.
+	fn file_times(&self) -> Self::FileTimes;
+
+	/// Creates a blank new set of options ready for configuration.
+	///
+	/// All options are initially set to `false`.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let mut options = OpenOptions::new();
+	/// let file = options.read(true).open("foo.txt");
+	/// ```
+	fn open_options(&self) -> Self::OpenOptions;
+
 	/// Returns the canonical, absolute form of a path with all intermediate
 	/// components normalized and symbolic links resolved.
 	///
@@ -39,11 +107,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	let path = fs::canonicalize("../a/../foo.txt")?;
-	/// 	Ok(())
+	///     let path = fs::canonicalize("../a/../foo.txt")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn canonicalize<P: AsRef<path::Path>>(path: P) -> io::Result<path::PathBuf>;
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf>;
 
 	/// Copies the contents of one file to another. This function will also
 	/// copy the permission bits of the original file to the destination file.
@@ -85,7 +153,8 @@ pub trait Fs {
 	///
 	/// * `from` is neither a regular file nor a symlink to a regular file.
 	/// * `from` does not exist.
-	/// * The current process does not have the permission rights to read `from` or write `to`.
+	/// * The current process does not have the permission rights to read
+	///   `from` or write `to`.
 	///
 	/// # Examples
 	///
@@ -93,11 +162,15 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::copy("foo.txt", "bar.txt")?; // Copy foo.txt to bar.txt
-	/// 	Ok(())
+	///     fs::copy("foo.txt", "bar.txt")?;  // Copy foo.txt to bar.txt
+	///     Ok(())
 	/// }
 	/// ```
-	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(from: P, to: Q) -> io::Result<u64>;
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<u64>;
 
 	/// Creates a new, empty directory at the provided path
 	///
@@ -119,8 +192,9 @@ pub trait Fs {
 	/// limited to just these cases:
 	///
 	/// * User lacks permissions to create directory at `path`.
-	/// * A parent of the given path doesn't exist. (To create a directory and all its missing
-	///   parents at the same time, use the [`create_dir_all`] function.)
+	/// * A parent of the given path doesn't exist. (To create a directory and all
+	///   its missing parents at the same time, use the [`create_dir_all`]
+	///   function.)
 	/// * `path` already exists.
 	///
 	/// # Examples
@@ -129,11 +203,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::create_dir("/some/dir")?;
-	/// 	Ok(())
+	///     fs::create_dir("/some/dir")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn create_dir<P: AsRef<path::Path>>(path: P) -> io::Result<()>;
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()>;
 
 	/// Recursively create a directory and all of its parent components if they
 	/// are missing.
@@ -156,8 +230,7 @@ pub trait Fs {
 	/// # Errors
 	///
 	/// The function will return an error if any directory specified in path does not exist and
-	/// could not be created. There may be other error conditions; see [`fs::create_dir`] for
-	/// specifics.
+	/// could not be created. There may be other error conditions; see [`fs::create_dir`] for specifics.
 	///
 	/// Notable exception is made for situations where any of the directories
 	/// specified in the `path` could not be created as it was being created concurrently.
@@ -173,11 +246,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::create_dir_all("/some/dir")?;
-	/// 	Ok(())
+	///     fs::create_dir_all("/some/dir")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn create_dir_all<P: AsRef<path::Path>>(path: P) -> io::Result<()>;
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()>;
 
 	/// Returns `Ok(true)` if the path points at an existing entity.
 	///
@@ -198,13 +271,12 @@ pub trait Fs {
 	/// ```no_run
 	/// use std::fs;
 	///
-	/// assert!(!fs::exists("does_not_exist.txt")
-	/// 	.expect("Can't check existence of file does_not_exist.txt"));
+	/// assert!(!fs::exists("does_not_exist.txt").expect("Can't check existence of file does_not_exist.txt"));
 	/// assert!(fs::exists("/root/secret_file.txt").is_err());
 	/// ```
 	///
 	/// [`Path::exists`]: crate::path::Path::exists
-	fn exists<P: AsRef<path::Path>>(path: P) -> io::Result<bool>;
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool>;
 
 	/// Creates a new hard link on the filesystem.
 	///
@@ -241,11 +313,12 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::hard_link("a.txt", "b.txt")?; // Hard link a.txt to b.txt
-	/// 	Ok(())
+	///     fs::hard_link("a.txt", "b.txt")?; // Hard link a.txt to b.txt
+	///     Ok(())
 	/// }
 	/// ```
 	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
 		original: P,
 		link: Q,
 	) -> io::Result<()>;
@@ -278,12 +351,12 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	let attr = fs::metadata("/some/file/path.txt")?;
-	/// 	// inspect attr ...
-	/// 	Ok(())
+	///     let attr = fs::metadata("/some/file/path.txt")?;
+	///     // inspect attr ...
+	///     Ok(())
 	/// }
 	/// ```
-	fn metadata<P: AsRef<path::Path>>(path: P) -> io::Result<Metadata>;
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata>;
 
 	/// Reads the entire contents of a file into a bytes vector.
 	///
@@ -306,19 +379,12 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> Result<(), Box<dyn std::error::Error + 'static>> {
-	/// 	let data: Vec<u8> = fs::read("image.jpg")?;
-	/// 	assert_eq!(
-	/// 		data[0..3],
-	/// 		[
-	/// 			0xFF,
-	/// 			0xD8,
-	/// 			0xFF
-	/// 		]
-	/// 	);
-	/// 	Ok(())
+	///     let data: Vec<u8> = fs::read("image.jpg")?;
+	///     assert_eq!(data[0..3], [0xFF, 0xD8, 0xFF]);
+	///     Ok(())
 	/// }
 	/// ```
-	fn read<P: AsRef<path::Path>>(path: P) -> io::Result<Vec<u8>>;
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>>;
 
 	/// Returns an iterator over the entries within a directory.
 	///
@@ -351,48 +417,46 @@ pub trait Fs {
 	/// # Examples
 	///
 	/// ```
-	/// use std::fs::DirEntry;
-	/// use std::fs::{self};
 	/// use std::io;
+	/// use std::fs::{self, DirEntry};
 	/// use std::path::Path;
 	///
 	/// // one possible implementation of walking a directory only visiting files
 	/// fn visit_dirs(dir: &Path, cb: &dyn Fn(&DirEntry)) -> io::Result<()> {
-	/// 	if dir.is_dir() {
-	/// 		for entry in fs::read_dir(dir)? {
-	/// 			let entry = entry?;
-	/// 			let path = entry.path();
-	/// 			if path.is_dir() {
-	/// 				visit_dirs(&path, cb)?;
-	/// 			} else {
-	/// 				cb(&entry);
-	/// 			}
-	/// 		}
-	/// 	}
-	/// 	Ok(())
+	///     if dir.is_dir() {
+	///         for entry in fs::read_dir(dir)? {
+	///             let entry = entry?;
+	///             let path = entry.path();
+	///             if path.is_dir() {
+	///                 visit_dirs(&path, cb)?;
+	///             } else {
+	///                 cb(&entry);
+	///             }
+	///         }
+	///     }
+	///     Ok(())
 	/// }
 	/// ```
 	///
 	/// ```rust,no_run
-	/// use std::fs;
-	/// use std::io;
+	/// use std::{fs, io};
 	///
 	/// fn main() -> io::Result<()> {
-	/// 	let mut entries = fs::read_dir(".")?
-	/// 		.map(|res| res.map(|e| e.path()))
-	/// 		.collect::<Result<Vec<_>, io::Error>>()?;
+	///     let mut entries = fs::read_dir(".")?
+	///         .map(|res| res.map(|e| e.path()))
+	///         .collect::<Result<Vec<_>, io::Error>>()?;
 	///
-	/// 	// The order in which `read_dir` returns entries is not guaranteed. If reproducible
-	/// 	// ordering is required the entries should be explicitly sorted.
+	///     // The order in which `read_dir` returns entries is not guaranteed. If reproducible
+	///     // ordering is required the entries should be explicitly sorted.
 	///
-	/// 	entries.sort();
+	///     entries.sort();
 	///
-	/// 	// The entries have now been sorted by their path.
+	///     // The entries have now been sorted by their path.
 	///
-	/// 	Ok(())
+	///     Ok(())
 	/// }
 	/// ```
-	fn read_dir<P: AsRef<path::Path>>(path: P) -> io::Result<ReadDir>;
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir>;
 
 	/// Reads a symbolic link, returning the file that the link points to.
 	///
@@ -419,11 +483,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	let path = fs::read_link("a.txt")?;
-	/// 	Ok(())
+	///     let path = fs::read_link("a.txt")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn read_link<P: AsRef<path::Path>>(path: P) -> io::Result<path::PathBuf>;
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf>;
 
 	/// Reads the entire contents of a file into a string.
 	///
@@ -446,16 +510,16 @@ pub trait Fs {
 	/// # Examples
 	///
 	/// ```no_run
-	/// use std::error::Error;
 	/// use std::fs;
+	/// use std::error::Error;
 	///
 	/// fn main() -> Result<(), Box<dyn Error>> {
-	/// 	let message: String = fs::read_to_string("message.txt")?;
-	/// 	println!("{}", message);
-	/// 	Ok(())
+	///     let message: String = fs::read_to_string("message.txt")?;
+	///     println!("{}", message);
+	///     Ok(())
 	/// }
 	/// ```
-	fn read_to_string<P: AsRef<path::Path>>(path: P) -> io::Result<String>;
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String>;
 
 	/// Removes an empty directory.
 	///
@@ -492,11 +556,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::remove_dir("/some/dir")?;
-	/// 	Ok(())
+	///     fs::remove_dir("/some/dir")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn remove_dir<P: AsRef<path::Path>>(path: P) -> io::Result<()>;
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()>;
 
 	/// Removes a directory at this path, after removing all its contents. Use
 	/// carefully!
@@ -506,25 +570,24 @@ pub trait Fs {
 	///
 	/// # Platform-specific behavior
 	///
-	/// This function currently corresponds to `openat`, `fdopendir`, `unlinkat` and `lstat`
-	/// functions on Unix (except for REDOX) and the `CreateFileW`, `GetFileInformationByHandleEx`,
+	/// This function currently corresponds to `openat`, `fdopendir`, `unlinkat` and `lstat` functions
+	/// on Unix (except for REDOX) and the `CreateFileW`, `GetFileInformationByHandleEx`,
 	/// `SetFileInformationByHandle`, and `NtCreateFile` functions on Windows. Note that, this
 	/// [may change in the future][changes].
 	///
 	/// [changes]: io#platform-specific-behavior
 	///
-	/// On REDOX, as well as when running in Miri for any target, this function is not protected
-	/// against time-of-check to time-of-use (TOCTOU) race conditions, and should not be used in
+	/// On REDOX, as well as when running in Miri for any target, this function is not protected against
+	/// time-of-check to time-of-use (TOCTOU) race conditions, and should not be used in
 	/// security-sensitive code on those platforms. All other platforms are protected.
 	///
 	/// # Errors
 	///
 	/// See [`fs::remove_file`] and [`fs::remove_dir`].
 	///
-	/// `remove_dir_all` will fail if `remove_dir` or `remove_file` fail on any constituent paths,
-	/// including the root `path`. As a result, the directory you are deleting must exist, meaning
-	/// that this function is not idempotent. Additionally, `remove_dir_all` will also fail if the
-	/// `path` is not a directory.
+	/// `remove_dir_all` will fail if `remove_dir` or `remove_file` fail on any constituent paths, including the root `path`.
+	/// As a result, the directory you are deleting must exist, meaning that this function is not idempotent.
+	/// Additionally, `remove_dir_all` will also fail if the `path` is not a directory.
 	///
 	/// Consider ignoring the error if validating the removal is not required for your use case.
 	///
@@ -539,11 +602,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::remove_dir_all("/some/dir")?;
-	/// 	Ok(())
+	///     fs::remove_dir_all("/some/dir")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn remove_dir_all<P: AsRef<path::Path>>(path: P) -> io::Result<()>;
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()>;
 
 	/// Removes a file from the filesystem.
 	///
@@ -579,11 +642,11 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::remove_file("a.txt")?;
-	/// 	Ok(())
+	///     fs::remove_file("a.txt")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn remove_file<P: AsRef<path::Path>>(path: P) -> io::Result<()>;
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()>;
 
 	/// Renames a file or directory to a new name, replacing the original file if
 	/// `to` already exists.
@@ -593,14 +656,12 @@ pub trait Fs {
 	/// # Platform-specific behavior
 	///
 	/// This function currently corresponds to the `rename` function on Unix
-	/// and the `SetFileInformationByHandle` function on Windows.
+	/// and the `MoveFileEx` function with the `MOVEFILE_REPLACE_EXISTING` flag on Windows.
 	///
 	/// Because of this, the behavior when both `from` and `to` exist differs. On
 	/// Unix, if `from` is a directory, `to` must also be an (empty) directory. If
-	/// `from` is not a directory, `to` must also be not a directory. The behavior
-	/// on Windows is the same on Windows 10 1607 and higher if `FileRenameInfoEx`
-	/// is supported by the filesystem; otherwise, `from` can be anything, but
-	/// `to` must *not* be a directory.
+	/// `from` is not a directory, `to` must also be not a directory. In contrast,
+	/// on Windows, `from` can be anything, but `to` must *not* be a directory.
 	///
 	/// Note that, this [may change in the future][changes].
 	///
@@ -621,11 +682,15 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::rename("a.txt", "b.txt")?; // Rename a.txt to b.txt
-	/// 	Ok(())
+	///     fs::rename("a.txt", "b.txt")?; // Rename a.txt to b.txt
+	///     Ok(())
 	/// }
 	/// ```
-	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(from: P, to: Q) -> io::Result<()>;
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<()>;
 
 	/// Changes the permissions found on a file or a directory.
 	///
@@ -651,13 +716,17 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	let mut perms = fs::metadata("foo.txt")?.permissions();
-	/// 	perms.set_readonly(true);
-	/// 	fs::set_permissions("foo.txt", perms)?;
-	/// 	Ok(())
+	///     let mut perms = fs::metadata("foo.txt")?.permissions();
+	///     perms.set_readonly(true);
+	///     fs::set_permissions("foo.txt", perms)?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn set_permissions<P: AsRef<path::Path>>(path: P, perm: Permissions) -> io::Result<()>;
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()>;
 
 	/// Queries the metadata about a file without following symlinks.
 	///
@@ -683,12 +752,15 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	let attr = fs::symlink_metadata("/some/file/path.txt")?;
-	/// 	// inspect attr ...
-	/// 	Ok(())
+	///     let attr = fs::symlink_metadata("/some/file/path.txt")?;
+	///     // inspect attr ...
+	///     Ok(())
 	/// }
 	/// ```
-	fn symlink_metadata<P: AsRef<path::Path>>(path: P) -> io::Result<Metadata>;
+	fn symlink_metadata<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+	) -> io::Result<Self::Metadata>;
 
 	/// Writes a slice as the entire contents of a file.
 	///
@@ -709,89 +781,136 @@ pub trait Fs {
 	/// use std::fs;
 	///
 	/// fn main() -> std::io::Result<()> {
-	/// 	fs::write("foo.txt", b"Lorem ipsum")?;
-	/// 	fs::write("bar.txt", "dolor sit")?;
-	/// 	Ok(())
+	///     fs::write("foo.txt", b"Lorem ipsum")?;
+	///     fs::write("bar.txt", "dolor sit")?;
+	///     Ok(())
 	/// }
 	/// ```
-	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()>;
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(
+		&self,
+		path: P,
+		contents: C,
+	) -> io::Result<()>;
 }
 
+#[derive(Clone, Copy, Debug, Default)]
 pub struct Native {}
 
 impl Fs for Native {
-	fn canonicalize<P: AsRef<path::Path>>(path: P) -> io::Result<path::PathBuf> {
+	type DirBuilder = std::fs::DirBuilder;
+	type DirEntry = std::fs::DirEntry;
+	type File = std::fs::File;
+	type FileTimes = std::fs::FileTimes;
+	type FileType = std::fs::FileType;
+	type Metadata = std::fs::Metadata;
+	type OpenOptions = std::fs::OpenOptions;
+	type Permissions = std::fs::Permissions;
+	type ReadDir = std::fs::ReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		std::fs::DirBuilder::new()
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		std::fs::FileTimes::new()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		std::fs::OpenOptions::new()
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
 		std::fs::canonicalize(path)
 	}
 
-	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(from: P, to: Q) -> io::Result<u64> {
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<u64> {
 		std::fs::copy(from, to)
 	}
 
-	fn create_dir<P: AsRef<path::Path>>(path: P) -> io::Result<()> {
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
 		std::fs::create_dir(path)
 	}
 
-	fn create_dir_all<P: AsRef<path::Path>>(path: P) -> io::Result<()> {
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
 		std::fs::create_dir_all(path)
 	}
 
-	fn exists<P: AsRef<path::Path>>(path: P) -> io::Result<bool> {
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
 		std::fs::exists(path)
 	}
 
 	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
 		original: P,
 		link: Q,
 	) -> io::Result<()> {
 		std::fs::hard_link(original, link)
 	}
 
-	fn metadata<P: AsRef<path::Path>>(path: P) -> io::Result<Metadata> {
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
 		std::fs::metadata(path)
 	}
 
-	fn read<P: AsRef<path::Path>>(path: P) -> io::Result<Vec<u8>> {
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
 		std::fs::read(path)
 	}
 
-	fn read_dir<P: AsRef<path::Path>>(path: P) -> io::Result<ReadDir> {
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
 		std::fs::read_dir(path)
 	}
 
-	fn read_link<P: AsRef<path::Path>>(path: P) -> io::Result<path::PathBuf> {
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
 		std::fs::read_link(path)
 	}
 
-	fn read_to_string<P: AsRef<path::Path>>(path: P) -> io::Result<String> {
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
 		std::fs::read_to_string(path)
 	}
 
-	fn remove_dir<P: AsRef<path::Path>>(path: P) -> io::Result<()> {
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
 		std::fs::remove_dir(path)
 	}
 
-	fn remove_dir_all<P: AsRef<path::Path>>(path: P) -> io::Result<()> {
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
 		std::fs::remove_dir_all(path)
 	}
 
-	fn remove_file<P: AsRef<path::Path>>(path: P) -> io::Result<()> {
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
 		std::fs::remove_file(path)
 	}
 
-	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(from: P, to: Q) -> io::Result<()> {
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<()> {
 		std::fs::rename(from, to)
 	}
 
-	fn set_permissions<P: AsRef<path::Path>>(path: P, perm: Permissions) -> io::Result<()> {
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
 		std::fs::set_permissions(path, perm)
 	}
 
-	fn symlink_metadata<P: AsRef<path::Path>>(path: P) -> io::Result<Metadata> {
+	fn symlink_metadata<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+	) -> io::Result<Self::Metadata> {
 		std::fs::symlink_metadata(path)
 	}
 
-	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(
+		&self,
+		path: P,
+		contents: C,
+	) -> io::Result<()> {
 		std::fs::write(path, contents)
 	}
 }
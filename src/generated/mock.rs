@@ -0,0 +1,350 @@
+// This file is auto-generated. DO NOT edit by hand. See README.md for more details.
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+use std::io;
+use std::path;
+use super::functions::Fs;
+use crate::Expectation;
+use crate::Expectations;
+use crate::MockReport;
+
+/// A scripted [`Fs`] answering calls according to [`Expectation`]s set up in advance.
+///
+/// Each method of [`Fs`] has an `expect_` counterpart adding an expectation for it. A call is
+/// answered by the first expectation of its method that accepts its arguments and has calls left,
+/// and fails with [`io::ErrorKind::Other`] if there is none. [`verify`](Self::verify) then
+/// reports the expectations that were not met, along with the calls that matched none.
+///
+/// Handles such as [`Fs::OpenOptions`] are not scripted, and come from the backend the mock is
+/// created with.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Expectation;
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::MockFs;
+///
+/// let fs = MockFs::new(MemFs::new());
+/// let config = Expectation::ok("verbose = true".to_owned());
+/// fs.expect_read_to_string(config.with("app.toml".into()));
+/// fs.expect_write(Expectation::err(io::ErrorKind::StorageFull).times(2));
+///
+/// assert_eq!(fs.read_to_string("app.toml")?, "verbose = true");
+/// assert!(fs.write("app.log", "started").is_err());
+/// let report = fs.verify().unwrap_err();
+/// assert_eq!(report.unmet, ["write(..) expected exactly 2 calls, got 1"]);
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MockFs<F: Fs> {
+	backend: F,
+	canonicalize: Expectations<path::PathBuf, path::PathBuf>,
+	copy: Expectations<(path::PathBuf, path::PathBuf), u64>,
+	create_dir: Expectations<path::PathBuf, ()>,
+	create_dir_all: Expectations<path::PathBuf, ()>,
+	exists: Expectations<path::PathBuf, bool>,
+	hard_link: Expectations<(path::PathBuf, path::PathBuf), ()>,
+	metadata: Expectations<path::PathBuf, F::Metadata>,
+	read: Expectations<path::PathBuf, Vec<u8>>,
+	read_dir: Expectations<path::PathBuf, F::ReadDir>,
+	read_link: Expectations<path::PathBuf, path::PathBuf>,
+	read_to_string: Expectations<path::PathBuf, String>,
+	remove_dir: Expectations<path::PathBuf, ()>,
+	remove_dir_all: Expectations<path::PathBuf, ()>,
+	remove_file: Expectations<path::PathBuf, ()>,
+	rename: Expectations<(path::PathBuf, path::PathBuf), ()>,
+	set_permissions: Expectations<(path::PathBuf, F::Permissions), ()>,
+	symlink_metadata: Expectations<path::PathBuf, F::Metadata>,
+	write: Expectations<(path::PathBuf, Vec<u8>), ()>,
+}
+
+impl<F: Fs> MockFs<F> {
+	/// Creates a mock without expectations, taking handles from `backend`.
+	pub fn new(backend: F) -> Self {
+		Self {
+			backend,
+			canonicalize: Expectations::new("canonicalize"),
+			copy: Expectations::new("copy"),
+			create_dir: Expectations::new("create_dir"),
+			create_dir_all: Expectations::new("create_dir_all"),
+			exists: Expectations::new("exists"),
+			hard_link: Expectations::new("hard_link"),
+			metadata: Expectations::new("metadata"),
+			read: Expectations::new("read"),
+			read_dir: Expectations::new("read_dir"),
+			read_link: Expectations::new("read_link"),
+			read_to_string: Expectations::new("read_to_string"),
+			remove_dir: Expectations::new("remove_dir"),
+			remove_dir_all: Expectations::new("remove_dir_all"),
+			remove_file: Expectations::new("remove_file"),
+			rename: Expectations::new("rename"),
+			set_permissions: Expectations::new("set_permissions"),
+			symlink_metadata: Expectations::new("symlink_metadata"),
+			write: Expectations::new("write"),
+		}
+	}
+
+	/// Expects calls to [`Fs::canonicalize`].
+	pub fn expect_canonicalize(
+		&self,
+		expectation: Expectation<path::PathBuf, path::PathBuf>,
+	) {
+		self.canonicalize.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::copy`].
+	pub fn expect_copy(
+		&self,
+		expectation: Expectation<(path::PathBuf, path::PathBuf), u64>,
+	) {
+		self.copy.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::create_dir`].
+	pub fn expect_create_dir(&self, expectation: Expectation<path::PathBuf, ()>) {
+		self.create_dir.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::create_dir_all`].
+	pub fn expect_create_dir_all(&self, expectation: Expectation<path::PathBuf, ()>) {
+		self.create_dir_all.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::exists`].
+	pub fn expect_exists(&self, expectation: Expectation<path::PathBuf, bool>) {
+		self.exists.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::hard_link`].
+	pub fn expect_hard_link(
+		&self,
+		expectation: Expectation<(path::PathBuf, path::PathBuf), ()>,
+	) {
+		self.hard_link.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::metadata`].
+	pub fn expect_metadata(&self, expectation: Expectation<path::PathBuf, F::Metadata>) {
+		self.metadata.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::read`].
+	pub fn expect_read(&self, expectation: Expectation<path::PathBuf, Vec<u8>>) {
+		self.read.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::read_dir`].
+	pub fn expect_read_dir(&self, expectation: Expectation<path::PathBuf, F::ReadDir>) {
+		self.read_dir.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::read_link`].
+	pub fn expect_read_link(
+		&self,
+		expectation: Expectation<path::PathBuf, path::PathBuf>,
+	) {
+		self.read_link.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::read_to_string`].
+	pub fn expect_read_to_string(
+		&self,
+		expectation: Expectation<path::PathBuf, String>,
+	) {
+		self.read_to_string.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::remove_dir`].
+	pub fn expect_remove_dir(&self, expectation: Expectation<path::PathBuf, ()>) {
+		self.remove_dir.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::remove_dir_all`].
+	pub fn expect_remove_dir_all(&self, expectation: Expectation<path::PathBuf, ()>) {
+		self.remove_dir_all.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::remove_file`].
+	pub fn expect_remove_file(&self, expectation: Expectation<path::PathBuf, ()>) {
+		self.remove_file.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::rename`].
+	pub fn expect_rename(
+		&self,
+		expectation: Expectation<(path::PathBuf, path::PathBuf), ()>,
+	) {
+		self.rename.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::set_permissions`].
+	pub fn expect_set_permissions(
+		&self,
+		expectation: Expectation<(path::PathBuf, F::Permissions), ()>,
+	) {
+		self.set_permissions.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::symlink_metadata`].
+	pub fn expect_symlink_metadata(
+		&self,
+		expectation: Expectation<path::PathBuf, F::Metadata>,
+	) {
+		self.symlink_metadata.push(expectation);
+	}
+
+	/// Expects calls to [`Fs::write`].
+	pub fn expect_write(&self, expectation: Expectation<(path::PathBuf, Vec<u8>), ()>) {
+		self.write.push(expectation);
+	}
+
+	/// Returns the expectations that were not met and the calls that matched none, if any.
+	pub fn verify(&self) -> Result<(), MockReport> {
+		let mut report = MockReport::default();
+		self.canonicalize.verify(&mut report);
+		self.copy.verify(&mut report);
+		self.create_dir.verify(&mut report);
+		self.create_dir_all.verify(&mut report);
+		self.exists.verify(&mut report);
+		self.hard_link.verify(&mut report);
+		self.metadata.verify(&mut report);
+		self.read.verify(&mut report);
+		self.read_dir.verify(&mut report);
+		self.read_link.verify(&mut report);
+		self.read_to_string.verify(&mut report);
+		self.remove_dir.verify(&mut report);
+		self.remove_dir_all.verify(&mut report);
+		self.remove_file.verify(&mut report);
+		self.rename.verify(&mut report);
+		self.set_permissions.verify(&mut report);
+		self.symlink_metadata.verify(&mut report);
+		self.write.verify(&mut report);
+		report.into_result()
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.backend
+	}
+}
+
+impl<F: Fs> Fs for MockFs<F> {
+	type DirBuilder = F::DirBuilder;
+	type DirEntry = F::DirEntry;
+	type File = F::File;
+	type FileTimes = F::FileTimes;
+	type FileType = F::FileType;
+	type Metadata = F::Metadata;
+	type OpenOptions = F::OpenOptions;
+	type Permissions = F::Permissions;
+	type ReadDir = F::ReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		self.backend.dir_builder()
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.backend.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		self.backend.open_options()
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.canonicalize.call(path.as_ref().to_owned())
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<u64> {
+		self.copy.call((from.as_ref().to_owned(), to.as_ref().to_owned()))
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.create_dir.call(path.as_ref().to_owned())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.create_dir_all.call(path.as_ref().to_owned())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.exists.call(path.as_ref().to_owned())
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		self.hard_link.call((original.as_ref().to_owned(), link.as_ref().to_owned()))
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.metadata.call(path.as_ref().to_owned())
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		self.read.call(path.as_ref().to_owned())
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		self.read_dir.call(path.as_ref().to_owned())
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.read_link.call(path.as_ref().to_owned())
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		self.read_to_string.call(path.as_ref().to_owned())
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.remove_dir.call(path.as_ref().to_owned())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.remove_dir_all.call(path.as_ref().to_owned())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.remove_file.call(path.as_ref().to_owned())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		from: P,
+		to: Q,
+	) -> io::Result<()> {
+		self.rename.call((from.as_ref().to_owned(), to.as_ref().to_owned()))
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		self.set_permissions.call((path.as_ref().to_owned(), perm))
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+	) -> io::Result<Self::Metadata> {
+		self.symlink_metadata.call(path.as_ref().to_owned())
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(
+		&self,
+		path: P,
+		contents: C,
+	) -> io::Result<()> {
+		self.write.call((path.as_ref().to_owned(), contents.as_ref().to_owned()))
+	}
+}
@@ -1,4 +1,14 @@
+// This file is auto-generated. DO NOT edit by hand. See README.md for more details.
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+mod async_fs;
+pub use async_fs::*;
+mod dynamic;
+pub use dynamic::*;
 mod functions;
 pub use functions::*;
+mod mock;
+pub use mock::*;
 mod structs;
 pub use structs::*;
+pub mod unix;
+pub mod windows;
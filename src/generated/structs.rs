@@ -1,17 +1,61 @@
 // This file is auto-generated. DO NOT edit by hand. See README.md for more details.
-#![allow(clippy::tabs_in_doc_comments)]
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// A builder used to create directories in various manners.
 ///
 /// This builder also supports platform-specific options.
-pub trait DirBuilder {
-	// fn new() -> DirBuilder;
-	// fn recursive(self: &mut Self, recursive: bool, ) -> &mut Self;
-	// fn create<P: AsRef<Path,> + , >(self: &Self, path: P, ) -> io::Result<(),>;
-	// impl core::fmt::Debug
+pub trait DirBuilder: Sized + std::fmt::Debug {
+	/// Indicates that directories should be created recursively, creating all
+	/// parent directories. Parents that do not exist are created with the same
+	/// security and permissions settings.
+	///
+	/// This option defaults to `false`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::fs::DirBuilder;
+	///
+	/// let mut builder = DirBuilder::new();
+	/// builder.recursive(true);
+	/// ```
+	fn recursive(&mut self, recursive: bool) -> &mut Self;
+
+	/// Creates the specified directory with the options configured in this
+	/// builder.
+	///
+	/// It is considered an error if the directory already exists unless
+	/// recursive mode is enabled.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::{self, DirBuilder};
+	///
+	/// let path = "/tmp/foo/bar/baz";
+	/// DirBuilder::new()
+	///     .recursive(true)
+	///     .create(path).unwrap();
+	///
+	/// assert!(fs::metadata(path).unwrap().is_dir());
+	/// ```
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()>;
 }
 
-// impl DirBuilder for std::fs::DirBuilder {}
+impl DirBuilder for std::fs::DirBuilder {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		std::fs::DirBuilder::recursive(self, recursive)
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		std::fs::DirBuilder::create(self, path)
+	}
+}
 
 /// Entries returned by the [`ReadDir`] iterator.
 ///
@@ -28,15 +72,156 @@ pub trait DirBuilder {
 /// Note that this [may change in the future][changes].
 ///
 /// [changes]: io#platform-specific-behavior
-pub trait DirEntry {
-	// fn path(self: &Self, ) -> PathBuf;
-	// fn metadata(self: &Self, ) -> io::Result<Metadata,>;
-	// fn file_type(self: &Self, ) -> io::Result<FileType,>;
-	// fn file_name(self: &Self, ) -> OsString;
-	// impl core::fmt::Debug
+pub trait DirEntry: Sized + std::fmt::Debug {
+	/// The backend counterpart of [`std::fs::FileType`].
+	type FileType: FileType;
+
+	/// The backend counterpart of [`std::fs::Metadata`].
+	type Metadata: Metadata<FileType = Self::FileType>;
+
+	/// Returns the full path to the file that this entry represents.
+	///
+	/// The full path is created by joining the original path to `read_dir`
+	/// with the filename of this entry.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     for entry in fs::read_dir(".")? {
+	///         let dir = entry?;
+	///         println!("{:?}", dir.path());
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	///
+	/// This prints output like:
+	///
+	/// ```text
+	/// "./whatever.txt"
+	/// "./foo.html"
+	/// "./hello_world.rs"
+	/// ```
+	///
+	/// The exact text, of course, depends on what files you have in `.`.
+	fn path(&self) -> PathBuf;
+
+	/// Returns the metadata for the file that this entry points at.
+	///
+	/// This function will not traverse symlinks if this entry points at a
+	/// symlink. To traverse symlinks use [`fs::metadata`] or [`fs::File::metadata`].
+	///
+	/// [`fs::metadata`]: metadata
+	/// [`fs::File::metadata`]: File::metadata
+	///
+	/// # Platform-specific behavior
+	///
+	/// On Windows this function is cheap to call (no extra system calls
+	/// needed), but on Unix platforms this function is the equivalent of
+	/// calling `symlink_metadata` on the path.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::fs;
+	///
+	/// if let Ok(entries) = fs::read_dir(".") {
+	///     for entry in entries {
+	///         if let Ok(entry) = entry {
+	///             // Here, `entry` is a `DirEntry`.
+	///             if let Ok(metadata) = entry.metadata() {
+	///                 // Now let's show our entry's permissions!
+	///                 println!("{:?}: {:?}", entry.path(), metadata.permissions());
+	///             } else {
+	///                 println!("Couldn't get metadata for {:?}", entry.path());
+	///             }
+	///         }
+	///     }
+	/// }
+	/// ```
+	fn metadata(&self) -> io::Result<Self::Metadata>;
+
+	/// Returns the file type for the file that this entry points at.
+	///
+	/// This function will not traverse symlinks if this entry points at a
+	/// symlink.
+	///
+	/// # Platform-specific behavior
+	///
+	/// On Windows and most Unix platforms this function is free (no extra
+	/// system calls needed), but some Unix platforms may require the equivalent
+	/// call to `symlink_metadata` to learn about the target file type.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::fs;
+	///
+	/// if let Ok(entries) = fs::read_dir(".") {
+	///     for entry in entries {
+	///         if let Ok(entry) = entry {
+	///             // Here, `entry` is a `DirEntry`.
+	///             if let Ok(file_type) = entry.file_type() {
+	///                 // Now let's show our entry's file type!
+	///                 println!("{:?}: {:?}", entry.path(), file_type);
+	///             } else {
+	///                 println!("Couldn't get file type for {:?}", entry.path());
+	///             }
+	///         }
+	///     }
+	/// }
+	/// ```
+	fn file_type(&self) -> io::Result<Self::FileType>;
+
+	/// Returns the file name of this directory entry without any
+	/// leading path component(s).
+	///
+	/// As an example,
+	/// the output of the function will result in "foo" for all the following paths:
+	/// - "./foo"
+	/// - "/the/foo"
+	/// - "../../foo"
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::fs;
+	///
+	/// if let Ok(entries) = fs::read_dir(".") {
+	///     for entry in entries {
+	///         if let Ok(entry) = entry {
+	///             // Here, `entry` is a `DirEntry`.
+	///             println!("{:?}", entry.file_name());
+	///         }
+	///     }
+	/// }
+	/// ```
+	fn file_name(&self) -> OsString;
 }
 
-// impl DirEntry for std::fs::DirEntry {}
+impl DirEntry for std::fs::DirEntry {
+	type FileType = std::fs::FileType;
+	type Metadata = std::fs::Metadata;
+
+	fn path(&self) -> PathBuf {
+		std::fs::DirEntry::path(self)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		std::fs::DirEntry::metadata(self)
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		std::fs::DirEntry::file_type(self)
+	}
+
+	fn file_name(&self) -> OsString {
+		std::fs::DirEntry::file_name(self)
+	}
+}
 
 /// An object providing access to an open file on the filesystem.
 ///
@@ -61,9 +246,9 @@ pub trait DirEntry {
 /// use std::io::prelude::*;
 ///
 /// fn main() -> std::io::Result<()> {
-/// 	let mut file = File::create("foo.txt")?;
-/// 	file.write_all(b"Hello, world!")?;
-/// 	Ok(())
+///     let mut file = File::create("foo.txt")?;
+///     file.write_all(b"Hello, world!")?;
+///     Ok(())
 /// }
 /// ```
 ///
@@ -74,11 +259,11 @@ pub trait DirEntry {
 /// use std::io::prelude::*;
 ///
 /// fn main() -> std::io::Result<()> {
-/// 	let mut file = File::open("foo.txt")?;
-/// 	let mut contents = String::new();
-/// 	file.read_to_string(&mut contents)?;
-/// 	assert_eq!(contents, "Hello, world!");
-/// 	Ok(())
+///     let mut file = File::open("foo.txt")?;
+///     let mut contents = String::new();
+///     file.read_to_string(&mut contents)?;
+///     assert_eq!(contents, "Hello, world!");
+///     Ok(())
 /// }
 /// ```
 ///
@@ -86,16 +271,16 @@ pub trait DirEntry {
 ///
 /// ```no_run
 /// use std::fs::File;
-/// use std::io::prelude::*;
 /// use std::io::BufReader;
+/// use std::io::prelude::*;
 ///
 /// fn main() -> std::io::Result<()> {
-/// 	let file = File::open("foo.txt")?;
-/// 	let mut buf_reader = BufReader::new(file);
-/// 	let mut contents = String::new();
-/// 	buf_reader.read_to_string(&mut contents)?;
-/// 	assert_eq!(contents, "Hello, world!");
-/// 	Ok(())
+///     let file = File::open("foo.txt")?;
+///     let mut buf_reader = BufReader::new(file);
+///     let mut contents = String::new();
+///     buf_reader.read_to_string(&mut contents)?;
+///     assert_eq!(contents, "Hello, world!");
+///     Ok(())
 /// }
 /// ```
 ///
@@ -118,68 +303,399 @@ pub trait DirEntry {
 /// [`sync_all`]: File::sync_all
 /// [`write`]: File::write
 /// [`read`]: File::read
-pub trait File {
-	// fn open<P: AsRef<Path,> + , >(path: P, ) -> io::Result<File,>;
-	// fn open_buffered<P: AsRef<Path,> + , >(path: P, ) -> io::Result<io::BufReader<File,>,>;
-	// fn create<P: AsRef<Path,> + , >(path: P, ) -> io::Result<File,>;
-	// fn create_buffered<P: AsRef<Path,> + , >(path: P, ) -> io::Result<io::BufWriter<File,>,>;
-	// fn create_new<P: AsRef<Path,> + , >(path: P, ) -> io::Result<File,>;
-	// fn options() -> OpenOptions;
-	// fn sync_all(self: &Self, ) -> io::Result<(),>;
-	// fn sync_data(self: &Self, ) -> io::Result<(),>;
-	// fn lock(self: &Self, ) -> io::Result<(),>;
-	// fn lock_shared(self: &Self, ) -> io::Result<(),>;
-	// fn try_lock(self: &Self, ) -> io::Result<bool,>;
-	// fn try_lock_shared(self: &Self, ) -> io::Result<bool,>;
-	// fn unlock(self: &Self, ) -> io::Result<(),>;
-	// fn set_len(self: &Self, size: u64, ) -> io::Result<(),>;
-	// fn metadata(self: &Self, ) -> io::Result<Metadata,>;
-	// fn try_clone(self: &Self, ) -> io::Result<File,>;
-	// fn set_permissions(self: &Self, perm: Permissions, ) -> io::Result<(),>;
-	// fn set_times(self: &Self, times: FileTimes, ) -> io::Result<(),>;
-	// fn set_modified(self: &Self, time: SystemTime, ) -> io::Result<(),>;
-	// impl core::fmt::Debug
-	// impl std::io::Read
-	// impl std::io::Write
-	// impl std::io::Seek
-	// impl std::io::Read
-	// impl std::io::Write
-	// impl std::io::Seek
-	// impl IsTerminal
-	// impl core::convert::From<File,>
+pub trait File: Sized + std::fmt::Debug + io::Read + io::Seek + io::Write {
+	/// The backend counterpart of [`std::fs::FileTimes`].
+	type FileTimes: FileTimes;
+
+	/// The backend counterpart of [`std::fs::Metadata`].
+	type Metadata: Metadata<Permissions = Self::Permissions>;
+
+	/// The backend counterpart of [`std::fs::Permissions`].
+	type Permissions: Permissions;
+
+	/// Attempts to sync all OS-internal file content and metadata to disk.
+	///
+	/// This function will attempt to ensure that all in-memory data reaches the
+	/// filesystem before returning.
+	///
+	/// This can be used to handle errors that would otherwise only be caught
+	/// when the `File` is closed, as dropping a `File` will ignore all errors.
+	/// Note, however, that `sync_all` is generally more expensive than closing
+	/// a file by dropping it, because the latter is not required to block until
+	/// the data has been written to the filesystem.
+	///
+	/// If synchronizing the metadata is not required, use [`sync_data`] instead.
+	///
+	/// [`sync_data`]: File::sync_data
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	/// use std::io::prelude::*;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut f = File::create("foo.txt")?;
+	///     f.write_all(b"Hello, world!")?;
+	///
+	///     f.sync_all()?;
+	///     Ok(())
+	/// }
+	/// ```
+	fn sync_all(&self) -> io::Result<()>;
+
+	/// This function is similar to [`sync_all`], except that it might not
+	/// synchronize file metadata to the filesystem.
+	///
+	/// This is intended for use cases that must synchronize content, but don't
+	/// need the metadata on disk. The goal of this method is to reduce disk
+	/// operations.
+	///
+	/// Note that some platforms may simply implement this in terms of
+	/// [`sync_all`].
+	///
+	/// [`sync_all`]: File::sync_all
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	/// use std::io::prelude::*;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut f = File::create("foo.txt")?;
+	///     f.write_all(b"Hello, world!")?;
+	///
+	///     f.sync_data()?;
+	///     Ok(())
+	/// }
+	/// ```
+	fn sync_data(&self) -> io::Result<()>;
+
+	/// Truncates or extends the underlying file, updating the size of
+	/// this file to become `size`.
+	///
+	/// If the `size` is less than the current file's size, then the file will
+	/// be shrunk. If it is greater than the current file's size, then the file
+	/// will be extended to `size` and have all of the intermediate data filled
+	/// in with 0s.
+	///
+	/// The file's cursor isn't changed. In particular, if the cursor was at the
+	/// end and the file is shrunk using this operation, the cursor will now be
+	/// past the end.
+	///
+	/// # Errors
+	///
+	/// This function will return an error if the file is not opened for writing.
+	/// Also, [`std::io::ErrorKind::InvalidInput`](crate::io::ErrorKind::InvalidInput)
+	/// will be returned if the desired length would cause an overflow due to
+	/// the implementation specifics.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut f = File::create("foo.txt")?;
+	///     f.set_len(10)?;
+	///     Ok(())
+	/// }
+	/// ```
+	///
+	/// Note that this method alters the content of the underlying file, even
+	/// though it takes `&self` rather than `&mut self`.
+	fn set_len(&self, size: u64) -> io::Result<()>;
+
+	/// Queries metadata about the underlying file.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut f = File::open("foo.txt")?;
+	///     let metadata = f.metadata()?;
+	///     Ok(())
+	/// }
+	/// ```
+	fn metadata(&self) -> io::Result<Self::Metadata>;
+
+	/// Creates a new `File` instance that shares the same underlying file handle
+	/// as the existing `File` instance. Reads, writes, and seeks will affect
+	/// both `File` instances simultaneously.
+	///
+	/// # Examples
+	///
+	/// Creates two handles for a file named `foo.txt`:
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut file = File::open("foo.txt")?;
+	///     let file_copy = file.try_clone()?;
+	///     Ok(())
+	/// }
+	/// ```
+	///
+	/// Assuming there’s a file named `foo.txt` with contents `abcdef\n`, create
+	/// two handles, seek one of them, and read the remaining bytes from the
+	/// other handle:
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	/// use std::io::SeekFrom;
+	/// use std::io::prelude::*;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut file = File::open("foo.txt")?;
+	///     let mut file_copy = file.try_clone()?;
+	///
+	///     file.seek(SeekFrom::Start(3))?;
+	///
+	///     let mut contents = vec![];
+	///     file_copy.read_to_end(&mut contents)?;
+	///     assert_eq!(contents, b"def\n");
+	///     Ok(())
+	/// }
+	/// ```
+	fn try_clone(&self) -> io::Result<Self>;
+
+	/// Changes the permissions on the underlying file.
+	///
+	/// # Platform-specific behavior
+	///
+	/// This function currently corresponds to the `fchmod` function on Unix and
+	/// the `SetFileInformationByHandle` function on Windows. Note that, this
+	/// [may change in the future][changes].
+	///
+	/// [changes]: io#platform-specific-behavior
+	///
+	/// # Errors
+	///
+	/// This function will return an error if the user lacks permission change
+	/// attributes on the underlying file. It may also return an error in other
+	/// os-specific unspecified cases.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs::File;
+	///
+	///     let file = File::open("foo.txt")?;
+	///     let mut perms = file.metadata()?.permissions();
+	///     perms.set_readonly(true);
+	///     file.set_permissions(perms)?;
+	///     Ok(())
+	/// }
+	/// ```
+	///
+	/// Note that this method alters the permissions of the underlying file,
+	/// even though it takes `&self` rather than `&mut self`.
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()>;
+
+	/// Changes the timestamps of the underlying file.
+	///
+	/// # Platform-specific behavior
+	///
+	/// This function currently corresponds to the `futimens` function on Unix (falling back to
+	/// `futimes` on macOS before 10.13) and the `SetFileTime` function on Windows. Note that this
+	/// [may change in the future][changes].
+	///
+	/// [changes]: io#platform-specific-behavior
+	///
+	/// # Errors
+	///
+	/// This function will return an error if the user lacks permission to change timestamps on the
+	/// underlying file. It may also return an error in other os-specific unspecified cases.
+	///
+	/// This function may return an error if the operating system lacks support to change one or
+	/// more of the timestamps set in the `FileTimes` structure.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs::{self, File, FileTimes};
+	///
+	///     let src = fs::metadata("src")?;
+	///     let dest = File::options().write(true).open("dest")?;
+	///     let times = FileTimes::new()
+	///         .set_accessed(src.accessed()?)
+	///         .set_modified(src.modified()?);
+	///     dest.set_times(times)?;
+	///     Ok(())
+	/// }
+	/// ```
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()>;
+
+	/// Changes the modification time of the underlying file.
+	///
+	/// This is an alias for `set_times(FileTimes::new().set_modified(time))`.
+	fn set_modified(&self, time: SystemTime) -> io::Result<()>;
 }
 
-// impl File for std::fs::File {}
+impl File for std::fs::File {
+	type FileTimes = std::fs::FileTimes;
+	type Metadata = std::fs::Metadata;
+	type Permissions = std::fs::Permissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		std::fs::File::sync_all(self)
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		std::fs::File::sync_data(self)
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		std::fs::File::set_len(self, size)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		std::fs::File::metadata(self)
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		std::fs::File::try_clone(self)
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		std::fs::File::set_permissions(self, perm)
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		std::fs::File::set_times(self, times)
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		std::fs::File::set_modified(self, time)
+	}
+}
 
 /// Representation of the various timestamps on a file.
-pub trait FileTimes {
-	// fn new() -> Self;
-	// fn set_accessed(self: Self, t: SystemTime, ) -> Self;
-	// fn set_modified(self: Self, t: SystemTime, ) -> Self;
-	// impl core::marker::Copy
-	// impl core::clone::Clone
-	// impl core::fmt::Debug
-	// impl core::default::Default
-	// impl std::os::darwin::fs::FileTimesExt
+pub trait FileTimes: Sized + Clone + std::fmt::Debug {
+	/// Set the last access time of a file.
+	fn set_accessed(self, t: SystemTime) -> Self;
+
+	/// Set the last modified time of a file.
+	fn set_modified(self, t: SystemTime) -> Self;
 }
 
-// impl FileTimes for std::fs::FileTimes {}
+impl FileTimes for std::fs::FileTimes {
+	fn set_accessed(self, t: SystemTime) -> Self {
+		std::fs::FileTimes::set_accessed(self, t)
+	}
+
+	fn set_modified(self, t: SystemTime) -> Self {
+		std::fs::FileTimes::set_modified(self, t)
+	}
+}
 
 /// A structure representing a type of file with accessors for each file type.
 /// It is returned by [`Metadata::file_type`] method.
-pub trait FileType {
-	// fn is_dir(self: &Self, ) -> bool;
-	// fn is_file(self: &Self, ) -> bool;
-	// fn is_symlink(self: &Self, ) -> bool;
-	// impl core::marker::Copy
-	// impl core::clone::Clone
-	// impl core::cmp::PartialEq
-	// impl core::cmp::Eq
-	// impl core::hash::Hash
-	// impl core::fmt::Debug
+pub trait FileType: Sized + Clone + std::fmt::Debug {
+	/// Tests whether this file type represents a directory. The
+	/// result is mutually exclusive to the results of
+	/// [`is_file`] and [`is_symlink`]; only zero or one of these
+	/// tests may pass.
+	///
+	/// [`is_file`]: FileType::is_file
+	/// [`is_symlink`]: FileType::is_symlink
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs;
+	///
+	///     let metadata = fs::metadata("foo.txt")?;
+	///     let file_type = metadata.file_type();
+	///
+	///     assert_eq!(file_type.is_dir(), false);
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_dir(&self) -> bool;
+
+	/// Tests whether this file type represents a regular file.
+	/// The result is mutually exclusive to the results of
+	/// [`is_dir`] and [`is_symlink`]; only zero or one of these
+	/// tests may pass.
+	///
+	/// When the goal is simply to read from (or write to) the source, the most
+	/// reliable way to test the source can be read (or written to) is to open
+	/// it. Only using `is_file` can break workflows like `diff <( prog_a )` on
+	/// a Unix-like system for example. See [`File::open`] or
+	/// [`OpenOptions::open`] for more information.
+	///
+	/// [`is_dir`]: FileType::is_dir
+	/// [`is_symlink`]: FileType::is_symlink
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs;
+	///
+	///     let metadata = fs::metadata("foo.txt")?;
+	///     let file_type = metadata.file_type();
+	///
+	///     assert_eq!(file_type.is_file(), true);
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_file(&self) -> bool;
+
+	/// Tests whether this file type represents a symbolic link.
+	/// The result is mutually exclusive to the results of
+	/// [`is_dir`] and [`is_file`]; only zero or one of these
+	/// tests may pass.
+	///
+	/// The underlying [`Metadata`] struct needs to be retrieved
+	/// with the [`fs::symlink_metadata`] function and not the
+	/// [`fs::metadata`] function. The [`fs::metadata`] function
+	/// follows symbolic links, so [`is_symlink`] would always
+	/// return `false` for the target file.
+	///
+	/// [`fs::metadata`]: metadata
+	/// [`fs::symlink_metadata`]: symlink_metadata
+	/// [`is_dir`]: FileType::is_dir
+	/// [`is_file`]: FileType::is_file
+	/// [`is_symlink`]: FileType::is_symlink
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::symlink_metadata("foo.txt")?;
+	///     let file_type = metadata.file_type();
+	///
+	///     assert_eq!(file_type.is_symlink(), false);
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_symlink(&self) -> bool;
 }
 
-// impl FileType for std::fs::FileType {}
+impl FileType for std::fs::FileType {
+	fn is_dir(&self) -> bool {
+		std::fs::FileType::is_dir(self)
+	}
+
+	fn is_file(&self) -> bool {
+		std::fs::FileType::is_file(self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		std::fs::FileType::is_symlink(self)
+	}
+}
 
 /// Metadata information about a file.
 ///
@@ -187,22 +703,256 @@ pub trait FileType {
 /// [`symlink_metadata`] function or method and represents known
 /// metadata about a file such as its permissions, size, modification
 /// times, etc.
-pub trait Metadata {
-	// fn file_type(self: &Self, ) -> FileType;
-	// fn is_dir(self: &Self, ) -> bool;
-	// fn is_file(self: &Self, ) -> bool;
-	// fn is_symlink(self: &Self, ) -> bool;
-	// fn len(self: &Self, ) -> u64;
-	// fn permissions(self: &Self, ) -> Permissions;
-	// fn modified(self: &Self, ) -> io::Result<SystemTime,>;
-	// fn accessed(self: &Self, ) -> io::Result<SystemTime,>;
-	// fn created(self: &Self, ) -> io::Result<SystemTime,>;
-	// impl core::clone::Clone
-	// impl core::fmt::Debug
-	// impl std::os::darwin::fs::MetadataExt
+pub trait Metadata: Sized + Clone + std::fmt::Debug {
+	/// The backend counterpart of [`std::fs::FileType`].
+	type FileType: FileType;
+
+	/// The backend counterpart of [`std::fs::Permissions`].
+	type Permissions: Permissions;
+
+	/// Returns the file type for this metadata.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs;
+	///
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     println!("{:?}", metadata.file_type());
+	///     Ok(())
+	/// }
+	/// ```
+	fn file_type(&self) -> Self::FileType;
+
+	/// Returns `true` if this metadata is for a directory. The
+	/// result is mutually exclusive to the result of
+	/// [`Metadata::is_file`], and will be false for symlink metadata
+	/// obtained from [`symlink_metadata`].
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// fn main() -> std::io::Result<()> {
+	///     use std::fs;
+	///
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     assert!(!metadata.is_dir());
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_dir(&self) -> bool;
+
+	/// Returns `true` if this metadata is for a regular file. The
+	/// result is mutually exclusive to the result of
+	/// [`Metadata::is_dir`], and will be false for symlink metadata
+	/// obtained from [`symlink_metadata`].
+	///
+	/// When the goal is simply to read from (or write to) the source, the most
+	/// reliable way to test the source can be read (or written to) is to open
+	/// it. Only using `is_file` can break workflows like `diff <( prog_a )` on
+	/// a Unix-like system for example. See [`File::open`] or
+	/// [`OpenOptions::open`] for more information.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     assert!(metadata.is_file());
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_file(&self) -> bool;
+
+	/// Returns `true` if this metadata is for a symbolic link.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	/// use std::path::Path;
+	/// use std::os::unix::fs::symlink;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let link_path = Path::new("link");
+	///     symlink("/origin_does_not_exist/", link_path)?;
+	///
+	///     let metadata = fs::symlink_metadata(link_path)?;
+	///
+	///     assert!(metadata.is_symlink());
+	///     Ok(())
+	/// }
+	/// ```
+	fn is_symlink(&self) -> bool;
+
+	/// Returns the size of the file, in bytes, this metadata is for.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     assert_eq!(0, metadata.len());
+	///     Ok(())
+	/// }
+	/// ```
+	fn len(&self) -> u64;
+
+	/// Returns the permissions of the file this metadata is for.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     assert!(!metadata.permissions().readonly());
+	///     Ok(())
+	/// }
+	/// ```
+	fn permissions(&self) -> Self::Permissions;
+
+	/// Returns the last modification time listed in this metadata.
+	///
+	/// The returned value corresponds to the `mtime` field of `stat` on Unix
+	/// platforms and the `ftLastWriteTime` field on Windows platforms.
+	///
+	/// # Errors
+	///
+	/// This field might not be available on all platforms, and will return an
+	/// `Err` on platforms where it is not available.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     if let Ok(time) = metadata.modified() {
+	///         println!("{time:?}");
+	///     } else {
+	///         println!("Not supported on this platform");
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	fn modified(&self) -> io::Result<SystemTime>;
+
+	/// Returns the last access time of this metadata.
+	///
+	/// The returned value corresponds to the `atime` field of `stat` on Unix
+	/// platforms and the `ftLastAccessTime` field on Windows platforms.
+	///
+	/// Note that not all platforms will keep this field update in a file's
+	/// metadata, for example Windows has an option to disable updating this
+	/// time when files are accessed and Linux similarly has `noatime`.
+	///
+	/// # Errors
+	///
+	/// This field might not be available on all platforms, and will return an
+	/// `Err` on platforms where it is not available.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     if let Ok(time) = metadata.accessed() {
+	///         println!("{time:?}");
+	///     } else {
+	///         println!("Not supported on this platform");
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	fn accessed(&self) -> io::Result<SystemTime>;
+
+	/// Returns the creation time listed in this metadata.
+	///
+	/// The returned value corresponds to the `btime` field of `statx` on
+	/// Linux kernel starting from to 4.11, the `birthtime` field of `stat` on other
+	/// Unix platforms, and the `ftCreationTime` field on Windows platforms.
+	///
+	/// # Errors
+	///
+	/// This field might not be available on all platforms, and will return an
+	/// `Err` on platforms or filesystems where it is not available.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let metadata = fs::metadata("foo.txt")?;
+	///
+	///     if let Ok(time) = metadata.created() {
+	///         println!("{time:?}");
+	///     } else {
+	///         println!("Not supported on this platform or filesystem");
+	///     }
+	///     Ok(())
+	/// }
+	/// ```
+	fn created(&self) -> io::Result<SystemTime>;
 }
 
-// impl Metadata for std::fs::Metadata {}
+impl Metadata for std::fs::Metadata {
+	type FileType = std::fs::FileType;
+	type Permissions = std::fs::Permissions;
+
+	fn file_type(&self) -> Self::FileType {
+		std::fs::Metadata::file_type(self)
+	}
+
+	fn is_dir(&self) -> bool {
+		std::fs::Metadata::is_dir(self)
+	}
+
+	fn is_file(&self) -> bool {
+		std::fs::Metadata::is_file(self)
+	}
+
+	fn is_symlink(&self) -> bool {
+		std::fs::Metadata::is_symlink(self)
+	}
+
+	fn len(&self) -> u64 {
+		std::fs::Metadata::len(self)
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		std::fs::Metadata::permissions(self)
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		std::fs::Metadata::modified(self)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		std::fs::Metadata::accessed(self)
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		std::fs::Metadata::created(self)
+	}
+}
 
 /// Options and flags which can be used to configure how a file is opened.
 ///
@@ -233,22 +983,238 @@ pub trait Metadata {
 /// ```no_run
 /// use std::fs::OpenOptions;
 ///
-/// let file = OpenOptions::new().read(true).write(true).create(true).open("foo.txt");
+/// let file = OpenOptions::new()
+///             .read(true)
+///             .write(true)
+///             .create(true)
+///             .open("foo.txt");
 /// ```
-pub trait OpenOptions {
-	// fn new() -> Self;
-	// fn read(self: &mut Self, read: bool, ) -> &mut Self;
-	// fn write(self: &mut Self, write: bool, ) -> &mut Self;
-	// fn append(self: &mut Self, append: bool, ) -> &mut Self;
-	// fn truncate(self: &mut Self, truncate: bool, ) -> &mut Self;
-	// fn create(self: &mut Self, create: bool, ) -> &mut Self;
-	// fn create_new(self: &mut Self, create_new: bool, ) -> &mut Self;
-	// fn open<P: AsRef<Path,> + , >(self: &Self, path: P, ) -> io::Result<File,>;
-	// impl core::clone::Clone
-	// impl core::fmt::Debug
+pub trait OpenOptions: Sized + Clone + std::fmt::Debug {
+	/// The backend counterpart of [`std::fs::File`].
+	type File: File;
+
+	/// Sets the option for read access.
+	///
+	/// This option, when true, will indicate that the file should be
+	/// `read`-able if opened.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().read(true).open("foo.txt");
+	/// ```
+	fn read(&mut self, read: bool) -> &mut Self;
+
+	/// Sets the option for write access.
+	///
+	/// This option, when true, will indicate that the file should be
+	/// `write`-able if opened.
+	///
+	/// If the file already exists, any write calls on it will overwrite its
+	/// contents, without truncating it.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().write(true).open("foo.txt");
+	/// ```
+	fn write(&mut self, write: bool) -> &mut Self;
+
+	/// Sets the option for the append mode.
+	///
+	/// This option, when true, means that writes will append to a file instead
+	/// of overwriting previous contents.
+	/// Note that setting `.write(true).append(true)` has the same effect as
+	/// setting only `.append(true)`.
+	///
+	/// Append mode guarantees that writes will be positioned at the current end of file,
+	/// even when there are other processes or threads appending to the same file. This is
+	/// unlike <code>[seek]\([SeekFrom]::[End]\(0))</code> followed by `write()`, which
+	/// has a race between seeking and writing during which another writer can write, with
+	/// our `write()` overwriting their data.
+	///
+	/// Keep in mind that this does not necessarily guarantee that data appended by
+	/// different processes or threads does not interleave. The amount of data accepted a
+	/// single `write()` call depends on the operating system and file system. A
+	/// successful `write()` is allowed to write only part of the given data, so even if
+	/// you're careful to provide the whole message in a single call to `write()`, there
+	/// is no guarantee that it will be written out in full. If you rely on the filesystem
+	/// accepting the message in a single write, make sure that all data that belongs
+	/// together is written in one operation. This can be done by concatenating strings
+	/// before passing them to [`write()`].
+	///
+	/// If a file is opened with both read and append access, beware that after
+	/// opening, and after every write, the position for reading may be set at the
+	/// end of the file. So, before writing, save the current position (using
+	/// <code>[Seek]::[stream_position]</code>), and restore it before the next read.
+	///
+	/// ## Note
+	///
+	/// This function doesn't create the file if it doesn't exist. Use the
+	/// [`OpenOptions::create`] method to do so.
+	///
+	/// [`write()`]: Write::write "io::Write::write"
+	/// [`flush()`]: Write::flush "io::Write::flush"
+	/// [stream_position]: Seek::stream_position "io::Seek::stream_position"
+	/// [seek]: Seek::seek "io::Seek::seek"
+	/// [Current]: SeekFrom::Current "io::SeekFrom::Current"
+	/// [End]: SeekFrom::End "io::SeekFrom::End"
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().append(true).open("foo.txt");
+	/// ```
+	fn append(&mut self, append: bool) -> &mut Self;
+
+	/// Sets the option for truncating a previous file.
+	///
+	/// If a file is successfully opened with this option set to true, it will truncate
+	/// the file to 0 length if it already exists.
+	///
+	/// The file must be opened with write access for truncate to work.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().write(true).truncate(true).open("foo.txt");
+	/// ```
+	fn truncate(&mut self, truncate: bool) -> &mut Self;
+
+	/// Sets the option to create a new file, or open it if it already exists.
+	///
+	/// In order for the file to be created, [`OpenOptions::write`] or
+	/// [`OpenOptions::append`] access must be used.
+	///
+	/// See also [`std::fs::write()`][self::write] for a simple function to
+	/// create a file with some given data.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().write(true).create(true).open("foo.txt");
+	/// ```
+	fn create(&mut self, create: bool) -> &mut Self;
+
+	/// Sets the option to create a new file, failing if it already exists.
+	///
+	/// No file is allowed to exist at the target location, also no (dangling) symlink. In this
+	/// way, if the call succeeds, the file returned is guaranteed to be new.
+	/// If a file exists at the target location, creating a new file will fail with [`AlreadyExists`]
+	/// or another error based on the situation. See [`OpenOptions::open`] for a
+	/// non-exhaustive list of likely errors.
+	///
+	/// This option is useful because it is atomic. Otherwise between checking
+	/// whether a file exists and creating a new one, the file may have been
+	/// created by another process (a TOCTOU race condition / attack).
+	///
+	/// If `.create_new(true)` is set, [`.create()`] and [`.truncate()`] are
+	/// ignored.
+	///
+	/// The file must be opened with write or append access in order to create
+	/// a new file.
+	///
+	/// [`.create()`]: OpenOptions::create
+	/// [`.truncate()`]: OpenOptions::truncate
+	/// [`AlreadyExists`]: io::ErrorKind::AlreadyExists
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().write(true)
+	///                              .create_new(true)
+	///                              .open("foo.txt");
+	/// ```
+	fn create_new(&mut self, create_new: bool) -> &mut Self;
+
+	/// Opens a file at `path` with the options specified by `self`.
+	///
+	/// # Errors
+	///
+	/// This function will return an error under a number of different
+	/// circumstances. Some of these error conditions are listed here, together
+	/// with their [`io::ErrorKind`]. The mapping to [`io::ErrorKind`]s is not
+	/// part of the compatibility contract of the function.
+	///
+	/// * [`NotFound`]: The specified file does not exist and neither `create`
+	///   or `create_new` is set.
+	/// * [`NotFound`]: One of the directory components of the file path does
+	///   not exist.
+	/// * [`PermissionDenied`]: The user lacks permission to get the specified
+	///   access rights for the file.
+	/// * [`PermissionDenied`]: The user lacks permission to open one of the
+	///   directory components of the specified path.
+	/// * [`AlreadyExists`]: `create_new` was specified and the file already
+	///   exists.
+	/// * [`InvalidInput`]: Invalid combinations of open options (truncate
+	///   without write access, no access mode set, etc.).
+	///
+	/// The following errors don't match any existing [`io::ErrorKind`] at the moment:
+	/// * One of the directory components of the specified file path
+	///   was not, in fact, a directory.
+	/// * Filesystem-level errors: full disk, write permission
+	///   requested on a read-only file system, exceeded disk quota, too many
+	///   open files, too long filename, too many symbolic links in the
+	///   specified path (Unix-like systems only), etc.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::OpenOptions;
+	///
+	/// let file = OpenOptions::new().read(true).open("foo.txt");
+	/// ```
+	///
+	/// [`AlreadyExists`]: io::ErrorKind::AlreadyExists
+	/// [`InvalidInput`]: io::ErrorKind::InvalidInput
+	/// [`NotFound`]: io::ErrorKind::NotFound
+	/// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File>;
 }
 
-// impl OpenOptions for std::fs::OpenOptions {}
+impl OpenOptions for std::fs::OpenOptions {
+	type File = std::fs::File;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		std::fs::OpenOptions::read(self, read)
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		std::fs::OpenOptions::write(self, write)
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		std::fs::OpenOptions::append(self, append)
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		std::fs::OpenOptions::truncate(self, truncate)
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		std::fs::OpenOptions::create(self, create)
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		std::fs::OpenOptions::create_new(self, create_new)
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		std::fs::OpenOptions::open(self, path)
+	}
+}
 
 /// Representation of the various permissions on a file.
 ///
@@ -258,16 +1224,125 @@ pub trait OpenOptions {
 /// through the [`PermissionsExt`] trait.
 ///
 /// [`PermissionsExt`]: crate::os::unix::fs::PermissionsExt
-pub trait Permissions {
-	// fn readonly(self: &Self, ) -> bool;
-	// fn set_readonly(self: &mut Self, readonly: bool, );
-	// impl core::clone::Clone
-	// impl core::cmp::PartialEq
-	// impl core::cmp::Eq
-	// impl core::fmt::Debug
+pub trait Permissions: Sized + Clone + std::fmt::Debug {
+	/// Returns `true` if these permissions describe a readonly (unwritable) file.
+	///
+	/// # Note
+	///
+	/// This function does not take Access Control Lists (ACLs) or Unix group
+	/// membership into account.
+	///
+	/// # Windows
+	///
+	/// On Windows this returns [`FILE_ATTRIBUTE_READONLY`](https://docs.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants).
+	/// If `FILE_ATTRIBUTE_READONLY` is set then writes to the file will fail
+	/// but the user may still have permission to change this flag. If
+	/// `FILE_ATTRIBUTE_READONLY` is *not* set then writes may still fail due
+	/// to lack of write permission.
+	/// The behavior of this attribute for directories depends on the Windows
+	/// version.
+	///
+	/// # Unix (including macOS)
+	///
+	/// On Unix-based platforms this checks if *any* of the owner, group or others
+	/// write permission bits are set. It does not check if the current
+	/// user is in the file's assigned group. It also does not check ACLs.
+	/// Therefore the return value of this function cannot be relied upon
+	/// to predict whether attempts to read or write the file will actually succeed.
+	/// The [`PermissionsExt`] trait gives direct access to the permission bits but
+	/// also does not read ACLs.
+	///
+	/// [`PermissionsExt`]: crate::os::unix::fs::PermissionsExt
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let mut f = File::create("foo.txt")?;
+	///     let metadata = f.metadata()?;
+	///
+	///     assert_eq!(false, metadata.permissions().readonly());
+	///     Ok(())
+	/// }
+	/// ```
+	fn readonly(&self) -> bool;
+
+	/// Modifies the readonly flag for this set of permissions. If the
+	/// `readonly` argument is `true`, using the resulting `Permission` will
+	/// update file permissions to forbid writing. Conversely, if it's `false`,
+	/// using the resulting `Permission` will update file permissions to allow
+	/// writing.
+	///
+	/// This operation does **not** modify the files attributes. This only
+	/// changes the in-memory value of these attributes for this `Permissions`
+	/// instance. To modify the files attributes use the [`set_permissions`]
+	/// function which commits these attribute changes to the file.
+	///
+	/// # Note
+	///
+	/// `set_readonly(false)` makes the file *world-writable* on Unix.
+	/// You can use the [`PermissionsExt`] trait on Unix to avoid this issue.
+	///
+	/// It also does not take Access Control Lists (ACLs) or Unix group
+	/// membership into account.
+	///
+	/// # Windows
+	///
+	/// On Windows this sets or clears [`FILE_ATTRIBUTE_READONLY`](https://docs.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants).
+	/// If `FILE_ATTRIBUTE_READONLY` is set then writes to the file will fail
+	/// but the user may still have permission to change this flag. If
+	/// `FILE_ATTRIBUTE_READONLY` is *not* set then the write may still fail if
+	/// the user does not have permission to write to the file.
+	///
+	/// In Windows 7 and earlier this attribute prevents deleting empty
+	/// directories. It does not prevent modifying the directory contents.
+	/// On later versions of Windows this attribute is ignored for directories.
+	///
+	/// # Unix (including macOS)
+	///
+	/// On Unix-based platforms this sets or clears the write access bit for
+	/// the owner, group *and* others, equivalent to `chmod a+w <file>`
+	/// or `chmod a-w <file>` respectively. The latter will grant write access
+	/// to all users! You can use the [`PermissionsExt`] trait on Unix
+	/// to avoid this issue.
+	///
+	/// [`PermissionsExt`]: crate::os::unix::fs::PermissionsExt
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use std::fs::File;
+	///
+	/// fn main() -> std::io::Result<()> {
+	///     let f = File::create("foo.txt")?;
+	///     let metadata = f.metadata()?;
+	///     let mut permissions = metadata.permissions();
+	///
+	///     permissions.set_readonly(true);
+	///
+	///     // filesystem doesn't change, only the in memory state of the
+	///     // readonly permission
+	///     assert_eq!(false, metadata.permissions().readonly());
+	///
+	///     // just this particular `permissions`.
+	///     assert_eq!(true, permissions.readonly());
+	///     Ok(())
+	/// }
+	/// ```
+	fn set_readonly(&mut self, readonly: bool);
 }
 
-// impl Permissions for std::fs::Permissions {}
+impl Permissions for std::fs::Permissions {
+	fn readonly(&self) -> bool {
+		std::fs::Permissions::readonly(self)
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		std::fs::Permissions::set_readonly(self, readonly)
+	}
+}
 
 /// Iterator over the entries in a directory.
 ///
@@ -283,9 +1358,13 @@ pub trait Permissions {
 ///
 /// This [`io::Result`] will be an [`Err`] if there's some sort of intermittent
 /// IO error during iteration.
-pub trait ReadDir {
-	// impl core::fmt::Debug
-	// impl core::iter::traits::iterator::Iterator
+pub trait ReadDir: Sized + std::fmt::Debug + Iterator<
+		Item = Result<Self::DirEntry, std::io::Error>,
+	> {
+	/// The backend counterpart of [`std::fs::DirEntry`].
+	type DirEntry: DirEntry;
 }
 
-// impl ReadDir for std::fs::ReadDir {}
+impl ReadDir for std::fs::ReadDir {
+	type DirEntry = std::fs::DirEntry;
+}
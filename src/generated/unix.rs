@@ -0,0 +1,350 @@
+// This file is auto-generated. DO NOT edit by hand. See README.md for more details.
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+#![cfg(unix)]
+use std::io;
+use std::os::fd::AsFd;
+use std::path;
+use super::functions::Fs;
+use super::functions::Native;
+use super::structs::DirBuilder;
+use super::structs::DirEntry;
+use super::structs::File;
+use super::structs::FileType;
+use super::structs::Metadata;
+use super::structs::OpenOptions;
+use super::structs::Permissions;
+
+/// Unix-specific extensions to [`DirBuilder`], mirroring [`std::os::unix::fs::DirBuilderExt`].
+pub trait DirBuilderExt: DirBuilder {
+	/// See [`std::os::unix::fs::DirBuilderExt::mode`].
+	fn mode(&mut self, mode: u32) -> &mut Self;
+}
+
+impl DirBuilderExt for std::fs::DirBuilder {
+	fn mode(&mut self, mode: u32) -> &mut Self {
+		<std::fs::DirBuilder as std::os::unix::fs::DirBuilderExt>::mode(self, mode)
+	}
+}
+
+/// Unix-specific extensions to [`DirEntry`], mirroring [`std::os::unix::fs::DirEntryExt`].
+pub trait DirEntryExt: DirEntry {
+	/// See [`std::os::unix::fs::DirEntryExt::ino`].
+	fn ino(&self) -> u64;
+}
+
+impl DirEntryExt for std::fs::DirEntry {
+	fn ino(&self) -> u64 {
+		<std::fs::DirEntry as std::os::unix::fs::DirEntryExt>::ino(self)
+	}
+}
+
+/// Unix-specific extensions to [`File`], mirroring [`std::os::unix::fs::FileExt`].
+pub trait FileExt: File {
+	/// See [`std::os::unix::fs::FileExt::read_at`].
+	fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+
+	/// See [`std::os::unix::fs::FileExt::read_exact_at`].
+	fn read_exact_at(&self, buf: &mut [u8], offset: u64) -> io::Result<()>;
+
+	/// See [`std::os::unix::fs::FileExt::write_at`].
+	fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
+
+	/// See [`std::os::unix::fs::FileExt::write_all_at`].
+	fn write_all_at(&self, buf: &[u8], offset: u64) -> io::Result<()>;
+}
+
+impl FileExt for std::fs::File {
+	fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+		<std::fs::File as std::os::unix::fs::FileExt>::read_at(self, buf, offset)
+	}
+
+	fn read_exact_at(&self, buf: &mut [u8], offset: u64) -> io::Result<()> {
+		<std::fs::File as std::os::unix::fs::FileExt>::read_exact_at(self, buf, offset)
+	}
+
+	fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+		<std::fs::File as std::os::unix::fs::FileExt>::write_at(self, buf, offset)
+	}
+
+	fn write_all_at(&self, buf: &[u8], offset: u64) -> io::Result<()> {
+		<std::fs::File as std::os::unix::fs::FileExt>::write_all_at(self, buf, offset)
+	}
+}
+
+/// Unix-specific extensions to [`FileType`], mirroring [`std::os::unix::fs::FileTypeExt`].
+pub trait FileTypeExt: FileType {
+	/// See [`std::os::unix::fs::FileTypeExt::is_block_device`].
+	fn is_block_device(&self) -> bool;
+
+	/// See [`std::os::unix::fs::FileTypeExt::is_char_device`].
+	fn is_char_device(&self) -> bool;
+
+	/// See [`std::os::unix::fs::FileTypeExt::is_fifo`].
+	fn is_fifo(&self) -> bool;
+
+	/// See [`std::os::unix::fs::FileTypeExt::is_socket`].
+	fn is_socket(&self) -> bool;
+}
+
+impl FileTypeExt for std::fs::FileType {
+	fn is_block_device(&self) -> bool {
+		<std::fs::FileType as std::os::unix::fs::FileTypeExt>::is_block_device(self)
+	}
+
+	fn is_char_device(&self) -> bool {
+		<std::fs::FileType as std::os::unix::fs::FileTypeExt>::is_char_device(self)
+	}
+
+	fn is_fifo(&self) -> bool {
+		<std::fs::FileType as std::os::unix::fs::FileTypeExt>::is_fifo(self)
+	}
+
+	fn is_socket(&self) -> bool {
+		<std::fs::FileType as std::os::unix::fs::FileTypeExt>::is_socket(self)
+	}
+}
+
+/// Unix-specific extensions to [`Metadata`], mirroring [`std::os::unix::fs::MetadataExt`].
+pub trait MetadataExt: Metadata {
+	/// See [`std::os::unix::fs::MetadataExt::dev`].
+	fn dev(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::ino`].
+	fn ino(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::mode`].
+	fn mode(&self) -> u32;
+
+	/// See [`std::os::unix::fs::MetadataExt::nlink`].
+	fn nlink(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::uid`].
+	fn uid(&self) -> u32;
+
+	/// See [`std::os::unix::fs::MetadataExt::gid`].
+	fn gid(&self) -> u32;
+
+	/// See [`std::os::unix::fs::MetadataExt::rdev`].
+	fn rdev(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::size`].
+	fn size(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::atime`].
+	fn atime(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::atime_nsec`].
+	fn atime_nsec(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::mtime`].
+	fn mtime(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::mtime_nsec`].
+	fn mtime_nsec(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::ctime`].
+	fn ctime(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::ctime_nsec`].
+	fn ctime_nsec(&self) -> i64;
+
+	/// See [`std::os::unix::fs::MetadataExt::blksize`].
+	fn blksize(&self) -> u64;
+
+	/// See [`std::os::unix::fs::MetadataExt::blocks`].
+	fn blocks(&self) -> u64;
+}
+
+impl MetadataExt for std::fs::Metadata {
+	fn dev(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::dev(self)
+	}
+
+	fn ino(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::ino(self)
+	}
+
+	fn mode(&self) -> u32 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::mode(self)
+	}
+
+	fn nlink(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::nlink(self)
+	}
+
+	fn uid(&self) -> u32 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::uid(self)
+	}
+
+	fn gid(&self) -> u32 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::gid(self)
+	}
+
+	fn rdev(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::rdev(self)
+	}
+
+	fn size(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::size(self)
+	}
+
+	fn atime(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::atime(self)
+	}
+
+	fn atime_nsec(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::atime_nsec(self)
+	}
+
+	fn mtime(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::mtime(self)
+	}
+
+	fn mtime_nsec(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::mtime_nsec(self)
+	}
+
+	fn ctime(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::ctime(self)
+	}
+
+	fn ctime_nsec(&self) -> i64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::ctime_nsec(self)
+	}
+
+	fn blksize(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::blksize(self)
+	}
+
+	fn blocks(&self) -> u64 {
+		<std::fs::Metadata as std::os::unix::fs::MetadataExt>::blocks(self)
+	}
+}
+
+/// Unix-specific extensions to [`OpenOptions`], mirroring [`std::os::unix::fs::OpenOptionsExt`].
+pub trait OpenOptionsExt: OpenOptions {
+	/// See [`std::os::unix::fs::OpenOptionsExt::mode`].
+	fn mode(&mut self, mode: u32) -> &mut Self;
+
+	/// See [`std::os::unix::fs::OpenOptionsExt::custom_flags`].
+	fn custom_flags(&mut self, flags: i32) -> &mut Self;
+}
+
+impl OpenOptionsExt for std::fs::OpenOptions {
+	fn mode(&mut self, mode: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::unix::fs::OpenOptionsExt>::mode(self, mode)
+	}
+
+	fn custom_flags(&mut self, flags: i32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::unix::fs::OpenOptionsExt>::custom_flags(
+			self,
+			flags,
+		)
+	}
+}
+
+/// Unix-specific extensions to [`Permissions`], mirroring [`std::os::unix::fs::PermissionsExt`].
+pub trait PermissionsExt: Permissions {
+	/// See [`std::os::unix::fs::PermissionsExt::mode`].
+	fn mode(&self) -> u32;
+
+	/// See [`std::os::unix::fs::PermissionsExt::set_mode`].
+	fn set_mode(&mut self, mode: u32);
+
+	/// See [`std::os::unix::fs::PermissionsExt::from_mode`].
+	fn from_mode(mode: u32) -> Self;
+}
+
+impl PermissionsExt for std::fs::Permissions {
+	fn mode(&self) -> u32 {
+		<std::fs::Permissions as std::os::unix::fs::PermissionsExt>::mode(self)
+	}
+
+	fn set_mode(&mut self, mode: u32) {
+		<std::fs::Permissions as std::os::unix::fs::PermissionsExt>::set_mode(self, mode)
+	}
+
+	fn from_mode(mode: u32) -> Self {
+		<std::fs::Permissions as std::os::unix::fs::PermissionsExt>::from_mode(mode)
+	}
+}
+
+/// Unix-specific functions of [`std::os::unix::fs`], as methods of [`Fs`].
+pub trait FsExt: Fs {
+	/// See [`std::os::unix::fs::chown`].
+	fn chown<P: AsRef<path::Path>>(
+		&self,
+		dir: P,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()>;
+
+	#[cfg(not(target_os = "fuchsia"))]
+	/// See [`std::os::unix::fs::chroot`].
+	fn chroot<P: AsRef<path::Path>>(&self, dir: P) -> io::Result<()>;
+
+	/// See [`std::os::unix::fs::fchown`].
+	fn fchown<F: AsFd>(
+		&self,
+		fd: F,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()>;
+
+	/// See [`std::os::unix::fs::lchown`].
+	fn lchown<P: AsRef<path::Path>>(
+		&self,
+		dir: P,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()>;
+
+	/// See [`std::os::unix::fs::symlink`].
+	fn symlink<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()>;
+}
+
+impl FsExt for Native {
+	fn chown<P: AsRef<path::Path>>(
+		&self,
+		dir: P,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()> {
+		std::os::unix::fs::chown(dir, uid, gid)
+	}
+
+	#[cfg(not(target_os = "fuchsia"))]
+	fn chroot<P: AsRef<path::Path>>(&self, dir: P) -> io::Result<()> {
+		std::os::unix::fs::chroot(dir)
+	}
+
+	fn fchown<F: AsFd>(
+		&self,
+		fd: F,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()> {
+		std::os::unix::fs::fchown(fd, uid, gid)
+	}
+
+	fn lchown<P: AsRef<path::Path>>(
+		&self,
+		dir: P,
+		uid: Option<u32>,
+		gid: Option<u32>,
+	) -> io::Result<()> {
+		std::os::unix::fs::lchown(dir, uid, gid)
+	}
+
+	fn symlink<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		std::os::unix::fs::symlink(original, link)
+	}
+}
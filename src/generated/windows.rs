@@ -0,0 +1,193 @@
+// This file is auto-generated. DO NOT edit by hand. See README.md for more details.
+#![allow(clippy::len_without_is_empty, clippy::tabs_in_doc_comments)]
+#![cfg(windows)]
+use std::io;
+use std::path;
+use std::time::SystemTime;
+use super::functions::Fs;
+use super::functions::Native;
+use super::structs::File;
+use super::structs::FileTimes;
+use super::structs::FileType;
+use super::structs::Metadata;
+use super::structs::OpenOptions;
+
+/// Windows-specific extensions to [`File`], mirroring [`std::os::windows::fs::FileExt`].
+pub trait FileExt: File {
+	/// See [`std::os::windows::fs::FileExt::seek_read`].
+	fn seek_read(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+
+	/// See [`std::os::windows::fs::FileExt::seek_write`].
+	fn seek_write(&self, buf: &[u8], offset: u64) -> io::Result<usize>;
+}
+
+impl FileExt for std::fs::File {
+	fn seek_read(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+		<std::fs::File as std::os::windows::fs::FileExt>::seek_read(self, buf, offset)
+	}
+
+	fn seek_write(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+		<std::fs::File as std::os::windows::fs::FileExt>::seek_write(self, buf, offset)
+	}
+}
+
+/// Windows-specific extensions to [`FileTimes`], mirroring [`std::os::windows::fs::FileTimesExt`].
+pub trait FileTimesExt: FileTimes {
+	/// See [`std::os::windows::fs::FileTimesExt::set_created`].
+	fn set_created(self, t: SystemTime) -> Self;
+}
+
+impl FileTimesExt for std::fs::FileTimes {
+	fn set_created(self, t: SystemTime) -> Self {
+		<std::fs::FileTimes as std::os::windows::fs::FileTimesExt>::set_created(self, t)
+	}
+}
+
+/// Windows-specific extensions to [`FileType`], mirroring [`std::os::windows::fs::FileTypeExt`].
+pub trait FileTypeExt: FileType {
+	/// See [`std::os::windows::fs::FileTypeExt::is_symlink_dir`].
+	fn is_symlink_dir(&self) -> bool;
+
+	/// See [`std::os::windows::fs::FileTypeExt::is_symlink_file`].
+	fn is_symlink_file(&self) -> bool;
+}
+
+impl FileTypeExt for std::fs::FileType {
+	fn is_symlink_dir(&self) -> bool {
+		<std::fs::FileType as std::os::windows::fs::FileTypeExt>::is_symlink_dir(self)
+	}
+
+	fn is_symlink_file(&self) -> bool {
+		<std::fs::FileType as std::os::windows::fs::FileTypeExt>::is_symlink_file(self)
+	}
+}
+
+/// Windows-specific extensions to [`Metadata`], mirroring [`std::os::windows::fs::MetadataExt`].
+pub trait MetadataExt: Metadata {
+	/// See [`std::os::windows::fs::MetadataExt::file_attributes`].
+	fn file_attributes(&self) -> u32;
+
+	/// See [`std::os::windows::fs::MetadataExt::creation_time`].
+	fn creation_time(&self) -> u64;
+
+	/// See [`std::os::windows::fs::MetadataExt::last_access_time`].
+	fn last_access_time(&self) -> u64;
+
+	/// See [`std::os::windows::fs::MetadataExt::last_write_time`].
+	fn last_write_time(&self) -> u64;
+
+	/// See [`std::os::windows::fs::MetadataExt::file_size`].
+	fn file_size(&self) -> u64;
+}
+
+impl MetadataExt for std::fs::Metadata {
+	fn file_attributes(&self) -> u32 {
+		<std::fs::Metadata as std::os::windows::fs::MetadataExt>::file_attributes(self)
+	}
+
+	fn creation_time(&self) -> u64 {
+		<std::fs::Metadata as std::os::windows::fs::MetadataExt>::creation_time(self)
+	}
+
+	fn last_access_time(&self) -> u64 {
+		<std::fs::Metadata as std::os::windows::fs::MetadataExt>::last_access_time(self)
+	}
+
+	fn last_write_time(&self) -> u64 {
+		<std::fs::Metadata as std::os::windows::fs::MetadataExt>::last_write_time(self)
+	}
+
+	fn file_size(&self) -> u64 {
+		<std::fs::Metadata as std::os::windows::fs::MetadataExt>::file_size(self)
+	}
+}
+
+/// Windows-specific extensions to [`OpenOptions`], mirroring [`std::os::windows::fs::OpenOptionsExt`].
+pub trait OpenOptionsExt: OpenOptions {
+	/// See [`std::os::windows::fs::OpenOptionsExt::access_mode`].
+	fn access_mode(&mut self, access: u32) -> &mut Self;
+
+	/// See [`std::os::windows::fs::OpenOptionsExt::share_mode`].
+	fn share_mode(&mut self, val: u32) -> &mut Self;
+
+	/// See [`std::os::windows::fs::OpenOptionsExt::custom_flags`].
+	fn custom_flags(&mut self, flags: u32) -> &mut Self;
+
+	/// See [`std::os::windows::fs::OpenOptionsExt::attributes`].
+	fn attributes(&mut self, val: u32) -> &mut Self;
+
+	/// See [`std::os::windows::fs::OpenOptionsExt::security_qos_flags`].
+	fn security_qos_flags(&mut self, flags: u32) -> &mut Self;
+}
+
+impl OpenOptionsExt for std::fs::OpenOptions {
+	fn access_mode(&mut self, access: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::windows::fs::OpenOptionsExt>::access_mode(
+			self,
+			access,
+		)
+	}
+
+	fn share_mode(&mut self, val: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::windows::fs::OpenOptionsExt>::share_mode(
+			self,
+			val,
+		)
+	}
+
+	fn custom_flags(&mut self, flags: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::windows::fs::OpenOptionsExt>::custom_flags(
+			self,
+			flags,
+		)
+	}
+
+	fn attributes(&mut self, val: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::windows::fs::OpenOptionsExt>::attributes(
+			self,
+			val,
+		)
+	}
+
+	fn security_qos_flags(&mut self, flags: u32) -> &mut Self {
+		<std::fs::OpenOptions as std::os::windows::fs::OpenOptionsExt>::security_qos_flags(
+			self,
+			flags,
+		)
+	}
+}
+
+/// Windows-specific functions of [`std::os::windows::fs`], as methods of [`Fs`].
+pub trait FsExt: Fs {
+	/// See [`std::os::windows::fs::symlink_dir`].
+	fn symlink_dir<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()>;
+
+	/// See [`std::os::windows::fs::symlink_file`].
+	fn symlink_file<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()>;
+}
+
+impl FsExt for Native {
+	fn symlink_dir<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		std::os::windows::fs::symlink_dir(original, link)
+	}
+
+	fn symlink_file<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		std::os::windows::fs::symlink_file(original, link)
+	}
+}
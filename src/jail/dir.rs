@@ -0,0 +1,92 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::Jail;
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::Fs;
+use crate::ReadDir;
+
+/// A [`DirBuilder`] of a [`JailFs`](crate::JailFs).
+pub struct JailDirBuilder<F: Fs> {
+	pub(super) inner: F::DirBuilder,
+	pub(super) jail: Arc<Jail<F>>,
+}
+
+impl<F: Fs> fmt::Debug for JailDirBuilder<F> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("JailDirBuilder")
+			.field("inner", &self.inner)
+			.field("root", &self.jail.root)
+			.finish()
+	}
+}
+
+impl<F: Fs> DirBuilder for JailDirBuilder<F> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.inner.recursive(recursive);
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		self.inner.create(self.jail.resolve(path.as_ref(), false)?)
+	}
+}
+
+/// Iterator over the entries of a directory of a [`JailFs`](crate::JailFs).
+#[derive(Debug)]
+pub struct JailReadDir<R> {
+	pub(super) inner: R,
+	/// The path the directory was read with, as given to the jail.
+	pub(super) dir: PathBuf,
+}
+
+impl<R: ReadDir> Iterator for JailReadDir<R> {
+	type Item = io::Result<JailDirEntry<R::DirEntry>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let entry = self.inner.next()?;
+		Some(entry.map(|inner| {
+			JailDirEntry {
+				path: self.dir.join(inner.file_name()),
+				inner,
+			}
+		}))
+	}
+}
+
+impl<R: ReadDir> ReadDir for JailReadDir<R> {
+	type DirEntry = JailDirEntry<R::DirEntry>;
+}
+
+/// An entry of a [`JailReadDir`], whose path is inside the jail.
+#[derive(Debug)]
+pub struct JailDirEntry<E> {
+	inner: E,
+	path: PathBuf,
+}
+
+impl<E: DirEntry> DirEntry for JailDirEntry<E> {
+	type FileType = E::FileType;
+	type Metadata = E::Metadata;
+
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.inner.metadata()
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		self.inner.file_type()
+	}
+
+	fn file_name(&self) -> OsString {
+		self.inner.file_name()
+	}
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Jail;
+use crate::Fs;
+use crate::OpenOptions;
+
+/// [`OpenOptions`] of a [`JailFs`](crate::JailFs).
+pub struct JailOpenOptions<F: Fs> {
+	pub(super) inner: F::OpenOptions,
+	pub(super) jail: Arc<Jail<F>>,
+	/// Whether [`create_new`](OpenOptions::create_new) is set, in which case the last component
+	/// of the path is not followed.
+	pub(super) create_new: bool,
+}
+
+impl<F: Fs> Clone for JailOpenOptions<F> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			jail: self.jail.clone(),
+			create_new: self.create_new,
+		}
+	}
+}
+
+impl<F: Fs> fmt::Debug for JailOpenOptions<F> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("JailOpenOptions")
+			.field("inner", &self.inner)
+			.field("root", &self.jail.root)
+			.field("create_new", &self.create_new)
+			.finish()
+	}
+}
+
+impl<F: Fs> OpenOptions for JailOpenOptions<F> {
+	type File = F::File;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.inner.read(read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.inner.write(write);
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.inner.append(append);
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.inner.truncate(truncate);
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.inner.create(create);
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.inner.create_new(create_new);
+		self.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		// As with `std`, any existing entry fails `create_new`, even a dangling symbolic link.
+		let path = self.jail.resolve(path.as_ref(), !self.create_new)?;
+		if self.create_new && self.jail.inner.symlink_metadata(&path).is_ok() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		self.inner.open(path)
+	}
+}
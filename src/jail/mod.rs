@@ -0,0 +1,319 @@
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::path;
+use std::path::Component;
+use std::sync::Arc;
+
+mod dir;
+pub use dir::*;
+mod file;
+pub use file::*;
+
+use crate::Fs;
+use crate::Metadata;
+
+/// The maximum number of symbolic links followed when resolving a path, as on Linux.
+const MAX_SYMLINKS: usize = 40;
+
+/// A decorator confining another [`Fs`] to the directory tree under a root.
+///
+/// Paths are resolved against the root whether they are absolute or relative, so `/a` and `a`
+/// both name `a` in the root. `..` is resolved lexically, and symbolic links are followed within
+/// the jail: a relative target is resolved from the directory of the link, while an absolute one
+/// must point under the root on the inner filesystem. Any path climbing above the root, through
+/// `..` or a symbolic link, fails with [`io::ErrorKind::PermissionDenied`] before the inner
+/// filesystem is called.
+///
+/// Paths are returned as seen from inside the jail: [`canonicalize`](Fs::canonicalize) and
+/// absolute [`read_link`](Fs::read_link) targets start at `/`, and [`DirEntry::path`] joins the
+/// path given to [`read_dir`](Fs::read_dir) with the name of the entry.
+///
+/// Resolution checks the inner filesystem before each call, so it does not guard against another
+/// process swapping a directory for a symbolic link in the meantime.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use std::path::Path;
+///
+/// use io_fs::Fs;
+/// use io_fs::JailFs;
+/// use io_fs::MemFs;
+///
+/// let inner = MemFs::new();
+/// inner.create_dir_all("/srv/tenant42/data")?;
+/// inner.write("/srv/secret", "hunter2")?;
+/// let fs = JailFs::new(inner, "/srv/tenant42")?;
+///
+/// fs.write("/data/notes.txt", "hello")?;
+/// assert_eq!(fs.get_ref().read_to_string("/srv/tenant42/data/notes.txt")?, "hello");
+/// assert_eq!(fs.canonicalize("data/notes.txt")?, Path::new("/data/notes.txt"));
+/// let error = fs.read("../secret").unwrap_err();
+/// assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+/// # Ok::<(), io::Error>(())
+/// ```
+///
+/// [`DirEntry::path`]: crate::DirEntry::path
+#[derive(Debug)]
+pub struct JailFs<F> {
+	jail: Arc<Jail<F>>,
+}
+
+impl<F> Clone for JailFs<F> {
+	fn clone(&self) -> Self {
+		Self {
+			jail: self.jail.clone(),
+		}
+	}
+}
+
+impl<F: Fs> JailFs<F> {
+	/// Confines `inner` to `root`, which must be a directory, canonicalized by `inner`.
+	pub fn new(inner: F, root: impl AsRef<path::Path>) -> io::Result<Self> {
+		let root = inner.canonicalize(root)?;
+		if !inner.metadata(&root)?.is_dir() {
+			return Err(io::ErrorKind::NotADirectory.into());
+		}
+		Ok(Self {
+			jail: Arc::new(Jail {
+				inner,
+				root,
+			}),
+		})
+	}
+}
+
+impl<F> JailFs<F> {
+	/// Returns the root of the jail on the inner filesystem.
+	pub fn root(&self) -> &path::Path {
+		&self.jail.root
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.jail.inner
+	}
+}
+
+#[derive(Debug)]
+struct Jail<F> {
+	inner: F,
+	root: path::PathBuf,
+}
+
+/// A step of a path being resolved, owning its name since symbolic link targets are spliced in.
+enum Step {
+	Root,
+	Parent,
+	Name(OsString),
+}
+
+impl Step {
+	fn parse(path: &path::Path, escaping: &path::Path) -> io::Result<Vec<Self>> {
+		let mut steps = Vec::new();
+		for component in path.components() {
+			steps.push(match component {
+				Component::Prefix(_) => return Err(escape(escaping)),
+				Component::RootDir => Self::Root,
+				Component::CurDir => continue,
+				Component::ParentDir => Self::Parent,
+				Component::Normal(name) => Self::Name(name.to_owned()),
+			});
+		}
+		Ok(steps)
+	}
+}
+
+fn escape(path: &path::Path) -> io::Error {
+	io::Error::new(io::ErrorKind::PermissionDenied, format!("{path:?} escapes the jail"))
+}
+
+impl<F: Fs> Jail<F> {
+	/// Maps `path` to the inner filesystem, following symbolic links in every component except
+	/// possibly the last.
+	fn resolve(&self, path: &path::Path, follow: bool) -> io::Result<path::PathBuf> {
+		let mut steps = VecDeque::from(Step::parse(path, path)?);
+		let mut names = Vec::new();
+		let mut symlinks = 0;
+		while let Some(step) = steps.pop_front() {
+			let name = match step {
+				Step::Root => {
+					names.clear();
+					continue;
+				}
+				Step::Parent => {
+					if names.pop().is_none() {
+						return Err(escape(path));
+					}
+					continue;
+				}
+				Step::Name(name) => name,
+			};
+			names.push(name);
+			if steps.is_empty() && !follow {
+				continue;
+			}
+			let resolved = self.join(&names);
+			// Missing paths are left to the inner filesystem to report.
+			if !self.inner.symlink_metadata(&resolved).is_ok_and(|metadata| metadata.is_symlink()) {
+				continue;
+			}
+			symlinks += 1;
+			if symlinks > MAX_SYMLINKS {
+				return Err(io::Error::other("too many levels of symbolic links"));
+			}
+			let target = self.inner.read_link(&resolved)?;
+			names.pop();
+			let target = if target.is_absolute() {
+				names.clear();
+				target.strip_prefix(&self.root).map_err(|_| escape(path))?.to_owned()
+			} else {
+				target
+			};
+			for step in Step::parse(&target, path)?.into_iter().rev() {
+				steps.push_front(step);
+			}
+		}
+		Ok(self.join(&names))
+	}
+
+	fn join(&self, names: &[OsString]) -> path::PathBuf {
+		let mut path = self.root.clone();
+		path.extend(names);
+		path
+	}
+
+	/// Maps a path of the inner filesystem back into the jail.
+	fn unresolve(&self, path: &path::Path) -> io::Result<path::PathBuf> {
+		let relative = path.strip_prefix(&self.root).map_err(|_| escape(path))?;
+		Ok(path::Path::new(path::MAIN_SEPARATOR_STR).join(relative))
+	}
+}
+
+impl<F: Fs> Fs for JailFs<F> {
+	type DirBuilder = JailDirBuilder<F>;
+	type DirEntry = JailDirEntry<F::DirEntry>;
+	type File = F::File;
+	type FileTimes = F::FileTimes;
+	type FileType = F::FileType;
+	type Metadata = F::Metadata;
+	type OpenOptions = JailOpenOptions<F>;
+	type Permissions = F::Permissions;
+	type ReadDir = JailReadDir<F::ReadDir>;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		JailDirBuilder {
+			inner: self.jail.inner.dir_builder(),
+			jail: self.jail.clone(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.jail.inner.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		JailOpenOptions {
+			inner: self.jail.inner.open_options(),
+			jail: self.jail.clone(),
+			create_new: false,
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = self.jail.resolve(path.as_ref(), true)?;
+		self.jail.unresolve(&self.jail.inner.canonicalize(path)?)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let from = self.jail.resolve(from.as_ref(), true)?;
+		let to = self.jail.resolve(to.as_ref(), true)?;
+		self.jail.inner.copy(from, to)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.jail.inner.create_dir(self.jail.resolve(path.as_ref(), false)?)
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.jail.inner.create_dir_all(self.jail.resolve(path.as_ref(), true)?)
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.jail.inner.exists(self.jail.resolve(path.as_ref(), true)?)
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let original = self.jail.resolve(original.as_ref(), false)?;
+		let link = self.jail.resolve(link.as_ref(), false)?;
+		self.jail.inner.hard_link(original, link)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.jail.inner.metadata(self.jail.resolve(path.as_ref(), true)?)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		self.jail.inner.read(self.jail.resolve(path.as_ref(), true)?)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		let path = path.as_ref();
+		Ok(JailReadDir {
+			inner: self.jail.inner.read_dir(self.jail.resolve(path, true)?)?,
+			dir: path.to_owned(),
+		})
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let target = self.jail.inner.read_link(self.jail.resolve(path.as_ref(), false)?)?;
+		if target.is_absolute() {
+			self.jail.unresolve(&target)
+		} else {
+			Ok(target)
+		}
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		self.jail.inner.read_to_string(self.jail.resolve(path.as_ref(), true)?)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.jail.inner.remove_dir(self.jail.resolve(path.as_ref(), false)?)
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.jail.inner.remove_dir_all(self.jail.resolve(path.as_ref(), false)?)
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.jail.inner.remove_file(self.jail.resolve(path.as_ref(), false)?)
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		let from = self.jail.resolve(from.as_ref(), false)?;
+		let to = self.jail.resolve(to.as_ref(), false)?;
+		self.jail.inner.rename(from, to)
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		self.jail.inner.set_permissions(self.jail.resolve(path.as_ref(), true)?, perm)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.jail.inner.symlink_metadata(self.jail.resolve(path.as_ref(), false)?)
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		self.jail.inner.write(self.jail.resolve(path.as_ref(), true)?, contents)
+	}
+}
@@ -1,2 +1,58 @@
+#[cfg(
+	any(
+		feature = "tar",
+		feature = "zip"
+	)
+)]
+mod archive;
+#[cfg(
+	any(
+		feature = "tar",
+		feature = "zip"
+	)
+)]
+pub use archive::*;
+mod blocking;
+pub use blocking::*;
+mod cassette;
+pub use cassette::*;
+pub mod conformance;
+#[cfg(
+	any(
+		feature = "gzip",
+		feature = "zip"
+	)
+)]
+mod crc32;
+pub mod differential;
+mod expectation;
+pub use expectation::*;
+mod fault;
+pub use fault::*;
 mod generated;
 pub use generated::*;
+mod jail;
+pub use jail::*;
+mod mem;
+pub use mem::*;
+mod mount;
+pub use mount::*;
+mod operation;
+pub use operation::*;
+mod overlay;
+pub use overlay::*;
+mod read_only;
+pub use read_only::*;
+mod recording;
+pub use recording::*;
+mod rng;
+#[cfg(feature = "tar")]
+mod tar;
+#[cfg(feature = "tar")]
+pub use tar::*;
+mod tree_diff;
+pub use tree_diff::*;
+#[cfg(feature = "zip")]
+mod zip;
+#[cfg(feature = "zip")]
+pub use zip::*;
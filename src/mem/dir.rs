@@ -0,0 +1,99 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::vec;
+
+use super::MemFileType;
+use super::MemFs;
+use super::MemMetadata;
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::Fs;
+use crate::ReadDir;
+
+/// A builder for creating directories in a [`MemFs`].
+#[derive(Debug)]
+pub struct MemDirBuilder {
+	fs: MemFs,
+	recursive: bool,
+}
+
+impl MemDirBuilder {
+	pub(super) fn new(fs: MemFs) -> Self {
+		Self {
+			fs,
+			recursive: false,
+		}
+	}
+}
+
+impl DirBuilder for MemDirBuilder {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.recursive = recursive;
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		if self.recursive {
+			self.fs.create_dir_all(path)
+		} else {
+			self.fs.create_dir(path)
+		}
+	}
+}
+
+/// Iterator over the entries of a directory in a [`MemFs`], as of when it was read.
+#[derive(Debug)]
+pub struct MemReadDir {
+	entries: vec::IntoIter<MemDirEntry>,
+}
+
+impl MemReadDir {
+	pub(super) fn new(entries: Vec<MemDirEntry>) -> Self {
+		Self {
+			entries: entries.into_iter(),
+		}
+	}
+}
+
+impl Iterator for MemReadDir {
+	type Item = io::Result<MemDirEntry>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.entries.next().map(Ok)
+	}
+}
+
+impl ReadDir for MemReadDir {
+	type DirEntry = MemDirEntry;
+}
+
+/// An entry returned by [`MemReadDir`].
+#[derive(Debug)]
+pub struct MemDirEntry {
+	pub(super) fs: MemFs,
+	pub(super) path: PathBuf,
+	pub(super) file_type: MemFileType,
+}
+
+impl DirEntry for MemDirEntry {
+	type FileType = MemFileType;
+	type Metadata = MemMetadata;
+
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.fs.symlink_metadata(&self.path)
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		Ok(self.file_type)
+	}
+
+	fn file_name(&self) -> OsString {
+		self.path.file_name().map(OsString::from).unwrap_or_default()
+	}
+}
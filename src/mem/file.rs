@@ -0,0 +1,321 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::tree::Ino;
+use super::tree::Kind;
+use super::tree::Tree;
+use super::MemFileTimes;
+use super::MemFs;
+use super::MemMetadata;
+use super::MemPermissions;
+use crate::File;
+use crate::OpenOptions;
+
+/// An open file in a [`MemFs`].
+///
+/// Like a file descriptor, a handle keeps its node alive after the last link to it is removed.
+/// Handles created through [`File::try_clone`] share their cursor.
+#[derive(Debug)]
+pub struct MemFile {
+	fs: MemFs,
+	ino: Ino,
+	position: Arc<Mutex<u64>>,
+	read: bool,
+	write: bool,
+	append: bool,
+}
+
+impl MemFile {
+	fn with_data<T>(
+		&self,
+		f: impl FnOnce(&mut Vec<u8>, &mut u64) -> io::Result<T>,
+	) -> io::Result<T> {
+		let mut tree = self.fs.lock();
+		let mut position = self.position.lock().unwrap_or_else(|error| error.into_inner());
+		match &mut tree.node_mut(self.ino).kind {
+			Kind::File(data) => f(Arc::make_mut(data), &mut position),
+			Kind::Dir(_) => Err(io::ErrorKind::IsADirectory.into()),
+			Kind::Symlink(_) => unreachable!("symbolic links are never opened"),
+		}
+	}
+
+	fn check_writable(&self) -> io::Result<()> {
+		if self.write || self.append {
+			Ok(())
+		} else {
+			Err(io::Error::new(io::ErrorKind::PermissionDenied, "file not opened for writing"))
+		}
+	}
+
+	fn touch(&self, accessed: bool, modified: bool) {
+		let mut tree = self.fs.lock();
+		let node = tree.node_mut(self.ino);
+		let now = SystemTime::now();
+		if accessed {
+			node.accessed = now;
+		}
+		if modified {
+			node.modified = now;
+		}
+	}
+}
+
+impl Drop for MemFile {
+	fn drop(&mut self) {
+		self.fs.lock().close_handle(self.ino);
+	}
+}
+
+impl io::Read for MemFile {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		if !self.read {
+			return Err(io::Error::new(
+				io::ErrorKind::PermissionDenied,
+				"file not opened for reading",
+			));
+		}
+		let read = self.with_data(|data, position| {
+			let start = usize::try_from(*position).unwrap_or(usize::MAX).min(data.len());
+			let len = buf.len().min(data.len() - start);
+			buf[..len].copy_from_slice(&data[start..start + len]);
+			*position += len as u64;
+			Ok(len)
+		})?;
+		self.touch(true, false);
+		Ok(read)
+	}
+}
+
+impl io::Write for MemFile {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.check_writable()?;
+		let append = self.append;
+		self.with_data(|data, position| {
+			if append {
+				*position = data.len() as u64;
+			}
+			let start = usize::try_from(*position).map_err(|_| io::ErrorKind::FileTooLarge)?;
+			let end = start + buf.len();
+			if data.len() < end {
+				data.resize(end, 0);
+			}
+			data[start..end].copy_from_slice(buf);
+			*position = end as u64;
+			Ok(())
+		})?;
+		self.touch(false, true);
+		Ok(buf.len())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl io::Seek for MemFile {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		let (base, offset) = match pos {
+			io::SeekFrom::Start(offset) => {
+				*self.position.lock().unwrap_or_else(|error| error.into_inner()) = offset;
+				return Ok(offset);
+			}
+			io::SeekFrom::End(offset) => (self.fs.lock().node(self.ino).len(), offset),
+			io::SeekFrom::Current(offset) => {
+				(*self.position.lock().unwrap_or_else(|error| error.into_inner()), offset)
+			}
+		};
+		let position = base.checked_add_signed(offset).ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"invalid seek to a negative or overflowing position",
+			)
+		})?;
+		*self.position.lock().unwrap_or_else(|error| error.into_inner()) = position;
+		Ok(position)
+	}
+}
+
+impl File for MemFile {
+	type FileTimes = MemFileTimes;
+	type Metadata = MemMetadata;
+	type Permissions = MemPermissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		Ok(())
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		Ok(())
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		if !(self.write || self.append) {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		let size = usize::try_from(size).map_err(|_| io::ErrorKind::FileTooLarge)?;
+		self.with_data(|data, _| {
+			data.resize(size, 0);
+			Ok(())
+		})?;
+		self.touch(false, true);
+		Ok(())
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		Ok(MemMetadata::new(self.fs.lock().node(self.ino)))
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		self.fs.lock().open_handle(self.ino);
+		Ok(Self {
+			fs: self.fs.clone(),
+			ino: self.ino,
+			position: Arc::clone(&self.position),
+			read: self.read,
+			write: self.write,
+			append: self.append,
+		})
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		self.fs.lock().node_mut(self.ino).readonly = perm.readonly;
+		Ok(())
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		let mut tree = self.fs.lock();
+		let node = tree.node_mut(self.ino);
+		if let Some(accessed) = times.accessed {
+			node.accessed = accessed;
+		}
+		if let Some(modified) = times.modified {
+			node.modified = modified;
+		}
+		Ok(())
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		self.fs.lock().node_mut(self.ino).modified = time;
+		Ok(())
+	}
+}
+
+/// Options for opening files in a [`MemFs`], validated the same way as [`std::fs::OpenOptions`].
+#[derive(Clone, Debug)]
+pub struct MemOpenOptions {
+	fs: MemFs,
+	read: bool,
+	write: bool,
+	append: bool,
+	truncate: bool,
+	create: bool,
+	create_new: bool,
+}
+
+impl MemOpenOptions {
+	pub(super) fn new(fs: MemFs) -> Self {
+		Self {
+			fs,
+			read: false,
+			write: false,
+			append: false,
+			truncate: false,
+			create: false,
+			create_new: false,
+		}
+	}
+
+	fn validate(&self) -> io::Result<()> {
+		if !self.read && !self.write && !self.append {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		let writable = self.write || self.append;
+		if !writable && (self.truncate || self.create || self.create_new) {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		if self.append && self.truncate && !self.create_new {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		Ok(())
+	}
+
+	fn open_ino(&self, tree: &mut Tree, path: &Path) -> io::Result<Ino> {
+		let resolved = tree.resolve(path, !self.create_new)?;
+		let ino = match resolved.ino {
+			Some(_) if self.create_new => return Err(io::ErrorKind::AlreadyExists.into()),
+			Some(ino) => ino,
+			None if self.create || self.create_new => {
+				let name = resolved.entry_name()?.clone();
+				return tree.insert(resolved.parent(), name, Kind::File(Arc::default()));
+			}
+			None => return Err(io::ErrorKind::NotFound.into()),
+		};
+		let writable = self.write || self.append;
+		let node = tree.node_mut(ino);
+		match &mut node.kind {
+			Kind::Dir(_) if writable => Err(io::ErrorKind::IsADirectory.into()),
+			Kind::File(_) if writable && node.readonly => {
+				Err(io::ErrorKind::PermissionDenied.into())
+			}
+			Kind::File(data) if self.truncate => {
+				*data = Arc::default();
+				node.modified = SystemTime::now();
+				Ok(ino)
+			}
+			_ => Ok(ino),
+		}
+	}
+}
+
+impl OpenOptions for MemOpenOptions {
+	type File = MemFile;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		self.validate()?;
+		let mut tree = self.fs.lock();
+		let ino = self.open_ino(&mut tree, path.as_ref())?;
+		tree.open_handle(ino);
+		drop(tree);
+		Ok(MemFile {
+			fs: self.fs.clone(),
+			ino,
+			position: Arc::new(Mutex::new(0)),
+			read: self.read,
+			write: self.write,
+			append: self.append,
+		})
+	}
+}
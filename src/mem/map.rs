@@ -0,0 +1,226 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+type Link<K, V> = Option<Arc<Node<K, V>>>;
+
+/// A persistent ordered map.
+///
+/// The map is a treap whose priorities are hashes of the keys. Cloning is O(1) and clones share
+/// every node; an update only copies the expected O(log n) nodes on the path to the changed entry.
+pub(super) struct Map<K, V> {
+	root: Link<K, V>,
+}
+
+#[derive(Clone)]
+struct Node<K, V> {
+	key: K,
+	value: V,
+	priority: u64,
+	left: Link<K, V>,
+	right: Link<K, V>,
+}
+
+fn priority<K: Hash + ?Sized>(key: &K) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	key.hash(&mut hasher);
+	hasher.finish()
+}
+
+impl<K, V> Map<K, V> {
+	pub fn new() -> Self {
+		Self {
+			root: None,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.root.is_none()
+	}
+
+	/// Returns whether both maps share the same nodes, which implies that they are equal.
+	pub fn ptr_eq(&self, other: &Self) -> bool {
+		match (&self.root, &other.root) {
+			(Some(a), Some(b)) => Arc::ptr_eq(a, b),
+			(None, None) => true,
+			_ => false,
+		}
+	}
+
+	/// Iterates over the entries in key order.
+	pub fn iter(&self) -> Iter<'_, K, V> {
+		let mut iter = Iter {
+			stack: Vec::new(),
+		};
+		iter.descend(&self.root);
+		iter
+	}
+
+	pub fn values(&self) -> impl Iterator<Item = &V> {
+		self.iter().map(|(_, value)| value)
+	}
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> Map<K, V> {
+	pub fn get<Q: Ord + ?Sized>(&self, key: &Q) -> Option<&V>
+	where
+		K: Borrow<Q>,
+	{
+		let mut link = &self.root;
+		while let Some(node) = link {
+			link = match key.cmp(node.key.borrow()) {
+				Ordering::Less => &node.left,
+				Ordering::Greater => &node.right,
+				Ordering::Equal => return Some(&node.value),
+			};
+		}
+		None
+	}
+
+	pub fn contains_key<Q: Ord + ?Sized>(&self, key: &Q) -> bool
+	where
+		K: Borrow<Q>,
+	{
+		self.get(key).is_some()
+	}
+
+	pub fn get_mut<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+	where
+		K: Borrow<Q>,
+	{
+		let mut link = &mut self.root;
+		while let Some(node) = link {
+			let node = Arc::make_mut(node);
+			link = match key.cmp(node.key.borrow()) {
+				Ordering::Less => &mut node.left,
+				Ordering::Greater => &mut node.right,
+				Ordering::Equal => return Some(&mut node.value),
+			};
+		}
+		None
+	}
+
+	pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+		let (left, previous, right) = split(self.root.take(), &key);
+		let node = Node {
+			priority: priority(&key),
+			key,
+			value,
+			left: None,
+			right: None,
+		};
+		self.root = merge(merge(left, Some(Arc::new(node))), right);
+		previous
+	}
+
+	pub fn remove<Q: Ord + ?Sized>(&mut self, key: &Q) -> Option<V>
+	where
+		K: Borrow<Q>,
+	{
+		let (left, previous, right) = split(self.root.take(), key);
+		self.root = merge(left, right);
+		previous
+	}
+}
+
+/// Splits a subtree into the entries ordered before `key`, the value at `key` and the entries
+/// ordered after it.
+fn split<K, V, Q>(link: Link<K, V>, key: &Q) -> (Link<K, V>, Option<V>, Link<K, V>)
+where
+	K: Borrow<Q> + Clone,
+	V: Clone,
+	Q: Ord + ?Sized,
+{
+	let Some(node) = link else {
+		return (None, None, None);
+	};
+	let mut node = Arc::unwrap_or_clone(node);
+	match key.cmp(node.key.borrow()) {
+		Ordering::Less => {
+			let (left, found, right) = split(node.left.take(), key);
+			node.left = right;
+			(left, found, Some(Arc::new(node)))
+		}
+		Ordering::Greater => {
+			let (left, found, right) = split(node.right.take(), key);
+			node.right = left;
+			(Some(Arc::new(node)), found, right)
+		}
+		Ordering::Equal => (node.left, Some(node.value), node.right),
+	}
+}
+
+/// Joins two subtrees where every key in `left` is ordered before every key in `right`.
+fn merge<K: Clone, V: Clone>(left: Link<K, V>, right: Link<K, V>) -> Link<K, V> {
+	match (left, right) {
+		(None, link) | (link, None) => link,
+		(Some(left), Some(right)) if left.priority >= right.priority => {
+			let mut left = Arc::unwrap_or_clone(left);
+			left.right = merge(left.right.take(), Some(right));
+			Some(Arc::new(left))
+		}
+		(Some(left), Some(right)) => {
+			let mut right = Arc::unwrap_or_clone(right);
+			right.left = merge(Some(left), right.left.take());
+			Some(Arc::new(right))
+		}
+	}
+}
+
+impl<K, V> Clone for Map<K, V> {
+	fn clone(&self) -> Self {
+		Self {
+			root: self.root.clone(),
+		}
+	}
+}
+
+impl<K, V> Default for Map<K, V> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for Map<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<K: Ord + Hash + Clone, V: Clone> FromIterator<(K, V)> for Map<K, V> {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+		let mut map = Self::new();
+		for (key, value) in iter {
+			map.insert(key, value);
+		}
+		map
+	}
+}
+
+/// In-order iterator over the entries of a [`Map`].
+pub(super) struct Iter<'a, K, V> {
+	stack: Vec<&'a Node<K, V>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+	fn descend(&mut self, mut link: &'a Link<K, V>) {
+		while let Some(node) = link {
+			self.stack.push(node);
+			link = &node.left;
+		}
+	}
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+	type Item = (&'a K, &'a V);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let node = self.stack.pop()?;
+		self.descend(&node.right);
+		Some((&node.key, &node.value))
+	}
+}
@@ -0,0 +1,146 @@
+use std::io;
+use std::time::SystemTime;
+
+use super::tree::Kind;
+use super::tree::Node;
+use crate::FileTimes;
+use crate::FileType;
+use crate::Metadata;
+use crate::Permissions;
+
+/// The type of a node in a [`MemFs`](super::MemFs).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MemFileType(Type);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Type {
+	Dir,
+	File,
+	Symlink,
+}
+
+impl FileType for MemFileType {
+	fn is_dir(&self) -> bool {
+		self.0 == Type::Dir
+	}
+
+	fn is_file(&self) -> bool {
+		self.0 == Type::File
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.0 == Type::Symlink
+	}
+}
+
+impl From<&Kind> for MemFileType {
+	fn from(kind: &Kind) -> Self {
+		Self(match kind {
+			Kind::Dir(_) => Type::Dir,
+			Kind::File(_) => Type::File,
+			Kind::Symlink(_) => Type::Symlink,
+		})
+	}
+}
+
+/// Permissions of a node in a [`MemFs`](super::MemFs). Only the read-only flag is modelled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemPermissions {
+	pub(super) readonly: bool,
+}
+
+impl Permissions for MemPermissions {
+	fn readonly(&self) -> bool {
+		self.readonly
+	}
+
+	fn set_readonly(&mut self, readonly: bool) {
+		self.readonly = readonly;
+	}
+}
+
+/// Timestamps to apply to a [`MemFile`](super::MemFile).
+#[derive(Clone, Debug, Default)]
+pub struct MemFileTimes {
+	pub(super) accessed: Option<SystemTime>,
+	pub(super) modified: Option<SystemTime>,
+}
+
+impl FileTimes for MemFileTimes {
+	fn set_accessed(mut self, t: SystemTime) -> Self {
+		self.accessed = Some(t);
+		self
+	}
+
+	fn set_modified(mut self, t: SystemTime) -> Self {
+		self.modified = Some(t);
+		self
+	}
+}
+
+/// A snapshot of the metadata of a node in a [`MemFs`](super::MemFs).
+#[derive(Clone, Debug)]
+pub struct MemMetadata {
+	file_type: MemFileType,
+	len: u64,
+	permissions: MemPermissions,
+	accessed: SystemTime,
+	modified: SystemTime,
+	created: SystemTime,
+}
+
+impl MemMetadata {
+	pub(super) fn new(node: &Node) -> Self {
+		Self {
+			file_type: MemFileType::from(&node.kind),
+			len: node.len(),
+			permissions: MemPermissions {
+				readonly: node.readonly,
+			},
+			accessed: node.accessed,
+			modified: node.modified,
+			created: node.created,
+		}
+	}
+}
+
+impl Metadata for MemMetadata {
+	type FileType = MemFileType;
+	type Permissions = MemPermissions;
+
+	fn file_type(&self) -> Self::FileType {
+		self.file_type
+	}
+
+	fn is_dir(&self) -> bool {
+		self.file_type.is_dir()
+	}
+
+	fn is_file(&self) -> bool {
+		self.file_type.is_file()
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.file_type.is_symlink()
+	}
+
+	fn len(&self) -> u64 {
+		self.len
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		self.permissions.clone()
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		Ok(self.modified)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		Ok(self.accessed)
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		Ok(self.created)
+	}
+}
@@ -0,0 +1,259 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+mod dir;
+pub use dir::*;
+mod file;
+pub use file::*;
+mod map;
+mod metadata;
+pub use metadata::*;
+mod snapshot;
+pub use snapshot::*;
+mod tree;
+use tree::Tree;
+
+use crate::Fs;
+use crate::Metadata;
+use crate::OpenOptions;
+
+/// An in-memory filesystem.
+///
+/// Relative paths are resolved against the root directory. Errors use the same
+/// [`io::ErrorKind`]s as [`std::fs`] on Linux. Clones share the same tree, so handles such as
+/// [`MemFile`] and [`MemDirBuilder`] keep working on the filesystem they were created from.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+///
+/// let fs = MemFs::new();
+/// fs.create_dir_all("a/b")?;
+/// fs.write("a/b/c.txt", "hello")?;
+/// assert_eq!(fs.read_to_string("/a/b/c.txt")?, "hello");
+/// assert_eq!(fs.remove_dir("a").unwrap_err().kind(), std::io::ErrorKind::DirectoryNotEmpty);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemFs {
+	tree: Arc<Mutex<Tree>>,
+}
+
+impl MemFs {
+	/// Creates an empty filesystem containing only the root directory.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a new symbolic link at `link` pointing to `original`.
+	///
+	/// Like [`std::os::unix::fs::symlink`], `original` is stored verbatim and may dangle.
+	pub fn symlink<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		self.lock().symlink(original.as_ref(), link.as_ref())
+	}
+
+	/// Captures the current state of the filesystem in O(1).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use io_fs::Fs;
+	/// use io_fs::MemChange;
+	/// use io_fs::MemFs;
+	///
+	/// let fs = MemFs::new();
+	/// fs.write("fixture.txt", "original")?;
+	/// let snapshot = fs.snapshot();
+	/// fs.write("fixture.txt", "mutated")?;
+	/// assert_eq!(fs.changes_since(&snapshot), [MemChange::Modified("/fixture.txt".into())]);
+	/// fs.restore(&snapshot);
+	/// assert_eq!(fs.read_to_string("fixture.txt")?, "original");
+	/// # Ok::<(), std::io::Error>(())
+	/// ```
+	pub fn snapshot(&self) -> MemSnapshot {
+		MemSnapshot {
+			state: self.lock().state().clone(),
+		}
+	}
+
+	/// Rolls the filesystem back to `snapshot` in O(1), discarding every change made since.
+	///
+	/// The snapshot may come from another `MemFs`. Open handles stay usable: a handle whose file
+	/// is not part of the snapshot behaves as though the file had been removed.
+	pub fn restore(&self, snapshot: &MemSnapshot) {
+		self.lock().restore(&snapshot.state);
+	}
+
+	/// Lists the paths that changed since `snapshot` was taken.
+	///
+	/// This is a shorthand for [`MemSnapshot::changes`] with a snapshot of the current state.
+	pub fn changes_since(&self, snapshot: &MemSnapshot) -> Vec<MemChange> {
+		snapshot.changes(&self.snapshot())
+	}
+
+	fn lock(&self) -> MutexGuard<'_, Tree> {
+		self.tree.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
+
+impl Fs for MemFs {
+	type DirBuilder = MemDirBuilder;
+	type DirEntry = MemDirEntry;
+	type File = MemFile;
+	type FileTimes = MemFileTimes;
+	type FileType = MemFileType;
+	type Metadata = MemMetadata;
+	type OpenOptions = MemOpenOptions;
+	type Permissions = MemPermissions;
+	type ReadDir = MemReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		MemDirBuilder::new(self.clone())
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		MemFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		MemOpenOptions::new(self.clone())
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.lock().canonicalize(path.as_ref())
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let mut source = self.open_options().read(true).open(from)?;
+		let metadata = crate::File::metadata(&source)?;
+		if !metadata.is_file() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"the source path is neither a regular file nor a symlink to a regular file",
+			));
+		}
+		let mut contents = Vec::new();
+		source.read_to_end(&mut contents)?;
+		let mut destination =
+			self.open_options().write(true).create(true).truncate(true).open(to)?;
+		destination.write_all(&contents)?;
+		crate::File::set_permissions(&destination, metadata.permissions())?;
+		Ok(contents.len() as u64)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.lock().create_dir(path.as_ref())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.lock().create_dir_all(path.as_ref())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		match self.metadata(path) {
+			Ok(_) => Ok(true),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+			Err(error) => Err(error),
+		}
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		self.lock().hard_link(original.as_ref(), link.as_ref())
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let tree = self.lock();
+		let ino = tree.lookup(path.as_ref(), true)?;
+		Ok(MemMetadata::new(tree.node(ino)))
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let mut contents = Vec::new();
+		self.open_options().read(true).open(path)?.read_to_end(&mut contents)?;
+		Ok(contents)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		let path = path.as_ref();
+		let mut tree = self.lock();
+		let entries = tree
+			.read_dir(path)?
+			.into_iter()
+			.map(|(name, ino)| {
+				MemDirEntry {
+					fs: self.clone(),
+					path: path.join(name),
+					file_type: MemFileType::from(&tree.node(ino).kind),
+				}
+			})
+			.collect();
+		Ok(MemReadDir::new(entries))
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.lock().read_link(path.as_ref())
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		String::from_utf8(self.read(path)?).map_err(|_| {
+			io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+		})
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.lock().remove_dir(path.as_ref())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.lock().remove_dir_all(path.as_ref())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.lock().remove_file(path.as_ref())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		self.lock().rename(from.as_ref(), to.as_ref())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let mut tree = self.lock();
+		let ino = tree.lookup(path.as_ref(), true)?;
+		tree.node_mut(ino).readonly = perm.readonly;
+		Ok(())
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let tree = self.lock();
+		let ino = tree.lookup(path.as_ref(), false)?;
+		Ok(MemMetadata::new(tree.node(ino)))
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		self.open_options()
+			.write(true)
+			.create(true)
+			.truncate(true)
+			.open(path)?
+			.write_all(contents.as_ref())
+	}
+}
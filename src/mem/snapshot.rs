@@ -0,0 +1,113 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::tree::Ino;
+use super::tree::Kind;
+use super::tree::Node;
+use super::tree::State;
+use super::tree::ROOT;
+
+/// The state of a [`MemFs`](super::MemFs) at the time [`MemFs::snapshot`](super::MemFs::snapshot)
+/// was called.
+///
+/// Snapshots share their nodes with the filesystem and with each other, so taking, cloning and
+/// restoring one is O(1). Data is only copied when a node that is shared gets modified.
+#[derive(Clone, Debug)]
+pub struct MemSnapshot {
+	pub(super) state: State,
+}
+
+/// A difference between two [`MemSnapshot`]s, as returned by [`MemSnapshot::changes`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MemChange {
+	/// The path only exists in the later snapshot. Entries below an added directory are not
+	/// listed.
+	Added(PathBuf),
+	/// The path only exists in the earlier snapshot. Entries below a removed directory are not
+	/// listed.
+	Removed(PathBuf),
+	/// The path exists in both snapshots but its type, contents, link target or permissions
+	/// differ.
+	Modified(PathBuf),
+}
+
+impl MemChange {
+	/// Returns the path that changed.
+	pub fn path(&self) -> &Path {
+		match self {
+			Self::Added(path) | Self::Removed(path) | Self::Modified(path) => path,
+		}
+	}
+}
+
+impl MemSnapshot {
+	/// Lists the paths that differ between `self` and the `later` snapshot, sorted by path.
+	///
+	/// Timestamps are ignored, as are the identities of nodes: a file that is replaced by an
+	/// identical one is unchanged.
+	pub fn changes(&self, later: &MemSnapshot) -> Vec<MemChange> {
+		let mut changes = Vec::new();
+		if !self.state.nodes.ptr_eq(&later.state.nodes) {
+			let mut path = PathBuf::from("/");
+			compare(&self.state, &later.state, ROOT, ROOT, &mut path, &mut changes);
+			changes.sort_by(|a, b| a.path().cmp(b.path()));
+		}
+		changes
+	}
+}
+
+fn node(state: &State, ino: Ino) -> &Node {
+	state.nodes.get(&ino).expect("dangling inode")
+}
+
+fn compare(
+	before: &State,
+	after: &State,
+	old: Ino,
+	new: Ino,
+	path: &mut PathBuf,
+	changes: &mut Vec<MemChange>,
+) {
+	let (Kind::Dir(old_entries), Kind::Dir(new_entries)) =
+		(&node(before, old).kind, &node(after, new).kind)
+	else {
+		unreachable!("only directories are compared entry by entry")
+	};
+	for (name, &old) in old_entries.iter() {
+		path.push(name);
+		match new_entries.get(name) {
+			None => changes.push(MemChange::Removed(path.clone())),
+			Some(&new) => {
+				let (old_node, new_node) = (node(before, old), node(after, new));
+				match (&old_node.kind, &new_node.kind) {
+					(Kind::Dir(_), Kind::Dir(_)) => {
+						if old_node.readonly != new_node.readonly {
+							changes.push(MemChange::Modified(path.clone()));
+						}
+						compare(before, after, old, new, path, changes);
+					}
+					_ if !same_contents(old_node, new_node) => {
+						changes.push(MemChange::Modified(path.clone()));
+					}
+					_ => {}
+				}
+			}
+		}
+		path.pop();
+	}
+	for (name, _) in new_entries.iter() {
+		if !old_entries.contains_key(name) {
+			changes.push(MemChange::Added(path.join(name)));
+		}
+	}
+}
+
+fn same_contents(a: &Node, b: &Node) -> bool {
+	let kind = match (&a.kind, &b.kind) {
+		(Kind::File(a), Kind::File(b)) => Arc::ptr_eq(a, b) || a == b,
+		(Kind::Symlink(a), Kind::Symlink(b)) => a == b,
+		_ => false,
+	};
+	kind && a.readonly == b.readonly
+}
@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use super::map::Map;
+
+/// Maximum number of symbolic links followed while resolving a single path, matching Linux.
+const MAX_SYMLINKS: usize = 40;
+
+pub(super) type Ino = u64;
+
+pub(super) const ROOT: Ino = 0;
+
+/// Inode numbers are unique across every [`MemFs`](super::MemFs), so a snapshot can be restored
+/// into any filesystem without its nodes being confused with ones that open handles refer to.
+static NEXT_INO: AtomicU64 = AtomicU64::new(ROOT + 1);
+
+/// The contents of a node. File data is shared with snapshots until it is written to.
+#[derive(Clone, Debug)]
+pub(super) enum Kind {
+	File(Arc<Vec<u8>>),
+	Dir(Map<OsString, Ino>),
+	Symlink(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct Node {
+	pub kind: Kind,
+	pub readonly: bool,
+	pub accessed: SystemTime,
+	pub modified: SystemTime,
+	pub created: SystemTime,
+	/// Number of directory entries referring to this node.
+	links: usize,
+}
+
+impl Node {
+	fn new(kind: Kind) -> Self {
+		let now = SystemTime::now();
+		Self {
+			kind,
+			readonly: false,
+			accessed: now,
+			modified: now,
+			created: now,
+			links: 0,
+		}
+	}
+
+	pub fn len(&self) -> u64 {
+		match &self.kind {
+			Kind::File(data) => data.len() as u64,
+			Kind::Dir(_) => 0,
+			Kind::Symlink(target) => target.as_os_str().len() as u64,
+		}
+	}
+}
+
+/// The result of walking a path.
+#[derive(Debug)]
+pub(super) struct Resolved {
+	/// The directories walked through, starting at the root. The last one contains `name`.
+	pub ancestors: Vec<(OsString, Ino)>,
+	/// The final path component, or `None` if the path resolved to a directory through `/`, `.`,
+	/// or `..`.
+	pub name: Option<OsString>,
+	/// The node the path refers to, if it exists.
+	pub ino: Option<Ino>,
+}
+
+impl Resolved {
+	pub fn parent(&self) -> Ino {
+		self.ancestors.last().map_or(ROOT, |(_, ino)| *ino)
+	}
+
+	pub fn existing(&self) -> io::Result<Ino> {
+		self.ino.ok_or_else(|| io::ErrorKind::NotFound.into())
+	}
+
+	/// Returns the final component for operations that create or remove a directory entry.
+	pub fn entry_name(&self) -> io::Result<&OsString> {
+		self.name.as_ref().ok_or_else(|| io::ErrorKind::ResourceBusy.into())
+	}
+
+	pub fn to_path(&self) -> PathBuf {
+		let mut path = PathBuf::from("/");
+		path.extend(self.ancestors.iter().skip(1).map(|(name, _)| name));
+		path.extend(&self.name);
+		path
+	}
+}
+
+/// The persistent part of a [`Tree`], which snapshots share.
+#[derive(Clone, Debug)]
+pub(super) struct State {
+	pub nodes: Map<Ino, Node>,
+	/// Nodes without links that are only kept alive by open handles.
+	orphans: Map<Ino, ()>,
+}
+
+#[derive(Debug)]
+pub(super) struct Tree {
+	state: State,
+	/// Number of open file handles referring to each node. Handles are not part of snapshots.
+	handles: HashMap<Ino, usize>,
+}
+
+impl Default for Tree {
+	fn default() -> Self {
+		let mut root = Node::new(Kind::Dir(Map::new()));
+		root.links = 1;
+		Self {
+			state: State {
+				nodes: Map::from_iter([(ROOT, root)]),
+				orphans: Map::new(),
+			},
+			handles: HashMap::new(),
+		}
+	}
+}
+
+impl Tree {
+	pub fn node(&self, ino: Ino) -> &Node {
+		self.state.nodes.get(&ino).expect("dangling inode")
+	}
+
+	pub fn node_mut(&mut self, ino: Ino) -> &mut Node {
+		self.state.nodes.get_mut(&ino).expect("dangling inode")
+	}
+
+	pub fn state(&self) -> &State {
+		&self.state
+	}
+
+	/// Replaces the tree with `state`. Nodes that open handles refer to are carried over as orphans
+	/// if `state` does not contain them, as though they had been removed.
+	pub fn restore(&mut self, state: &State) {
+		let mut state = state.clone();
+		for &ino in self.handles.keys() {
+			if state.nodes.contains_key(&ino) {
+				continue;
+			}
+			let mut node = self.node(ino).clone();
+			node.links = 0;
+			if let Kind::Dir(entries) = &mut node.kind {
+				*entries = Map::new();
+			}
+			state.nodes.insert(ino, node);
+			state.orphans.insert(ino, ());
+		}
+		let stale: Vec<Ino> = state
+			.orphans
+			.iter()
+			.map(|(ino, ())| *ino)
+			.filter(|ino| !self.handles.contains_key(ino))
+			.collect();
+		self.state = state;
+		for ino in stale {
+			self.collect(ino);
+		}
+	}
+
+	fn entries(&self, ino: Ino) -> io::Result<&Map<OsString, Ino>> {
+		match &self.node(ino).kind {
+			Kind::Dir(entries) => Ok(entries),
+			_ => Err(io::ErrorKind::NotADirectory.into()),
+		}
+	}
+
+	fn entries_mut(&mut self, ino: Ino) -> io::Result<&mut Map<OsString, Ino>> {
+		let node = self.node_mut(ino);
+		node.modified = SystemTime::now();
+		match &mut node.kind {
+			Kind::Dir(entries) => Ok(entries),
+			_ => Err(io::ErrorKind::NotADirectory.into()),
+		}
+	}
+
+	/// Walks `path` from the root, following symbolic links in every component except possibly the
+	/// last. Relative paths are resolved against the root.
+	pub fn resolve(&self, path: &Path, follow: bool) -> io::Result<Resolved> {
+		let mut components: VecDeque<Component> = path.components().collect();
+		let mut ancestors = vec![(OsString::new(), ROOT)];
+		let mut symlinks = 0;
+		while let Some(component) = components.pop_front() {
+			let name = match component {
+				Component::Prefix(_) | Component::RootDir => {
+					ancestors.truncate(1);
+					continue;
+				}
+				Component::CurDir => continue,
+				Component::ParentDir => {
+					if ancestors.len() > 1 {
+						ancestors.pop();
+					}
+					continue;
+				}
+				Component::Normal(name) => name,
+			};
+			let dir = ancestors.last().expect("root is never popped").1;
+			let is_last = components.is_empty();
+			let Some(&ino) = self.entries(dir)?.get(name) else {
+				if is_last {
+					return Ok(Resolved {
+						ancestors,
+						name: Some(name.to_owned()),
+						ino: None,
+					});
+				}
+				return Err(io::ErrorKind::NotFound.into());
+			};
+			match &self.node(ino).kind {
+				Kind::Symlink(target) if follow || !is_last => {
+					symlinks += 1;
+					if symlinks > MAX_SYMLINKS {
+						return Err(io::Error::other("too many levels of symbolic links"));
+					}
+					for component in target.components().rev() {
+						components.push_front(component);
+					}
+				}
+				Kind::Dir(_) if !is_last => ancestors.push((name.to_owned(), ino)),
+				_ if !is_last => return Err(io::ErrorKind::NotADirectory.into()),
+				_ => {
+					return Ok(Resolved {
+						ancestors,
+						name: Some(name.to_owned()),
+						ino: Some(ino),
+					})
+				}
+			}
+		}
+		let ino = ancestors.last().expect("root is never popped").1;
+		Ok(Resolved {
+			ancestors,
+			name: None,
+			ino: Some(ino),
+		})
+	}
+
+	pub fn lookup(&self, path: &Path, follow: bool) -> io::Result<Ino> {
+		self.resolve(path, follow)?.existing()
+	}
+
+	/// Adds a new node named `name` to the directory `parent`.
+	pub fn insert(&mut self, parent: Ino, name: OsString, kind: Kind) -> io::Result<Ino> {
+		let ino = NEXT_INO.fetch_add(1, Ordering::Relaxed);
+		self.link(parent, name, ino)?;
+		let mut node = Node::new(kind);
+		node.links = 1;
+		self.state.nodes.insert(ino, node);
+		Ok(ino)
+	}
+
+	/// Adds a directory entry for an existing node.
+	pub fn link(&mut self, parent: Ino, name: OsString, ino: Ino) -> io::Result<()> {
+		let entries = self.entries_mut(parent)?;
+		if entries.contains_key(&name) {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		entries.insert(name, ino);
+		if let Some(node) = self.state.nodes.get_mut(&ino) {
+			node.links += 1;
+		}
+		Ok(())
+	}
+
+	/// Removes a directory entry, dropping its node once nothing refers to it anymore.
+	pub fn unlink(&mut self, parent: Ino, name: &OsString) -> io::Result<Ino> {
+		let ino = self.entries_mut(parent)?.remove(name).ok_or(io::ErrorKind::NotFound)?;
+		self.node_mut(ino).links -= 1;
+		self.collect(ino);
+		Ok(ino)
+	}
+
+	pub fn open_handle(&mut self, ino: Ino) {
+		*self.handles.entry(ino).or_default() += 1;
+	}
+
+	pub fn close_handle(&mut self, ino: Ino) {
+		let handles = self.handles.get_mut(&ino).expect("handle was never opened");
+		*handles -= 1;
+		if *handles == 0 {
+			self.handles.remove(&ino);
+		}
+		self.collect(ino);
+	}
+
+	fn collect(&mut self, ino: Ino) {
+		if self.node(ino).links > 0 {
+			return;
+		}
+		if self.handles.contains_key(&ino) {
+			self.state.orphans.insert(ino, ());
+			return;
+		}
+		self.state.orphans.remove(&ino);
+		if let Some(Node {
+			kind: Kind::Dir(entries),
+			..
+		}) = self.state.nodes.remove(&ino)
+		{
+			for &child in entries.values() {
+				self.node_mut(child).links -= 1;
+				self.collect(child);
+			}
+		}
+	}
+
+	pub fn create_dir(&mut self, path: &Path) -> io::Result<()> {
+		let resolved = self.resolve(path, false)?;
+		if resolved.ino.is_some() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		let name = resolved.entry_name()?.clone();
+		self.insert(resolved.parent(), name, Kind::Dir(Map::new()))?;
+		Ok(())
+	}
+
+	pub fn create_dir_all(&mut self, path: &Path) -> io::Result<()> {
+		if path == Path::new("") {
+			return Ok(());
+		}
+		match self.create_dir(path) {
+			Ok(()) => return Ok(()),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+			Err(_) if self.is_dir(path) => return Ok(()),
+			Err(error) => return Err(error),
+		}
+		match path.parent() {
+			Some(parent) => self.create_dir_all(parent)?,
+			None => return Err(io::Error::other("failed to create whole tree")),
+		}
+		match self.create_dir(path) {
+			Ok(()) => Ok(()),
+			Err(_) if self.is_dir(path) => Ok(()),
+			Err(error) => Err(error),
+		}
+	}
+
+	fn is_dir(&self, path: &Path) -> bool {
+		self.lookup(path, true).is_ok_and(|ino| matches!(self.node(ino).kind, Kind::Dir(_)))
+	}
+
+	pub fn remove_file(&mut self, path: &Path) -> io::Result<()> {
+		let resolved = self.resolve(path, false)?;
+		let ino = resolved.existing()?;
+		if let Kind::Dir(_) = self.node(ino).kind {
+			return Err(io::ErrorKind::IsADirectory.into());
+		}
+		self.unlink(resolved.parent(), resolved.entry_name()?)?;
+		Ok(())
+	}
+
+	pub fn remove_dir(&mut self, path: &Path) -> io::Result<()> {
+		let resolved = self.resolve(path, false)?;
+		let ino = resolved.existing()?;
+		if !self.entries(ino)?.is_empty() {
+			return Err(io::ErrorKind::DirectoryNotEmpty.into());
+		}
+		self.unlink(resolved.parent(), resolved.entry_name()?)?;
+		Ok(())
+	}
+
+	pub fn remove_dir_all(&mut self, path: &Path) -> io::Result<()> {
+		let resolved = self.resolve(path, false)?;
+		let ino = resolved.existing()?;
+		// Symbolic links are removed themselves rather than followed.
+		if let Kind::File(_) = self.node(ino).kind {
+			return Err(io::ErrorKind::NotADirectory.into());
+		}
+		self.unlink(resolved.parent(), resolved.entry_name()?)?;
+		Ok(())
+	}
+
+	pub fn rename(&mut self, from: &Path, to: &Path) -> io::Result<()> {
+		let source = self.resolve(from, false)?;
+		let destination = self.resolve(to, false)?;
+		let ino = source.existing()?;
+		let source_name = source.entry_name()?;
+		let destination_name = destination.entry_name()?;
+		if destination.ancestors.iter().any(|(_, ancestor)| *ancestor == ino) {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		if source.ancestors.iter().any(|(_, ancestor)| Some(*ancestor) == destination.ino) {
+			return Err(io::ErrorKind::DirectoryNotEmpty.into());
+		}
+		let source_is_dir = matches!(self.node(ino).kind, Kind::Dir(_));
+		if let Some(existing) = destination.ino {
+			if existing == ino {
+				return Ok(());
+			}
+			match (source_is_dir, &self.node(existing).kind) {
+				(true, Kind::Dir(entries)) if !entries.is_empty() => {
+					return Err(io::ErrorKind::DirectoryNotEmpty.into())
+				}
+				(true, Kind::Dir(_)) | (false, Kind::File(_) | Kind::Symlink(_)) => {}
+				(true, _) => return Err(io::ErrorKind::NotADirectory.into()),
+				(false, Kind::Dir(_)) => return Err(io::ErrorKind::IsADirectory.into()),
+			}
+			self.unlink(destination.parent(), destination_name)?;
+		}
+		// Link before unlinking so the node is never collected in between.
+		self.link(destination.parent(), destination_name.clone(), ino)?;
+		self.unlink(source.parent(), source_name)?;
+		Ok(())
+	}
+
+	pub fn hard_link(&mut self, original: &Path, link: &Path) -> io::Result<()> {
+		let ino = self.lookup(original, false)?;
+		let resolved = self.resolve(link, false)?;
+		if resolved.ino.is_some() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		if let Kind::Dir(_) = self.node(ino).kind {
+			return Err(io::ErrorKind::PermissionDenied.into());
+		}
+		self.link(resolved.parent(), resolved.entry_name()?.clone(), ino)
+	}
+
+	pub fn symlink(&mut self, original: &Path, link: &Path) -> io::Result<()> {
+		let resolved = self.resolve(link, false)?;
+		if resolved.ino.is_some() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		let name = resolved.entry_name()?.clone();
+		self.insert(resolved.parent(), name, Kind::Symlink(original.to_owned()))?;
+		Ok(())
+	}
+
+	pub fn read_link(&self, path: &Path) -> io::Result<PathBuf> {
+		let ino = self.lookup(path, false)?;
+		match &self.node(ino).kind {
+			Kind::Symlink(target) => Ok(target.clone()),
+			_ => Err(io::ErrorKind::InvalidInput.into()),
+		}
+	}
+
+	pub fn canonicalize(&self, path: &Path) -> io::Result<PathBuf> {
+		let resolved = self.resolve(path, true)?;
+		resolved.existing()?;
+		Ok(resolved.to_path())
+	}
+
+	pub fn read_dir(&mut self, path: &Path) -> io::Result<Vec<(OsString, Ino)>> {
+		let ino = self.lookup(path, true)?;
+		let entries = self.entries(ino)?.iter().map(|(name, ino)| (name.clone(), *ino)).collect();
+		self.node_mut(ino).accessed = SystemTime::now();
+		Ok(entries)
+	}
+}
@@ -0,0 +1,137 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::MountFs;
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::DynDirEntry;
+use crate::DynFs;
+use crate::DynReadDir;
+use crate::Fs;
+use crate::Metadata;
+use crate::ReadDir;
+
+/// A [`DirBuilder`] of a [`MountFs`].
+#[derive(Debug)]
+pub struct MountDirBuilder {
+	pub(super) fs: MountFs,
+	pub(super) recursive: bool,
+}
+
+impl DirBuilder for MountDirBuilder {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.recursive = recursive;
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		if self.recursive {
+			Fs::create_dir_all(&self.fs, path)
+		} else {
+			Fs::create_dir(&self.fs, path)
+		}
+	}
+}
+
+/// Iterator over the entries of a directory of a [`MountFs`], followed by the mount points it
+/// contains.
+pub struct MountReadDir {
+	pub(super) inner: Box<dyn DynReadDir>,
+	/// The path the directory was read with.
+	pub(super) dir: PathBuf,
+	/// The names and backends of the mount points in the directory, listed in place of the
+	/// entries of the same name.
+	pub(super) mount_points: Vec<(OsString, Arc<dyn DynFs>)>,
+}
+
+impl fmt::Debug for MountReadDir {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mount_points = self.mount_points.iter().map(|(name, _)| name).collect::<Vec<_>>();
+		f.debug_struct("MountReadDir")
+			.field("inner", &self.inner)
+			.field("dir", &self.dir)
+			.field("mount_points", &mount_points)
+			.finish()
+	}
+}
+
+impl Iterator for MountReadDir {
+	type Item = io::Result<MountDirEntry>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		for entry in self.inner.by_ref() {
+			let entry = match entry {
+				Ok(entry) => entry,
+				Err(error) => return Some(Err(error)),
+			};
+			let name = DirEntry::file_name(&entry);
+			if self.mount_points.iter().all(|(mount_point, _)| *mount_point != name) {
+				return Some(Ok(MountDirEntry {
+					path: self.dir.join(name),
+					kind: Kind::Entry(entry),
+				}));
+			}
+		}
+		let (name, fs) = self.mount_points.pop()?;
+		Some(Ok(MountDirEntry {
+			path: self.dir.join(name),
+			kind: Kind::MountPoint(fs),
+		}))
+	}
+}
+
+impl ReadDir for MountReadDir {
+	type DirEntry = MountDirEntry;
+}
+
+/// An entry of a [`MountReadDir`], which may be a mount point.
+pub struct MountDirEntry {
+	path: PathBuf,
+	kind: Kind,
+}
+
+enum Kind {
+	Entry(Box<dyn DynDirEntry>),
+	/// A mount point, described by the root of its backend.
+	MountPoint(Arc<dyn DynFs>),
+}
+
+impl fmt::Debug for MountDirEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MountDirEntry")
+			.field("path", &self.path)
+			.field("mount_point", &matches!(self.kind, Kind::MountPoint(_)))
+			.finish()
+	}
+}
+
+impl DirEntry for MountDirEntry {
+	type FileType = Box<dyn crate::DynFileType>;
+	type Metadata = Box<dyn crate::DynMetadata>;
+
+	fn path(&self) -> PathBuf {
+		self.path.clone()
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		match &self.kind {
+			Kind::Entry(entry) => DirEntry::metadata(entry),
+			Kind::MountPoint(fs) => fs.symlink_metadata(Path::new("/")),
+		}
+	}
+
+	fn file_type(&self) -> io::Result<Self::FileType> {
+		match &self.kind {
+			Kind::Entry(entry) => DirEntry::file_type(entry),
+			Kind::MountPoint(_) => Ok(DirEntry::metadata(self)?.file_type()),
+		}
+	}
+
+	fn file_name(&self) -> OsString {
+		self.path.file_name().map(OsString::from).unwrap_or_default()
+	}
+}
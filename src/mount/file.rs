@@ -0,0 +1,176 @@
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::MountFs;
+use crate::DynFile;
+use crate::DynFileTimes;
+use crate::DynMetadata;
+use crate::DynPermissions;
+use crate::File;
+use crate::FileTimes;
+use crate::OpenOptions;
+
+/// Timestamps to apply to a [`MountFile`], whatever the mount it belongs to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MountFileTimes {
+	accessed: Option<SystemTime>,
+	modified: Option<SystemTime>,
+}
+
+impl FileTimes for MountFileTimes {
+	fn set_accessed(mut self, t: SystemTime) -> Self {
+		self.accessed = Some(t);
+		self
+	}
+
+	fn set_modified(mut self, t: SystemTime) -> Self {
+		self.modified = Some(t);
+		self
+	}
+}
+
+/// A [`File`] of a [`MountFs`].
+#[derive(Debug)]
+pub struct MountFile {
+	inner: Box<dyn DynFile>,
+	/// Empty timestamps of the backend of the file, to convert [`MountFileTimes`] into.
+	times: Box<dyn DynFileTimes>,
+}
+
+impl io::Read for MountFile {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.inner.read(buf)
+	}
+}
+
+impl io::Write for MountFile {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.inner.write(buf)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+impl io::Seek for MountFile {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		self.inner.seek(pos)
+	}
+}
+
+impl File for MountFile {
+	type FileTimes = MountFileTimes;
+	type Metadata = Box<dyn DynMetadata>;
+	type Permissions = Box<dyn DynPermissions>;
+
+	fn sync_all(&self) -> io::Result<()> {
+		File::sync_all(&self.inner)
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		File::sync_data(&self.inner)
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		File::set_len(&self.inner, size)
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		File::metadata(&self.inner)
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		Ok(Self {
+			inner: File::try_clone(&self.inner)?,
+			times: self.times.clone(),
+		})
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		File::set_permissions(&self.inner, perm)
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		let mut converted = self.times.clone();
+		if let Some(accessed) = times.accessed {
+			converted = FileTimes::set_accessed(converted, accessed);
+		}
+		if let Some(modified) = times.modified {
+			converted = FileTimes::set_modified(converted, modified);
+		}
+		File::set_times(&self.inner, converted)
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		File::set_modified(&self.inner, time)
+	}
+}
+
+/// [`OpenOptions`] of a [`MountFs`], applied to the backend of the path once it is opened.
+#[derive(Clone, Debug)]
+pub struct MountOpenOptions {
+	pub(super) fs: MountFs,
+	pub(super) flags: Flags,
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct Flags {
+	read: bool,
+	write: bool,
+	append: bool,
+	truncate: bool,
+	create: bool,
+	create_new: bool,
+}
+
+impl OpenOptions for MountOpenOptions {
+	type File = MountFile;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.flags.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let routed = self.fs.route(path.as_ref())?;
+		let mut options = routed.mount.fs.open_options();
+		options
+			.read(self.flags.read)
+			.write(self.flags.write)
+			.append(self.flags.append)
+			.truncate(self.flags.truncate)
+			.create(self.flags.create)
+			.create_new(self.flags.create_new);
+		Ok(MountFile {
+			inner: options.open(&routed.path)?,
+			times: routed.mount.fs.file_times(),
+		})
+	}
+}
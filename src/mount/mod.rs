@@ -0,0 +1,358 @@
+use std::fmt;
+use std::io;
+use std::path;
+use std::path::Component;
+use std::sync::Arc;
+
+mod dir;
+pub use dir::*;
+mod file;
+pub use file::*;
+
+use crate::DynFs;
+use crate::Fs;
+use crate::Metadata;
+use crate::Permissions;
+
+/// A filesystem made of other filesystems mounted at different paths.
+///
+/// Each call is routed to the backend mounted at the longest mount point containing its path, and
+/// the backend sees the path relative to its mount point, so a backend mounted at `/tmp` sees
+/// `/tmp/a` as `/a`. Paths outside every mount point do not exist: mount a backend at `/` to
+/// cover them. Relative paths are resolved against the root, and `..` is resolved lexically.
+///
+/// Mount points are listed by [`read_dir`](Fs::read_dir) in their parent directory, whether or not
+/// the backend of the parent has a directory there. [`rename`](Fs::rename) and
+/// [`hard_link`](Fs::hard_link) fail with [`io::ErrorKind::CrossesDevices`] across mounts, while
+/// [`copy`](Fs::copy) copies the contents and read-only flag. Removing or renaming a mount point or
+/// one of its ancestors fails with [`io::ErrorKind::ResourceBusy`].
+///
+/// Handles come from the backend of the path they are created for. [`Permissions`] can only be
+/// applied to paths of the mount they were read from.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::MountFs;
+/// use io_fs::ReadOnlyFs;
+///
+/// let config = MemFs::new();
+/// config.write("app.toml", "verbose = true")?;
+/// let mut fs = MountFs::new();
+/// fs.mount("/", MemFs::new());
+/// fs.mount("/config", ReadOnlyFs::new(config));
+/// fs.mount("/tmp", MemFs::new());
+///
+/// assert_eq!(fs.read_to_string("/config/app.toml")?, "verbose = true");
+/// fs.write("/tmp/app.toml", "verbose = false")?;
+/// let error = fs.rename("/tmp/app.toml", "/config/app.toml").unwrap_err();
+/// assert_eq!(error.kind(), io::ErrorKind::CrossesDevices);
+/// assert_eq!(fs.read_dir("/")?.count(), 2);
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct MountFs {
+	/// The mounts, the longest mount points first.
+	mounts: Arc<Vec<Mount>>,
+}
+
+#[derive(Clone)]
+struct Mount {
+	point: path::PathBuf,
+	fs: Arc<dyn DynFs>,
+}
+
+impl MountFs {
+	/// Creates a filesystem without any mounts, in which no path exists.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Mounts `fs` at `point`, returning the backend previously mounted there, if any.
+	///
+	/// Mount points are resolved like other paths, and do not need to exist in the backend of
+	/// their parent.
+	pub fn mount(
+		&mut self,
+		point: impl AsRef<path::Path>,
+		fs: impl DynFs,
+	) -> Option<Arc<dyn DynFs>> {
+		let point = normalize(point.as_ref());
+		let mounts = Arc::make_mut(&mut self.mounts);
+		let fs: Arc<dyn DynFs> = Arc::new(fs);
+		if let Some(mount) = mounts.iter_mut().find(|mount| mount.point == point) {
+			return Some(std::mem::replace(&mut mount.fs, fs));
+		}
+		let index = mounts.partition_point(|mount| {
+			mount.point.components().count() >= point.components().count()
+		});
+		mounts.insert(
+			index,
+			Mount {
+				point,
+				fs,
+			},
+		);
+		None
+	}
+
+	/// Removes the backend mounted at `point`, if any.
+	pub fn unmount(&mut self, point: impl AsRef<path::Path>) -> Option<Arc<dyn DynFs>> {
+		let point = normalize(point.as_ref());
+		let mounts = Arc::make_mut(&mut self.mounts);
+		let index = mounts.iter().position(|mount| mount.point == point)?;
+		Some(mounts.remove(index).fs)
+	}
+
+	/// Returns the mount points, the longest first.
+	pub fn mount_points(&self) -> impl Iterator<Item = &path::Path> {
+		self.mounts.iter().map(|mount| mount.point.as_path())
+	}
+}
+
+impl fmt::Debug for MountFs {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MountFs")
+			.field("mount_points", &self.mount_points().collect::<Vec<_>>())
+			.finish()
+	}
+}
+
+/// Resolves `path` lexically into an absolute path.
+fn normalize(path: &path::Path) -> path::PathBuf {
+	let mut normal = path::PathBuf::from(path::MAIN_SEPARATOR_STR);
+	for component in path.components() {
+		match component {
+			Component::Prefix(_) | Component::RootDir => normal = path::MAIN_SEPARATOR_STR.into(),
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normal.pop();
+			}
+			Component::Normal(name) => normal.push(name),
+		}
+	}
+	normal
+}
+
+/// A path routed to a mount, as seen by its backend.
+struct Routed<'a> {
+	mount: &'a Mount,
+	path: path::PathBuf,
+}
+
+impl Routed<'_> {
+	/// Maps a path returned by the backend back into the mount.
+	fn unroute(&self, path: path::PathBuf) -> path::PathBuf {
+		match path.strip_prefix(path::MAIN_SEPARATOR_STR) {
+			Ok(relative) => self.mount.point.join(relative),
+			Err(_) => path,
+		}
+	}
+}
+
+impl MountFs {
+	fn route(&self, path: &path::Path) -> io::Result<Routed<'_>> {
+		let path = normalize(path);
+		let mount = self.mounts.iter().find(|mount| path.starts_with(&mount.point));
+		let Some(mount) = mount else {
+			return Err(io::ErrorKind::NotFound.into());
+		};
+		let relative = path.strip_prefix(&mount.point).expect("the mount point is a prefix");
+		Ok(Routed {
+			mount,
+			path: path::Path::new(path::MAIN_SEPARATOR_STR).join(relative),
+		})
+	}
+
+	/// Routes the two paths of a call, which must belong to the same mount.
+	fn route_both(
+		&self,
+		from: &path::Path,
+		to: &path::Path,
+	) -> io::Result<(Routed<'_>, Routed<'_>)> {
+		let from = self.route(from)?;
+		let to = self.route(to)?;
+		if from.mount.point != to.mount.point {
+			return Err(io::ErrorKind::CrossesDevices.into());
+		}
+		Ok((from, to))
+	}
+
+	/// Fails if `path` is a mount point or contains one.
+	fn check_not_busy(&self, path: &path::Path) -> io::Result<()> {
+		let path = normalize(path);
+		if self.mounts.iter().any(|mount| mount.point.starts_with(&path)) {
+			return Err(io::ErrorKind::ResourceBusy.into());
+		}
+		Ok(())
+	}
+}
+
+impl Fs for MountFs {
+	type DirBuilder = MountDirBuilder;
+	type DirEntry = MountDirEntry;
+	type File = MountFile;
+	type FileTimes = MountFileTimes;
+	type FileType = Box<dyn crate::DynFileType>;
+	type Metadata = Box<dyn crate::DynMetadata>;
+	type OpenOptions = MountOpenOptions;
+	type Permissions = Box<dyn crate::DynPermissions>;
+	type ReadDir = MountReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		MountDirBuilder {
+			fs: self.clone(),
+			recursive: false,
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		MountFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		MountOpenOptions {
+			fs: self.clone(),
+			flags: Flags::default(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let routed = self.route(path.as_ref())?;
+		Ok(routed.unroute(routed.mount.fs.canonicalize(&routed.path)?))
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		match self.route_both(from.as_ref(), to.as_ref()) {
+			Ok((from, to)) => from.mount.fs.copy(&from.path, &to.path),
+			Err(error) if error.kind() == io::ErrorKind::CrossesDevices => {
+				let metadata = Fs::metadata(self, &from)?;
+				if !metadata.is_file() {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidInput,
+						"the source path is neither a regular file nor a symlink to a regular file",
+					));
+				}
+				let contents = Fs::read(self, from)?;
+				Fs::write(self, &to, &contents)?;
+				let mut perm = Fs::metadata(self, &to)?.permissions();
+				perm.set_readonly(metadata.permissions().readonly());
+				Fs::set_permissions(self, to, perm)?;
+				Ok(contents.len() as u64)
+			}
+			Err(error) => Err(error),
+		}
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.create_dir(&routed.path)
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.create_dir_all(&routed.path)
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		match self.route(path.as_ref()) {
+			Ok(routed) => routed.mount.fs.exists(&routed.path),
+			Err(_) => Ok(false),
+		}
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let (original, link) = self.route_both(original.as_ref(), link.as_ref())?;
+		original.mount.fs.hard_link(&original.path, &link.path)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.metadata(&routed.path)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.read(&routed.path)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		let path = path.as_ref();
+		let routed = self.route(path)?;
+		let inner = routed.mount.fs.read_dir(&routed.path)?;
+		let dir = normalize(path);
+		let mount_points = self
+			.mounts
+			.iter()
+			.filter(|mount| mount.point.parent() == Some(dir.as_path()))
+			.map(|mount| (mount.point.file_name().unwrap_or_default().to_owned(), mount.fs.clone()))
+			.collect();
+		Ok(MountReadDir {
+			inner,
+			dir: path.to_owned(),
+			mount_points,
+		})
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let routed = self.route(path.as_ref())?;
+		Ok(routed.unroute(routed.mount.fs.read_link(&routed.path)?))
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.read_to_string(&routed.path)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.check_not_busy(path.as_ref())?;
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.remove_dir(&routed.path)
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.check_not_busy(path.as_ref())?;
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.remove_dir_all(&routed.path)
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		self.check_not_busy(path.as_ref())?;
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.remove_file(&routed.path)
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		self.check_not_busy(from.as_ref())?;
+		self.check_not_busy(to.as_ref())?;
+		let (from, to) = self.route_both(from.as_ref(), to.as_ref())?;
+		from.mount.fs.rename(&from.path, &to.path)
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.set_permissions(&routed.path, perm)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.symlink_metadata(&routed.path)
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let routed = self.route(path.as_ref())?;
+		routed.mount.fs.write(&routed.path, contents.as_ref())
+	}
+}
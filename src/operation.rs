@@ -0,0 +1,146 @@
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// A call on an [`Fs`](crate::Fs) or on one of its handles, as seen by decorators such as
+/// [`FaultFs`](crate::FaultFs).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+	Canonicalize,
+	Copy,
+	CreateDir,
+	CreateDirAll,
+	Exists,
+	HardLink,
+	Metadata,
+	Read,
+	ReadDir,
+	ReadLink,
+	ReadToString,
+	RemoveDir,
+	RemoveDirAll,
+	RemoveFile,
+	Rename,
+	SetPermissions,
+	SymlinkMetadata,
+	Write,
+	/// [`DirBuilder::create`](crate::DirBuilder::create).
+	DirBuilderCreate,
+	/// [`OpenOptions::open`](crate::OpenOptions::open).
+	Open,
+	/// [`io::Read::read`](std::io::Read::read) on a file, including the calls made by the other
+	/// methods of [`io::Read`](std::io::Read).
+	FileRead,
+	/// [`io::Write::write`](std::io::Write::write) on a file, including the calls made by the
+	/// other methods of [`io::Write`](std::io::Write).
+	FileWrite,
+	FileFlush,
+	FileSeek,
+	FileSyncAll,
+	FileSyncData,
+	FileSetLen,
+	FileMetadata,
+	FileTryClone,
+	FileSetPermissions,
+	FileSetTimes,
+	FileSetModified,
+}
+
+impl Operation {
+	/// Every operation, in declaration order.
+	pub const ALL: [Self; 32] = [
+		Self::Canonicalize,
+		Self::Copy,
+		Self::CreateDir,
+		Self::CreateDirAll,
+		Self::Exists,
+		Self::HardLink,
+		Self::Metadata,
+		Self::Read,
+		Self::ReadDir,
+		Self::ReadLink,
+		Self::ReadToString,
+		Self::RemoveDir,
+		Self::RemoveDirAll,
+		Self::RemoveFile,
+		Self::Rename,
+		Self::SetPermissions,
+		Self::SymlinkMetadata,
+		Self::Write,
+		Self::DirBuilderCreate,
+		Self::Open,
+		Self::FileRead,
+		Self::FileWrite,
+		Self::FileFlush,
+		Self::FileSeek,
+		Self::FileSyncAll,
+		Self::FileSyncData,
+		Self::FileSetLen,
+		Self::FileMetadata,
+		Self::FileTryClone,
+		Self::FileSetPermissions,
+		Self::FileSetTimes,
+		Self::FileSetModified,
+	];
+
+	/// Returns the name of the method, such as `create_dir` or `File::sync_all`.
+	pub fn name(self) -> &'static str {
+		match self {
+			Self::Canonicalize => "canonicalize",
+			Self::Copy => "copy",
+			Self::CreateDir => "create_dir",
+			Self::CreateDirAll => "create_dir_all",
+			Self::Exists => "exists",
+			Self::HardLink => "hard_link",
+			Self::Metadata => "metadata",
+			Self::Read => "read",
+			Self::ReadDir => "read_dir",
+			Self::ReadLink => "read_link",
+			Self::ReadToString => "read_to_string",
+			Self::RemoveDir => "remove_dir",
+			Self::RemoveDirAll => "remove_dir_all",
+			Self::RemoveFile => "remove_file",
+			Self::Rename => "rename",
+			Self::SetPermissions => "set_permissions",
+			Self::SymlinkMetadata => "symlink_metadata",
+			Self::Write => "write",
+			Self::DirBuilderCreate => "DirBuilder::create",
+			Self::Open => "OpenOptions::open",
+			Self::FileRead => "File::read",
+			Self::FileWrite => "File::write",
+			Self::FileFlush => "File::flush",
+			Self::FileSeek => "File::seek",
+			Self::FileSyncAll => "File::sync_all",
+			Self::FileSyncData => "File::sync_data",
+			Self::FileSetLen => "File::set_len",
+			Self::FileMetadata => "File::metadata",
+			Self::FileTryClone => "File::try_clone",
+			Self::FileSetPermissions => "File::set_permissions",
+			Self::FileSetTimes => "File::set_times",
+			Self::FileSetModified => "File::set_modified",
+		}
+	}
+
+	/// Returns whether the operation writes file contents, and may therefore be cut short.
+	pub fn is_write(self) -> bool {
+		matches!(self, Self::Write | Self::FileWrite)
+	}
+}
+
+impl fmt::Display for Operation {
+	/// Formats the operation as its [name](Self::name).
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+impl FromStr for Operation {
+	type Err = io::Error;
+
+	/// Parses the [name](Self::name) of an operation.
+	fn from_str(name: &str) -> io::Result<Self> {
+		Self::ALL.into_iter().find(|operation| operation.name() == name).ok_or_else(|| {
+			io::Error::new(io::ErrorKind::InvalidInput, format!("unknown operation `{name}`"))
+		})
+	}
+}
@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use super::LowerFs;
+use super::OverlayFs;
+use crate::DirBuilder;
+use crate::DirEntry;
+use crate::Fs;
+use crate::ReadDir;
+
+/// A [`DirBuilder`] of an [`OverlayFs`].
+pub struct OverlayDirBuilder<U, L> {
+	pub(super) fs: OverlayFs<U, L>,
+	pub(super) recursive: bool,
+}
+
+impl<U, L> fmt::Debug for OverlayDirBuilder<U, L> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("OverlayDirBuilder")
+			.field("recursive", &self.recursive)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<U: Fs, L: LowerFs<U>> DirBuilder for OverlayDirBuilder<U, L> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.recursive = recursive;
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		if self.recursive {
+			self.fs.create_dir_all(path)
+		} else {
+			self.fs.create_dir(path)
+		}
+	}
+}
+
+/// Iterator over the merged entries of a directory of an [`OverlayFs`], read up front.
+#[derive(Debug)]
+pub struct OverlayReadDir<E> {
+	pub(super) entries: VecDeque<io::Result<E>>,
+}
+
+impl<E> Iterator for OverlayReadDir<E> {
+	type Item = io::Result<E>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.entries.pop_front()
+	}
+}
+
+impl<E: DirEntry> ReadDir for OverlayReadDir<E> {
+	type DirEntry = E;
+}
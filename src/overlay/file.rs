@@ -0,0 +1,126 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use super::normalize;
+use super::Layer;
+use super::LowerFs;
+use super::OverlayFs;
+use crate::Fs;
+use crate::OpenOptions;
+
+/// [`OpenOptions`] of an [`OverlayFs`].
+///
+/// Files are opened from the layer holding them, unless they are opened to be written or
+/// created, in which case they are first copied up.
+pub struct OverlayOpenOptions<U: Fs, L: Fs> {
+	pub(super) upper: U::OpenOptions,
+	pub(super) lower: L::OpenOptions,
+	pub(super) flags: Flags,
+	pub(super) fs: OverlayFs<U, L>,
+}
+
+/// The options that may change the filesystem when opening a file.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Flags {
+	write: bool,
+	append: bool,
+	truncate: bool,
+	create: bool,
+	create_new: bool,
+}
+
+impl<U: Fs, L: Fs> Clone for OverlayOpenOptions<U, L> {
+	fn clone(&self) -> Self {
+		Self {
+			upper: self.upper.clone(),
+			lower: self.lower.clone(),
+			flags: self.flags.clone(),
+			fs: self.fs.clone(),
+		}
+	}
+}
+
+impl<U: Fs, L: Fs> fmt::Debug for OverlayOpenOptions<U, L> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("OverlayOpenOptions")
+			.field("upper", &self.upper)
+			.field("lower", &self.lower)
+			.finish_non_exhaustive()
+	}
+}
+
+impl<U: Fs, L: LowerFs<U>> OpenOptions for OverlayOpenOptions<U, L> {
+	type File = U::File;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.upper.read(read);
+		self.lower.read(read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.upper.write(write);
+		self.lower.write(write);
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.upper.append(append);
+		self.lower.append(append);
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.upper.truncate(truncate);
+		self.lower.truncate(truncate);
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.upper.create(create);
+		self.lower.create(create);
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.upper.create_new(create_new);
+		self.lower.create_new(create_new);
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let path = normalize(path.as_ref())?;
+		let layers = &self.fs.layers;
+		let Flags {
+			write,
+			append,
+			truncate,
+			create,
+			create_new,
+		} = self.flags;
+		let location = layers.locate(&path)?;
+		if location.metadata().is_none() {
+			if create || create_new {
+				layers.prepare(&path)?;
+			}
+			return self.upper.open(path);
+		}
+		if !(write || append || truncate || create || create_new) {
+			return match location.layer()? {
+				Layer::Upper => self.upper.open(path),
+				Layer::Lower => self.lower.open(path),
+			};
+		}
+		if create_new {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		layers.copy_up(&path)?;
+		self.upper.open(path)
+	}
+}
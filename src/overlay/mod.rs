@@ -0,0 +1,680 @@
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::ffi::OsString;
+use std::io;
+use std::path;
+use std::path::Component;
+use std::sync::Arc;
+
+mod dir;
+pub use dir::*;
+mod file;
+pub use file::*;
+
+use crate::DirEntry;
+use crate::File;
+use crate::FileType;
+use crate::Fs;
+use crate::Metadata;
+use crate::OpenOptions;
+
+/// The prefix of the markers recording, in a directory of the upper layer, that an entry of the
+/// lower layer was removed.
+const WHITEOUT_PREFIX: &str = ".wh.";
+
+/// The marker recording that a directory of the upper layer hides the contents of the lower one.
+const OPAQUE: &str = ".wh..wh..opq";
+
+/// An [`Fs`] that can be the lower layer of an [`OverlayFs`] over `U`, sharing its data types.
+///
+/// Layers of different types can be combined by boxing both as [`DynFs`](crate::DynFs).
+pub trait LowerFs<U: Fs>:
+	Fs<
+	DirEntry = U::DirEntry,
+	File = U::File,
+	FileTimes = U::FileTimes,
+	FileType = U::FileType,
+	Metadata = U::Metadata,
+	Permissions = U::Permissions,
+>
+{
+}
+
+impl<U: Fs, L> LowerFs<U> for L where
+	L: Fs<
+		DirEntry = U::DirEntry,
+		File = U::File,
+		FileTimes = U::FileTimes,
+		FileType = U::FileType,
+		Metadata = U::Metadata,
+		Permissions = U::Permissions,
+	>
+{
+}
+
+/// A union of a writable upper [`Fs`] over a lower one, in the manner of Linux's overlayfs.
+///
+/// Reads fall through to the lower layer for paths the upper one does not have, and directories
+/// present in both list the entries of both. All changes go to the upper layer, and the lower one
+/// is never modified: files and directories of the lower layer are copied up, along with their
+/// parents and permissions, before being written to, and removing them leaves a whiteout in the
+/// upper layer hiding them. A directory created or renamed over one of the lower layer is made
+/// opaque, hiding its former contents.
+///
+/// Whiteouts are stored as empty files named `.wh.<name>` next to the entry they hide, and opaque
+/// directories contain an empty `.wh..wh..opq` file, so the state of the overlay can be reopened
+/// from its layers. Such names are hidden from listings, and using them in paths fails with
+/// [`io::ErrorKind::InvalidInput`].
+///
+/// Both layers are addressed with the same paths, relative paths being resolved against the root.
+/// `..` is resolved lexically, and symbolic links are only followed within the layer holding them,
+/// so they cannot be copied up: changing one of the lower layer fails with
+/// [`io::ErrorKind::Unsupported`]. To overlay directories of the disk, wrap them in
+/// [`JailFs`](crate::JailFs)s.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::OverlayFs;
+///
+/// let lower = MemFs::new();
+/// lower.create_dir("src")?;
+/// lower.write("src/main.rs", "fn main() {}")?;
+/// lower.write("src/lib.rs", "")?;
+/// let fs = OverlayFs::new(MemFs::new(), lower);
+///
+/// fs.write("src/main.rs", "fn main() { run() }")?;
+/// fs.remove_file("src/lib.rs")?;
+/// assert_eq!(fs.read_to_string("src/main.rs")?, "fn main() { run() }");
+/// assert_eq!(fs.read_dir("src")?.count(), 1);
+/// assert_eq!(fs.lower().read_to_string("src/main.rs")?, "fn main() {}");
+/// assert!(fs.lower().exists("src/lib.rs")?);
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct OverlayFs<U, L> {
+	layers: Arc<Layers<U, L>>,
+}
+
+impl<U, L> Clone for OverlayFs<U, L> {
+	fn clone(&self) -> Self {
+		Self {
+			layers: self.layers.clone(),
+		}
+	}
+}
+
+impl<U, L> OverlayFs<U, L> {
+	/// Overlays `upper` over `lower`, picking up the whiteouts already in `upper`.
+	pub fn new(upper: U, lower: L) -> Self {
+		Self {
+			layers: Arc::new(Layers {
+				upper,
+				lower,
+			}),
+		}
+	}
+
+	pub fn upper(&self) -> &U {
+		&self.layers.upper
+	}
+
+	pub fn lower(&self) -> &L {
+		&self.layers.lower
+	}
+}
+
+#[derive(Debug)]
+struct Layers<U, L> {
+	upper: U,
+	lower: L,
+}
+
+/// The layer an entry is read from.
+enum Layer {
+	Upper,
+	Lower,
+}
+
+/// The entries found at a path in each layer, without following a final symbolic link.
+struct Location<M> {
+	upper: Option<M>,
+	/// The entry of the lower layer, if it is not hidden by the upper one.
+	lower: Option<M>,
+	/// Why neither layer has an entry, if so.
+	missing: io::ErrorKind,
+}
+
+impl<M: Metadata> Location<M> {
+	fn layer(&self) -> io::Result<Layer> {
+		match (&self.upper, &self.lower) {
+			(Some(_), _) => Ok(Layer::Upper),
+			(None, Some(_)) => Ok(Layer::Lower),
+			(None, None) => Err(self.missing.into()),
+		}
+	}
+
+	fn metadata(&self) -> Option<&M> {
+		self.upper.as_ref().or(self.lower.as_ref())
+	}
+
+	fn existing(&self) -> io::Result<&M> {
+		self.metadata().ok_or_else(|| self.missing.into())
+	}
+}
+
+/// Resolves `path` lexically into an absolute path.
+fn normalize(path: &path::Path) -> io::Result<path::PathBuf> {
+	let mut normal = path::PathBuf::from(path::MAIN_SEPARATOR_STR);
+	for component in path.components() {
+		match component {
+			Component::Prefix(_) | Component::RootDir => normal = path::MAIN_SEPARATOR_STR.into(),
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normal.pop();
+			}
+			Component::Normal(name) => {
+				if is_marker(name) {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidInput,
+						format!("names starting with `{WHITEOUT_PREFIX}` are reserved"),
+					));
+				}
+				normal.push(name);
+			}
+		}
+	}
+	Ok(normal)
+}
+
+fn is_marker(name: &OsStr) -> bool {
+	name.as_encoded_bytes().starts_with(WHITEOUT_PREFIX.as_bytes())
+}
+
+fn whiteout_name(name: &OsStr) -> OsString {
+	let mut whiteout = OsString::from(WHITEOUT_PREFIX);
+	whiteout.push(name);
+	whiteout
+}
+
+fn whiteout_path(path: &path::Path) -> path::PathBuf {
+	path.with_file_name(whiteout_name(path.file_name().unwrap_or_default()))
+}
+
+/// Returns the entry a lookup found, or `None` if there is none. Other errors, such as
+/// [`io::ErrorKind::PermissionDenied`], are returned rather than taken for a missing entry.
+fn found<T>(result: io::Result<T>) -> io::Result<Option<T>> {
+	match result {
+		Ok(value) => Ok(Some(value)),
+		Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+		Err(error) => Err(error),
+	}
+}
+
+/// Returns whether the entries of a layer can be looked up under `metadata`.
+fn is_traversable<M: Metadata>(metadata: Option<&M>) -> bool {
+	metadata.is_some_and(|metadata| metadata.is_dir() || metadata.is_symlink())
+}
+
+impl<U: Fs, L: LowerFs<U>> Layers<U, L> {
+	fn locate(&self, path: &path::Path) -> io::Result<Location<U::Metadata>> {
+		let mut current = path::PathBuf::from(path::MAIN_SEPARATOR_STR);
+		let mut upper = found(self.upper.symlink_metadata(&current))?;
+		let mut lower = found(self.lower.symlink_metadata(&current))?;
+		for component in path.components() {
+			let Component::Normal(name) = component else {
+				continue;
+			};
+			let reachable = match &upper {
+				Some(metadata) if metadata.is_dir() => {
+					lower.as_ref().is_some_and(Metadata::is_dir) && !self.is_opaque(&current)?
+				}
+				Some(_) => false,
+				None => is_traversable(lower.as_ref()),
+			};
+			let in_file = upper
+				.as_ref()
+				.or(lower.as_ref())
+				.is_some_and(|metadata| !metadata.is_dir() && !metadata.is_symlink());
+			let upper_traversable = is_traversable(upper.as_ref());
+			current.push(name);
+			let whiteout = upper.is_some() && self.is_whiteout(&current)?;
+			upper = if upper_traversable {
+				found(self.upper.symlink_metadata(&current))?
+			} else {
+				None
+			};
+			lower = if reachable && !whiteout {
+				found(self.lower.symlink_metadata(&current))?
+			} else {
+				None
+			};
+			if upper.is_none() && lower.is_none() {
+				let missing = if in_file {
+					io::ErrorKind::NotADirectory
+				} else {
+					io::ErrorKind::NotFound
+				};
+				return Ok(Location {
+					upper,
+					lower,
+					missing,
+				});
+			}
+		}
+		Ok(Location {
+			upper,
+			lower,
+			missing: io::ErrorKind::NotFound,
+		})
+	}
+
+	fn is_whiteout(&self, path: &path::Path) -> io::Result<bool> {
+		Ok(found(self.upper.symlink_metadata(whiteout_path(path)))?.is_some())
+	}
+
+	fn is_opaque(&self, dir: &path::Path) -> io::Result<bool> {
+		Ok(found(self.upper.symlink_metadata(dir.join(OPAQUE)))?.is_some())
+	}
+
+	fn metadata(&self, path: &path::Path) -> io::Result<U::Metadata> {
+		match self.locate(path)?.layer()? {
+			Layer::Upper => self.upper.metadata(path),
+			Layer::Lower => self.lower.metadata(path),
+		}
+	}
+
+	/// Lists a directory, merging the entries of both layers unless the upper one hides the lower
+	/// one.
+	fn entries(&self, dir: &path::Path) -> io::Result<Vec<io::Result<U::DirEntry>>> {
+		let location = self.locate(dir)?;
+		let Some(upper) = &location.upper else {
+			location.existing()?;
+			return Ok(self.lower.read_dir(dir)?.collect());
+		};
+		let mut entries = Vec::new();
+		// The names of the upper layer, including markers, which hide those of the lower one.
+		let mut hidden = BTreeSet::new();
+		for entry in self.upper.read_dir(dir)? {
+			match entry {
+				Ok(entry) => {
+					let name = entry.file_name();
+					if !is_marker(&name) {
+						entries.push(Ok(entry));
+					}
+					hidden.insert(name);
+				}
+				Err(error) => entries.push(Err(error)),
+			}
+		}
+		let merged = upper.is_dir()
+			&& location.lower.as_ref().is_some_and(Metadata::is_dir)
+			&& !self.is_opaque(dir)?;
+		if merged {
+			for entry in self.lower.read_dir(dir)? {
+				match entry {
+					Ok(entry)
+						if hidden.contains(&entry.file_name())
+							|| hidden.contains(&whiteout_name(&entry.file_name())) => {}
+					entry => entries.push(entry),
+				}
+			}
+		}
+		Ok(entries)
+	}
+
+	/// Copies the entry at `path` and its parents from the lower layer to the upper one, if they
+	/// are not there yet. Directories are copied without their contents.
+	fn copy_up(&self, path: &path::Path) -> io::Result<()> {
+		let location = self.locate(path)?;
+		if location.upper.is_some() {
+			return Ok(());
+		}
+		let metadata = location.existing()?.clone();
+		if let Some(parent) = path.parent() {
+			self.copy_up(parent)?;
+		}
+		if metadata.is_dir() {
+			self.upper.create_dir(path)?;
+		} else if metadata.is_file() {
+			self.upper.write(path, self.lower.read(path)?)?;
+			if let Ok(modified) = metadata.modified() {
+				self.upper.open_options().write(true).open(path)?.set_modified(modified)?;
+			}
+		} else {
+			return Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"symbolic links of the lower layer cannot be copied up",
+			));
+		}
+		self.upper.set_permissions(path, metadata.permissions())
+	}
+
+	/// Copies the directory at `path` and everything it contains to the upper layer.
+	fn copy_up_tree(&self, path: &path::Path) -> io::Result<()> {
+		self.copy_up(path)?;
+		for entry in self.entries(path)? {
+			let entry = entry?;
+			if entry.file_type()?.is_dir() {
+				self.copy_up_tree(&entry.path())?;
+			} else {
+				self.copy_up(&entry.path())?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Hides the entry of the lower layer at `path`.
+	fn whiteout(&self, path: &path::Path) -> io::Result<()> {
+		if let Some(parent) = path.parent() {
+			self.copy_up(parent)?;
+		}
+		self.upper.write(whiteout_path(path), "")
+	}
+
+	/// Readies the upper layer for a new entry at `path`, whose parent must be a directory.
+	fn prepare(&self, path: &path::Path) -> io::Result<()> {
+		let Some(parent) = path.parent() else {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		};
+		if !self.metadata(parent)?.is_dir() {
+			return Err(io::ErrorKind::NotADirectory.into());
+		}
+		self.copy_up(parent)?;
+		if self.is_whiteout(path)? {
+			self.upper.remove_file(whiteout_path(path))?;
+		}
+		Ok(())
+	}
+
+	/// Hides the contents the lower layer has at `dir`, if any, from the directory of the upper
+	/// one.
+	fn make_opaque(&self, dir: &path::Path) -> io::Result<()> {
+		if found(self.lower.symlink_metadata(dir))?.is_some() {
+			self.upper.write(dir.join(OPAQUE), "")?;
+		}
+		Ok(())
+	}
+
+	/// Removes the markers of a directory of the upper layer, so that it can be removed or
+	/// replaced once it has no other entries.
+	fn clear_markers(&self, dir: &path::Path) -> io::Result<()> {
+		for entry in self.upper.read_dir(dir)? {
+			let entry = entry?;
+			if is_marker(&entry.file_name()) {
+				self.upper.remove_file(entry.path())?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl<U: Fs, L: LowerFs<U>> Fs for OverlayFs<U, L> {
+	type DirBuilder = OverlayDirBuilder<U, L>;
+	type DirEntry = U::DirEntry;
+	type File = U::File;
+	type FileTimes = U::FileTimes;
+	type FileType = U::FileType;
+	type Metadata = U::Metadata;
+	type OpenOptions = OverlayOpenOptions<U, L>;
+	type Permissions = U::Permissions;
+	type ReadDir = OverlayReadDir<U::DirEntry>;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		OverlayDirBuilder {
+			fs: self.clone(),
+			recursive: false,
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.layers.upper.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		OverlayOpenOptions {
+			upper: self.layers.upper.open_options(),
+			lower: self.layers.lower.open_options(),
+			flags: Flags::default(),
+			fs: self.clone(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = normalize(path.as_ref())?;
+		match self.layers.locate(&path)?.layer()? {
+			Layer::Upper => self.layers.upper.canonicalize(path),
+			Layer::Lower => self.layers.lower.canonicalize(path),
+		}
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let metadata = self.metadata(&from)?;
+		if !metadata.is_file() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"the source path is neither a regular file nor a symlink to a regular file",
+			));
+		}
+		let contents = self.read(from)?;
+		self.write(&to, &contents)?;
+		self.set_permissions(to, metadata.permissions())?;
+		Ok(contents.len() as u64)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		if self.layers.locate(&path)?.metadata().is_some() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		self.layers.prepare(&path)?;
+		self.layers.upper.create_dir(&path)?;
+		self.layers.make_opaque(&path)
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		let mut ancestors = path.ancestors().collect::<Vec<_>>();
+		ancestors.reverse();
+		for (index, ancestor) in ancestors.iter().enumerate() {
+			match self.layers.metadata(ancestor) {
+				Ok(metadata) if metadata.is_dir() => {}
+				Ok(_) if index + 1 == ancestors.len() => {
+					return Err(io::ErrorKind::AlreadyExists.into())
+				}
+				Ok(_) => return Err(io::ErrorKind::NotADirectory.into()),
+				Err(error) if error.kind() == io::ErrorKind::NotFound => {
+					self.create_dir(ancestor)?
+				}
+				Err(error) => return Err(error),
+			}
+		}
+		Ok(())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		let path = normalize(path.as_ref())?;
+		match self.layers.locate(&path)?.layer() {
+			Ok(Layer::Upper) => self.layers.upper.exists(path),
+			Ok(Layer::Lower) => self.layers.lower.exists(path),
+			Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+			Err(error) => Err(error),
+		}
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let original = normalize(original.as_ref())?;
+		let link = normalize(link.as_ref())?;
+		self.layers.copy_up(&original)?;
+		if self.layers.locate(&link)?.metadata().is_some() {
+			return Err(io::ErrorKind::AlreadyExists.into());
+		}
+		self.layers.prepare(&link)?;
+		self.layers.upper.hard_link(original, link)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.layers.metadata(&normalize(path.as_ref())?)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let path = normalize(path.as_ref())?;
+		match self.layers.locate(&path)?.layer()? {
+			Layer::Upper => self.layers.upper.read(path),
+			Layer::Lower => self.layers.lower.read(path),
+		}
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		Ok(OverlayReadDir {
+			entries: VecDeque::from(self.layers.entries(&normalize(path.as_ref())?)?),
+		})
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = normalize(path.as_ref())?;
+		match self.layers.locate(&path)?.layer()? {
+			Layer::Upper => self.layers.upper.read_link(path),
+			Layer::Lower => self.layers.lower.read_link(path),
+		}
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		let path = normalize(path.as_ref())?;
+		match self.layers.locate(&path)?.layer()? {
+			Layer::Upper => self.layers.upper.read_to_string(path),
+			Layer::Lower => self.layers.lower.read_to_string(path),
+		}
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		let location = self.layers.locate(&path)?;
+		if !location.existing()?.is_dir() {
+			return Err(io::ErrorKind::NotADirectory.into());
+		}
+		if !self.layers.entries(&path)?.is_empty() {
+			return Err(io::ErrorKind::DirectoryNotEmpty.into());
+		}
+		if location.upper.is_some() {
+			self.layers.clear_markers(&path)?;
+			self.layers.upper.remove_dir(&path)?;
+		}
+		if location.lower.is_some() {
+			self.layers.whiteout(&path)?;
+		}
+		Ok(())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		let location = self.layers.locate(&path)?;
+		match (&location.upper, &location.lower) {
+			(Some(_), _) => self.layers.upper.remove_dir_all(&path)?,
+			(None, Some(metadata)) if metadata.is_file() => {
+				return Err(io::ErrorKind::NotADirectory.into())
+			}
+			(None, Some(_)) => {}
+			(None, None) => return Err(location.missing.into()),
+		}
+		if location.lower.is_some() {
+			self.layers.whiteout(&path)?;
+		}
+		Ok(())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		let location = self.layers.locate(&path)?;
+		match (&location.upper, &location.lower) {
+			(Some(_), _) => self.layers.upper.remove_file(&path)?,
+			(None, Some(metadata)) if metadata.is_dir() => {
+				return Err(io::ErrorKind::IsADirectory.into())
+			}
+			(None, Some(_)) => {}
+			(None, None) => return Err(location.missing.into()),
+		}
+		if location.lower.is_some() {
+			self.layers.whiteout(&path)?;
+		}
+		Ok(())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		let from = normalize(from.as_ref())?;
+		let to = normalize(to.as_ref())?;
+		let source = self.layers.locate(&from)?;
+		let metadata = source.existing()?;
+		if from == to {
+			return Ok(());
+		}
+		let is_dir = metadata.is_dir();
+		if is_dir && to.starts_with(&from) {
+			return Err(io::ErrorKind::InvalidInput.into());
+		}
+		let target = self.layers.locate(&to)?;
+		if let Some(replaced) = target.metadata() {
+			if is_dir && !replaced.is_dir() {
+				return Err(io::ErrorKind::NotADirectory.into());
+			}
+			if !is_dir && replaced.is_dir() {
+				return Err(io::ErrorKind::IsADirectory.into());
+			}
+			if is_dir && !self.layers.entries(&to)?.is_empty() {
+				return Err(io::ErrorKind::DirectoryNotEmpty.into());
+			}
+			if replaced.is_dir() && target.upper.is_some() {
+				self.layers.clear_markers(&to)?;
+			}
+		}
+		if is_dir {
+			self.layers.copy_up_tree(&from)?;
+		} else {
+			self.layers.copy_up(&from)?;
+		}
+		self.layers.prepare(&to)?;
+		self.layers.upper.rename(&from, &to)?;
+		if source.lower.is_some() {
+			self.layers.whiteout(&from)?;
+		}
+		if is_dir {
+			self.layers.make_opaque(&to)?;
+		}
+		Ok(())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		self.layers.copy_up(&path)?;
+		self.layers.upper.set_permissions(path, perm)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let path = normalize(path.as_ref())?;
+		let location = self.layers.locate(&path)?;
+		location.existing().cloned()
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let path = normalize(path.as_ref())?;
+		if self.layers.locate(&path)?.metadata().is_some() {
+			self.layers.copy_up(&path)?;
+		} else {
+			self.layers.prepare(&path)?;
+		}
+		self.layers.upper.write(path, contents)
+	}
+}
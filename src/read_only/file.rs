@@ -0,0 +1,167 @@
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::denied;
+use crate::DirBuilder;
+use crate::File;
+use crate::OpenOptions;
+
+/// A [`DirBuilder`] of a [`ReadOnlyFs`](crate::ReadOnlyFs), which never creates anything.
+#[derive(Debug)]
+pub struct ReadOnlyDirBuilder<B> {
+	pub(super) inner: B,
+}
+
+impl<B: DirBuilder> DirBuilder for ReadOnlyDirBuilder<B> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.inner.recursive(recursive);
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+}
+
+/// A [`File`] of a [`ReadOnlyFs`](crate::ReadOnlyFs), which can be read and inspected but not
+/// changed.
+#[derive(Debug)]
+pub struct ReadOnlyFile<H> {
+	inner: H,
+}
+
+impl<H: io::Read> io::Read for ReadOnlyFile<H> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.inner.read(buf)
+	}
+}
+
+impl<H> io::Write for ReadOnlyFile<H> {
+	fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+		Err(denied())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+impl<H: io::Seek> io::Seek for ReadOnlyFile<H> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		self.inner.seek(pos)
+	}
+}
+
+impl<H: File> File for ReadOnlyFile<H> {
+	type FileTimes = H::FileTimes;
+	type Metadata = H::Metadata;
+	type Permissions = H::Permissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		self.inner.sync_all()
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		self.inner.sync_data()
+	}
+
+	fn set_len(&self, _size: u64) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.inner.metadata()
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		Ok(Self {
+			inner: self.inner.try_clone()?,
+		})
+	}
+
+	fn set_permissions(&self, _perm: Self::Permissions) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_times(&self, _times: Self::FileTimes) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_modified(&self, _time: SystemTime) -> io::Result<()> {
+		Err(denied())
+	}
+}
+
+/// [`OpenOptions`] of a [`ReadOnlyFs`](crate::ReadOnlyFs), refusing to open files in any mode
+/// but reading.
+#[derive(Clone, Debug)]
+pub struct ReadOnlyOpenOptions<O> {
+	pub(super) inner: O,
+	pub(super) flags: Flags,
+}
+
+/// The options that may change the filesystem when opening a file.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Flags {
+	write: bool,
+	append: bool,
+	truncate: bool,
+	create: bool,
+	create_new: bool,
+}
+
+impl<O: OpenOptions> OpenOptions for ReadOnlyOpenOptions<O> {
+	type File = ReadOnlyFile<O::File>;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.inner.read(read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.inner.write(write);
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.inner.append(append);
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.inner.truncate(truncate);
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.inner.create(create);
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.inner.create_new(create_new);
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let Flags {
+			write,
+			append,
+			truncate,
+			create,
+			create_new,
+		} = self.flags;
+		if write || append || truncate || create || create_new {
+			return Err(denied());
+		}
+		Ok(ReadOnlyFile {
+			inner: self.inner.open(path)?,
+		})
+	}
+}
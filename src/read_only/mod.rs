@@ -0,0 +1,183 @@
+use std::io;
+use std::path;
+
+mod file;
+use file::Flags;
+pub use file::*;
+
+use crate::Fs;
+
+/// A decorator exposing another [`Fs`] without allowing any change to it.
+///
+/// Calls that only read are forwarded, as are opens that neither write, append, truncate nor
+/// create. Every other call fails with [`io::ErrorKind::ReadOnlyFilesystem`] without reaching the
+/// inner filesystem, even when it would have failed for another reason, such as a missing path.
+/// Files opened for reading cannot be resized or have their permissions or times changed either.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+///
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::OpenOptions;
+/// use io_fs::ReadOnlyFs;
+///
+/// let inner = MemFs::new();
+/// inner.write("config.toml", "verbose = true")?;
+/// let fs = ReadOnlyFs::new(inner);
+///
+/// assert_eq!(fs.read_to_string("config.toml")?, "verbose = true");
+/// assert!(fs.open_options().read(true).open("config.toml").is_ok());
+/// let error = fs.remove_file("config.toml").unwrap_err();
+/// assert_eq!(error.kind(), io::ErrorKind::ReadOnlyFilesystem);
+/// assert!(fs.open_options().append(true).open("config.toml").is_err());
+/// # Ok::<(), io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReadOnlyFs<F> {
+	inner: F,
+}
+
+impl<F> ReadOnlyFs<F> {
+	pub fn new(inner: F) -> Self {
+		Self {
+			inner,
+		}
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.inner
+	}
+
+	pub fn into_inner(self) -> F {
+		self.inner
+	}
+}
+
+/// The error returned by every call that would change the filesystem.
+fn denied() -> io::Error {
+	io::ErrorKind::ReadOnlyFilesystem.into()
+}
+
+impl<F: Fs> Fs for ReadOnlyFs<F> {
+	type DirBuilder = ReadOnlyDirBuilder<F::DirBuilder>;
+	type DirEntry = F::DirEntry;
+	type File = ReadOnlyFile<F::File>;
+	type FileTimes = F::FileTimes;
+	type FileType = F::FileType;
+	type Metadata = F::Metadata;
+	type OpenOptions = ReadOnlyOpenOptions<F::OpenOptions>;
+	type Permissions = F::Permissions;
+	type ReadDir = F::ReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		ReadOnlyDirBuilder {
+			inner: self.inner.dir_builder(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.inner.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		ReadOnlyOpenOptions {
+			inner: self.inner.open_options(),
+			flags: Flags::default(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.inner.canonicalize(path)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		_from: P,
+		_to: Q,
+	) -> io::Result<u64> {
+		Err(denied())
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.inner.exists(path)
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		_original: P,
+		_link: Q,
+	) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.inner.metadata(path)
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		self.inner.read(path)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		self.inner.read_dir(path)
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.inner.read_link(path)
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		self.inner.read_to_string(path)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, _path: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		_from: P,
+		_to: Q,
+	) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		_path: P,
+		_perm: Self::Permissions,
+	) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		self.inner.symlink_metadata(path)
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(
+		&self,
+		_path: P,
+		_contents: C,
+	) -> io::Result<()> {
+		Err(denied())
+	}
+}
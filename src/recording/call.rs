@@ -0,0 +1,122 @@
+use std::fmt;
+use std::io;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::path::MAIN_SEPARATOR_STR;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::time::SystemTime;
+
+use crate::Operation;
+
+/// A call recorded by a [`RecordingFs`](crate::RecordingFs).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Call {
+	pub operation: Operation,
+	/// The paths passed to the call, or the path a file was opened with for calls on a file.
+	pub paths: Vec<PathBuf>,
+	/// The number of bytes read, written or copied by a successful call moving file contents.
+	pub bytes: Option<u64>,
+	pub result: Result<(), io::ErrorKind>,
+	/// When the call returned.
+	pub timestamp: SystemTime,
+}
+
+impl Call {
+	/// Returns whether any of the paths of the call is `dir` or lies under it. Paths are compared
+	/// once `.` and `..` are resolved lexically, with relative paths taken from the root.
+	pub fn is_under(&self, dir: impl AsRef<Path>) -> bool {
+		let dir = normalize(dir.as_ref());
+		self.paths.iter().any(|path| normalize(path).starts_with(&dir))
+	}
+}
+
+/// Resolves `path` lexically into an absolute path.
+fn normalize(path: &Path) -> PathBuf {
+	let mut normal = PathBuf::from(MAIN_SEPARATOR_STR);
+	for component in path.components() {
+		match component {
+			Component::Prefix(_) | Component::RootDir => normal = MAIN_SEPARATOR_STR.into(),
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normal.pop();
+			}
+			Component::Normal(name) => normal.push(name),
+		}
+	}
+	normal
+}
+
+impl fmt::Display for Call {
+	/// Formats the call as in `rename("a", "b") -> Ok` or `File::write("log") 12 bytes -> Ok`.
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}(", self.operation)?;
+		for (index, path) in self.paths.iter().enumerate() {
+			if index > 0 {
+				write!(f, ", ")?;
+			}
+			write!(f, "{path:?}")?;
+		}
+		write!(f, ")")?;
+		if let Some(bytes) = self.bytes {
+			write!(f, " {bytes} bytes")?;
+		}
+		match self.result {
+			Ok(()) => write!(f, " -> Ok"),
+			Err(kind) => write!(f, " -> Err({kind:?})"),
+		}
+	}
+}
+
+/// The log of a [`RecordingFs`](crate::RecordingFs), shared with its handles.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Recorder {
+	calls: Arc<Mutex<Vec<Call>>>,
+}
+
+impl Recorder {
+	/// Records the call returning `result`.
+	pub fn record<T>(
+		&self,
+		operation: Operation,
+		paths: &[&Path],
+		result: io::Result<T>,
+	) -> io::Result<T> {
+		self.push(operation, paths, &result, None);
+		result
+	}
+
+	/// Records the call returning `result`, along with the bytes it moved if it succeeded.
+	pub fn record_bytes<T>(
+		&self,
+		operation: Operation,
+		paths: &[&Path],
+		result: io::Result<T>,
+		bytes: impl FnOnce(&T) -> u64,
+	) -> io::Result<T> {
+		self.push(operation, paths, &result, result.as_ref().ok().map(bytes));
+		result
+	}
+
+	fn push<T>(
+		&self,
+		operation: Operation,
+		paths: &[&Path],
+		result: &io::Result<T>,
+		bytes: Option<u64>,
+	) {
+		self.lock().push(Call {
+			operation,
+			paths: paths.iter().map(|path| path.to_path_buf()).collect(),
+			bytes,
+			result: result.as_ref().map(|_| ()).map_err(io::Error::kind),
+			timestamp: SystemTime::now(),
+		});
+	}
+
+	pub fn lock(&self) -> MutexGuard<'_, Vec<Call>> {
+		self.calls.lock().unwrap_or_else(|error| error.into_inner())
+	}
+}
@@ -0,0 +1,167 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::call::Recorder;
+use crate::DirBuilder;
+use crate::File;
+use crate::OpenOptions;
+use crate::Operation;
+
+/// A [`DirBuilder`] of a [`RecordingFs`](crate::RecordingFs).
+#[derive(Debug)]
+pub struct RecordingDirBuilder<B> {
+	pub(super) inner: B,
+	pub(super) recorder: Recorder,
+}
+
+impl<B: DirBuilder> DirBuilder for RecordingDirBuilder<B> {
+	fn recursive(&mut self, recursive: bool) -> &mut Self {
+		self.inner.recursive(recursive);
+		self
+	}
+
+	fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::DirBuilderCreate, &[path], self.inner.create(path))
+	}
+}
+
+/// A [`File`] of a [`RecordingFs`](crate::RecordingFs), remembering the path it was opened with.
+#[derive(Debug)]
+pub struct RecordingFile<H> {
+	inner: H,
+	path: PathBuf,
+	recorder: Recorder,
+}
+
+impl<H> RecordingFile<H> {
+	fn record<T>(&self, operation: Operation, result: io::Result<T>) -> io::Result<T> {
+		self.recorder.record(operation, &[&self.path], result)
+	}
+
+	fn record_len(&self, operation: Operation, result: io::Result<usize>) -> io::Result<usize> {
+		self.recorder.record_bytes(operation, &[&self.path], result, |len| *len as u64)
+	}
+}
+
+impl<H: io::Read> io::Read for RecordingFile<H> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let result = self.inner.read(buf);
+		self.record_len(Operation::FileRead, result)
+	}
+}
+
+impl<H: io::Write> io::Write for RecordingFile<H> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let result = self.inner.write(buf);
+		self.record_len(Operation::FileWrite, result)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		let result = self.inner.flush();
+		self.record(Operation::FileFlush, result)
+	}
+}
+
+impl<H: io::Seek> io::Seek for RecordingFile<H> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		let result = self.inner.seek(pos);
+		self.record(Operation::FileSeek, result)
+	}
+}
+
+impl<H: File> File for RecordingFile<H> {
+	type FileTimes = H::FileTimes;
+	type Metadata = H::Metadata;
+	type Permissions = H::Permissions;
+
+	fn sync_all(&self) -> io::Result<()> {
+		self.record(Operation::FileSyncAll, self.inner.sync_all())
+	}
+
+	fn sync_data(&self) -> io::Result<()> {
+		self.record(Operation::FileSyncData, self.inner.sync_data())
+	}
+
+	fn set_len(&self, size: u64) -> io::Result<()> {
+		self.record(Operation::FileSetLen, self.inner.set_len(size))
+	}
+
+	fn metadata(&self) -> io::Result<Self::Metadata> {
+		self.record(Operation::FileMetadata, self.inner.metadata())
+	}
+
+	fn try_clone(&self) -> io::Result<Self> {
+		let inner = self.record(Operation::FileTryClone, self.inner.try_clone())?;
+		Ok(Self {
+			inner,
+			path: self.path.clone(),
+			recorder: self.recorder.clone(),
+		})
+	}
+
+	fn set_permissions(&self, perm: Self::Permissions) -> io::Result<()> {
+		self.record(Operation::FileSetPermissions, self.inner.set_permissions(perm))
+	}
+
+	fn set_times(&self, times: Self::FileTimes) -> io::Result<()> {
+		self.record(Operation::FileSetTimes, self.inner.set_times(times))
+	}
+
+	fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+		self.record(Operation::FileSetModified, self.inner.set_modified(time))
+	}
+}
+
+/// [`OpenOptions`] of a [`RecordingFs`](crate::RecordingFs).
+#[derive(Clone, Debug)]
+pub struct RecordingOpenOptions<O> {
+	pub(super) inner: O,
+	pub(super) recorder: Recorder,
+}
+
+impl<O: OpenOptions> OpenOptions for RecordingOpenOptions<O> {
+	type File = RecordingFile<O::File>;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.inner.read(read);
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.inner.write(write);
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.inner.append(append);
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.inner.truncate(truncate);
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.inner.create(create);
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.inner.create_new(create_new);
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		let path = path.as_ref();
+		let inner = self.recorder.record(Operation::Open, &[path], self.inner.open(path))?;
+		Ok(RecordingFile {
+			inner,
+			path: path.to_owned(),
+			recorder: self.recorder.clone(),
+		})
+	}
+}
@@ -0,0 +1,279 @@
+use std::io;
+use std::path;
+
+mod call;
+pub use call::Call;
+use call::Recorder;
+mod file;
+pub use file::*;
+
+use crate::Fs;
+use crate::Operation;
+
+/// A decorator recording every call made to another [`Fs`] and to its handles into a log of
+/// [`Call`]s.
+///
+/// Calls are forwarded unchanged and recorded when they return, whether they succeeded or not.
+/// Clones and handles share the same log.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::Operation;
+/// use io_fs::RecordingFs;
+///
+/// let fs = RecordingFs::new(MemFs::new());
+/// fs.create_dir_all("/data/cache")?;
+/// fs.write("/data/cache/entry", "contents")?;
+/// fs.remove_dir_all("/data/cache")?;
+///
+/// assert_eq!(fs.calls()[1].to_string(), r#"write("/data/cache/entry") 8 bytes -> Ok"#);
+/// fs.assert_called_under(Operation::RemoveDirAll, "/data");
+/// fs.assert_never_called_under(Operation::RemoveDirAll, "/home");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct RecordingFs<F> {
+	inner: F,
+	recorder: Recorder,
+}
+
+impl<F> RecordingFs<F> {
+	/// Wraps `inner` with an empty log.
+	pub fn new(inner: F) -> Self {
+		Self {
+			inner,
+			recorder: Recorder::default(),
+		}
+	}
+
+	/// Returns the calls recorded so far, in the order they returned.
+	pub fn calls(&self) -> Vec<Call> {
+		self.recorder.lock().clone()
+	}
+
+	/// Empties the log.
+	pub fn clear(&self) {
+		self.recorder.lock().clear();
+	}
+
+	/// Returns the calls of `operation` with a path that is `dir` or lies under it.
+	pub fn calls_under(&self, operation: Operation, dir: impl AsRef<path::Path>) -> Vec<Call> {
+		let dir = dir.as_ref();
+		self.recorder
+			.lock()
+			.iter()
+			.filter(|call| call.operation == operation && call.is_under(dir))
+			.cloned()
+			.collect()
+	}
+
+	/// Returns whether `operation` was ever called with a path that is `dir` or lies under it.
+	pub fn was_called_under(&self, operation: Operation, dir: impl AsRef<path::Path>) -> bool {
+		let dir = dir.as_ref();
+		self.recorder.lock().iter().any(|call| call.operation == operation && call.is_under(dir))
+	}
+
+	/// Panics, listing the log, unless `operation` was called under `dir`.
+	#[track_caller]
+	pub fn assert_called_under(&self, operation: Operation, dir: impl AsRef<path::Path>) {
+		let dir = dir.as_ref();
+		if !self.was_called_under(operation, dir) {
+			panic!("{operation} was never called under {dir:?}, calls:\n{}", self.log());
+		}
+	}
+
+	/// Panics, listing the log, if `operation` was called under `dir`.
+	#[track_caller]
+	pub fn assert_never_called_under(&self, operation: Operation, dir: impl AsRef<path::Path>) {
+		let dir = dir.as_ref();
+		if self.was_called_under(operation, dir) {
+			panic!("{operation} was called under {dir:?}, calls:\n{}", self.log());
+		}
+	}
+
+	fn log(&self) -> String {
+		self.recorder.lock().iter().map(|call| format!("  {call}\n")).collect()
+	}
+
+	pub fn get_ref(&self) -> &F {
+		&self.inner
+	}
+
+	pub fn into_inner(self) -> F {
+		self.inner
+	}
+}
+
+impl<F: Fs> Fs for RecordingFs<F> {
+	type DirBuilder = RecordingDirBuilder<F::DirBuilder>;
+	type DirEntry = F::DirEntry;
+	type File = RecordingFile<F::File>;
+	type FileTimes = F::FileTimes;
+	type FileType = F::FileType;
+	type Metadata = F::Metadata;
+	type OpenOptions = RecordingOpenOptions<F::OpenOptions>;
+	type Permissions = F::Permissions;
+	type ReadDir = F::ReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		RecordingDirBuilder {
+			inner: self.inner.dir_builder(),
+			recorder: self.recorder.clone(),
+		}
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		self.inner.file_times()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		RecordingOpenOptions {
+			inner: self.inner.open_options(),
+			recorder: self.recorder.clone(),
+		}
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::Canonicalize, &[path], self.inner.canonicalize(path))
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+		let from = from.as_ref();
+		let to = to.as_ref();
+		self.recorder.record_bytes(
+			Operation::Copy,
+			&[
+				from,
+				to,
+			],
+			self.inner.copy(from, to),
+			|copied| *copied,
+		)
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::CreateDir, &[path], self.inner.create_dir(path))
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::CreateDirAll, &[path], self.inner.create_dir_all(path))
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::Exists, &[path], self.inner.exists(path))
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+		&self,
+		original: P,
+		link: Q,
+	) -> io::Result<()> {
+		let original = original.as_ref();
+		let link = link.as_ref();
+		self.recorder.record(
+			Operation::HardLink,
+			&[
+				original,
+				link,
+			],
+			self.inner.hard_link(original, link),
+		)
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::Metadata, &[path], self.inner.metadata(path))
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let path = path.as_ref();
+		self.recorder.record_bytes(Operation::Read, &[path], self.inner.read(path), |contents| {
+			contents.len() as u64
+		})
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::ReadDir, &[path], self.inner.read_dir(path))
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::ReadLink, &[path], self.inner.read_link(path))
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		let path = path.as_ref();
+		self.recorder.record_bytes(
+			Operation::ReadToString,
+			&[path],
+			self.inner.read_to_string(path),
+			|contents| contents.len() as u64,
+		)
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::RemoveDir, &[path], self.inner.remove_dir(path))
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::RemoveDirAll, &[path], self.inner.remove_dir_all(path))
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, path: P) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::RemoveFile, &[path], self.inner.remove_file(path))
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, from: P, to: Q) -> io::Result<()> {
+		let from = from.as_ref();
+		let to = to.as_ref();
+		self.recorder.record(
+			Operation::Rename,
+			&[
+				from,
+				to,
+			],
+			self.inner.rename(from, to),
+		)
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(
+		&self,
+		path: P,
+		perm: Self::Permissions,
+	) -> io::Result<()> {
+		let path = path.as_ref();
+		self.recorder.record(
+			Operation::SetPermissions,
+			&[path],
+			self.inner.set_permissions(path, perm),
+		)
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let path = path.as_ref();
+		self.recorder.record(Operation::SymlinkMetadata, &[path], self.inner.symlink_metadata(path))
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) -> io::Result<()> {
+		let path = path.as_ref();
+		let contents = contents.as_ref();
+		let len = contents.len() as u64;
+		self.recorder.record_bytes(
+			Operation::Write,
+			&[path],
+			self.inner.write(path, contents),
+			|()| len,
+		)
+	}
+}
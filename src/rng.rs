@@ -0,0 +1,29 @@
+/// A small deterministic generator, so that random behaviour can be reproduced from a seed.
+#[derive(Clone, Debug)]
+pub(crate) struct Rng(u64);
+
+impl Rng {
+	pub fn new(seed: u64) -> Self {
+		Self(seed)
+	}
+
+	/// Returns the next value of a SplitMix64 sequence.
+	pub fn next(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	/// Returns a value in `0..bound`.
+	pub fn below(&mut self, bound: usize) -> usize {
+		(self.next() % bound as u64) as usize
+	}
+
+	/// Returns `true` with the given probability.
+	pub fn chance(&mut self, probability: f64) -> bool {
+		// The top 53 bits are uniformly distributed over the precision of an `f64`.
+		((self.next() >> 11) as f64 / (1u64 << 53) as f64) < probability
+	}
+}
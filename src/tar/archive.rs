@@ -0,0 +1,340 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::archive;
+use crate::archive::Tree;
+use crate::archive::ROOT;
+
+pub(super) const BLOCK: usize = 512;
+
+/// What an entry is, with files locating their contents in the archive.
+pub(super) type Kind = archive::Kind<Range<usize>>;
+
+fn invalid(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, format!("malformed tar archive: {message}"))
+}
+
+#[derive(Debug)]
+pub(super) struct Node {
+	pub kind: Kind,
+	/// The permission bits, without the file type.
+	pub mode: u32,
+	pub uid: u64,
+	pub gid: u64,
+	pub modified: SystemTime,
+	/// Number of directory entries referring to this node.
+	pub links: u64,
+}
+
+impl archive::Node for Node {
+	type Data = Range<usize>;
+
+	fn kind(&self) -> &Kind {
+		&self.kind
+	}
+
+	fn kind_mut(&mut self) -> &mut Kind {
+		&mut self.kind
+	}
+}
+
+impl Node {
+	fn dir() -> Self {
+		Self {
+			kind: Kind::Dir(BTreeMap::new()),
+			mode: 0o755,
+			uid: 0,
+			gid: 0,
+			modified: SystemTime::UNIX_EPOCH,
+			links: 1,
+		}
+	}
+
+	pub fn len(&self) -> u64 {
+		match &self.kind {
+			Kind::File(range) => range.len() as u64,
+			Kind::Dir(_) => 0,
+			Kind::Symlink(target) => target.as_os_str().len() as u64,
+		}
+	}
+}
+
+/// A header together with the overrides of the extended headers preceding it.
+#[derive(Default)]
+struct Header {
+	path: Option<Vec<u8>>,
+	link: Option<Vec<u8>>,
+	size: Option<u64>,
+	modified: Option<SystemTime>,
+}
+
+/// A parsed archive: the uncompressed bytes and a tree of nodes indexing into them.
+pub(super) struct Archive {
+	pub bytes: Arc<[u8]>,
+	pub tree: Tree<Node>,
+}
+
+impl Archive {
+	pub fn parse(bytes: Arc<[u8]>) -> io::Result<Self> {
+		let mut archive = Self {
+			bytes: Arc::clone(&bytes),
+			tree: Tree::new(Node::dir()),
+		};
+		let mut offset = 0;
+		let mut extended = Header::default();
+		while offset < bytes.len() {
+			let block =
+				bytes.get(offset..offset + BLOCK).ok_or_else(|| invalid("truncated header"))?;
+			if block.iter().all(|&byte| byte == 0) {
+				break;
+			}
+			verify_checksum(block)?;
+			let size = match extended.size {
+				Some(size) => size,
+				None => number(&block[124..136])?,
+			};
+			let start = offset + BLOCK;
+			let size = usize::try_from(size)
+				.ok()
+				.filter(|size| {
+					size.checked_next_multiple_of(BLOCK)
+						.is_some_and(|padded| padded <= bytes.len() - start)
+				})
+				.ok_or_else(|| invalid("entry extends past the end of the archive"))?;
+			let end = start + size;
+			offset = start + size.next_multiple_of(BLOCK);
+			let data = &bytes[start..end];
+			match block[156] {
+				b'x' => {
+					pax(data, &mut extended)?;
+					continue;
+				}
+				b'L' => {
+					extended.path = Some(until_nul(data).to_vec());
+					continue;
+				}
+				b'K' => {
+					extended.link = Some(until_nul(data).to_vec());
+					continue;
+				}
+				// Global extended headers and GNU volume labels do not describe entries.
+				b'g' | b'V' => continue,
+				_ => {}
+			}
+			let header = std::mem::take(&mut extended);
+			archive.add(block, header, start..end)?;
+		}
+		Ok(archive)
+	}
+
+	/// Adds the entry described by the header `block`, creating its parent directories as needed.
+	fn add(&mut self, block: &[u8], header: Header, data: Range<usize>) -> io::Result<()> {
+		let path = header.path.unwrap_or_else(|| {
+			let name = until_nul(&block[..100]);
+			// Only POSIX headers have a prefix. GNU ones, whose magic is `"ustar  \0"`, store
+			// access and change times and sparse data there instead.
+			let prefix = until_nul(&block[345..500]);
+			if &block[257..263] == b"ustar\0" && !prefix.is_empty() {
+				[
+					prefix,
+					b"/",
+					name,
+				]
+				.concat()
+			} else {
+				name.to_vec()
+			}
+		});
+		let link = header.link.unwrap_or_else(|| until_nul(&block[157..257]).to_vec());
+		let modified = match header.modified {
+			Some(modified) => modified,
+			None => SystemTime::UNIX_EPOCH + Duration::from_secs(number(&block[136..148])?),
+		};
+		let mode = u32::try_from(number(&block[100..108])? & 0o7777).expect("masked");
+		let kind = match block[156] {
+			b'0' | b'\0' | b'7' => Kind::File(data),
+			b'5' => Kind::Dir(BTreeMap::new()),
+			b'2' => Kind::Symlink(PathBuf::from(String::from_utf8_lossy(&link).into_owned())),
+			b'1' => {
+				let target = self.find(&components(&link)?)?;
+				if let Kind::Dir(_) = self.tree.nodes[target].kind {
+					return Err(invalid("hard link to a directory"));
+				}
+				return self.link(&components(&path)?, target);
+			}
+			b'S' => {
+				return Err(io::Error::new(
+					io::ErrorKind::Unsupported,
+					"GNU sparse files are not supported",
+				))
+			}
+			// Devices and FIFOs cannot be represented.
+			_ => return Ok(()),
+		};
+		let node = Node {
+			kind,
+			mode,
+			uid: number(&block[108..116])?,
+			gid: number(&block[116..124])?,
+			modified,
+			links: 0,
+		};
+		let components = components(&path)?;
+		let index = self.tree.nodes.len();
+		self.tree.nodes.push(node);
+		self.link(&components, index)
+	}
+
+	/// Adds a directory entry for the node at `index`, replacing any existing entry.
+	fn link(&mut self, components: &[OsString], index: usize) -> io::Result<()> {
+		let Some((name, parents)) = components.split_last() else {
+			// An entry for the root directory itself.
+			let node = &mut self.tree.nodes[index];
+			if let Kind::Dir(_) = node.kind {
+				let (mode, modified) = (node.mode, node.modified);
+				self.tree.nodes[ROOT].mode = mode;
+				self.tree.nodes[ROOT].modified = modified;
+			}
+			return Ok(());
+		};
+		let mut dir = ROOT;
+		for parent in parents {
+			dir = match self.tree.entries(dir)?.get(parent) {
+				Some(&child) if matches!(self.tree.nodes[child].kind, Kind::Dir(_)) => child,
+				Some(_) => return Err(invalid("an entry is nested inside a file")),
+				None => {
+					let child = self.tree.nodes.len();
+					self.tree.nodes.push(Node::dir());
+					self.tree
+						.entries_mut(dir)
+						.expect("checked to be a directory")
+						.insert(parent.clone(), child);
+					child
+				}
+			};
+		}
+		let existing = self.tree.entries(dir)?.get(name).copied();
+		if let Some(existing) = existing {
+			let is_dir = |node: &Node| matches!(node.kind, Kind::Dir(_));
+			if is_dir(&self.tree.nodes[existing]) && is_dir(&self.tree.nodes[index]) {
+				// A directory listed again, usually after entries inside it, keeps its contents.
+				let node = &self.tree.nodes[index];
+				let (mode, uid, gid, modified) = (node.mode, node.uid, node.gid, node.modified);
+				let existing = &mut self.tree.nodes[existing];
+				existing.mode = mode;
+				existing.uid = uid;
+				existing.gid = gid;
+				existing.modified = modified;
+				return Ok(());
+			}
+			self.tree.nodes[existing].links -= 1;
+		}
+		self.tree.nodes[index].links += 1;
+		self.tree.entries_mut(dir).expect("checked to be a directory").insert(name.clone(), index);
+		Ok(())
+	}
+
+	/// Finds an entry by its path in the archive, without following symbolic links.
+	fn find(&self, components: &[OsString]) -> io::Result<usize> {
+		components.iter().try_fold(ROOT, |dir, name| {
+			match &self.tree.nodes[dir].kind {
+				Kind::Dir(entries) => entries.get(name).copied(),
+				_ => None,
+			}
+			.ok_or_else(|| invalid("hard link to a missing entry"))
+		})
+	}
+}
+
+/// Splits a path stored in the archive into its components, which must stay inside the archive.
+fn components(path: &[u8]) -> io::Result<Vec<OsString>> {
+	let path = String::from_utf8_lossy(path);
+	archive::components(&path)
+		.ok_or_else(|| invalid(&format!("entry {path:?} escapes the archive")))
+}
+
+fn until_nul(bytes: &[u8]) -> &[u8] {
+	bytes.split(|&byte| byte == 0).next().unwrap_or_default()
+}
+
+/// Parses a numeric header field, which is octal text or, in GNU archives, big-endian binary
+/// flagged by the high bit of the first byte.
+fn number(field: &[u8]) -> io::Result<u64> {
+	if field.first().is_some_and(|byte| byte & 0x80 != 0) {
+		return field[1..]
+			.iter()
+			.try_fold(u64::from(field[0] & 0x7f), |value, &byte| {
+				value.checked_mul(256).map(|value| value | u64::from(byte))
+			})
+			.ok_or_else(|| invalid("numeric field out of range"));
+	}
+	let text = std::str::from_utf8(until_nul(field)).map_err(|_| invalid("bad numeric field"))?;
+	let text = text.trim_matches(' ');
+	if text.is_empty() {
+		return Ok(0);
+	}
+	u64::from_str_radix(text, 8).map_err(|_| invalid("bad numeric field"))
+}
+
+fn verify_checksum(block: &[u8]) -> io::Result<()> {
+	let expected = number(&block[148..156])?;
+	let sum: u64 = block
+		.iter()
+		.enumerate()
+		.map(|(index, &byte)| {
+			if (148..156).contains(&index) {
+				u64::from(b' ')
+			} else {
+				u64::from(byte)
+			}
+		})
+		.sum();
+	if sum == expected {
+		Ok(())
+	} else {
+		Err(invalid("header checksum mismatch"))
+	}
+}
+
+/// Applies the records of a PAX extended header, each formatted as `"<len> <key>=<value>\n"`.
+fn pax(mut data: &[u8], header: &mut Header) -> io::Result<()> {
+	while !data.is_empty() {
+		let space =
+			data.iter().position(|&byte| byte == b' ').ok_or_else(|| invalid("bad pax record"))?;
+		let len = std::str::from_utf8(&data[..space])
+			.ok()
+			.and_then(|len| len.parse::<usize>().ok())
+			.filter(|&len| len > space && len <= data.len() && data[len - 1] == b'\n')
+			.ok_or_else(|| invalid("bad pax record"))?;
+		let record = &data[space + 1..len - 1];
+		data = &data[len..];
+		let Some(equals) = record.iter().position(|&byte| byte == b'=') else {
+			return Err(invalid("bad pax record"));
+		};
+		let (key, value) = (&record[..equals], &record[equals + 1..]);
+		let text = || std::str::from_utf8(value).map_err(|_| invalid("bad pax record"));
+		match key {
+			b"path" => header.path = Some(value.to_vec()),
+			b"linkpath" => header.link = Some(value.to_vec()),
+			b"size" => header.size = Some(text()?.parse().map_err(|_| invalid("bad pax size"))?),
+			b"mtime" => {
+				let seconds: f64 = text()?.parse().map_err(|_| invalid("bad pax mtime"))?;
+				let offset = Duration::try_from_secs_f64(seconds.abs())
+					.map_err(|_| invalid("bad pax mtime"))?;
+				header.modified = Some(if seconds < 0.0 {
+					SystemTime::UNIX_EPOCH - offset
+				} else {
+					SystemTime::UNIX_EPOCH + offset
+				});
+			}
+			_ => {}
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,79 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::archive::Kind;
+use super::TarFs;
+use super::TarMetadata;
+use crate::archive::Flags;
+use crate::ArchiveFile;
+use crate::OpenOptions;
+
+/// An open entry of a [`TarFs`].
+///
+/// Handles created through [`File::try_clone`](crate::File::try_clone) share their cursor.
+pub type TarFile = ArchiveFile<TarMetadata>;
+
+/// Options for opening entries of a [`TarFs`]. Any option that could modify the archive makes
+/// [`OpenOptions::open`] fail with [`io::ErrorKind::ReadOnlyFilesystem`].
+#[derive(Clone, Debug)]
+pub struct TarOpenOptions {
+	fs: TarFs,
+	flags: Flags,
+}
+
+impl TarOpenOptions {
+	pub(super) fn new(fs: TarFs) -> Self {
+		Self {
+			fs,
+			flags: Flags::default(),
+		}
+	}
+}
+
+impl OpenOptions for TarOpenOptions {
+	type File = TarFile;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.flags.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		self.flags.check()?;
+		let archive = &self.fs.archive;
+		let index = archive.tree.lookup(path.as_ref(), true)?;
+		let node = &archive.tree.nodes[index];
+		let range = match &node.kind {
+			Kind::File(range) => Some(range.clone()),
+			Kind::Dir(_) => None,
+			Kind::Symlink(_) => unreachable!("symbolic links are followed"),
+		};
+		Ok(TarFile::new(Arc::clone(&archive.bytes), range, TarMetadata::new(node)))
+	}
+}
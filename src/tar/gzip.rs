@@ -0,0 +1,53 @@
+use std::io;
+
+use crate::crc32::crc32;
+
+const DEFLATE: u8 = 8;
+const FHCRC: u8 = 1 << 1;
+const FEXTRA: u8 = 1 << 2;
+const FNAME: u8 = 1 << 3;
+const FCOMMENT: u8 = 1 << 4;
+
+fn invalid(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, format!("malformed gzip stream: {message}"))
+}
+
+/// Decompresses a gzip stream made of a single member, verifying its trailer.
+pub(super) fn decompress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+	let truncated = || invalid("unexpected end of data");
+	let header = bytes.get(..10).ok_or_else(truncated)?;
+	if header[2] != DEFLATE {
+		return Err(invalid("unsupported compression method"));
+	}
+	let flags = header[3];
+	let mut offset = 10;
+	if flags & FEXTRA != 0 {
+		let len = bytes.get(offset..offset + 2).ok_or_else(truncated)?;
+		offset += 2 + usize::from(u16::from_le_bytes([
+			len[0],
+			len[1],
+		]));
+	}
+	for flag in [
+		FNAME,
+		FCOMMENT,
+	] {
+		if flags & flag != 0 {
+			let len = bytes.get(offset..).and_then(|rest| rest.iter().position(|&byte| byte == 0));
+			offset += len.ok_or_else(truncated)? + 1;
+		}
+	}
+	if flags & FHCRC != 0 {
+		offset += 2;
+	}
+	let trailer_start = bytes.len().checked_sub(8).filter(|&start| start >= offset);
+	let trailer = &bytes[trailer_start.ok_or_else(truncated)?..];
+	let data = miniz_oxide::inflate::decompress_to_vec(&bytes[offset..bytes.len() - 8])
+		.map_err(|error| invalid(&format!("bad deflate stream: {error}")))?;
+	let expected_crc = u32::from_le_bytes(trailer[..4].try_into().expect("length checked"));
+	let expected_len = u32::from_le_bytes(trailer[4..].try_into().expect("length checked"));
+	if crc32(&data) != expected_crc || data.len() as u32 != expected_len {
+		return Err(invalid("checksum mismatch"));
+	}
+	Ok(data)
+}
@@ -0,0 +1,118 @@
+use std::io;
+use std::time::SystemTime;
+
+use super::archive::Node;
+use crate::ArchiveFileTimes;
+use crate::ArchiveFileType;
+use crate::ArchivePermissions;
+use crate::FileType;
+use crate::Metadata;
+
+/// The type of an entry in a [`TarFs`](super::TarFs).
+pub type TarFileType = ArchiveFileType;
+
+/// Permissions of an entry in a [`TarFs`](super::TarFs). The entry is read-only if its mode
+/// grants nobody write access.
+pub type TarPermissions = ArchivePermissions;
+
+/// Timestamps for a [`TarFile`](super::TarFile). Archives cannot be modified, so they are never
+/// applied.
+pub type TarFileTimes = ArchiveFileTimes;
+
+/// Metadata of an entry in a [`TarFs`](super::TarFs), including the ownership and mode recorded
+/// in the archive.
+///
+/// Tar archives only record modification times, so [`Metadata::accessed`] and
+/// [`Metadata::created`] return [`io::ErrorKind::Unsupported`].
+#[derive(Clone, Debug)]
+pub struct TarMetadata {
+	file_type: TarFileType,
+	len: u64,
+	mode: u32,
+	uid: u64,
+	gid: u64,
+	modified: SystemTime,
+	links: u64,
+}
+
+impl TarMetadata {
+	pub(super) fn new(node: &Node) -> Self {
+		Self {
+			file_type: TarFileType::from(&node.kind),
+			len: node.len(),
+			mode: node.mode,
+			uid: node.uid,
+			gid: node.gid,
+			modified: node.modified,
+			links: node.links,
+		}
+	}
+
+	/// Returns the permission bits of the entry, such as `0o644`.
+	pub fn mode(&self) -> u32 {
+		self.mode
+	}
+
+	/// Returns the numeric user id of the owner of the entry.
+	pub fn uid(&self) -> u64 {
+		self.uid
+	}
+
+	/// Returns the numeric group id of the owner of the entry.
+	pub fn gid(&self) -> u64 {
+		self.gid
+	}
+
+	/// Returns the number of paths referring to the entry, which is greater than one for files
+	/// that were archived with hard links.
+	pub fn nlink(&self) -> u64 {
+		self.links
+	}
+}
+
+fn unsupported() -> io::Error {
+	io::Error::new(io::ErrorKind::Unsupported, "not recorded in tar archives")
+}
+
+impl Metadata for TarMetadata {
+	type FileType = TarFileType;
+	type Permissions = TarPermissions;
+
+	fn file_type(&self) -> Self::FileType {
+		self.file_type
+	}
+
+	fn is_dir(&self) -> bool {
+		self.file_type.is_dir()
+	}
+
+	fn is_file(&self) -> bool {
+		self.file_type.is_file()
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.file_type.is_symlink()
+	}
+
+	fn len(&self) -> u64 {
+		self.len
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		TarPermissions {
+			readonly: self.mode & 0o222 == 0,
+		}
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		Ok(self.modified)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		Err(unsupported())
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		Err(unsupported())
+	}
+}
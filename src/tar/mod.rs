@@ -0,0 +1,211 @@
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::path;
+use std::sync::Arc;
+
+mod archive;
+use archive::Archive;
+mod file;
+pub use file::*;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod metadata;
+pub use metadata::*;
+mod writer;
+pub use writer::*;
+
+use crate::archive::denied;
+use crate::ArchiveDirBuilder;
+use crate::ArchiveDirEntry;
+use crate::ArchiveReadDir;
+use crate::Fs;
+use crate::OpenOptions;
+
+/// The first bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [
+	0x1f,
+	0x8b,
+];
+
+/// A read-only filesystem backed by the contents of a tar archive.
+///
+/// The archive is indexed once, when the filesystem is created, and entries are read straight from
+/// its bytes. The ustar, PAX and GNU formats are understood, so long paths are supported, and
+/// symbolic links, hard links, modes, owners and modification times are preserved in
+/// [`TarMetadata`]. With the `gzip` feature, compressed archives are detected and decompressed up
+/// front. Every operation that would modify the archive fails with
+/// [`io::ErrorKind::ReadOnlyFilesystem`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use io_fs::Fs;
+/// use io_fs::Native;
+/// use io_fs::TarFs;
+///
+/// let rootfs = TarFs::open(&Native {}, "rootfs.tar")?;
+/// let release = rootfs.read_to_string("/etc/os-release")?;
+/// assert!(rootfs.remove_file("/etc/os-release").is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct TarFs {
+	archive: Arc<Archive>,
+}
+
+impl TarFs {
+	/// Parses an archive held in memory, for example one embedded with [`include_bytes!`].
+	///
+	/// Gzip-compressed archives are decompressed first, which requires the `gzip` feature;
+	/// without it they are rejected with [`io::ErrorKind::Unsupported`].
+	pub fn new(bytes: impl Into<Arc<[u8]>>) -> io::Result<Self> {
+		let bytes = bytes.into();
+		let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+			Self::decompress(&bytes)?.into()
+		} else {
+			bytes
+		};
+		Ok(Self {
+			archive: Arc::new(Archive::parse(bytes)?),
+		})
+	}
+
+	#[cfg(feature = "gzip")]
+	fn decompress(bytes: &[u8]) -> io::Result<Vec<u8>> {
+		gzip::decompress(bytes)
+	}
+
+	#[cfg(not(feature = "gzip"))]
+	fn decompress(_: &[u8]) -> io::Result<Vec<u8>> {
+		Err(io::Error::new(
+			io::ErrorKind::Unsupported,
+			"compressed tar archives require the gzip feature",
+		))
+	}
+
+	/// Reads and parses the archive at `path` in `fs`.
+	pub fn open<F: Fs, P: AsRef<path::Path>>(fs: &F, path: P) -> io::Result<Self> {
+		Self::new(fs.read(path)?)
+	}
+}
+
+impl fmt::Debug for TarFs {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TarFs")
+			.field("len", &self.archive.bytes.len())
+			.field("entries", &(self.archive.tree.nodes.len() - 1))
+			.finish()
+	}
+}
+
+/// A builder for directories in a [`TarFs`], which always fails.
+pub type TarDirBuilder = ArchiveDirBuilder;
+
+/// Iterator over the entries of a directory in a [`TarFs`].
+pub type TarReadDir = ArchiveReadDir<TarFs>;
+
+/// An entry returned by [`TarReadDir`].
+pub type TarDirEntry = ArchiveDirEntry<TarFs>;
+
+impl Fs for TarFs {
+	type DirBuilder = TarDirBuilder;
+	type DirEntry = TarDirEntry;
+	type File = TarFile;
+	type FileTimes = TarFileTimes;
+	type FileType = TarFileType;
+	type Metadata = TarMetadata;
+	type OpenOptions = TarOpenOptions;
+	type Permissions = TarPermissions;
+	type ReadDir = TarReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		TarDirBuilder::default()
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		TarFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		TarOpenOptions::new(self.clone())
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		Ok(self.archive.tree.resolve(path.as_ref(), true)?.1)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<u64> {
+		Err(denied())
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.archive.tree.exists(path.as_ref())
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let index = self.archive.tree.lookup(path.as_ref(), true)?;
+		Ok(TarMetadata::new(&self.archive.tree.nodes[index]))
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let mut contents = Vec::new();
+		self.open_options().read(true).open(path)?.read_to_end(&mut contents)?;
+		Ok(contents)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		ArchiveReadDir::new(self, &self.archive.tree, path.as_ref())
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.archive.tree.read_link(path.as_ref())
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		String::from_utf8(self.read(path)?).map_err(|_| {
+			io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+		})
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(&self, _: P, _: Self::Permissions) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let index = self.archive.tree.lookup(path.as_ref(), false)?;
+		Ok(TarMetadata::new(&self.archive.tree.nodes[index]))
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, _: P, _: C) -> io::Result<()> {
+		Err(denied())
+	}
+}
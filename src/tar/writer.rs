@@ -0,0 +1,234 @@
+use std::ffi::OsString;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use super::archive::BLOCK;
+use crate::DirEntry;
+use crate::FileType;
+use crate::Fs;
+use crate::Metadata;
+use crate::Permissions;
+
+/// Largest value that fits the 11 octal digits of the size and mtime header fields.
+const MAX_OCTAL: u64 = 0o777_7777_7777;
+
+/// Name of the PAX extended headers, which only carry the records that do not fit the ustar
+/// header following them.
+const PAX_NAME: &[u8] = b"././@PaxHeader";
+
+/// Serializes trees of any [`Fs`] into a POSIX tar stream.
+///
+/// The output only depends on the trees and the options: entries are written in sorted order,
+/// owners are always root, and modes are derived from the entry type and its read-only flag.
+/// Paths and link targets that do not fit in a ustar header are stored in PAX extended headers.
+/// Hard links are not detected, so every path to a file is written as a separate copy.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::SystemTime;
+///
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+/// use io_fs::TarFs;
+/// use io_fs::TarWriter;
+///
+/// let fs = MemFs::new();
+/// fs.create_dir_all("/release/bin")?;
+/// fs.write("/release/bin/tool", "#!/bin/sh")?;
+/// let mut writer = TarWriter::new(Vec::new());
+/// writer.mtime(SystemTime::UNIX_EPOCH).append_tree(&fs, "/release")?;
+/// let archive = TarFs::new(writer.finish()?)?;
+/// assert_eq!(archive.read_to_string("/bin/tool")?, "#!/bin/sh");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TarWriter<W: io::Write> {
+	out: W,
+	mtime: Option<SystemTime>,
+}
+
+/// An entry to be written, with its path relative to the archive root.
+struct Entry<'a> {
+	path: &'a [u8],
+	kind: u8,
+	mode: u32,
+	size: u64,
+	mtime: u64,
+	link: &'a [u8],
+}
+
+impl<W: io::Write> TarWriter<W> {
+	/// Creates a writer appending entries to `out`.
+	pub fn new(out: W) -> Self {
+		Self {
+			out,
+			mtime: None,
+		}
+	}
+
+	/// Records `mtime` as the modification time of every entry instead of the times reported by
+	/// the filesystem, which makes archives reproducible.
+	pub fn mtime(&mut self, mtime: SystemTime) -> &mut Self {
+		self.mtime = Some(mtime);
+		self
+	}
+
+	/// Appends every entry below `root` in `fs`, with paths relative to `root`. Symbolic links are
+	/// archived as links rather than followed.
+	pub fn append_tree<F: Fs, P: AsRef<Path>>(&mut self, fs: &F, root: P) -> io::Result<()> {
+		self.append_dir(fs, root.as_ref(), &[])
+	}
+
+	fn append_dir<F: Fs>(&mut self, fs: &F, dir: &Path, prefix: &[u8]) -> io::Result<()> {
+		let mut names = fs
+			.read_dir(dir)?
+			.map(|entry| entry.map(|entry| entry.file_name()))
+			.collect::<io::Result<Vec<OsString>>>()?;
+		names.sort();
+		for name in names {
+			let path = dir.join(&name);
+			let archived = [
+				prefix,
+				name.as_encoded_bytes(),
+			]
+			.concat();
+			let metadata = fs.symlink_metadata(&path)?;
+			let mtime = self.mtime.map_or_else(|| metadata.modified(), Ok).map_or(0, |time| {
+				time.duration_since(SystemTime::UNIX_EPOCH).map_or(0, |since| since.as_secs())
+			});
+			let readonly = metadata.permissions().readonly();
+			let file_type = metadata.file_type();
+			if file_type.is_dir() {
+				let archived = [
+					&archived[..],
+					b"/",
+				]
+				.concat();
+				self.append(&Entry {
+					path: &archived,
+					kind: b'5',
+					mode: if readonly {
+						0o555
+					} else {
+						0o755
+					},
+					size: 0,
+					mtime,
+					link: &[],
+				})?;
+				self.append_dir(fs, &path, &archived)?;
+			} else if file_type.is_symlink() {
+				let target = fs.read_link(&path)?;
+				self.append(&Entry {
+					path: &archived,
+					kind: b'2',
+					mode: 0o777,
+					size: 0,
+					mtime,
+					link: target.as_os_str().as_encoded_bytes(),
+				})?;
+			} else {
+				let contents = fs.read(&path)?;
+				self.append(&Entry {
+					path: &archived,
+					kind: b'0',
+					mode: if readonly {
+						0o444
+					} else {
+						0o644
+					},
+					size: contents.len() as u64,
+					mtime,
+					link: &[],
+				})?;
+				self.out.write_all(&contents)?;
+				self.pad(contents.len())?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Writes the header of `entry`, preceded by a PAX extended header if needed.
+	fn append(&mut self, entry: &Entry<'_>) -> io::Result<()> {
+		let mut records = Vec::new();
+		if entry.path.len() > 100 {
+			records.extend(pax_record(b"path", entry.path));
+		}
+		if entry.link.len() > 100 {
+			records.extend(pax_record(b"linkpath", entry.link));
+		}
+		if entry.size > MAX_OCTAL {
+			records.extend(pax_record(b"size", entry.size.to_string().as_bytes()));
+		}
+		if entry.mtime > MAX_OCTAL {
+			records.extend(pax_record(b"mtime", entry.mtime.to_string().as_bytes()));
+		}
+		if !records.is_empty() {
+			self.out.write_all(&header(&Entry {
+				path: PAX_NAME,
+				kind: b'x',
+				mode: 0o644,
+				size: records.len() as u64,
+				mtime: 0,
+				link: &[],
+			}))?;
+			self.out.write_all(&records)?;
+			self.pad(records.len())?;
+		}
+		self.out.write_all(&header(entry))
+	}
+
+	fn pad(&mut self, len: usize) -> io::Result<()> {
+		let padding = len.next_multiple_of(BLOCK) - len;
+		self.out.write_all(&[0; BLOCK][..padding])
+	}
+
+	/// Writes the end-of-archive marker and returns the underlying writer.
+	pub fn finish(mut self) -> io::Result<W> {
+		self.out.write_all(&[0; 2 * BLOCK])?;
+		self.out.flush()?;
+		Ok(self.out)
+	}
+}
+
+/// Formats a PAX record as `"<len> <key>=<value>\n"`, where `len` counts its own digits too.
+fn pax_record(key: &[u8], value: &[u8]) -> Vec<u8> {
+	let rest = key.len() + value.len() + 3;
+	let mut len = rest + rest.to_string().len();
+	if len.to_string().len() + rest != len {
+		len += 1;
+	}
+	[
+		len.to_string().as_bytes(),
+		b" ",
+		key,
+		b"=",
+		value,
+		b"\n",
+	]
+	.concat()
+}
+
+/// Builds a ustar header block, truncating fields that are also stored in a PAX header.
+fn header(entry: &Entry<'_>) -> [u8; BLOCK] {
+	let mut block = [0; BLOCK];
+	let mut field = |range: std::ops::Range<usize>, value: &[u8]| {
+		let len = value.len().min(range.len());
+		block[range.start..range.start + len].copy_from_slice(&value[..len]);
+	};
+	field(0..100, entry.path);
+	field(100..108, format!("{:07o}", entry.mode).as_bytes());
+	field(108..116, b"0000000");
+	field(116..124, b"0000000");
+	field(124..136, format!("{:011o}", entry.size.min(MAX_OCTAL)).as_bytes());
+	field(136..148, format!("{:011o}", entry.mtime.min(MAX_OCTAL)).as_bytes());
+	field(148..156, b"        ");
+	field(156..157, &[entry.kind]);
+	field(157..257, entry.link);
+	field(257..265, b"ustar\x0000");
+	let checksum: u32 = block.iter().map(|&byte| u32::from(byte)).sum();
+	block[148..156].copy_from_slice(format!("{checksum:06o}\0 ").as_bytes());
+	block
+}
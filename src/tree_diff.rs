@@ -0,0 +1,351 @@
+use std::ffi::OsString;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::DirEntry;
+use crate::FileType;
+use crate::Fs;
+use crate::Metadata;
+use crate::Permissions;
+
+/// Longest file contents, in characters, rendered inline by [`TreeDiff`]'s `Display` impl.
+const MAX_INLINE_LEN: usize = 40;
+
+/// An entry of a tree compared by [`diff_trees`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreeEntry {
+	Dir,
+	File(Vec<u8>),
+	Symlink(PathBuf),
+}
+
+impl fmt::Display for TreeEntry {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Dir => write!(f, "directory"),
+			Self::File(contents) => {
+				match std::str::from_utf8(contents) {
+					Ok(text) if text.chars().count() <= MAX_INLINE_LEN => {
+						write!(f, "file {text:?}")
+					}
+					_ => write!(f, "file ({} bytes)", contents.len()),
+				}
+			}
+			Self::Symlink(target) => write!(f, "symlink to {target:?}"),
+		}
+	}
+}
+
+/// A single difference found by [`diff_trees`], with a path relative to the compared roots.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TreeChange {
+	/// The entry only exists in the second tree. Entries inside an added directory are listed too.
+	Added {
+		path: PathBuf,
+		entry: TreeEntry,
+	},
+	/// The entry only exists in the first tree. Entries inside a removed directory are listed too.
+	Removed {
+		path: PathBuf,
+		entry: TreeEntry,
+	},
+	/// The entry changed type, contents or link target.
+	Modified {
+		path: PathBuf,
+		before: TreeEntry,
+		after: TreeEntry,
+	},
+	/// The entry exists in both trees with a different read-only flag.
+	Permissions {
+		path: PathBuf,
+		readonly: bool,
+	},
+}
+
+impl TreeChange {
+	/// Returns the path of the changed entry, relative to the compared roots.
+	pub fn path(&self) -> &Path {
+		match self {
+			Self::Added {
+				path,
+				..
+			}
+			| Self::Removed {
+				path,
+				..
+			}
+			| Self::Modified {
+				path,
+				..
+			}
+			| Self::Permissions {
+				path,
+				..
+			} => path,
+		}
+	}
+}
+
+impl fmt::Display for TreeChange {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let path = self.path().display();
+		match self {
+			Self::Added {
+				entry,
+				..
+			} => write!(f, "+ {path}: {entry}"),
+			Self::Removed {
+				entry,
+				..
+			} => write!(f, "- {path}: {entry}"),
+			Self::Modified {
+				before,
+				after,
+				..
+			} => write!(f, "~ {path}: {before} -> {after}"),
+			Self::Permissions {
+				readonly: true,
+				..
+			} => write!(f, "~ {path}: made read-only"),
+			Self::Permissions {
+				readonly: false,
+				..
+			} => write!(f, "~ {path}: made writable"),
+		}
+	}
+}
+
+/// The differences between two trees, as returned by [`diff_trees`].
+///
+/// The `Display` impl renders one line per change, prefixed with `+`, `-` or `~`, which makes
+/// it suitable for assertion messages.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TreeDiff {
+	changes: Vec<TreeChange>,
+}
+
+impl TreeDiff {
+	/// Returns whether the trees are identical.
+	pub fn is_empty(&self) -> bool {
+		self.changes.is_empty()
+	}
+
+	/// Returns the changes, sorted by path.
+	pub fn changes(&self) -> &[TreeChange] {
+		&self.changes
+	}
+}
+
+impl fmt::Display for TreeDiff {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.changes.is_empty() {
+			return write!(f, "no changes");
+		}
+		for (index, change) in self.changes.iter().enumerate() {
+			if index > 0 {
+				writeln!(f)?;
+			}
+			write!(f, "{change}")?;
+		}
+		Ok(())
+	}
+}
+
+impl IntoIterator for TreeDiff {
+	type IntoIter = std::vec::IntoIter<TreeChange>;
+	type Item = TreeChange;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.changes.into_iter()
+	}
+}
+
+/// Compares the tree below `before_root` in `before` with the tree below `after_root` in
+/// `after`.
+///
+/// The trees are walked with [`Fs::read_dir`], [`Fs::symlink_metadata`], [`Fs::read`] and
+/// [`Fs::read_link`], so any two backends can be compared, as can two states of the same one, for
+/// example a [`MemFs`](crate::MemFs) and a second `MemFs` into which a
+/// [`MemSnapshot`](crate::MemSnapshot) was restored. Symbolic links are not followed, and
+/// timestamps and hard links are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use io_fs::diff_trees;
+/// use io_fs::Fs;
+/// use io_fs::MemFs;
+///
+/// let before = MemFs::new();
+/// before.write("debug", "off")?;
+/// let after = MemFs::new();
+/// after.write("debug", "on")?;
+/// after.create_dir("cache")?;
+/// let diff = diff_trees(&before, "/", &after, "/")?;
+/// assert_eq!(diff.to_string(), "+ cache: directory\n~ debug: file \"off\" -> file \"on\"");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn diff_trees<A: Fs, B: Fs>(
+	before: &A,
+	before_root: impl AsRef<Path>,
+	after: &B,
+	after_root: impl AsRef<Path>,
+) -> io::Result<TreeDiff> {
+	let mut walk = Walk {
+		before: Side {
+			fs: before,
+			root: before_root.as_ref(),
+		},
+		after: Side {
+			fs: after,
+			root: after_root.as_ref(),
+		},
+		changes: Vec::new(),
+	};
+	walk.dir(Path::new(""))?;
+	walk.changes.sort_by(|a, b| a.path().cmp(b.path()));
+	Ok(TreeDiff {
+		changes: walk.changes,
+	})
+}
+
+/// One of the trees compared by [`diff_trees`].
+struct Side<'a, F> {
+	fs: &'a F,
+	root: &'a Path,
+}
+
+impl<F: Fs> Side<'_, F> {
+	fn names(&self, dir: &Path) -> io::Result<Vec<OsString>> {
+		let mut names = self
+			.fs
+			.read_dir(self.root.join(dir))?
+			.map(|entry| entry.map(|entry| entry.file_name()))
+			.collect::<io::Result<Vec<_>>>()?;
+		names.sort();
+		Ok(names)
+	}
+
+	/// Reads the entry at `path` along with its read-only flag.
+	fn entry(&self, path: &Path) -> io::Result<(TreeEntry, bool)> {
+		let path = self.root.join(path);
+		let metadata = self.fs.symlink_metadata(&path)?;
+		let file_type = metadata.file_type();
+		let entry = if file_type.is_dir() {
+			TreeEntry::Dir
+		} else if file_type.is_symlink() {
+			TreeEntry::Symlink(self.fs.read_link(&path)?)
+		} else {
+			TreeEntry::File(self.fs.read(&path)?)
+		};
+		Ok((entry, metadata.permissions().readonly()))
+	}
+
+	/// Lists the entries inside the directory at `dir`, recursively.
+	fn descendants(&self, dir: &Path, found: &mut dyn FnMut(PathBuf, TreeEntry)) -> io::Result<()> {
+		for name in self.names(dir)? {
+			let path = dir.join(name);
+			let (entry, _) = self.entry(&path)?;
+			if entry == TreeEntry::Dir {
+				self.descendants(&path, found)?;
+			}
+			found(path, entry);
+		}
+		Ok(())
+	}
+}
+
+struct Walk<'a, A, B> {
+	before: Side<'a, A>,
+	after: Side<'a, B>,
+	changes: Vec<TreeChange>,
+}
+
+impl<A: Fs, B: Fs> Walk<'_, A, B> {
+	fn dir(&mut self, dir: &Path) -> io::Result<()> {
+		let before = self.before.names(dir)?;
+		let after = self.after.names(dir)?;
+		for name in &before {
+			let path = dir.join(name);
+			if after.binary_search(name).is_ok() {
+				self.entry(path)?;
+			} else {
+				self.removed(path)?;
+			}
+		}
+		for name in after.iter().filter(|name| before.binary_search(name).is_err()) {
+			self.added(dir.join(name))?;
+		}
+		Ok(())
+	}
+
+	fn entry(&mut self, path: PathBuf) -> io::Result<()> {
+		let (before, was_readonly) = self.before.entry(&path)?;
+		let (after, readonly) = self.after.entry(&path)?;
+		if was_readonly != readonly {
+			self.changes.push(TreeChange::Permissions {
+				path: path.clone(),
+				readonly,
+			});
+		}
+		match (&before, &after) {
+			(TreeEntry::Dir, TreeEntry::Dir) => return self.dir(&path),
+			(TreeEntry::Dir, _) => self.removed_descendants(&path)?,
+			(_, TreeEntry::Dir) => self.added_descendants(&path)?,
+			_ if before == after => return Ok(()),
+			_ => {}
+		}
+		self.changes.push(TreeChange::Modified {
+			path,
+			before,
+			after,
+		});
+		Ok(())
+	}
+
+	fn added(&mut self, path: PathBuf) -> io::Result<()> {
+		let (entry, _) = self.after.entry(&path)?;
+		if entry == TreeEntry::Dir {
+			self.added_descendants(&path)?;
+		}
+		self.changes.push(TreeChange::Added {
+			path,
+			entry,
+		});
+		Ok(())
+	}
+
+	fn removed(&mut self, path: PathBuf) -> io::Result<()> {
+		let (entry, _) = self.before.entry(&path)?;
+		if entry == TreeEntry::Dir {
+			self.removed_descendants(&path)?;
+		}
+		self.changes.push(TreeChange::Removed {
+			path,
+			entry,
+		});
+		Ok(())
+	}
+
+	fn added_descendants(&mut self, dir: &Path) -> io::Result<()> {
+		let changes = &mut self.changes;
+		self.after.descendants(dir, &mut |path, entry| {
+			changes.push(TreeChange::Added {
+				path,
+				entry,
+			});
+		})
+	}
+
+	fn removed_descendants(&mut self, dir: &Path) -> io::Result<()> {
+		let changes = &mut self.changes;
+		self.before.descendants(dir, &mut |path, entry| {
+			changes.push(TreeChange::Removed {
+				path,
+				entry,
+			});
+		})
+	}
+}
@@ -0,0 +1,439 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::archive;
+use crate::archive::Tree;
+use crate::archive::ROOT;
+use crate::crc32::crc32;
+
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY: u32 = 0x0606_4b50;
+const ZIP64_LOCATOR: u32 = 0x0706_4b50;
+const CENTRAL_DIRECTORY_HEADER: u32 = 0x0201_4b50;
+const LOCAL_FILE_HEADER: u32 = 0x0403_4b50;
+
+/// Extra field holding 64-bit sizes and offsets.
+const ZIP64_EXTRA: u16 = 0x0001;
+/// Extra field holding Unix timestamps.
+const EXTENDED_TIMESTAMP_EXTRA: u16 = 0x5455;
+
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+const ENCRYPTED_FLAG: u16 = 1;
+/// Upper byte of "version made by" for archives created on Unix, whose external attributes hold
+/// a mode.
+const UNIX_HOST: u8 = 3;
+
+const S_IFMT: u32 = 0o170_000;
+const S_IFDIR: u32 = 0o040_000;
+const S_IFLNK: u32 = 0o120_000;
+
+fn invalid(message: &str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, format!("malformed zip archive: {message}"))
+}
+
+/// Little-endian reader over a part of the archive.
+struct Reader<'a> {
+	data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	fn at(data: &'a [u8], offset: u64) -> io::Result<Self> {
+		let offset = usize::try_from(offset).map_err(|_| invalid("offset out of range"))?;
+		let data = data.get(offset..).ok_or_else(|| invalid("offset out of range"))?;
+		Ok(Self {
+			data,
+		})
+	}
+
+	fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+		if self.data.len() < len {
+			return Err(invalid("unexpected end of data"));
+		}
+		let (bytes, rest) = self.data.split_at(len);
+		self.data = rest;
+		Ok(bytes)
+	}
+
+	fn u8(&mut self) -> io::Result<u8> {
+		Ok(self.bytes(1)?[0])
+	}
+
+	fn u16(&mut self) -> io::Result<u16> {
+		Ok(u16::from_le_bytes(self.bytes(2)?.try_into().expect("length checked")))
+	}
+
+	fn u32(&mut self) -> io::Result<u32> {
+		Ok(u32::from_le_bytes(self.bytes(4)?.try_into().expect("length checked")))
+	}
+
+	fn u64(&mut self) -> io::Result<u64> {
+		Ok(u64::from_le_bytes(self.bytes(8)?.try_into().expect("length checked")))
+	}
+
+	fn signature(&mut self, signature: u32, what: &str) -> io::Result<()> {
+		if self.u32()? == signature {
+			Ok(())
+		} else {
+			Err(invalid(&format!("missing {what} signature")))
+		}
+	}
+}
+
+/// Where and how the data of a file entry is stored.
+#[derive(Clone, Debug)]
+pub(super) struct Data {
+	pub method: u16,
+	pub encrypted: bool,
+	pub range: Range<usize>,
+	pub size: u64,
+	pub crc32: u32,
+}
+
+/// What an entry is, with files locating their data in the archive.
+pub(super) type Kind = archive::Kind<Data>;
+
+#[derive(Debug)]
+pub(super) struct Node {
+	pub kind: Kind,
+	pub readonly: bool,
+	pub modified: Option<SystemTime>,
+	/// The Unix mode, for archives created on Unix.
+	pub mode: Option<u32>,
+}
+
+impl archive::Node for Node {
+	type Data = Data;
+
+	fn kind(&self) -> &Kind {
+		&self.kind
+	}
+
+	fn kind_mut(&mut self) -> &mut Kind {
+		&mut self.kind
+	}
+}
+
+impl Node {
+	fn dir() -> Self {
+		Self {
+			kind: Kind::Dir(BTreeMap::new()),
+			readonly: false,
+			modified: None,
+			mode: None,
+		}
+	}
+
+	pub fn len(&self) -> u64 {
+		match &self.kind {
+			Kind::File(data) => data.size,
+			Kind::Dir(_) => 0,
+			Kind::Symlink(target) => target.as_os_str().len() as u64,
+		}
+	}
+}
+
+/// A parsed archive: the raw bytes and a tree of nodes indexing into them.
+pub(super) struct Archive {
+	pub bytes: Arc<[u8]>,
+	pub tree: Tree<Node>,
+}
+
+/// The fields of a central directory header that describe an entry.
+struct Header {
+	name: Vec<u8>,
+	flags: u16,
+	method: u16,
+	crc32: u32,
+	compressed_size: u64,
+	size: u64,
+	offset: u64,
+	modified: Option<SystemTime>,
+	unix: bool,
+	attributes: u32,
+}
+
+impl Archive {
+	pub fn parse(bytes: Arc<[u8]>) -> io::Result<Self> {
+		let mut archive = Self {
+			bytes,
+			tree: Tree::new(Node::dir()),
+		};
+		let (count, offset) = archive.central_directory()?;
+		let mut reader = Reader::at(&archive.bytes, offset)?;
+		let mut headers = Vec::new();
+		for _ in 0..count {
+			headers.push(Self::header(&mut reader)?);
+		}
+		for header in headers {
+			archive.add(header)?;
+		}
+		Ok(archive)
+	}
+
+	/// Locates the central directory, returning its number of entries and its offset.
+	fn central_directory(&self) -> io::Result<(u64, u64)> {
+		// The end of central directory record is 22 bytes followed by a comment of up to 64 KiB.
+		let bytes = &self.bytes;
+		if bytes.len() < 22 {
+			return Err(invalid("end of central directory not found"));
+		}
+		let start = bytes.len().saturating_sub(22 + usize::from(u16::MAX));
+		let end = (start..=bytes.len() - 22)
+			.rev()
+			.find(|&at| bytes[at..at + 4] == END_OF_CENTRAL_DIRECTORY.to_le_bytes())
+			.ok_or_else(|| invalid("end of central directory not found"))?;
+		let mut reader = Reader::at(bytes, end as u64 + 10)?;
+		let count = reader.u16()?;
+		reader.u32()?;
+		let offset = reader.u32()?;
+		if count != u16::MAX && offset != u32::MAX {
+			return Ok((u64::from(count), u64::from(offset)));
+		}
+		let locator = end.checked_sub(20).ok_or_else(|| invalid("zip64 locator not found"))?;
+		let mut reader = Reader::at(bytes, locator as u64)?;
+		reader.signature(ZIP64_LOCATOR, "zip64 locator")?;
+		reader.u32()?;
+		let mut reader = Reader::at(bytes, reader.u64()?)?;
+		reader.signature(ZIP64_END_OF_CENTRAL_DIRECTORY, "zip64 end of central directory")?;
+		reader.bytes(20)?;
+		reader.u64()?;
+		let count = reader.u64()?;
+		reader.u64()?;
+		Ok((count, reader.u64()?))
+	}
+
+	fn header(reader: &mut Reader<'_>) -> io::Result<Header> {
+		reader.signature(CENTRAL_DIRECTORY_HEADER, "central directory header")?;
+		let [_, host] = reader.u16()?.to_le_bytes();
+		reader.u16()?;
+		let flags = reader.u16()?;
+		let method = reader.u16()?;
+		let time = reader.u16()?;
+		let date = reader.u16()?;
+		let crc32 = reader.u32()?;
+		let mut compressed_size = u64::from(reader.u32()?);
+		let mut size = u64::from(reader.u32()?);
+		let name_len = usize::from(reader.u16()?);
+		let extra_len = usize::from(reader.u16()?);
+		let comment_len = usize::from(reader.u16()?);
+		reader.u16()?;
+		reader.u16()?;
+		let attributes = reader.u32()?;
+		let mut offset = u64::from(reader.u32()?);
+		let name = reader.bytes(name_len)?.to_vec();
+		let mut extra = Reader {
+			data: reader.bytes(extra_len)?,
+		};
+		reader.bytes(comment_len)?;
+
+		let mut modified = dos_time(date, time);
+		while !extra.data.is_empty() {
+			let id = extra.u16()?;
+			let len = usize::from(extra.u16()?);
+			let mut field = Reader {
+				data: extra.bytes(len)?,
+			};
+			match id {
+				// Only the values that overflowed in the header are present, in this order.
+				ZIP64_EXTRA => {
+					for value in [
+						&mut size,
+						&mut compressed_size,
+						&mut offset,
+					] {
+						if *value == u64::from(u32::MAX) {
+							*value = field.u64()?;
+						}
+					}
+				}
+				EXTENDED_TIMESTAMP_EXTRA if len >= 5 && field.u8()? & 1 != 0 => {
+					let seconds = field.u32()? as i32;
+					modified = Some(unix_time(i64::from(seconds)));
+				}
+				_ => {}
+			}
+		}
+		Ok(Header {
+			name,
+			flags,
+			method,
+			crc32,
+			compressed_size,
+			size,
+			offset,
+			modified,
+			unix: host == UNIX_HOST,
+			attributes,
+		})
+	}
+
+	/// Adds the entry described by `header`, creating its parent directories as needed.
+	fn add(&mut self, header: Header) -> io::Result<()> {
+		let name = String::from_utf8_lossy(&header.name);
+		let components = archive::components(&name)
+			.ok_or_else(|| invalid(&format!("entry {name:?} escapes the archive")))?;
+		let mode = header.unix.then_some(header.attributes >> 16).filter(|mode| *mode != 0);
+		let is_dir = name.ends_with('/')
+			|| mode.map_or(header.attributes & 0x10 != 0, |mode| mode & S_IFMT == S_IFDIR);
+		let is_symlink = mode.is_some_and(|mode| mode & S_IFMT == S_IFLNK);
+		let readonly = match mode {
+			Some(mode) => mode & 0o222 == 0,
+			None => header.attributes & 0x01 != 0,
+		};
+		let Some((file_name, parents)) = components.split_last() else {
+			// An entry for the root directory itself.
+			return Ok(());
+		};
+
+		let mut dir = ROOT;
+		for parent in parents {
+			dir = match self.tree.entries(dir)?.get(parent) {
+				Some(&child) => child,
+				None => self.push(dir, parent.clone(), Node::dir())?,
+			};
+		}
+		let kind = if is_dir {
+			Kind::Dir(BTreeMap::new())
+		} else {
+			let data = self.data(&header)?;
+			if is_symlink {
+				let target = self.contents(&data)?;
+				Kind::Symlink(PathBuf::from(String::from_utf8_lossy(&target).into_owned()))
+			} else {
+				Kind::File(data)
+			}
+		};
+		let node = Node {
+			kind,
+			readonly,
+			modified: header.modified,
+			mode,
+		};
+		let Some(&existing) = self.tree.entries(dir)?.get(file_name) else {
+			self.push(dir, file_name.clone(), node)?;
+			return Ok(());
+		};
+		let existing = &mut self.tree.nodes[existing];
+		match (&existing.kind, node.kind) {
+			// A directory may be listed after entries inside it were added.
+			(Kind::Dir(_), Kind::Dir(_)) => {
+				existing.readonly = node.readonly;
+				existing.modified = node.modified;
+				existing.mode = node.mode;
+			}
+			(Kind::Dir(_), _) | (_, Kind::Dir(_)) => {
+				return Err(invalid(&format!("{name:?} is both a file and a directory")));
+			}
+			// Later entries replace earlier ones with the same name.
+			(_, kind) => {
+				*existing = Node {
+					kind,
+					..node
+				}
+			}
+		}
+		Ok(())
+	}
+
+	fn push(&mut self, dir: usize, name: OsString, node: Node) -> io::Result<usize> {
+		let index = self.tree.nodes.len();
+		let entries = self
+			.tree
+			.entries_mut(dir)
+			.ok_or_else(|| invalid("an entry is nested inside a file"))?;
+		entries.insert(name, index);
+		self.tree.nodes.push(node);
+		Ok(index)
+	}
+
+	/// Finds the data of an entry by reading its local file header.
+	fn data(&self, header: &Header) -> io::Result<Data> {
+		let mut reader = Reader::at(&self.bytes, header.offset)?;
+		reader.signature(LOCAL_FILE_HEADER, "local file header")?;
+		reader.bytes(22)?;
+		let skip = usize::from(reader.u16()?) + usize::from(reader.u16()?);
+		reader.bytes(skip)?;
+		let start = self.bytes.len() - reader.data.len();
+		let len =
+			usize::try_from(header.compressed_size).map_err(|_| invalid("entry too large"))?;
+		reader.bytes(len)?;
+		Ok(Data {
+			method: header.method,
+			encrypted: header.flags & ENCRYPTED_FLAG != 0,
+			range: start..start + len,
+			size: header.size,
+			crc32: header.crc32,
+		})
+	}
+
+	/// Decompresses the data of an entry and verifies its checksum.
+	pub fn contents(&self, data: &Data) -> io::Result<Arc<[u8]>> {
+		if data.encrypted {
+			return Err(io::Error::new(io::ErrorKind::Unsupported, "encrypted zip entry"));
+		}
+		let raw = &self.bytes[data.range.clone()];
+		let contents: Arc<[u8]> = match data.method {
+			STORED => raw.into(),
+			DEFLATED => {
+				let limit = usize::try_from(data.size).map_err(|_| invalid("entry too large"))?;
+				miniz_oxide::inflate::decompress_to_vec_with_limit(raw, limit)
+					.map_err(|error| invalid(&format!("bad deflate stream: {error}")))?
+					.into()
+			}
+			method => {
+				return Err(io::Error::new(
+					io::ErrorKind::Unsupported,
+					format!("unsupported zip compression method {method}"),
+				))
+			}
+		};
+		if contents.len() as u64 != data.size {
+			return Err(invalid("entry size does not match its header"));
+		}
+		if crc32(&contents) != data.crc32 {
+			return Err(invalid("checksum mismatch"));
+		}
+		Ok(contents)
+	}
+}
+
+/// Converts an MS-DOS date and time, which zip archives store in local time, treating it as UTC.
+fn dos_time(date: u16, time: u16) -> Option<SystemTime> {
+	let year = i64::from(date >> 9) + 1980;
+	let month = i64::from((date >> 5) & 0xf);
+	let day = i64::from(date & 0x1f);
+	if !(1..=12).contains(&month) || day == 0 {
+		return None;
+	}
+	// Days since the Unix epoch of a date in the proleptic Gregorian calendar.
+	let (y, m) = if month <= 2 {
+		(year - 1, month + 9)
+	} else {
+		(year, month - 3)
+	};
+	let era = y.div_euclid(400);
+	let year_of_era = y - era * 400;
+	let day_of_year = (153 * m + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	let days = era * 146_097 + day_of_era - 719_468;
+	let seconds = i64::from(time >> 11) * 3600
+		+ i64::from((time >> 5) & 0x3f) * 60
+		+ i64::from(time & 0x1f) * 2;
+	Some(unix_time(days * 86_400 + seconds))
+}
+
+fn unix_time(seconds: i64) -> SystemTime {
+	let offset = Duration::from_secs(seconds.unsigned_abs());
+	if seconds < 0 {
+		SystemTime::UNIX_EPOCH - offset
+	} else {
+		SystemTime::UNIX_EPOCH + offset
+	}
+}
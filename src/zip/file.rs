@@ -0,0 +1,84 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::archive::Kind;
+use super::ZipFs;
+use super::ZipMetadata;
+use crate::archive::Flags;
+use crate::ArchiveFile;
+use crate::OpenOptions;
+
+/// An open entry of a [`ZipFs`].
+///
+/// Deflated entries are decompressed when opened. Handles created through
+/// [`File::try_clone`](crate::File::try_clone) share their cursor.
+pub type ZipFile = ArchiveFile<ZipMetadata>;
+
+/// Options for opening entries of a [`ZipFs`]. Any option that could modify the archive makes
+/// [`OpenOptions::open`] fail with [`io::ErrorKind::ReadOnlyFilesystem`].
+#[derive(Clone, Debug)]
+pub struct ZipOpenOptions {
+	fs: ZipFs,
+	flags: Flags,
+}
+
+impl ZipOpenOptions {
+	pub(super) fn new(fs: ZipFs) -> Self {
+		Self {
+			fs,
+			flags: Flags::default(),
+		}
+	}
+}
+
+impl OpenOptions for ZipOpenOptions {
+	type File = ZipFile;
+
+	fn read(&mut self, read: bool) -> &mut Self {
+		self.flags.read = read;
+		self
+	}
+
+	fn write(&mut self, write: bool) -> &mut Self {
+		self.flags.write = write;
+		self
+	}
+
+	fn append(&mut self, append: bool) -> &mut Self {
+		self.flags.append = append;
+		self
+	}
+
+	fn truncate(&mut self, truncate: bool) -> &mut Self {
+		self.flags.truncate = truncate;
+		self
+	}
+
+	fn create(&mut self, create: bool) -> &mut Self {
+		self.flags.create = create;
+		self
+	}
+
+	fn create_new(&mut self, create_new: bool) -> &mut Self {
+		self.flags.create_new = create_new;
+		self
+	}
+
+	fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<Self::File> {
+		self.flags.check()?;
+		let archive = &self.fs.archive;
+		let index = archive.tree.lookup(path.as_ref(), true)?;
+		let node = &archive.tree.nodes[index];
+		let metadata = ZipMetadata::new(node);
+		match &node.kind {
+			Kind::File(data) => {
+				let contents = archive.contents(data)?;
+				let range = 0..contents.len();
+				Ok(ZipFile::new(contents, Some(range), metadata))
+			}
+			Kind::Dir(_) => Ok(ZipFile::new(Arc::clone(&archive.bytes), None, metadata)),
+			Kind::Symlink(_) => unreachable!("symbolic links are followed"),
+		}
+	}
+}
@@ -0,0 +1,98 @@
+use std::io;
+use std::time::SystemTime;
+
+use super::archive::Node;
+use crate::ArchiveFileTimes;
+use crate::ArchiveFileType;
+use crate::ArchivePermissions;
+use crate::FileType;
+use crate::Metadata;
+
+/// The type of an entry in a [`ZipFs`](super::ZipFs).
+pub type ZipFileType = ArchiveFileType;
+
+/// Permissions of an entry in a [`ZipFs`](super::ZipFs), derived from its Unix mode or MS-DOS
+/// attributes.
+pub type ZipPermissions = ArchivePermissions;
+
+/// Timestamps for a [`ZipFile`](super::ZipFile). Archives cannot be modified, so they are never
+/// applied.
+pub type ZipFileTimes = ArchiveFileTimes;
+
+/// Metadata of an entry in a [`ZipFs`](super::ZipFs).
+///
+/// Zip archives only record modification times, so [`Metadata::accessed`] and
+/// [`Metadata::created`] return [`io::ErrorKind::Unsupported`].
+#[derive(Clone, Debug)]
+pub struct ZipMetadata {
+	file_type: ZipFileType,
+	len: u64,
+	permissions: ZipPermissions,
+	modified: Option<SystemTime>,
+	mode: Option<u32>,
+}
+
+impl ZipMetadata {
+	pub(super) fn new(node: &Node) -> Self {
+		Self {
+			file_type: ZipFileType::from(&node.kind),
+			len: node.len(),
+			permissions: ZipPermissions {
+				readonly: node.readonly,
+			},
+			modified: node.modified,
+			mode: node.mode,
+		}
+	}
+
+	/// Returns the Unix mode of the entry, including its file type bits, if the archive was
+	/// created on Unix.
+	pub fn unix_mode(&self) -> Option<u32> {
+		self.mode
+	}
+}
+
+fn unsupported() -> io::Error {
+	io::Error::new(io::ErrorKind::Unsupported, "not recorded in zip archives")
+}
+
+impl Metadata for ZipMetadata {
+	type FileType = ZipFileType;
+	type Permissions = ZipPermissions;
+
+	fn file_type(&self) -> Self::FileType {
+		self.file_type
+	}
+
+	fn is_dir(&self) -> bool {
+		self.file_type.is_dir()
+	}
+
+	fn is_file(&self) -> bool {
+		self.file_type.is_file()
+	}
+
+	fn is_symlink(&self) -> bool {
+		self.file_type.is_symlink()
+	}
+
+	fn len(&self) -> u64 {
+		self.len
+	}
+
+	fn permissions(&self) -> Self::Permissions {
+		self.permissions.clone()
+	}
+
+	fn modified(&self) -> io::Result<SystemTime> {
+		self.modified.ok_or_else(unsupported)
+	}
+
+	fn accessed(&self) -> io::Result<SystemTime> {
+		Err(unsupported())
+	}
+
+	fn created(&self) -> io::Result<SystemTime> {
+		Err(unsupported())
+	}
+}
@@ -0,0 +1,178 @@
+use std::fmt;
+use std::io;
+use std::io::Read;
+use std::path;
+use std::sync::Arc;
+
+mod archive;
+use archive::Archive;
+mod file;
+pub use file::*;
+mod metadata;
+pub use metadata::*;
+
+use crate::archive::denied;
+use crate::ArchiveDirBuilder;
+use crate::ArchiveDirEntry;
+use crate::ArchiveReadDir;
+use crate::Fs;
+use crate::OpenOptions;
+
+/// A read-only filesystem backed by the contents of a zip archive.
+///
+/// The central directory is parsed once, when the filesystem is created. Entries may be stored or
+/// deflated, and their checksums are verified when they are opened. Directories that only appear
+/// as prefixes of other entries are listed too, and entries created on Unix keep their modes, so
+/// symbolic links are supported. Every operation that would modify the archive fails with
+/// [`io::ErrorKind::ReadOnlyFilesystem`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use io_fs::Fs;
+/// use io_fs::Native;
+/// use io_fs::ZipFs;
+///
+/// let assets = ZipFs::open(&Native {}, "assets.zip")?;
+/// let shader = assets.read_to_string("/shaders/main.wgsl")?;
+/// assert!(assets.write("/shaders/main.wgsl", "").is_err());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct ZipFs {
+	archive: Arc<Archive>,
+}
+
+impl ZipFs {
+	/// Parses an archive held in memory, for example one embedded with [`include_bytes!`].
+	pub fn new(bytes: impl Into<Arc<[u8]>>) -> io::Result<Self> {
+		Ok(Self {
+			archive: Arc::new(Archive::parse(bytes.into())?),
+		})
+	}
+
+	/// Reads and parses the archive at `path` in `fs`.
+	pub fn open<F: Fs, P: AsRef<path::Path>>(fs: &F, path: P) -> io::Result<Self> {
+		Self::new(fs.read(path)?)
+	}
+}
+
+impl fmt::Debug for ZipFs {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ZipFs")
+			.field("len", &self.archive.bytes.len())
+			.field("entries", &(self.archive.tree.nodes.len() - 1))
+			.finish()
+	}
+}
+
+/// A builder for directories in a [`ZipFs`], which always fails.
+pub type ZipDirBuilder = ArchiveDirBuilder;
+
+/// Iterator over the entries of a directory in a [`ZipFs`].
+pub type ZipReadDir = ArchiveReadDir<ZipFs>;
+
+/// An entry returned by [`ZipReadDir`].
+pub type ZipDirEntry = ArchiveDirEntry<ZipFs>;
+
+impl Fs for ZipFs {
+	type DirBuilder = ZipDirBuilder;
+	type DirEntry = ZipDirEntry;
+	type File = ZipFile;
+	type FileTimes = ZipFileTimes;
+	type FileType = ZipFileType;
+	type Metadata = ZipMetadata;
+	type OpenOptions = ZipOpenOptions;
+	type Permissions = ZipPermissions;
+	type ReadDir = ZipReadDir;
+
+	fn dir_builder(&self) -> Self::DirBuilder {
+		ZipDirBuilder::default()
+	}
+
+	fn file_times(&self) -> Self::FileTimes {
+		ZipFileTimes::default()
+	}
+
+	fn open_options(&self) -> Self::OpenOptions {
+		ZipOpenOptions::new(self.clone())
+	}
+
+	fn canonicalize<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		Ok(self.archive.tree.resolve(path.as_ref(), true)?.1)
+	}
+
+	fn copy<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<u64> {
+		Err(denied())
+	}
+
+	fn create_dir<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn create_dir_all<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn exists<P: AsRef<path::Path>>(&self, path: P) -> io::Result<bool> {
+		self.archive.tree.exists(path.as_ref())
+	}
+
+	fn hard_link<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let index = self.archive.tree.lookup(path.as_ref(), true)?;
+		Ok(ZipMetadata::new(&self.archive.tree.nodes[index]))
+	}
+
+	fn read<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+		let mut contents = Vec::new();
+		self.open_options().read(true).open(path)?.read_to_end(&mut contents)?;
+		Ok(contents)
+	}
+
+	fn read_dir<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::ReadDir> {
+		ArchiveReadDir::new(self, &self.archive.tree, path.as_ref())
+	}
+
+	fn read_link<P: AsRef<path::Path>>(&self, path: P) -> io::Result<path::PathBuf> {
+		self.archive.tree.read_link(path.as_ref())
+	}
+
+	fn read_to_string<P: AsRef<path::Path>>(&self, path: P) -> io::Result<String> {
+		String::from_utf8(self.read(path)?).map_err(|_| {
+			io::Error::new(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+		})
+	}
+
+	fn remove_dir<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_dir_all<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn remove_file<P: AsRef<path::Path>>(&self, _: P) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn rename<P: AsRef<path::Path>, Q: AsRef<path::Path>>(&self, _: P, _: Q) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn set_permissions<P: AsRef<path::Path>>(&self, _: P, _: Self::Permissions) -> io::Result<()> {
+		Err(denied())
+	}
+
+	fn symlink_metadata<P: AsRef<path::Path>>(&self, path: P) -> io::Result<Self::Metadata> {
+		let index = self.archive.tree.lookup(path.as_ref(), false)?;
+		Ok(ZipMetadata::new(&self.archive.tree.nodes[index]))
+	}
+
+	fn write<P: AsRef<path::Path>, C: AsRef<[u8]>>(&self, _: P, _: C) -> io::Result<()> {
+		Err(denied())
+	}
+}
@@ -0,0 +1,95 @@
+use std::future::Future;
+use std::io;
+use std::panic;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::thread;
+use std::thread::Thread;
+
+use io_fs::AsyncFs;
+use io_fs::Blocking;
+use io_fs::DirEntry;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::Native;
+use io_fs::ThreadPool;
+
+struct Unpark(Thread);
+
+impl Wake for Unpark {
+	fn wake(self: Arc<Self>) {
+		self.0.unpark();
+	}
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+	let waker = Arc::new(Unpark(thread::current())).into();
+	let mut cx = Context::from_waker(&waker);
+	let mut future = pin!(future);
+	loop {
+		match future.as_mut().poll(&mut cx) {
+			Poll::Ready(output) => return output,
+			Poll::Pending => thread::park(),
+		}
+	}
+}
+
+async fn round_trip<F: AsyncFs>(fs: &F, root: &std::path::Path) -> io::Result<()> {
+	fs.create_dir_all(root.join("dir")).await?;
+	fs.write(root.join("dir/file"), "contents").await?;
+	assert_eq!(fs.read_to_string(root.join("dir/file")).await?, "contents");
+	assert_eq!(fs.metadata(root.join("dir/file")).await?.len(), 8);
+	let names = fs
+		.read_dir(root.join("dir"))
+		.await?
+		.map(|entry| entry.map(|entry| entry.file_name()))
+		.collect::<io::Result<Vec<_>>>()?;
+	assert_eq!(names, ["file"]);
+	let error = fs.remove_dir(root.join("dir")).await.unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::DirectoryNotEmpty);
+	fs.remove_dir_all(root.join("dir")).await?;
+	assert!(!fs.exists(root.join("dir")).await?);
+	Ok(())
+}
+
+#[test]
+fn native() {
+	let scratch = io_fs::conformance::Scratch::new(&Native {}).unwrap();
+	block_on(round_trip(&Native {}, scratch.path())).unwrap();
+}
+
+#[test]
+fn blocking() {
+	let fs = Blocking::with_pool(MemFs::new(), ThreadPool::new(2));
+	block_on(round_trip(&fs, "/".as_ref())).unwrap();
+}
+
+#[test]
+fn blocking_shares_filesystem() {
+	let mem_fs = MemFs::new();
+	let fs = Blocking::new(mem_fs.clone());
+	block_on(fs.write("file", "contents")).unwrap();
+	assert_eq!(mem_fs.read("file").unwrap(), b"contents");
+	assert_eq!(block_on(fs.clone().read("file")).unwrap(), b"contents");
+}
+
+#[test]
+fn pool_runs_queued_jobs() {
+	let pool = ThreadPool::new(2);
+	let tasks = (0..64).map(|index| pool.spawn(move || index * 2)).collect::<Vec<_>>();
+	let results = tasks.into_iter().map(block_on).collect::<Vec<_>>();
+	assert_eq!(results, (0..64).map(|index| index * 2).collect::<Vec<_>>());
+}
+
+#[test]
+fn pool_resumes_panics() {
+	let pool = ThreadPool::new(1);
+	let task = pool.spawn(|| panic!("job panicked"));
+	let payload = panic::catch_unwind(panic::AssertUnwindSafe(|| block_on(task))).unwrap_err();
+	assert_eq!(payload.downcast_ref::<&str>(), Some(&"job panicked"));
+	assert_eq!(block_on(pool.spawn(|| 1)), 1);
+}
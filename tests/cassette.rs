@@ -0,0 +1,144 @@
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::Write;
+use std::path::Path;
+
+use io_fs::conformance::Scratch;
+use io_fs::CaptureFs;
+use io_fs::Cassette;
+use io_fs::DirBuilder;
+use io_fs::DirEntry;
+use io_fs::File;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::Native;
+use io_fs::OpenOptions;
+use io_fs::Operation;
+use io_fs::ReplayFs;
+
+/// Exercises most calls, returning what it observed.
+fn session<F: Fs>(fs: &F, root: &Path) -> Vec<String> {
+	let mut seen = Vec::new();
+	fs.dir_builder().recursive(true).create(root.join("a/b")).unwrap();
+	fs.write(root.join("a/text"), "hello").unwrap();
+	fs.write(
+		root.join("a/binary"),
+		[
+			0xff,
+			0,
+			1,
+		],
+	)
+	.unwrap();
+	seen.push(format!("{:?}", fs.read(root.join("a/binary"))));
+	seen.push(format!("{:?}", fs.copy(root.join("a/text"), root.join("a/b/copy"))));
+	seen.push(format!("{:?}", fs.remove_dir(root.join("a")).map_err(|error| error.kind())));
+	seen.push(format!("{:?}", fs.read_to_string(root.join("missing")).map_err(|e| e.kind())));
+
+	let mut names = fs
+		.read_dir(root.join("a"))
+		.unwrap()
+		.map(|entry| {
+			let entry = entry.unwrap();
+			let metadata = entry.metadata().unwrap();
+			(entry.file_name(), metadata.is_dir(), metadata.len())
+		})
+		.collect::<Vec<_>>();
+	names.sort();
+	seen.push(format!("{names:?}"));
+
+	let mut file = fs.open_options().read(true).write(true).open(root.join("a/text")).unwrap();
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+	file.seek(io::SeekFrom::Start(0)).unwrap();
+	file.write_all(b"J").unwrap();
+	file.try_clone().unwrap().set_len(3).unwrap();
+	seen.push(format!("{contents} {}", file.metadata().unwrap().len()));
+	let modified = fs.metadata(root.join("a/text")).unwrap().modified().unwrap();
+	seen.push(format!("{modified:?}"));
+
+	fs.rename(root.join("a/text"), root.join("a/b/text")).unwrap();
+	seen.push(fs.read_to_string(root.join("a/b/text")).unwrap());
+	seen.push(format!("{:?}", fs.exists(root.join("a/text"))));
+	fs.remove_dir_all(root.join("a")).unwrap();
+	seen
+}
+
+#[test]
+fn replays_a_native_session() {
+	let native = Native {};
+	let scratch = Scratch::new(&native).unwrap();
+	let fs = CaptureFs::new(Native {});
+	let recorded = session(&fs, scratch.path());
+	let cassette = fs.cassette();
+	assert_eq!(cassette.operations().filter(|op| *op == Operation::Open).count(), 1);
+
+	let cassette = Cassette::parse(&cassette.to_string()).unwrap();
+	assert_eq!(cassette, fs.cassette());
+	let fs = ReplayFs::new(cassette);
+	assert_eq!(session(&fs, scratch.path()), recorded);
+	fs.assert_finished();
+}
+
+#[test]
+fn replays_errors() {
+	let fs = CaptureFs::new(Native {});
+	let error = fs.remove_file("/nonexistent/io-fs-cassette").unwrap_err();
+	let fs = ReplayFs::new(fs.cassette());
+	let replayed = fs.remove_file("/nonexistent/io-fs-cassette").unwrap_err();
+	assert_eq!(replayed.kind(), error.kind());
+	assert_eq!(replayed.raw_os_error(), error.raw_os_error());
+
+	let cassette = r#"{"op":"create_dir","args":["x"],"err":{"kind":"Other","message":"boom"}}"#;
+	let fs = ReplayFs::new(cassette.parse().unwrap());
+	assert_eq!(fs.create_dir("x").unwrap_err().to_string(), "boom");
+}
+
+#[test]
+fn saves_and_loads() {
+	let fs = CaptureFs::new(MemFs::new());
+	fs.write("file", "contents").unwrap();
+	let storage = MemFs::new();
+	fs.cassette().save(&storage, "cassette.jsonl").unwrap();
+	assert_eq!(
+		storage.read_to_string("cassette.jsonl").unwrap(),
+		"{\"op\":\"write\",\"args\":[\"file\",\"contents\"],\"ok\":null}\n"
+	);
+	assert_eq!(Cassette::load(&storage, "cassette.jsonl").unwrap(), fs.cassette());
+}
+
+#[test]
+fn rejects_malformed_lines() {
+	let error = Cassette::parse("\n{\"op\":\"write\",\"args\":[]}").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+	assert_eq!(error.to_string(), "line 2: expected either `ok` or `err`");
+	let error = Cassette::parse(r#"{"op":"frobnicate","args":[],"ok":null}"#).unwrap_err();
+	assert_eq!(error.to_string(), "line 1: unknown operation `frobnicate`");
+}
+
+#[test]
+#[should_panic = "unexpected call write(\"b\", \"x\"), line 1 of the cassette expected {\"op\":\"write\",\"args\":[\"a\",\"x\"],\"ok\":null}"]
+fn unexpected_calls_panic() {
+	let fs = ReplayFs::new(r#"{"op":"write","args":["a","x"],"ok":null}"#.parse().unwrap());
+	let _ = fs.write("b", "x");
+}
+
+#[test]
+#[should_panic = "unexpected call exists(\"a\") after the end of the cassette"]
+fn calls_after_the_end_panic() {
+	let fs = ReplayFs::new(Cassette::new());
+	let _ = fs.exists("a");
+}
+
+#[test]
+#[should_panic = "1 recorded calls were never replayed, starting at line 2"]
+fn unfinished_replays_panic() {
+	let fs = CaptureFs::new(MemFs::new());
+	fs.create_dir("a").unwrap();
+	fs.create_dir("b").unwrap();
+	let fs = ReplayFs::new(fs.cassette());
+	fs.create_dir("a").unwrap();
+	fs.assert_finished();
+}
@@ -0,0 +1,53 @@
+mod native {
+	io_fs::fs_conformance_tests!(|| io_fs::Native {});
+}
+
+mod mem_fs {
+	io_fs::fs_conformance_tests!(io_fs::MemFs::new);
+}
+
+mod dyn_fs {
+	io_fs::fs_conformance_tests!(|| -> Box<dyn io_fs::DynFs> { Box::new(io_fs::MemFs::new()) });
+}
+
+mod fault_fs {
+	io_fs::fs_conformance_tests!(|| io_fs::FaultFs::new(io_fs::MemFs::new()));
+}
+
+mod recording_fs {
+	io_fs::fs_conformance_tests!(|| io_fs::RecordingFs::new(io_fs::MemFs::new()));
+}
+
+mod capture_fs {
+	io_fs::fs_conformance_tests!(|| io_fs::CaptureFs::new(io_fs::MemFs::new()));
+}
+
+mod jail_fs {
+	use io_fs::Fs;
+
+	io_fs::fs_conformance_tests!(|| {
+		let inner = io_fs::MemFs::new();
+		inner.create_dir("/jail").unwrap();
+		io_fs::JailFs::new(inner, "/jail").unwrap()
+	});
+}
+
+mod overlay_fs {
+	use io_fs::Fs;
+
+	io_fs::fs_conformance_tests!(|| {
+		let lower = io_fs::MemFs::new();
+		lower.create_dir_all(std::env::temp_dir()).unwrap();
+		lower.write(std::env::temp_dir().join("lower"), "lower").unwrap();
+		io_fs::OverlayFs::new(io_fs::MemFs::new(), lower)
+	});
+}
+
+mod mount_fs {
+	io_fs::fs_conformance_tests!(|| {
+		let mut fs = io_fs::MountFs::new();
+		fs.mount("/", io_fs::MemFs::new());
+		fs.mount(std::env::temp_dir(), io_fs::MemFs::new());
+		fs
+	});
+}
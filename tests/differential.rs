@@ -0,0 +1,22 @@
+#![cfg(target_os = "linux")]
+
+const SEEDS: u64 = 64;
+const LEN: usize = 48;
+
+#[test]
+fn mem_fs() {
+	for seed in 0..SEEDS {
+		io_fs::differential::check(io_fs::MemFs::new, seed, LEN);
+	}
+}
+
+#[test]
+fn dyn_fs() {
+	for seed in 0..SEEDS {
+		io_fs::differential::check(
+			|| -> Box<dyn io_fs::DynFs> { Box::new(io_fs::MemFs::new()) },
+			seed,
+			LEN,
+		);
+	}
+}
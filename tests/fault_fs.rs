@@ -0,0 +1,109 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use io_fs::Fault;
+use io_fs::FaultFs;
+use io_fs::FaultRule;
+use io_fs::File;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::OpenOptions;
+use io_fs::Operation;
+
+fn error(kind: io::ErrorKind) -> Fault {
+	Fault::Error(kind)
+}
+
+#[test]
+fn nth_read_fails() {
+	let fs = FaultFs::new(MemFs::new());
+	fs.write("file", "abc").unwrap();
+	fs.inject(FaultRule::new(error(io::ErrorKind::Other)).operation(Operation::FileRead).nth(3));
+	let mut file = fs.open_options().read(true).open("file").unwrap();
+	let mut buf = [0; 1];
+	assert_eq!(file.read(&mut buf).unwrap(), 1);
+	assert_eq!(file.read(&mut buf).unwrap(), 1);
+	assert_eq!(file.read(&mut buf).unwrap_err().kind(), io::ErrorKind::Other);
+	assert_eq!(file.read(&mut buf).unwrap(), 1);
+	assert_eq!(buf, *b"c");
+}
+
+#[test]
+fn path_glob_selects_calls() {
+	let fs = FaultFs::new(MemFs::new());
+	fs.create_dir_all("/logs/old").unwrap();
+	fs.inject(FaultRule::new(error(io::ErrorKind::PermissionDenied)).path("/logs/*.log"));
+	let denied = fs.write("/logs/app.log", "").unwrap_err();
+	assert_eq!(denied.kind(), io::ErrorKind::PermissionDenied);
+	fs.write("/logs/app.txt", "").unwrap();
+	fs.write("/logs/old/app.log", "").unwrap();
+	assert!(fs.rename("/logs/app.txt", "/logs/app.log").is_err());
+
+	fs.clear();
+	fs.inject(FaultRule::new(error(io::ErrorKind::PermissionDenied)).path("/logs/**/?pp.log"));
+	assert!(fs.remove_file("/logs/old/app.log").is_err());
+	let missing = fs.remove_file("/logs/old/app.txt").unwrap_err();
+	assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn os_errors() {
+	let fs = FaultFs::new(MemFs::new());
+	fs.inject(FaultRule::new(Fault::Os(28)).operation(Operation::CreateDir));
+	let error = fs.create_dir("dir").unwrap_err();
+	assert_eq!(error.raw_os_error(), Some(28));
+	assert!(!fs.exists("dir").unwrap());
+}
+
+#[test]
+fn short_writes() {
+	let fs = FaultFs::new(MemFs::new());
+	fs.inject(FaultRule::new(Fault::ShortWrite(2)).operation(Operation::FileWrite).nth(1));
+	let mut file = fs.open_options().write(true).create(true).open("file").unwrap();
+	assert_eq!(file.write(b"abcd").unwrap(), 2);
+	file.write_all(b"efgh").unwrap();
+	drop(file);
+	assert_eq!(fs.read("file").unwrap(), b"abefgh");
+
+	// Short writes never apply to other operations.
+	fs.inject(FaultRule::new(Fault::ShortWrite(0)));
+	assert_eq!(fs.read("file").unwrap(), b"abefgh");
+	assert_eq!(fs.write("file", "ab").unwrap_err().kind(), io::ErrorKind::WriteZero);
+	assert_eq!(fs.read("file").unwrap(), b"");
+}
+
+#[test]
+fn sync_all_fails() {
+	let fs = FaultFs::new(MemFs::new());
+	let file = fs.open_options().write(true).create(true).open("file").unwrap();
+	fs.inject(FaultRule::new(error(io::ErrorKind::Other)).operation(Operation::FileSyncAll));
+	assert!(file.sync_all().is_err());
+	assert!(file.try_clone().unwrap().sync_all().is_err());
+	file.sync_data().unwrap();
+	fs.clear();
+	file.sync_all().unwrap();
+}
+
+#[test]
+fn probability_is_seeded() {
+	let failures = |seed| {
+		let fs = FaultFs::new(MemFs::new());
+		fs.inject(FaultRule::new(error(io::ErrorKind::Other)).probability(0.5, seed));
+		(0..64).map(|_| fs.exists("file").is_err()).collect::<Vec<_>>()
+	};
+	assert_eq!(failures(1), failures(1));
+	assert_ne!(failures(1), failures(2));
+	assert!(failures(1).contains(&true) && failures(1).contains(&false));
+}
+
+#[test]
+fn first_firing_rule_wins() {
+	let fs = FaultFs::new(MemFs::new());
+	fs.inject(FaultRule::new(error(io::ErrorKind::NotFound)).nth(2));
+	fs.inject(FaultRule::new(error(io::ErrorKind::Other)).nth(1).operation(Operation::Exists));
+	fs.inject(FaultRule::new(error(io::ErrorKind::Other)).nth(2));
+	assert_eq!(fs.exists("a").unwrap_err().kind(), io::ErrorKind::Other);
+	assert_eq!(fs.exists("a").unwrap_err().kind(), io::ErrorKind::NotFound);
+	assert!(!fs.exists("a").unwrap());
+}
@@ -0,0 +1,132 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use io_fs::conformance::expect_error;
+use io_fs::DirEntry;
+use io_fs::Fs;
+use io_fs::JailFs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+
+fn jail() -> JailFs<MemFs> {
+	let inner = MemFs::new();
+	inner.create_dir_all("/srv/tenant42/data").unwrap();
+	inner.write("/srv/tenant42/data/file", "inside").unwrap();
+	inner.write("/srv/secret", "outside").unwrap();
+	JailFs::new(inner, "/srv/tenant42").unwrap()
+}
+
+#[test]
+fn paths_are_rooted() {
+	let fs = jail();
+	assert_eq!(fs.read_to_string("/data/file").unwrap(), "inside");
+	assert_eq!(fs.read_to_string("data/file").unwrap(), "inside");
+	assert_eq!(fs.read_to_string("data/../data/./file").unwrap(), "inside");
+	fs.create_dir("/logs").unwrap();
+	fs.write("logs/app.log", "started").unwrap();
+	assert!(fs.get_ref().exists("/srv/tenant42/logs/app.log").unwrap());
+	assert!(!fs.exists("/srv").unwrap());
+	assert_eq!(fs.root(), Path::new("/srv/tenant42"));
+}
+
+#[test]
+fn parent_dirs_cannot_escape() {
+	let fs = jail();
+	expect_error(fs.read("../secret"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.read("/data/../../secret"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.exists(".."), io::ErrorKind::PermissionDenied);
+	expect_error(fs.write("../secret", "overwritten"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.rename("/data/file", "../stolen"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.copy("../secret", "copy"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.open_options().read(true).open("/../secret"), io::ErrorKind::PermissionDenied);
+	assert_eq!(fs.get_ref().read_to_string("/srv/secret").unwrap(), "outside");
+	assert!(!fs.get_ref().exists("/srv/stolen").unwrap());
+}
+
+#[test]
+fn symlinks_are_followed_inside() {
+	let fs = jail();
+	let inner = fs.get_ref();
+	inner.symlink("data/file", "/srv/tenant42/relative").unwrap();
+	inner.symlink("/srv/tenant42/data", "/srv/tenant42/absolute").unwrap();
+	inner.symlink("../data/file", "/srv/tenant42/data/sibling").unwrap();
+
+	assert_eq!(fs.read_to_string("relative").unwrap(), "inside");
+	assert_eq!(fs.read_to_string("absolute/file").unwrap(), "inside");
+	assert_eq!(fs.read_to_string("data/sibling").unwrap(), "inside");
+	assert_eq!(fs.canonicalize("absolute/sibling").unwrap(), Path::new("/data/file"));
+	assert_eq!(fs.read_link("absolute").unwrap(), Path::new("/data"));
+	assert_eq!(fs.read_link("relative").unwrap(), Path::new("data/file"));
+}
+
+#[test]
+fn symlinks_cannot_escape() {
+	let fs = jail();
+	let inner = fs.get_ref();
+	inner.symlink("/srv/secret", "/srv/tenant42/absolute").unwrap();
+	inner.symlink("../../secret", "/srv/tenant42/data/relative").unwrap();
+	inner.symlink("/srv", "/srv/tenant42/dir").unwrap();
+	inner.symlink("loop", "/srv/tenant42/loop").unwrap();
+
+	expect_error(fs.read("absolute"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.read("data/relative"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.read("dir/secret"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.write("absolute", "overwritten"), io::ErrorKind::PermissionDenied);
+	expect_error(
+		fs.open_options().write(true).open("data/relative"),
+		io::ErrorKind::PermissionDenied,
+	);
+	expect_error(fs.read_link("absolute"), io::ErrorKind::PermissionDenied);
+	expect_error(fs.canonicalize("dir"), io::ErrorKind::PermissionDenied);
+	assert!(fs.read("loop").is_err());
+	assert_eq!(inner.read_to_string("/srv/secret").unwrap(), "outside");
+
+	// The links themselves can be inspected and removed.
+	assert!(fs.symlink_metadata("absolute").unwrap().is_symlink());
+	assert_eq!(fs.read_link("data/relative").unwrap(), Path::new("../../secret"));
+	fs.remove_file("absolute").unwrap();
+	assert!(inner.exists("/srv/secret").unwrap());
+}
+
+#[test]
+fn create_new_does_not_follow_symlinks() {
+	let fs = jail();
+	let inner = fs.get_ref();
+	inner.symlink("data/missing", "/srv/tenant42/dangling").unwrap();
+	inner.symlink("data/file", "/srv/tenant42/link").unwrap();
+
+	let mut options = fs.open_options();
+	options.write(true).create_new(true);
+	expect_error(options.open("dangling"), io::ErrorKind::AlreadyExists);
+	expect_error(options.open("link"), io::ErrorKind::AlreadyExists);
+	expect_error(options.open("data/file"), io::ErrorKind::AlreadyExists);
+	assert!(!fs.exists("data/missing").unwrap());
+	options.open("data/new").unwrap();
+	assert!(fs.exists("data/new").unwrap());
+
+	// Without `create_new`, the last component is followed as before.
+	fs.open_options().write(true).create(true).open("dangling").unwrap();
+	assert!(fs.exists("data/missing").unwrap());
+}
+
+#[test]
+fn dir_entries_are_inside() {
+	let fs = jail();
+	let paths =
+		fs.read_dir("/data/..").unwrap().map(|entry| entry.unwrap().path()).collect::<Vec<_>>();
+	assert_eq!(paths, [PathBuf::from("/data/../data")]);
+	let names = fs.read_dir("data").unwrap().map(|entry| entry.unwrap().file_name());
+	assert_eq!(names.collect::<Vec<_>>(), ["file"]);
+}
+
+#[test]
+fn root_must_be_a_directory() {
+	let inner = MemFs::new();
+	inner.write("file", "").unwrap();
+	let error = JailFs::new(inner.clone(), "/missing").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::NotFound);
+	let error = JailFs::new(inner, "/file").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::NotADirectory);
+}
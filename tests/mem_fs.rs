@@ -0,0 +1,172 @@
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::PathBuf;
+
+use io_fs::conformance::entry_names;
+use io_fs::conformance::expect_error;
+use io_fs::DirEntry;
+use io_fs::File;
+use io_fs::FileType;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+
+#[test]
+fn relative_paths_resolve_against_root() {
+	let fs = MemFs::new();
+	fs.create_dir_all("a/b").unwrap();
+	fs.write("a/b/c", "c").unwrap();
+	assert_eq!(fs.read_to_string("/a/b/c").unwrap(), "c");
+	assert_eq!(fs.canonicalize("a/./b/../b/c").unwrap(), PathBuf::from("/a/b/c"));
+	assert_eq!(entry_names(&fs, "/"), ["a"]);
+}
+
+#[test]
+fn errors_match_linux() {
+	let fs = MemFs::new();
+	fs.create_dir("/dir").unwrap();
+	fs.write("/dir/file", "").unwrap();
+	expect_error(fs.create_dir("/dir"), io::ErrorKind::AlreadyExists);
+	expect_error(fs.remove_dir("/dir"), io::ErrorKind::DirectoryNotEmpty);
+	expect_error(fs.remove_file("/dir"), io::ErrorKind::IsADirectory);
+	expect_error(fs.read("/dir/file/child"), io::ErrorKind::NotADirectory);
+	expect_error(fs.read("/missing"), io::ErrorKind::NotFound);
+	expect_error(fs.read_link("/dir/file"), io::ErrorKind::InvalidInput);
+	expect_error(fs.rename("/dir", "/dir/nested"), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn symlinks_are_followed() {
+	let fs = MemFs::new();
+	fs.create_dir("/dir").unwrap();
+	fs.write("/dir/file", "target").unwrap();
+	fs.symlink("dir", "/link").unwrap();
+	fs.symlink("/missing", "/dangling").unwrap();
+	assert_eq!(fs.read_to_string("/link/file").unwrap(), "target");
+	assert_eq!(fs.read_link("/link").unwrap(), PathBuf::from("dir"));
+	assert!(fs.symlink_metadata("/link").unwrap().is_symlink());
+	assert!(fs.metadata("/link").unwrap().is_dir());
+	assert!(!fs.exists("/dangling").unwrap());
+	assert_eq!(fs.canonicalize("/link/file").unwrap(), PathBuf::from("/dir/file"));
+}
+
+#[test]
+fn hard_links_share_contents() {
+	let fs = MemFs::new();
+	fs.write("/a", "shared").unwrap();
+	fs.hard_link("/a", "/b").unwrap();
+	fs.remove_file("/a").unwrap();
+	assert_eq!(fs.read_to_string("/b").unwrap(), "shared");
+}
+
+#[test]
+fn open_options_follow_std() {
+	let fs = MemFs::new();
+	expect_error(fs.open_options().open("/file"), io::ErrorKind::InvalidInput);
+	expect_error(fs.open_options().read(true).open("/file"), io::ErrorKind::NotFound);
+	expect_error(
+		fs.open_options().append(true).truncate(true).create(true).open("/file"),
+		io::ErrorKind::InvalidInput,
+	);
+
+	let mut file = fs.open_options().write(true).create_new(true).open("/file").unwrap();
+	file.write_all(b"hello world").unwrap();
+	expect_error(
+		fs.open_options().write(true).create_new(true).open("/file"),
+		io::ErrorKind::AlreadyExists,
+	);
+
+	let mut file = fs.open_options().append(true).open("/file").unwrap();
+	file.write_all(b"!").unwrap();
+	assert_eq!(fs.read_to_string("/file").unwrap(), "hello world!");
+
+	fs.open_options().write(true).truncate(true).open("/file").unwrap();
+	assert_eq!(fs.read("/file").unwrap(), b"");
+}
+
+#[test]
+fn files_read_write_and_seek() {
+	let fs = MemFs::new();
+	let mut file = fs.open_options().read(true).write(true).create(true).open("/file").unwrap();
+	file.write_all(b"hello").unwrap();
+	file.seek(SeekFrom::Start(1)).unwrap();
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "ello");
+
+	// Writing past the end fills the gap with zeroes.
+	file.seek(SeekFrom::End(2)).unwrap();
+	file.write_all(b"!").unwrap();
+	assert_eq!(fs.read("/file").unwrap(), b"hello\0\0!");
+	assert_eq!(file.metadata().unwrap().len(), 8);
+	assert_eq!(file.seek(SeekFrom::Current(-9)).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn set_len_requires_write_access() {
+	let fs = MemFs::new();
+	fs.write("/file", "contents").unwrap();
+
+	let file = fs.open_options().read(true).open("/file").unwrap();
+	assert_eq!(file.set_len(0).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+	// Appending implies write access, as with `std`.
+	let file = fs.open_options().append(true).open("/file").unwrap();
+	file.set_len(4).unwrap();
+	assert_eq!(fs.read_to_string("/file").unwrap(), "cont");
+
+	let file = fs.open_options().write(true).open("/file").unwrap();
+	file.set_len(6).unwrap();
+	assert_eq!(fs.read("/file").unwrap(), b"cont\0\0");
+}
+
+#[test]
+fn removed_files_stay_open() {
+	let fs = MemFs::new();
+	fs.write("/file", "kept").unwrap();
+	let mut file = fs.open_options().read(true).open("/file").unwrap();
+	fs.remove_file("/file").unwrap();
+	assert!(!fs.exists("/file").unwrap());
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "kept");
+}
+
+#[test]
+fn directories_are_listed_and_moved() {
+	let fs = MemFs::new();
+	fs.create_dir_all("/from/nested").unwrap();
+	fs.write("/from/a", "a").unwrap();
+	fs.write("/from/nested/b", "b").unwrap();
+	assert_eq!(
+		entry_names(&fs, "/from"),
+		[
+			"a",
+			"nested"
+		]
+	);
+
+	fs.rename("/from", "/to").unwrap();
+	assert!(!fs.exists("/from").unwrap());
+	assert_eq!(fs.read_to_string("/to/nested/b").unwrap(), "b");
+
+	let entry =
+		fs.read_dir("/to").unwrap().find(|entry| entry.as_ref().unwrap().file_name() == "nested");
+	assert!(entry.unwrap().unwrap().file_type().unwrap().is_dir());
+
+	fs.remove_dir_all("/to").unwrap();
+	assert!(entry_names(&fs, "/").is_empty());
+}
+
+#[test]
+fn clones_share_the_tree() {
+	let fs = MemFs::new();
+	let clone = fs.clone();
+	clone.write("/shared", "shared").unwrap();
+	assert_eq!(fs.read_to_string("/shared").unwrap(), "shared");
+	assert!(MemFs::new().read("/shared").is_err());
+}
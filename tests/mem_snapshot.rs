@@ -0,0 +1,140 @@
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+
+use io_fs::File;
+use io_fs::Fs;
+use io_fs::MemChange;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+use io_fs::Permissions;
+
+fn fixture() -> MemFs {
+	let fs = MemFs::new();
+	fs.create_dir_all("/data/nested").unwrap();
+	fs.write("/data/a", "a").unwrap();
+	fs.write("/data/nested/b", "b").unwrap();
+	fs.symlink("a", "/data/link").unwrap();
+	fs
+}
+
+#[test]
+fn restore_rolls_back_changes() {
+	let fs = fixture();
+	let snapshot = fs.snapshot();
+	fs.write("/data/a", "changed").unwrap();
+	fs.remove_dir_all("/data/nested").unwrap();
+	fs.write("/new", "").unwrap();
+	fs.rename("/data/link", "/moved").unwrap();
+
+	fs.restore(&snapshot);
+	assert_eq!(fs.read_to_string("/data/a").unwrap(), "a");
+	assert_eq!(fs.read_to_string("/data/nested/b").unwrap(), "b");
+	assert_eq!(fs.read_link("/data/link").unwrap(), PathBuf::from("a"));
+	assert!(!fs.exists("/new").unwrap());
+	assert!(!fs.exists("/moved").unwrap());
+
+	// A snapshot can be restored any number of times, into any filesystem.
+	fs.write("/data/a", "again").unwrap();
+	let other = MemFs::new();
+	other.restore(&snapshot);
+	assert_eq!(other.read_to_string("/data/a").unwrap(), "a");
+	fs.restore(&snapshot);
+	assert_eq!(fs.read_to_string("/data/a").unwrap(), "a");
+	assert!(fs.changes_since(&snapshot).is_empty());
+}
+
+#[test]
+fn snapshots_are_not_affected_by_later_writes() {
+	let fs = fixture();
+	let hard_link = "/data/hard";
+	fs.hard_link("/data/a", hard_link).unwrap();
+	let snapshot = fs.snapshot();
+	let mut file = fs.open_options().append(true).open(hard_link).unwrap();
+	file.write_all(b"ppended").unwrap();
+	assert_eq!(fs.read_to_string("/data/a").unwrap(), "appended");
+
+	let copy = MemFs::new();
+	copy.restore(&snapshot);
+	assert_eq!(copy.read_to_string("/data/a").unwrap(), "a");
+	copy.write(hard_link, "shared").unwrap();
+	assert_eq!(copy.read_to_string("/data/a").unwrap(), "shared");
+	assert_eq!(fs.read_to_string("/data/a").unwrap(), "appended");
+}
+
+#[test]
+fn open_handles_survive_a_restore() {
+	let fs = fixture();
+	let snapshot = fs.snapshot();
+	let mut created = fs.open_options().read(true).write(true).create(true).open("/c").unwrap();
+	let mut existing = fs.open_options().read(true).open("/data/a").unwrap();
+	fs.write("/data/a", "changed").unwrap();
+
+	fs.restore(&snapshot);
+	assert!(!fs.exists("/c").unwrap());
+	created.write_all(b"orphan").unwrap();
+	assert_eq!(created.metadata().unwrap().len(), 6);
+	let mut contents = String::new();
+	existing.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "a");
+	drop(created);
+	assert!(fs.changes_since(&snapshot).is_empty());
+}
+
+#[test]
+fn changes_between_snapshots() {
+	let fs = fixture();
+	let before = fs.snapshot();
+	assert!(before.changes(&fs.snapshot()).is_empty());
+
+	fs.write("/data/a", "changed").unwrap();
+	fs.read("/data/nested/b").unwrap();
+	fs.remove_dir_all("/data/nested").unwrap();
+	fs.create_dir_all("/added/dir").unwrap();
+	fs.remove_file("/data/link").unwrap();
+	fs.symlink("elsewhere", "/data/link").unwrap();
+	let mut perm = fs.metadata("/data").unwrap().permissions();
+	perm.set_readonly(true);
+	fs.set_permissions("/data", perm).unwrap();
+	let after = fs.snapshot();
+	assert_eq!(
+		before.changes(&after),
+		[
+			MemChange::Added("/added".into()),
+			MemChange::Modified("/data".into()),
+			MemChange::Modified("/data/a".into()),
+			MemChange::Modified("/data/link".into()),
+			MemChange::Removed("/data/nested".into()),
+		]
+	);
+	assert_eq!(after.changes(&before)[0], MemChange::Removed("/added".into()));
+
+	// Recreating identical contents is not a change.
+	fs.restore(&before);
+	fs.remove_file("/data/a").unwrap();
+	fs.write("/data/a", "a").unwrap();
+	assert!(fs.changes_since(&before).is_empty());
+}
+
+#[test]
+fn large_trees() {
+	let fs = MemFs::new();
+	for i in 0..1000 {
+		fs.write(format!("/{i:04}"), i.to_string()).unwrap();
+	}
+	let snapshot = fs.snapshot();
+	for i in (0..1000).step_by(2) {
+		fs.remove_file(format!("/{i:04}")).unwrap();
+	}
+	let names = fs
+		.read_dir("/")
+		.unwrap()
+		.map(|entry| io_fs::DirEntry::file_name(&entry.unwrap()).into_string().unwrap());
+	let expected = (1..1000).step_by(2).map(|i| format!("{i:04}"));
+	assert!(names.eq(expected));
+	assert_eq!(fs.changes_since(&snapshot).len(), 500);
+	fs.restore(&snapshot);
+	assert_eq!(fs.read_dir("/").unwrap().count(), 1000);
+	assert_eq!(fs.read_to_string("/0998").unwrap(), "998");
+}
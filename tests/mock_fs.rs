@@ -0,0 +1,110 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use io_fs::Expectation;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::MockFs;
+use io_fs::MockReport;
+use io_fs::Permissions;
+
+fn path(path: &str) -> PathBuf {
+	PathBuf::from(path)
+}
+
+#[test]
+fn answers_matching_expectations_in_order() {
+	let fs = MockFs::new(MemFs::new());
+	fs.expect_exists(Expectation::ok(false).with(path("lock")));
+	fs.expect_exists(Expectation::ok(true).with(path("lock")));
+	fs.expect_copy(Expectation::returning(|(from, _): &(PathBuf, PathBuf)| {
+		Ok(from.as_os_str().len() as u64)
+	}));
+
+	assert!(!fs.exists("lock").unwrap());
+	assert!(fs.exists("lock").unwrap());
+	assert_eq!(fs.copy("abc", "d").unwrap(), 3);
+	fs.verify().unwrap();
+}
+
+#[test]
+fn reports_unmet_and_unexpected_calls() {
+	let fs = MockFs::new(MemFs::new());
+	fs.expect_remove_file(Expectation::ok(()).with(path("a")));
+	fs.expect_rename(
+		Expectation::ok(()).matching(|(from, _): &(PathBuf, PathBuf)| from.starts_with("/tmp")),
+	);
+
+	let error = fs.rename("/var/a", "b").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::Other);
+	assert_eq!(error.to_string(), r#"unexpected call rename("/var/a", "b")"#);
+	assert!(fs.read("c").is_err());
+
+	let report = fs.verify().unwrap_err();
+	assert_eq!(
+		report,
+		MockReport {
+			unmet: vec![
+				r#"remove_file("a") expected exactly 1 calls, got 0"#.to_owned(),
+				"rename(matching arguments) expected exactly 1 calls, got 0".to_owned(),
+			],
+			unexpected: vec![
+				r#"read("c")"#.to_owned(),
+				r#"rename("/var/a", "b")"#.to_owned(),
+			],
+		}
+	);
+	assert_eq!(
+		report.to_string(),
+		"mock expectations failed
+  unmet: remove_file(\"a\") expected exactly 1 calls, got 0
+  unmet: rename(matching arguments) expected exactly 1 calls, got 0
+  unexpected: read(\"c\")
+  unexpected: rename(\"/var/a\", \"b\")"
+	);
+}
+
+#[test]
+fn counts_calls() {
+	let fs = MockFs::new(MemFs::new());
+	fs.expect_create_dir(Expectation::ok(()).times(2));
+	fs.expect_create_dir(Expectation::err(io::ErrorKind::AlreadyExists).at_least(1));
+	fs.expect_remove_dir(Expectation::ok(()).any_times());
+
+	fs.create_dir("a").unwrap();
+	fs.create_dir("b").unwrap();
+	for _ in 0..3 {
+		assert_eq!(fs.create_dir("c").unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+	}
+	fs.verify().unwrap();
+}
+
+#[test]
+fn returns_values_of_the_backend() {
+	let backend = MemFs::new();
+	backend.write("template", "abc").unwrap();
+	let metadata = backend.metadata("template").unwrap();
+	let fs = MockFs::new(backend);
+	fs.expect_metadata(Expectation::ok(metadata).with(path("/srv/data")));
+	fs.expect_set_permissions(Expectation::returning(|(path, perm): &(PathBuf, _)| {
+		assert_eq!(path, Path::new("/srv/data"));
+		assert!(Permissions::readonly(perm));
+		Ok(())
+	}));
+
+	let metadata = fs.metadata("/srv/data").unwrap();
+	assert_eq!(metadata.len(), 3);
+	let mut perm = metadata.permissions();
+	perm.set_readonly(true);
+	fs.set_permissions("/srv/data", perm).unwrap();
+	fs.verify().unwrap();
+}
+
+#[test]
+fn mocks_can_be_shared_between_threads() {
+	fn assert_send_sync<T: Send + Sync>(_: &T) {}
+	let fs = MockFs::new(MemFs::new());
+	assert_send_sync(&fs);
+}
@@ -0,0 +1,137 @@
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use io_fs::conformance::entry_names;
+use io_fs::DirEntry;
+use io_fs::File;
+use io_fs::FileTimes;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::MountFs;
+use io_fs::OpenOptions;
+use io_fs::Permissions;
+
+fn mounts() -> (MountFs, MemFs, MemFs) {
+	let root = MemFs::new();
+	let tmp = MemFs::new();
+	let mut fs = MountFs::new();
+	fs.mount("/", root.clone());
+	fs.mount("/tmp", tmp.clone());
+	(fs, root, tmp)
+}
+
+#[test]
+fn routes_to_the_longest_mount_point() {
+	let (fs, root, tmp) = mounts();
+	fs.write("/tmp/a", "tmp").unwrap();
+	fs.write("/tmpfile", "root").unwrap();
+	fs.write("tmp/../b", "root").unwrap();
+	assert_eq!(tmp.read_to_string("/a").unwrap(), "tmp");
+	assert_eq!(root.read_to_string("/tmpfile").unwrap(), "root");
+	assert_eq!(root.read_to_string("/b").unwrap(), "root");
+	assert!(!root.exists("/tmp/a").unwrap());
+
+	tmp.symlink("/a", "/link").unwrap();
+	assert_eq!(fs.read_link("/tmp/link").unwrap(), Path::new("/tmp/a"));
+	assert_eq!(fs.canonicalize("/tmp/link").unwrap(), Path::new("/tmp/a"));
+	let paths = fs.read_dir("/tmp").unwrap().map(|entry| entry.unwrap().path());
+	assert_eq!(
+		paths.collect::<Vec<_>>(),
+		[
+			PathBuf::from("/tmp/a"),
+			PathBuf::from("/tmp/link")
+		]
+	);
+}
+
+#[test]
+fn lists_mount_points() {
+	let (mut fs, root, _) = mounts();
+	let config = MemFs::new();
+	config.write("/app.toml", "").unwrap();
+	fs.mount("/etc/config", config);
+	root.create_dir("/tmp").unwrap();
+	root.write("/tmp/hidden", "").unwrap();
+
+	assert_eq!(entry_names(&fs, "/"), ["tmp"]);
+	assert_eq!(entry_names(&fs, "/tmp"), Vec::<String>::new());
+	assert_eq!(fs.read_dir("/etc").unwrap_err().kind(), io::ErrorKind::NotFound);
+	root.create_dir("/etc").unwrap();
+	assert_eq!(entry_names(&fs, "/etc"), ["config"]);
+	assert_eq!(entry_names(&fs, "/etc/config"), ["app.toml"]);
+	let entry = fs.read_dir("/etc").unwrap().next().unwrap().unwrap();
+	assert_eq!(entry.path(), Path::new("/etc/config"));
+	assert!(entry.file_type().unwrap().is_dir());
+	assert!(fs.metadata("/etc/config").unwrap().is_dir());
+}
+
+#[test]
+fn links_and_renames_do_not_cross_mounts() {
+	let (fs, root, tmp) = mounts();
+	fs.write("/file", "abc").unwrap();
+	let error = fs.rename("/file", "/tmp/file").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::CrossesDevices);
+	let error = fs.hard_link("/file", "/tmp/file").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::CrossesDevices);
+	fs.rename("/file", "/renamed").unwrap();
+
+	let mut perm = fs.metadata("/renamed").unwrap().permissions();
+	perm.set_readonly(true);
+	fs.set_permissions("/renamed", perm).unwrap();
+	assert_eq!(fs.copy("/renamed", "/tmp/copy").unwrap(), 3);
+	assert_eq!(tmp.read_to_string("/copy").unwrap(), "abc");
+	assert!(tmp.metadata("/copy").unwrap().permissions().readonly());
+	assert!(root.exists("/renamed").unwrap());
+}
+
+#[test]
+fn mount_points_are_busy() {
+	let (fs, _, _) = mounts();
+	fs.write("/tmp/file", "").unwrap();
+	fs.create_dir("/dir").unwrap();
+	let busy = [
+		fs.remove_dir_all("/tmp"),
+		fs.remove_dir("/tmp"),
+		fs.remove_dir_all("/"),
+		fs.rename("/tmp", "/other"),
+		fs.rename("/dir", "/tmp"),
+	];
+	for result in busy {
+		assert_eq!(result.unwrap_err().kind(), io::ErrorKind::ResourceBusy);
+	}
+	fs.remove_dir_all("/dir").unwrap();
+	assert!(fs.exists("/tmp/file").unwrap());
+}
+
+#[test]
+fn mounts_can_be_replaced() {
+	let (mut fs, _, tmp) = mounts();
+	let previous = fs.mount("/tmp", MemFs::new()).unwrap();
+	assert!(!fs.exists("/tmp/a").unwrap());
+	tmp.write("/a", "").unwrap();
+	assert!(previous.exists(Path::new("/a")).unwrap());
+	assert!(fs.unmount("/tmp").is_some());
+	assert!(fs.unmount("/tmp").is_none());
+	assert_eq!(fs.mount_points().collect::<Vec<_>>(), [Path::new("/")]);
+	assert!(fs.unmount("/").is_some());
+	assert!(!fs.exists("/").unwrap());
+	assert_eq!(fs.read("/a").unwrap_err().kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn file_times_apply_to_every_mount() {
+	let (fs, _, _) = mounts();
+	let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+	for path in [
+		"/file",
+		"/tmp/file",
+	] {
+		let file = fs.open_options().write(true).create(true).open(path).unwrap();
+		file.set_times(fs.file_times().set_modified(time)).unwrap();
+		assert_eq!(fs.metadata(path).unwrap().modified().unwrap(), time);
+	}
+}
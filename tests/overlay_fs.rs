@@ -0,0 +1,228 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+
+use io_fs::conformance::entry_names;
+use io_fs::DirEntry;
+use io_fs::Fault;
+use io_fs::FaultFs;
+use io_fs::FaultRule;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+use io_fs::Operation;
+use io_fs::OverlayFs;
+use io_fs::Permissions;
+
+fn overlay() -> OverlayFs<MemFs, MemFs> {
+	let lower = MemFs::new();
+	lower.create_dir_all("/src/bin").unwrap();
+	lower.write("/src/lib.rs", "lib").unwrap();
+	lower.write("/src/bin/main.rs", "main").unwrap();
+	lower.write("/README", "readme").unwrap();
+	OverlayFs::new(MemFs::new(), lower)
+}
+
+#[test]
+fn writes_are_copied_up() {
+	let fs = overlay();
+	let mut perm = fs.lower().metadata("/src/lib.rs").unwrap().permissions();
+	perm.set_readonly(true);
+	fs.lower().set_permissions("/src/lib.rs", perm).unwrap();
+
+	assert_eq!(fs.read_to_string("/src/bin/main.rs").unwrap(), "main");
+	fs.write("/src/bin/main.rs", "changed").unwrap();
+	assert_eq!(fs.read_to_string("/src/bin/main.rs").unwrap(), "changed");
+	assert_eq!(fs.upper().read_to_string("/src/bin/main.rs").unwrap(), "changed");
+	assert_eq!(fs.lower().read_to_string("/src/bin/main.rs").unwrap(), "main");
+	assert!(!fs.upper().exists("/src/lib.rs").unwrap());
+
+	// Copied up files keep their permissions.
+	assert!(fs.write("/src/lib.rs", "changed").is_err());
+	assert!(fs.upper().metadata("/src/lib.rs").unwrap().permissions().readonly());
+	assert_eq!(fs.read_to_string("/src/lib.rs").unwrap(), "lib");
+}
+
+#[test]
+fn listings_are_merged() {
+	let fs = overlay();
+	fs.write("/src/new.rs", "").unwrap();
+	fs.write("/src/lib.rs", "changed").unwrap();
+	assert_eq!(
+		entry_names(&fs, "/src"),
+		[
+			"bin",
+			"lib.rs",
+			"new.rs"
+		]
+	);
+	assert_eq!(
+		entry_names(&fs, "/"),
+		[
+			"README",
+			"src"
+		]
+	);
+	let paths = fs.read_dir("/src/bin").unwrap().map(|entry| entry.unwrap().path());
+	assert_eq!(paths.collect::<Vec<_>>(), [PathBuf::from("/src/bin/main.rs")]);
+}
+
+#[test]
+fn removals_leave_whiteouts() {
+	let fs = overlay();
+	fs.remove_file("/src/lib.rs").unwrap();
+	fs.remove_dir_all("/src/bin").unwrap();
+	assert_eq!(entry_names(&fs, "/src"), Vec::<String>::new());
+	assert!(!fs.exists("/src/bin/main.rs").unwrap());
+	assert_eq!(fs.read("/src/lib.rs").unwrap_err().kind(), io::ErrorKind::NotFound);
+	assert_eq!(fs.remove_file("/src/lib.rs").unwrap_err().kind(), io::ErrorKind::NotFound);
+	assert!(fs.lower().exists("/src/bin/main.rs").unwrap());
+	fs.remove_dir("/src").unwrap();
+	assert_eq!(entry_names(&fs, "/"), ["README"]);
+
+	// Whiteouts are kept in the upper layer.
+	let reopened = OverlayFs::new(fs.upper().clone(), fs.lower().clone());
+	assert_eq!(entry_names(&reopened, "/"), ["README"]);
+
+	// Recreating a directory does not bring back its former contents.
+	fs.create_dir_all("/src/bin").unwrap();
+	assert_eq!(entry_names(&fs, "/src"), ["bin"]);
+	assert_eq!(entry_names(&fs, "/src/bin"), Vec::<String>::new());
+	fs.write("/src/lib.rs", "new").unwrap();
+	assert_eq!(fs.read_to_string("/src/lib.rs").unwrap(), "new");
+}
+
+#[test]
+fn renames_across_layers() {
+	let fs = overlay();
+	fs.rename("/README", "/README.md").unwrap();
+	assert_eq!(
+		entry_names(&fs, "/"),
+		[
+			"README.md",
+			"src"
+		]
+	);
+	assert_eq!(fs.read_to_string("/README.md").unwrap(), "readme");
+
+	fs.write("/src/bin/extra.rs", "extra").unwrap();
+	fs.rename("/src", "/code").unwrap();
+	assert_eq!(
+		entry_names(&fs, "/"),
+		[
+			"README.md",
+			"code"
+		]
+	);
+	assert_eq!(
+		entry_names(&fs, "/code"),
+		[
+			"bin",
+			"lib.rs"
+		]
+	);
+	assert_eq!(
+		entry_names(&fs, "/code/bin"),
+		[
+			"extra.rs",
+			"main.rs"
+		]
+	);
+	assert_eq!(fs.read_to_string("/code/bin/main.rs").unwrap(), "main");
+
+	// Renaming over a directory emptied of the lower entries hides them for good.
+	fs.create_dir("/src").unwrap();
+	fs.rename("/code/bin", "/src").unwrap();
+	assert_eq!(
+		entry_names(&fs, "/src"),
+		[
+			"extra.rs",
+			"main.rs"
+		]
+	);
+	let error = fs.rename("/code", "/src").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::DirectoryNotEmpty);
+	let error = fs.rename("/code", "/README.md").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::NotADirectory);
+	let error = fs.rename("/README.md", "/code").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::IsADirectory);
+	assert_eq!(
+		entry_names(fs.lower(), "/"),
+		[
+			"README",
+			"src"
+		]
+	);
+	assert_eq!(
+		entry_names(fs.lower(), "/src"),
+		[
+			"bin",
+			"lib.rs"
+		]
+	);
+}
+
+#[test]
+fn files_are_opened_from_their_layer() {
+	let fs = overlay();
+	let mut contents = String::new();
+	let mut file = fs.open_options().read(true).open("/src/lib.rs").unwrap();
+	file.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "lib");
+	assert!(!fs.upper().exists("/src").unwrap());
+
+	let mut file = fs.open_options().append(true).open("/src/lib.rs").unwrap();
+	file.write_all(b" and more").unwrap();
+	drop(file);
+	assert_eq!(fs.read_to_string("/src/lib.rs").unwrap(), "lib and more");
+	assert_eq!(fs.lower().read_to_string("/src/lib.rs").unwrap(), "lib");
+
+	fs.remove_file("/README").unwrap();
+	let error = fs.open_options().read(true).open("/README").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::NotFound);
+	fs.open_options().write(true).create_new(true).open("/README").unwrap();
+	assert_eq!(fs.read("/README").unwrap(), b"");
+	let error = fs.open_options().write(true).create_new(true).open("/src/lib.rs").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+}
+
+#[test]
+fn markers_are_reserved() {
+	let fs = overlay();
+	fs.remove_file("/README").unwrap();
+	let error = fs.read("/.wh.README").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+	assert_eq!(fs.write("/.wh.x", "").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn lower_symlinks_are_read_only() {
+	let fs = overlay();
+	fs.lower().symlink("lib.rs", "/src/link.rs").unwrap();
+	assert_eq!(fs.read_to_string("/src/link.rs").unwrap(), "lib");
+	assert!(fs.symlink_metadata("/src/link.rs").unwrap().is_symlink());
+	let error = fs.write("/src/link.rs", "").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+	fs.remove_file("/src/link.rs").unwrap();
+	assert!(!fs.exists("/src/link.rs").unwrap());
+}
+
+#[test]
+fn layer_errors_are_not_taken_for_missing_entries() {
+	let lower = FaultFs::new(MemFs::new());
+	lower.write("/stale", "lower").unwrap();
+	let fs = OverlayFs::new(FaultFs::new(MemFs::new()), lower);
+	fs.write("/stale", "upper").unwrap();
+	let rule = FaultRule::new(Fault::Error(io::ErrorKind::PermissionDenied));
+	fs.upper().inject(rule.operation(Operation::SymlinkMetadata).path("/stale"));
+	assert_eq!(fs.read("/stale").unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+	assert_eq!(fs.exists("/stale").unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+
+	// Like `std`, paths through files are errors rather than missing entries.
+	fs.upper().clear();
+	assert!(!fs.exists("/missing").unwrap());
+	let error = fs.exists("/stale/child").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::NotADirectory);
+}
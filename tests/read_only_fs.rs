@@ -0,0 +1,97 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+
+use io_fs::conformance::expect_error;
+use io_fs::DirBuilder;
+use io_fs::File;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+use io_fs::Permissions;
+use io_fs::ReadOnlyFs;
+
+fn read_only() -> ReadOnlyFs<MemFs> {
+	let fs = MemFs::new();
+	fs.create_dir_all("/data/empty").unwrap();
+	fs.write("/data/file", "abc").unwrap();
+	ReadOnlyFs::new(fs)
+}
+
+#[test]
+fn forwards_reads() {
+	let fs = read_only();
+	assert_eq!(fs.read("/data/file").unwrap(), b"abc");
+	assert_eq!(fs.read_to_string("/data/file").unwrap(), "abc");
+	assert!(fs.exists("/data/empty").unwrap());
+	assert!(fs.metadata("/data/empty").unwrap().is_dir());
+	assert_eq!(fs.symlink_metadata("/data/file").unwrap().len(), 3);
+	assert_eq!(fs.read_dir("/data").unwrap().count(), 2);
+	assert_eq!(
+		fs.canonicalize("/data/../data/file").unwrap(),
+		fs.get_ref().canonicalize("/data/file").unwrap()
+	);
+	assert_eq!(fs.read("/data/missing").unwrap_err().kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn denies_changes() {
+	let fs = read_only();
+	expect_error(fs.write("/data/file", "def"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.write("/data/new", "def"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.copy("/data/file", "/data/copy"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.create_dir("/data/dir"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.create_dir_all("/data/dir/sub"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(
+		fs.dir_builder().recursive(true).create("/data/dir"),
+		io::ErrorKind::ReadOnlyFilesystem,
+	);
+	expect_error(fs.hard_link("/data/file", "/data/link"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.remove_dir("/data/empty"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.remove_dir_all("/data"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.remove_file("/data/file"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.remove_file("/data/missing"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(fs.rename("/data/file", "/data/renamed"), io::ErrorKind::ReadOnlyFilesystem);
+	let mut perm = fs.metadata("/data/file").unwrap().permissions();
+	perm.set_readonly(true);
+	expect_error(fs.set_permissions("/data/file", perm), io::ErrorKind::ReadOnlyFilesystem);
+
+	let inner = fs.into_inner();
+	assert_eq!(inner.read("/data/file").unwrap(), b"abc");
+	assert_eq!(inner.read_dir("/data").unwrap().count(), 2);
+}
+
+#[test]
+fn opens_files_for_reading_only() {
+	let fs = read_only();
+	let mut file = fs.open_options().read(true).open("/data/file").unwrap();
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+	assert_eq!(contents, "abc");
+	assert_eq!(file.metadata().unwrap().len(), 3);
+	expect_error(file.write(b"def"), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(file.set_len(0), io::ErrorKind::ReadOnlyFilesystem);
+	expect_error(
+		file.set_modified(std::time::SystemTime::UNIX_EPOCH),
+		io::ErrorKind::ReadOnlyFilesystem,
+	);
+	file.flush().unwrap();
+
+	expect_error(
+		fs.open_options().write(true).open("/data/file"),
+		io::ErrorKind::ReadOnlyFilesystem,
+	);
+	expect_error(
+		fs.open_options().read(true).append(true).open("/data/file"),
+		io::ErrorKind::ReadOnlyFilesystem,
+	);
+	expect_error(
+		fs.open_options().write(true).create_new(true).open("/data/new"),
+		io::ErrorKind::ReadOnlyFilesystem,
+	);
+	let mut options = fs.open_options();
+	options.read(true).write(true).write(false);
+	assert!(options.open("/data/file").is_ok());
+	assert_eq!(fs.read("/data/file").unwrap(), b"abc");
+}
@@ -0,0 +1,93 @@
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::OpenOptions;
+use io_fs::Operation;
+use io_fs::RecordingFs;
+
+#[test]
+fn records_paths_bytes_and_results() {
+	let fs = RecordingFs::new(MemFs::new());
+	fs.write("a", "abc").unwrap();
+	assert_eq!(fs.copy("a", "b").unwrap(), 3);
+	fs.read_to_string("b").unwrap();
+	fs.remove_file("missing").unwrap_err();
+	fs.rename("a", "c").unwrap();
+
+	let rendered = fs.calls().iter().map(ToString::to_string).collect::<Vec<_>>();
+	assert_eq!(
+		rendered,
+		[
+			r#"write("a") 3 bytes -> Ok"#,
+			r#"copy("a", "b") 3 bytes -> Ok"#,
+			r#"read_to_string("b") 3 bytes -> Ok"#,
+			r#"remove_file("missing") -> Err(NotFound)"#,
+			r#"rename("a", "c") -> Ok"#,
+		]
+	);
+	let calls = fs.calls();
+	assert_eq!(calls[3].result, Err(io::ErrorKind::NotFound));
+	assert!(calls.windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp));
+
+	fs.clear();
+	assert!(fs.calls().is_empty());
+}
+
+#[test]
+fn records_file_calls_with_their_path() {
+	let fs = RecordingFs::new(MemFs::new());
+	let mut file = fs.open_options().write(true).create(true).open("log").unwrap();
+	file.write_all(b"hello").unwrap();
+	let mut file = fs.open_options().read(true).open("log").unwrap();
+	let mut contents = String::new();
+	file.read_to_string(&mut contents).unwrap();
+
+	let calls = fs.calls();
+	assert_eq!(calls[0].operation, Operation::Open);
+	assert_eq!(calls[1].to_string(), r#"File::write("log") 5 bytes -> Ok"#);
+	let read = calls
+		.iter()
+		.filter(|call| call.operation == Operation::FileRead)
+		.map(|call| call.bytes.unwrap())
+		.sum::<u64>();
+	assert_eq!(read, 5);
+	assert!(calls.iter().all(|call| call.paths == [Path::new("log")]));
+}
+
+#[test]
+fn assertions_under_a_directory() {
+	let fs = RecordingFs::new(MemFs::new());
+	fs.create_dir_all("/data/old").unwrap();
+	fs.create_dir("/database").unwrap();
+	fs.remove_dir_all("/data/old").unwrap();
+	fs.remove_dir_all("/database").unwrap();
+
+	assert!(fs.was_called_under(Operation::RemoveDirAll, "/data"));
+	assert_eq!(fs.calls_under(Operation::RemoveDirAll, "/data").len(), 1);
+	fs.assert_called_under(Operation::CreateDirAll, "/data/old");
+	fs.assert_never_called_under(Operation::RemoveDir, "/");
+}
+
+#[test]
+fn assertions_resolve_dots_in_paths() {
+	let fs = RecordingFs::new(MemFs::new());
+	let _ = fs.remove_dir_all("/data/../etc");
+	let _ = fs.remove_dir_all("logs/./old");
+
+	fs.assert_never_called_under(Operation::RemoveDirAll, "/data");
+	fs.assert_called_under(Operation::RemoveDirAll, "/etc");
+	fs.assert_called_under(Operation::RemoveDirAll, "/logs/old");
+	fs.assert_called_under(Operation::RemoveDirAll, "/tmp/../logs");
+}
+
+#[test]
+#[should_panic = "remove_dir_all was called under \"/data\", calls:\n  remove_dir_all(\"/data\") -> Err(NotFound)\n"]
+fn failed_assertions_list_the_calls() {
+	let fs = RecordingFs::new(MemFs::new());
+	let _ = fs.remove_dir_all("/data");
+	fs.assert_never_called_under(Operation::RemoveDirAll, "/data");
+}
@@ -0,0 +1,224 @@
+#![cfg(feature = "tar")]
+
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use io_fs::DirEntry;
+use io_fs::File;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+use io_fs::Permissions;
+use io_fs::TarFs;
+use io_fs::TarWriter;
+
+const ROOTFS: &[u8] = include_bytes!("fixtures/rootfs.tar");
+
+const DEEP: &str = "/deep/nested-directory/nested-directory/nested-directory/nested-directory/\
+                    nested-directory/nested-directory/nested-directory/nested-directory/file.txt";
+
+fn check_rootfs(fs: &TarFs) {
+	assert_eq!(fs.read_to_string("/etc/os-release").unwrap(), "NAME=demo\n");
+	assert_eq!(fs.read_to_string("bin/tool").unwrap(), "#!/bin/sh\necho hi\n");
+	assert_eq!(fs.read_to_string(DEEP).unwrap(), "deep\n");
+	assert_eq!(fs.read("/missing").unwrap_err().kind(), io::ErrorKind::NotFound);
+	assert_eq!(fs.read("/etc").unwrap_err().kind(), io::ErrorKind::IsADirectory);
+
+	let names = fs.read_dir("/").unwrap().map(|entry| entry.unwrap().file_name());
+	assert_eq!(
+		names.collect::<Vec<_>>(),
+		[
+			"bin",
+			"deep",
+			"etc"
+		]
+	);
+	let names = fs.read_dir("/etc").unwrap().map(|entry| entry.unwrap().file_name());
+	assert_eq!(
+		names.collect::<Vec<_>>(),
+		[
+			"os-release",
+			"os-release.bak",
+			"release"
+		]
+	);
+}
+
+#[test]
+fn reads_pax_and_gnu_archives() {
+	check_rootfs(&TarFs::new(ROOTFS).unwrap());
+	check_rootfs(&TarFs::new(include_bytes!("fixtures/rootfs-gnu.tar").as_slice()).unwrap());
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn reads_gzip_archives() {
+	let fs = TarFs::new(include_bytes!("fixtures/rootfs.tar.gz").as_slice()).unwrap();
+	check_rootfs(&fs);
+
+	let mut corrupt = include_bytes!("fixtures/rootfs.tar.gz").to_vec();
+	let len = corrupt.len();
+	corrupt[len - 5] ^= 0xff;
+	assert_eq!(TarFs::new(corrupt).unwrap_err().kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn preserves_metadata() {
+	let fs = TarFs::new(ROOTFS).unwrap();
+	let metadata = fs.metadata("/etc/os-release").unwrap();
+	assert!(metadata.is_file());
+	assert_eq!(metadata.len(), 10);
+	assert_eq!(metadata.mode(), 0o644);
+	assert_eq!((metadata.uid(), metadata.gid()), (1000, 100));
+	let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+	assert_eq!(metadata.modified().unwrap(), modified);
+	assert_eq!(metadata.accessed().unwrap_err().kind(), io::ErrorKind::Unsupported);
+	assert!(!metadata.permissions().readonly());
+
+	let tool = fs.metadata("/bin/tool").unwrap();
+	assert_eq!((tool.mode(), tool.uid()), (0o755, 0));
+	let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+	assert_eq!(tool.modified().unwrap(), modified);
+	assert!(fs.metadata("/bin/readonly").unwrap().permissions().readonly());
+	// Directories that only appear as prefixes of other entries get default metadata.
+	assert_eq!(fs.metadata("/bin").unwrap().mode(), 0o755);
+}
+
+#[test]
+fn resolves_links() {
+	let fs = TarFs::new(ROOTFS).unwrap();
+	assert_eq!(fs.read_link("/etc/release").unwrap(), PathBuf::from("os-release"));
+	assert_eq!(fs.canonicalize("/etc/release").unwrap(), PathBuf::from("/etc/os-release"));
+	assert!(fs.symlink_metadata("/etc/release").unwrap().is_symlink());
+	assert_eq!(fs.read_to_string("/etc/release").unwrap(), "NAME=demo\n");
+	assert_eq!(fs.read_link("/etc/os-release").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+	// Hard links share the node of their target.
+	assert_eq!(fs.read_to_string("/etc/os-release.bak").unwrap(), "NAME=demo\n");
+	assert!(fs.symlink_metadata("/etc/os-release.bak").unwrap().is_file());
+	assert_eq!(fs.metadata("/etc/os-release.bak").unwrap().nlink(), 2);
+	assert_eq!(fs.metadata("/bin/tool").unwrap().nlink(), 1);
+
+	let mut file = fs.open_options().read(true).open("/etc/os-release.bak").unwrap();
+	assert_eq!(file.seek(SeekFrom::End(-5)).unwrap(), 5);
+	let mut tail = String::new();
+	file.read_to_string(&mut tail).unwrap();
+	assert_eq!(tail, "demo\n");
+	assert_eq!(file.metadata().unwrap().len(), 10);
+}
+
+#[test]
+fn rejects_mutations() {
+	let fs = TarFs::new(ROOTFS).unwrap();
+	let perm = fs.metadata("/bin/tool").unwrap().permissions();
+	let results = [
+		fs.write("/bin/tool", ""),
+		fs.create_dir_all("/usr/bin"),
+		fs.remove_file("/bin/tool"),
+		fs.remove_dir_all("/etc"),
+		fs.rename("/bin/tool", "/bin/other"),
+		fs.set_permissions("/bin/tool", perm),
+		fs.open_options().read(true).write(true).open("/bin/tool").map(drop),
+	];
+	for result in results {
+		assert_eq!(result.unwrap_err().kind(), io::ErrorKind::ReadOnlyFilesystem);
+	}
+	let file = fs.open_options().read(true).open("/bin/tool").unwrap();
+	assert_eq!(file.set_len(0).unwrap_err().kind(), io::ErrorKind::ReadOnlyFilesystem);
+}
+
+#[test]
+fn malformed_archives() {
+	let mut corrupt = ROOTFS.to_vec();
+	corrupt[10] ^= 0xff;
+	assert_eq!(TarFs::new(corrupt).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	let truncated = &ROOTFS[..ROOTFS.len() / 3];
+	assert_eq!(TarFs::new(truncated).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	assert!(TarFs::new(&[][..]).unwrap().read_dir("/").unwrap().next().is_none());
+}
+
+/// Returns an archive of a single GNU header, whose access time fills the bytes where POSIX
+/// headers have a path prefix.
+fn gnu_archive(name: &str, kind: u8) -> Vec<u8> {
+	let mut block = [0; 512];
+	block[..name.len()].copy_from_slice(name.as_bytes());
+	block[100..108].copy_from_slice(b"0000644\0");
+	block[124..136].copy_from_slice(b"00000000000\0");
+	block[136..148].copy_from_slice(b"00000000000\0");
+	block[156] = kind;
+	block[257..265].copy_from_slice(b"ustar  \0");
+	block[345..357].copy_from_slice(b"14566636411\0");
+	block[148..156].fill(b' ');
+	let sum = block.iter().map(|&byte| u32::from(byte)).sum::<u32>();
+	block[148..156].copy_from_slice(format!("{sum:06o}\0 ").as_bytes());
+	[
+		&block[..],
+		&[0; 1024],
+	]
+	.concat()
+}
+
+#[test]
+fn gnu_headers_have_no_prefix() {
+	let fs = TarFs::new(gnu_archive("file.txt", b'0')).unwrap();
+	let names = fs.read_dir("/").unwrap().map(|entry| entry.unwrap().file_name());
+	assert_eq!(names.collect::<Vec<_>>(), ["file.txt"]);
+
+	let error = TarFs::new(gnu_archive("sparse.img", b'S')).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+}
+
+fn source() -> MemFs {
+	let fs = MemFs::new();
+	fs.create_dir_all("/src/docs").unwrap();
+	fs.write("/src/b.txt", "bee").unwrap();
+	fs.write("/src/a.txt", "a".repeat(1000)).unwrap();
+	fs.write(format!("/src/docs/{}", "long-name-".repeat(12)), "long").unwrap();
+	fs.symlink("a.txt", "/src/link").unwrap();
+	let mut perm = fs.metadata("/src/b.txt").unwrap().permissions();
+	perm.set_readonly(true);
+	fs.set_permissions("/src/b.txt", perm).unwrap();
+	fs
+}
+
+#[test]
+fn writes_deterministic_archives() {
+	let fs = source();
+	let mtime = SystemTime::UNIX_EPOCH + Duration::from_secs(1_234_567_890);
+	let archive = |fs: &MemFs| {
+		let mut writer = TarWriter::new(Vec::new());
+		writer.mtime(mtime).append_tree(fs, "/src").unwrap();
+		writer.finish().unwrap()
+	};
+	let bytes = archive(&fs);
+	assert_eq!(bytes.len() % 512, 0);
+	// Rewriting an unchanged file must not change the output.
+	fs.write("/src/a.txt", "a".repeat(1000)).unwrap();
+	assert_eq!(archive(&fs), bytes);
+
+	let tar = TarFs::new(bytes).unwrap();
+	let names = tar.read_dir("/").unwrap().map(|entry| entry.unwrap().file_name());
+	assert_eq!(
+		names.collect::<Vec<_>>(),
+		[
+			"a.txt",
+			"b.txt",
+			"docs",
+			"link"
+		]
+	);
+	assert_eq!(tar.read_to_string("/a.txt").unwrap(), "a".repeat(1000));
+	assert_eq!(tar.read_to_string(format!("/docs/{}", "long-name-".repeat(12))).unwrap(), "long");
+	assert_eq!(tar.read_link("/link").unwrap(), PathBuf::from("a.txt"));
+	assert_eq!(tar.metadata("/a.txt").unwrap().mode(), 0o644);
+	assert_eq!(tar.metadata("/b.txt").unwrap().mode(), 0o444);
+	assert_eq!(tar.metadata("/docs").unwrap().mode(), 0o755);
+	assert_eq!(tar.metadata("/docs").unwrap().modified().unwrap(), mtime);
+	assert_eq!(tar.metadata("/a.txt").unwrap().uid(), 0);
+}
@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use io_fs::conformance::Scratch;
+use io_fs::diff_trees;
+use io_fs::Fs;
+use io_fs::MemFs;
+use io_fs::Metadata;
+use io_fs::Native;
+use io_fs::Permissions;
+use io_fs::TreeChange;
+use io_fs::TreeEntry;
+
+fn fixture<F: Fs>(fs: &F, root: &std::path::Path) {
+	fs.create_dir_all(root.join("src/bin")).unwrap();
+	fs.write(root.join("src/lib.rs"), "pub fn f() {}").unwrap();
+	fs.write(root.join("src/bin/main.rs"), "fn main() {}").unwrap();
+	fs.write(root.join("README"), "readme").unwrap();
+}
+
+#[test]
+fn identical_trees() {
+	let before = MemFs::new();
+	fixture(&before, "/".as_ref());
+	let native = Native {};
+	let scratch = Scratch::new(&native).unwrap();
+	fixture(&native, scratch.path());
+
+	let diff = diff_trees(&before, "/", &native, scratch.path()).unwrap();
+	assert!(diff.is_empty(), "{diff}");
+	assert_eq!(diff.to_string(), "no changes");
+}
+
+#[test]
+fn added_and_removed_entries() {
+	let before = MemFs::new();
+	fixture(&before, "/".as_ref());
+	let after = MemFs::new();
+	fixture(&after, "/".as_ref());
+	after.remove_dir_all("/src/bin").unwrap();
+	after.create_dir_all("/target/debug").unwrap();
+	after.write("/target/debug/app", [0xff; 64]).unwrap();
+
+	let diff = diff_trees(&before, "/", &after, "/").unwrap();
+	assert_eq!(
+		diff.changes(),
+		[
+			TreeChange::Removed {
+				path: "src/bin".into(),
+				entry: TreeEntry::Dir,
+			},
+			TreeChange::Removed {
+				path: "src/bin/main.rs".into(),
+				entry: TreeEntry::File(b"fn main() {}".to_vec()),
+			},
+			TreeChange::Added {
+				path: "target".into(),
+				entry: TreeEntry::Dir,
+			},
+			TreeChange::Added {
+				path: "target/debug".into(),
+				entry: TreeEntry::Dir,
+			},
+			TreeChange::Added {
+				path: "target/debug/app".into(),
+				entry: TreeEntry::File(vec![0xff; 64]),
+			},
+		]
+	);
+	let rendered = [
+		"- src/bin: directory",
+		"- src/bin/main.rs: file \"fn main() {}\"",
+		"+ target: directory",
+		"+ target/debug: directory",
+		"+ target/debug/app: file (64 bytes)",
+	];
+	assert_eq!(diff.to_string(), rendered.join("\n"));
+}
+
+#[test]
+fn modified_entries() {
+	let before = MemFs::new();
+	fixture(&before, "/".as_ref());
+	before.symlink("src/lib.rs", "/link").unwrap();
+	let snapshot = before.snapshot();
+	before.write("/src/lib.rs", "pub fn g() {}").unwrap();
+	before.remove_file("/link").unwrap();
+	before.symlink("README", "/link").unwrap();
+	before.remove_file("/README").unwrap();
+	before.create_dir("/README").unwrap();
+	before.write("/README/index.md", "").unwrap();
+	let mut perm = before.metadata("/src/bin/main.rs").unwrap().permissions();
+	perm.set_readonly(true);
+	before.set_permissions("/src/bin/main.rs", perm).unwrap();
+
+	let after = MemFs::new();
+	after.restore(&before.snapshot());
+	before.restore(&snapshot);
+	let diff = diff_trees(&before, "/", &after, "/").unwrap();
+	let lines: Vec<String> = diff.changes().iter().map(ToString::to_string).collect();
+	assert_eq!(
+		lines,
+		[
+			"~ README: file \"readme\" -> directory",
+			"+ README/index.md: file \"\"",
+			"~ link: symlink to \"src/lib.rs\" -> symlink to \"README\"",
+			"~ src/bin/main.rs: made read-only",
+			"~ src/lib.rs: file \"pub fn f() {}\" -> file \"pub fn g() {}\"",
+		]
+	);
+	let reverse = diff_trees(&after, "/", &before, "/").unwrap();
+	assert_eq!(reverse.changes()[0].to_string(), "~ README: directory -> file \"readme\"");
+	assert_eq!(reverse.changes()[1].path(), PathBuf::from("README/index.md"));
+	assert!(matches!(reverse.changes()[1], TreeChange::Removed { .. }));
+}
+
+#[test]
+fn missing_root() {
+	let fs = MemFs::new();
+	let error = diff_trees(&fs, "/missing", &fs, "/").unwrap_err();
+	assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+}
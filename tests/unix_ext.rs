@@ -0,0 +1,69 @@
+#![cfg(unix)]
+
+use io_fs::conformance::Scratch;
+use io_fs::unix::DirBuilderExt;
+use io_fs::unix::DirEntryExt;
+use io_fs::unix::FsExt;
+use io_fs::unix::MetadataExt;
+use io_fs::unix::OpenOptionsExt;
+use io_fs::unix::PermissionsExt;
+use io_fs::Fs;
+use io_fs::Native;
+
+/// Returns the permission bits of `path`, as code generic over the backend would.
+fn mode<F: Fs>(fs: &F, path: &std::path::Path) -> u32
+where
+	F::Metadata: MetadataExt,
+{
+	fs.metadata(path).unwrap().mode() & 0o777
+}
+
+#[test]
+fn modes_through_handles() {
+	let native = Native {};
+	let scratch = Scratch::new(&native).unwrap();
+
+	let dir = scratch.path().join("dir");
+	native.dir_builder().mode(0o700).create(&dir).unwrap();
+	assert_eq!(mode(&native, &dir), 0o700);
+
+	let file = dir.join("file");
+	native.open_options().write(true).create_new(true).mode(0o640).open(&file).unwrap();
+	assert_eq!(mode(&native, &file), 0o640);
+
+	let mut permissions = native.metadata(&file).unwrap().permissions();
+	permissions.set_mode(0o600);
+	native.set_permissions(&file, permissions).unwrap();
+	assert_eq!(mode(&native, &file), 0o600);
+	assert_eq!(
+		native.metadata(&file).unwrap().permissions().mode() & 0o777,
+		<std::fs::Permissions as PermissionsExt>::from_mode(0o600).mode()
+	);
+}
+
+#[test]
+fn inode_numbers_match_entries() {
+	let native = Native {};
+	let scratch = Scratch::new(&native).unwrap();
+	native.write(scratch.path().join("file"), "contents").unwrap();
+
+	let entry = native.read_dir(scratch.path()).unwrap().next().unwrap().unwrap();
+	let metadata = entry.metadata().unwrap();
+	assert_eq!(entry.ino(), metadata.ino());
+	assert_eq!(metadata.size(), 8);
+	assert_eq!(metadata.nlink(), 1);
+}
+
+#[test]
+fn symlink_through_fs() {
+	let native = Native {};
+	let scratch = Scratch::new(&native).unwrap();
+	let original = scratch.path().join("original");
+	let link = scratch.path().join("link");
+	native.write(&original, "contents").unwrap();
+
+	native.symlink(&original, &link).unwrap();
+	assert!(native.symlink_metadata(&link).unwrap().is_symlink());
+	assert_eq!(native.read_link(&link).unwrap(), original);
+	assert_eq!(native.read_to_string(&link).unwrap(), "contents");
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "zip")]
+
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use io_fs::DirBuilder;
+use io_fs::DirEntry;
+use io_fs::File;
+use io_fs::FileType;
+use io_fs::Fs;
+use io_fs::Metadata;
+use io_fs::OpenOptions;
+use io_fs::Permissions;
+use io_fs::ZipFs;
+
+const ASSETS: &[u8] = include_bytes!("fixtures/assets.zip");
+
+fn assets() -> ZipFs {
+	ZipFs::new(ASSETS).unwrap()
+}
+
+#[test]
+fn reads_stored_and_deflated_entries() {
+	let fs = assets();
+	assert_eq!(fs.read_to_string("/textures/grass.txt").unwrap(), "green ".repeat(200));
+	assert_eq!(fs.read("textures/stone.bin").unwrap(), (0..=255).collect::<Vec<u8>>());
+	assert_eq!(fs.read_to_string("config/nested/deep.toml").unwrap(), "deep = true\n");
+	assert!(fs.exists("/README").unwrap());
+	assert!(!fs.exists("/missing").unwrap());
+	assert_eq!(fs.read("/missing").unwrap_err().kind(), io::ErrorKind::NotFound);
+	assert_eq!(fs.read("/README/child").unwrap_err().kind(), io::ErrorKind::NotADirectory);
+	assert_eq!(fs.read("/textures").unwrap_err().kind(), io::ErrorKind::IsADirectory);
+
+	let metadata = fs.metadata("/textures/grass.txt").unwrap();
+	assert!(metadata.is_file());
+	assert_eq!(metadata.len(), 1200);
+	let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_715_949_010);
+	assert_eq!(metadata.modified().unwrap(), modified);
+	assert_eq!(metadata.accessed().unwrap_err().kind(), io::ErrorKind::Unsupported);
+	assert_eq!(metadata.unix_mode(), Some(0o100_644));
+	assert!(!metadata.permissions().readonly());
+	assert!(fs.metadata("/textures/stone.bin").unwrap().permissions().readonly());
+}
+
+#[test]
+fn lists_directories() {
+	let fs = assets();
+	let entries: Vec<_> = fs.read_dir("/").unwrap().map(Result::unwrap).collect();
+	let names: Vec<_> = entries.iter().map(DirEntry::file_name).collect();
+	assert_eq!(
+		names,
+		[
+			"README",
+			"config",
+			"latest",
+			"textures"
+		]
+	);
+	assert!(entries[1].file_type().unwrap().is_dir());
+	assert!(entries[2].file_type().unwrap().is_symlink());
+	let paths = fs.read_dir("config").unwrap().map(|entry| entry.unwrap().path());
+	assert_eq!(paths.collect::<Vec<_>>(), [PathBuf::from("config/nested")]);
+	assert!(fs.metadata("/config/nested").unwrap().is_dir());
+	assert_eq!(fs.read_dir("/README").unwrap_err().kind(), io::ErrorKind::NotADirectory);
+}
+
+#[test]
+fn follows_symlinks() {
+	let fs = assets();
+	assert_eq!(fs.read_link("/latest").unwrap(), PathBuf::from("textures/grass.txt"));
+	assert_eq!(fs.canonicalize("/latest").unwrap(), PathBuf::from("/textures/grass.txt"));
+	assert_eq!(fs.canonicalize("config/nested/..").unwrap(), PathBuf::from("/config"));
+	assert_eq!(fs.read("/latest").unwrap().len(), 1200);
+	assert!(fs.symlink_metadata("/latest").unwrap().is_symlink());
+	assert!(fs.metadata("/latest").unwrap().is_file());
+	assert_eq!(fs.read_link("/README").unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn seeks_within_entries() {
+	let fs = assets();
+	let mut file = fs.open_options().read(true).open("/textures/stone.bin").unwrap();
+	assert_eq!(file.seek(SeekFrom::End(-6)).unwrap(), 250);
+	let mut tail = Vec::new();
+	file.read_to_end(&mut tail).unwrap();
+	assert_eq!(
+		tail,
+		[
+			250,
+			251,
+			252,
+			253,
+			254,
+			255
+		]
+	);
+
+	let mut clone = file.try_clone().unwrap();
+	file.seek(SeekFrom::Start(16)).unwrap();
+	assert_eq!(clone.seek(SeekFrom::Current(-8)).unwrap(), 8);
+	let mut buf = [0; 4];
+	clone.read_exact(&mut buf).unwrap();
+	assert_eq!(
+		buf,
+		[
+			8,
+			9,
+			10,
+			11
+		]
+	);
+	assert_eq!(file.metadata().unwrap().len(), 256);
+	assert_eq!(file.seek(SeekFrom::Current(-100)).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn rejects_mutations() {
+	let fs = assets();
+	let perm = fs.metadata("/README").unwrap().permissions();
+	let results = [
+		fs.write("/README", ""),
+		fs.write("/new", ""),
+		fs.create_dir("/dir"),
+		fs.create_dir_all("/textures"),
+		fs.remove_file("/README"),
+		fs.remove_dir_all("/textures"),
+		fs.rename("/README", "/README.md"),
+		fs.hard_link("/README", "/link"),
+		fs.set_permissions("/README", perm),
+		fs.copy("/README", "/copy").map(drop),
+		fs.dir_builder().recursive(true).create("/dir"),
+		fs.open_options().read(true).append(true).open("/README").map(drop),
+	];
+	for result in results {
+		assert_eq!(result.unwrap_err().kind(), io::ErrorKind::ReadOnlyFilesystem);
+	}
+	let mut file = fs.open_options().read(true).open("/README").unwrap();
+	assert_eq!(
+		io::Write::write(&mut file, b"x").unwrap_err().kind(),
+		io::ErrorKind::ReadOnlyFilesystem
+	);
+	assert_eq!(file.set_len(0).unwrap_err().kind(), io::ErrorKind::ReadOnlyFilesystem);
+	let error = fs.open_options().open("/README").unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn zip64_archives() {
+	let fs = ZipFs::new(include_bytes!("fixtures/zip64.zip").as_slice()).unwrap();
+	assert_eq!(fs.read_to_string("/big/data.txt").unwrap(), "zip64 ".repeat(1000));
+	assert_eq!(fs.metadata("/big/data.txt").unwrap().unix_mode(), None);
+}
+
+#[test]
+fn malformed_archives() {
+	assert_eq!(ZipFs::new(&b"not a zip"[..]).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	let mut truncated = ASSETS.to_vec();
+	truncated.drain(..40);
+	assert_eq!(ZipFs::new(truncated).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+	// Corrupt the stored contents of stone.bin, which is only detected when it is read.
+	let mut corrupt = ASSETS.to_vec();
+	let start = corrupt
+		.windows(4)
+		.position(|window| {
+			window
+				== [
+					0,
+					1,
+					2,
+					3,
+				]
+		})
+		.unwrap();
+	corrupt[start + 100] ^= 0xff;
+	let fs = ZipFs::new(corrupt).unwrap();
+	assert_eq!(fs.read("/textures/stone.bin").unwrap_err().kind(), io::ErrorKind::InvalidData);
+	assert_eq!(fs.read_to_string("/README").unwrap(), "hello from zip");
+}